@@ -3,7 +3,12 @@
 extern crate petgraph;
 extern crate test;
 
-use petgraph::graph::Graph;
+use petgraph::Undirected;
+use petgraph::graph::{
+    node_index,
+    Graph,
+};
+use petgraph::graph::inline::InlineGraph;
 
 
 #[bench]
@@ -38,3 +43,45 @@ fn bench_remove(b: &mut test::Bencher) {
         }
     })
 }
+
+/// An undirected graph where each node has about three neighbors, at
+/// scattered indices.
+fn low_degree_graph() -> Graph<(), (), Undirected> {
+    let n = 100_000;
+    let mut g = Graph::with_capacity(n, 3 * n / 2);
+    for _ in 0..n {
+        g.add_node(());
+    }
+    for i in 0..3 * n / 2 {
+        g.add_edge(node_index(i % n), node_index(i * 7919 % n), ());
+    }
+    g
+}
+
+#[bench]
+fn bench_neighbors_graph(b: &mut test::Bencher) {
+    let g = low_degree_graph();
+    b.iter(|| {
+        let mut sum = 0;
+        for a in g.node_indices() {
+            for x in g.neighbors(a) {
+                sum += x.index();
+            }
+        }
+        sum
+    })
+}
+
+#[bench]
+fn bench_neighbors_inline(b: &mut test::Bencher) {
+    let g = InlineGraph::from(low_degree_graph());
+    b.iter(|| {
+        let mut sum = 0;
+        for a in g.node_indices() {
+            for x in g.neighbors(a) {
+                sum += x.index();
+            }
+        }
+        sum
+    })
+}
@@ -0,0 +1,239 @@
+use core::ops::Add;
+use core::slice;
+use alloc::vec::Vec;
+
+use super::super::{
+    EdgeType,
+    Graph,
+};
+use super::super::graph::{
+    DefIndex,
+    IndexType,
+    NodeIndex,
+};
+
+/// The shortest path costs between all pairs of nodes, see
+/// [`floyd_warshall`](fn.floyd_warshall.html).
+///
+/// The matrix is dense, with a row per node in node index order. The costs
+/// of unreachable pairs are `None`. If it was computed with
+/// [`floyd_warshall_paths`](fn.floyd_warshall_paths.html), it also has a
+/// successor matrix, and rebuilds the paths with
+/// [`path`](#method.path).
+#[derive(Clone, Debug, PartialEq)]
+pub struct DistanceMatrix<K, Ix: IndexType = DefIndex> {
+    node_count: usize,
+    dist: Vec<Option<K>>,
+    /// The node after `a` on the path from `a` to `b`, at `a * n + b`.
+    next: Option<Vec<Option<NodeIndex<Ix>>>>,
+}
+
+impl<K, Ix> DistanceMatrix<K, Ix>
+    where K: Copy,
+          Ix: IndexType,
+{
+    /// Return the number of nodes, the number of rows and of columns.
+    pub fn node_count(&self) -> usize {
+        self.node_count
+    }
+
+    /// Return the cost of the shortest path from `a` to `b`, or `None` if
+    /// `b` is not reachable from `a`.
+    ///
+    /// **Panics** if `a` or `b` is out of bounds.
+    pub fn get(&self, a: NodeIndex<Ix>, b: NodeIndex<Ix>) -> Option<K> {
+        assert!(b.index() < self.node_count, "DistanceMatrix::get: node index out of bounds");
+        self.dist[a.index() * self.node_count + b.index()]
+    }
+
+    /// Return the costs of the shortest paths from `a`, indexed by target
+    /// node index.
+    ///
+    /// **Panics** if `a` is out of bounds.
+    pub fn row(&self, a: NodeIndex<Ix>) -> &[Option<K>] {
+        let n = self.node_count;
+        &self.dist[a.index() * n..(a.index() + 1) * n]
+    }
+
+    /// Return an iterator of the rows, in node index order.
+    pub fn rows(&self) -> Rows<K> {
+        Rows {
+            iter: self.dist.chunks(if self.node_count == 0 { 1 } else { self.node_count }),
+        }
+    }
+
+    /// Return `true` if the matrix has the successors to rebuild paths.
+    pub fn has_paths(&self) -> bool {
+        self.next.is_some()
+    }
+
+    /// Return the nodes of a shortest path from `a` to `b`, both inclusive,
+    /// or `None` if `b` is not reachable from `a`.
+    ///
+    /// **Panics** if the matrix has no successors, or if `a` or `b` is out
+    /// of bounds.
+    pub fn path(&self, a: NodeIndex<Ix>, b: NodeIndex<Ix>) -> Option<Vec<NodeIndex<Ix>>> {
+        let next = match self.next {
+            None => panic!("DistanceMatrix::path: computed without successors"),
+            Some(ref next) => next,
+        };
+        if self.get(a, b).is_none() {
+            return None
+        }
+        let n = self.node_count;
+        let mut path = vec![a];
+        let mut node = a;
+        while node != b {
+            node = next[node.index() * n + b.index()].unwrap();
+            path.push(node);
+        }
+        Some(path)
+    }
+}
+
+/// An iterator of the rows of a [`DistanceMatrix`](struct.DistanceMatrix.html).
+pub struct Rows<'a, K: 'a> {
+    iter: slice::Chunks<'a, Option<K>>,
+}
+
+impl<'a, K> Iterator for Rows<'a, K> {
+    type Item = &'a [Option<K>];
+
+    fn next(&mut self) -> Option<&'a [Option<K>]> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// Compute the cost of the shortest paths between all pairs of nodes, with
+/// the Floyd–Warshall algorithm.
+///
+/// `edge_cost` maps an edge weight to its cost, which may be negative. The
+/// edges of an undirected graph go both ways, so a negative edge is a
+/// negative cycle there.
+///
+/// Return `None` if the graph has a cycle of negative cost, since shortest
+/// paths are then undefined.
+///
+/// Computes in **O(|V|³)** time and **O(|V|²)** space. For sparse graphs
+/// with non-negative costs, a [`dijkstra`](fn.dijkstra.html) search per node
+/// is faster.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::algo::floyd_warshall;
+/// use petgraph::graph::node_index as n;
+///
+/// let g = Graph::<(), i32>::from_edges(&[(0, 1, 4), (1, 2, -2), (0, 2, 3), (2, 3, 1)]);
+/// let dist = floyd_warshall(&g, |w| *w).unwrap();
+/// assert_eq!(dist.get(n(0), n(3)), Some(3));
+/// assert_eq!(dist.get(n(3), n(0)), None);
+/// assert_eq!(dist.row(n(1)), &[None, Some(0), Some(-2), Some(-1)]);
+/// ```
+pub fn floyd_warshall<N, E, Ty, Ix, F, K>(g: &Graph<N, E, Ty, Ix>, edge_cost: F)
+    -> Option<DistanceMatrix<K, Ix>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+          F: FnMut(&E) -> K,
+          K: Default + Add<Output=K> + Copy + PartialOrd,
+{
+    all_pairs(g, edge_cost, false)
+}
+
+/// Compute the cost of the shortest paths between all pairs of nodes, and
+/// the successor matrix to rebuild the paths with
+/// [`DistanceMatrix::path`](struct.DistanceMatrix.html#method.path).
+///
+/// This is [`floyd_warshall`](fn.floyd_warshall.html), with twice the space.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::algo::floyd_warshall_paths;
+/// use petgraph::graph::node_index as n;
+///
+/// let g = Graph::<(), u32>::from_edges(&[(0, 1, 1), (1, 2, 1), (0, 2, 5)]);
+/// let dist = floyd_warshall_paths(&g, |w| *w).unwrap();
+/// assert_eq!(dist.path(n(0), n(2)), Some(vec![n(0), n(1), n(2)]));
+/// assert_eq!(dist.path(n(2), n(0)), None);
+/// ```
+pub fn floyd_warshall_paths<N, E, Ty, Ix, F, K>(g: &Graph<N, E, Ty, Ix>, edge_cost: F)
+    -> Option<DistanceMatrix<K, Ix>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+          F: FnMut(&E) -> K,
+          K: Default + Add<Output=K> + Copy + PartialOrd,
+{
+    all_pairs(g, edge_cost, true)
+}
+
+fn all_pairs<N, E, Ty, Ix, F, K>(g: &Graph<N, E, Ty, Ix>, mut edge_cost: F, with_paths: bool)
+    -> Option<DistanceMatrix<K, Ix>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+          F: FnMut(&E) -> K,
+          K: Default + Add<Output=K> + Copy + PartialOrd,
+{
+    let n = g.node_count();
+    let zero = K::default();
+    let mut dist = vec![None; n * n];
+    let mut next = vec![None; if with_paths { n * n } else { 0 }];
+    for a in 0..n {
+        dist[a * n + a] = Some(zero);
+        if with_paths {
+            next[a * n + a] = Some(NodeIndex::new(a));
+        }
+    }
+    for edge in g.raw_edges() {
+        let cost = edge_cost(&edge.weight);
+        let (a, b) = (edge.source(), edge.target());
+        let directions = if g.is_directed() { 1 } else { 2 };
+        for &(x, y) in &[(a, b), (b, a)][..directions] {
+            let ix = x.index() * n + y.index();
+            if dist[ix].map_or(true, |d| cost < d) {
+                dist[ix] = Some(cost);
+                if with_paths {
+                    next[ix] = Some(y);
+                }
+            }
+        }
+    }
+
+    // a negative cycle is reported as soon as it is found, since going round
+    // it again and again would overflow the distances
+    if (0..n).any(|a| dist[a * n + a].map_or(false, |d| d < zero)) {
+        return None
+    }
+    for k in 0..n {
+        for i in 0..n {
+            let dik = match dist[i * n + k] {
+                None => continue,
+                Some(d) => d,
+            };
+            for j in 0..n {
+                let dkj = match dist[k * n + j] {
+                    None => continue,
+                    Some(d) => d,
+                };
+                let cost = dik + dkj;
+                if i == j && cost < zero {
+                    return None
+                }
+                if dist[i * n + j].map_or(true, |d| cost < d) {
+                    dist[i * n + j] = Some(cost);
+                    if with_paths {
+                        next[i * n + j] = next[i * n + k];
+                    }
+                }
+            }
+        }
+    }
+
+    Some(DistanceMatrix {
+        node_count: n,
+        dist: dist,
+        next: if with_paths { Some(next) } else { None },
+    })
+}
@@ -0,0 +1,208 @@
+use core::mem;
+use core::ops::{
+    Add,
+    Sub,
+};
+use alloc::vec::Vec;
+
+use super::super::{
+    EdgeType,
+    Graph,
+};
+use super::super::graph::{
+    IndexType,
+    NodeIndex,
+};
+
+/// Solve the *assignment problem* with the Hungarian algorithm.
+///
+/// `costs` is a matrix with a row per worker and a column per job, with at
+/// least as many columns as rows. Assign each row a distinct column so that
+/// the total cost is minimal.
+///
+/// Return the total cost and the column assigned to each row. Costs may be
+/// negative; the cost type must support subtraction without underflow, so
+/// use signed integers or floats.
+///
+/// Runs in **O(n² m)** time for *n* rows and *m* columns.
+///
+/// **Panics** if there are more rows than columns, or if the rows have
+/// different lengths.
+///
+/// ```
+/// use petgraph::algo::hungarian;
+///
+/// let costs = [[4, 1, 3],
+///              [2, 0, 5],
+///              [3, 2, 2]];
+/// assert_eq!(hungarian(&costs), (5, vec![1, 0, 2]));
+/// ```
+pub fn hungarian<K, R>(costs: &[R]) -> (K, Vec<usize>)
+    where K: Copy + PartialOrd + Default + Add<Output=K> + Sub<Output=K>,
+          R: AsRef<[K]>,
+{
+    let rows = costs.len();
+    let cols = costs.first().map_or(0, |row| row.as_ref().len());
+    assert!(costs.iter().all(|row| row.as_ref().len() == cols),
+            "hungarian: rows of different lengths");
+    assert!(rows <= cols, "hungarian: more rows than columns");
+    let assignment = assign(rows, cols, |i, j| Some(costs[i].as_ref()[j])).unwrap();
+    let total = assignment.iter().enumerate().fold(K::default(), |total, (i, &j)| {
+        total + costs[i].as_ref()[j]
+    });
+    (total, assignment)
+}
+
+/// Compute a minimum cost matching of the nodes `left` in a bipartite graph.
+///
+/// Match each node of `left` with a distinct neighbor, so that the sum of
+/// `edge_cost` over the matching edges is minimal. Edge directions are
+/// ignored, edges between two nodes of `left` are ignored, and of several
+/// edges between the same pair of nodes the cheapest is used.
+///
+/// Return the total cost and the matched pairs, in the order of `left`, or
+/// `None` if some node of `left` can't be matched.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::hungarian_matching;
+/// use petgraph::graph::node_index as n;
+///
+/// // workers 0 and 1, jobs 2 and 3
+/// let g = Graph::<(), i32, Undirected>::from_edges(&[
+///     (0, 2, 3), (0, 3, 5), (1, 2, 1)]);
+/// assert_eq!(hungarian_matching(&g, &[n(0), n(1)], |w| *w),
+///            Some((6, vec![(n(0), n(3)), (n(1), n(2))])));
+/// ```
+pub fn hungarian_matching<N, E, Ty, Ix, F, K>(g: &Graph<N, E, Ty, Ix>,
+                                              left: &[NodeIndex<Ix>],
+                                              mut edge_cost: F)
+    -> Option<(K, Vec<(NodeIndex<Ix>, NodeIndex<Ix>)>)>
+    where Ty: EdgeType,
+          Ix: IndexType,
+          F: FnMut(&E) -> K,
+          K: Copy + PartialOrd + Default + Add<Output=K> + Sub<Output=K>,
+{
+    // number the nodes on both sides
+    const NONE: usize = !0;
+    let mut row_of = vec![NONE; g.node_count()];
+    for (i, &a) in left.iter().enumerate() {
+        row_of[a.index()] = i;
+    }
+    let mut col_of = vec![NONE; g.node_count()];
+    let mut right = Vec::new();
+    let mut costs = vec![Vec::new(); left.len()];
+    for edge in g.raw_edges() {
+        let (mut a, mut b) = (edge.source(), edge.target());
+        if row_of[a.index()] == NONE {
+            mem::swap(&mut a, &mut b);
+        }
+        if row_of[a.index()] == NONE || row_of[b.index()] != NONE {
+            continue
+        }
+        if col_of[b.index()] == NONE {
+            col_of[b.index()] = right.len();
+            right.push(b);
+        }
+        costs[row_of[a.index()]].push((col_of[b.index()], edge_cost(&edge.weight)));
+    }
+    if right.len() < left.len() {
+        return None
+    }
+    let mut matrix = vec![None; left.len() * right.len()];
+    for (i, row) in costs.into_iter().enumerate() {
+        for (j, cost) in row {
+            let entry = &mut matrix[i * right.len() + j];
+            match *entry {
+                Some(old) if !(cost < old) => {}
+                _ => *entry = Some(cost),
+            }
+        }
+    }
+    let assignment = match assign(left.len(), right.len(), |i, j| matrix[i * right.len() + j]) {
+        None => return None,
+        Some(assignment) => assignment,
+    };
+    let mut total = K::default();
+    let mut pairs = Vec::with_capacity(left.len());
+    for (i, &j) in assignment.iter().enumerate() {
+        total = total + matrix[i * right.len() + j].unwrap();
+        pairs.push((left[i], right[j]));
+    }
+    Some((total, pairs))
+}
+
+/// The Hungarian algorithm with potentials, for `rows <= cols`, where
+/// `cost` returns `None` for forbidden pairs.
+///
+/// Rows are added one at a time, each time growing a shortest augmenting
+/// path with Dijkstra's algorithm on the reduced costs. Return the column of
+/// each row, or `None` if there is no complete assignment.
+fn assign<K, F>(rows: usize, cols: usize, cost: F) -> Option<Vec<usize>>
+    where K: Copy + PartialOrd + Default + Add<Output=K> + Sub<Output=K>,
+          F: Fn(usize, usize) -> Option<K>,
+{
+    // Column 0 is a sentinel, real columns are 1..cols + 1; row_of[j] is the
+    // row matched to column j, plus one.
+    let zero = K::default();
+    let mut u = vec![zero; rows + 1];
+    let mut v = vec![zero; cols + 1];
+    let mut row_of = vec![0; cols + 1];
+    let mut way = vec![0; cols + 1];
+    for i in 1..rows + 1 {
+        row_of[0] = i;
+        let mut j0 = 0;
+        let mut min_reduced = vec![None; cols + 1];
+        let mut used = vec![false; cols + 1];
+        while row_of[j0] != 0 {
+            used[j0] = true;
+            let i0 = row_of[j0];
+            let mut delta = None;
+            let mut j1 = 0;
+            for j in 1..cols + 1 {
+                if used[j] {
+                    continue
+                }
+                if let Some(c) = cost(i0 - 1, j - 1) {
+                    let reduced = c - u[i0] - v[j];
+                    if min_reduced[j].map_or(true, |m| reduced < m) {
+                        min_reduced[j] = Some(reduced);
+                        way[j] = j0;
+                    }
+                }
+                if let Some(m) = min_reduced[j] {
+                    if delta.map_or(true, |d| m < d) {
+                        delta = Some(m);
+                        j1 = j;
+                    }
+                }
+            }
+            let delta = match delta {
+                None => return None,
+                Some(delta) => delta,
+            };
+            for j in 0..cols + 1 {
+                if used[j] {
+                    u[row_of[j]] = u[row_of[j]] + delta;
+                    v[j] = v[j] - delta;
+                } else if let Some(m) = min_reduced[j] {
+                    min_reduced[j] = Some(m - delta);
+                }
+            }
+            j0 = j1;
+        }
+        // flip the augmenting path
+        while j0 != 0 {
+            let j1 = way[j0];
+            row_of[j0] = row_of[j1];
+            j0 = j1;
+        }
+    }
+    let mut assignment = vec![0; rows];
+    for j in 1..cols + 1 {
+        if row_of[j] != 0 {
+            assignment[row_of[j] - 1] = j - 1;
+        }
+    }
+    Some(assignment)
+}
@@ -0,0 +1,418 @@
+use alloc::vec::Vec;
+
+use super::super::{
+    EdgeType,
+    Graph,
+};
+use super::super::graph::{
+    IndexType,
+    NodeIndex,
+};
+use super::super::unionfind::UnionFind;
+
+/// Compute a *canonical order* of the nodes of the graph.
+///
+/// Return the nodes in an order that depends only on the structure of the
+/// graph: if `g` and `h` are isomorphic, renumbering the nodes of each in
+/// its canonical order makes the two graphs identical, with the same edges
+/// between the same indices. Weights are ignored; see
+/// [`canonical_form_by`](fn.canonical_form_by.html) to tell nodes apart by
+/// weight.
+///
+/// The order is found by individualization and refinement, as in *nauty*:
+/// nodes are split into classes by their degrees into the other classes
+/// until the partition is stable, then each node of a class in turn is put
+/// in a class of its own, recursively, and the best of the orders reached
+/// is kept. Automorphisms found on the way prune the search. This works well
+/// for small graphs, but can take exponential time on hard cases.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::algo::canonical_form;
+///
+/// // the same path a -> b -> c, numbered differently
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2)]);
+/// let h = Graph::<(), ()>::from_edges(&[(2, 0), (1, 2)]);
+///
+/// let relabel = |g: &Graph<(), ()>| {
+///     let order = canonical_form(g);
+///     let mut position = vec![0; order.len()];
+///     for (i, a) in order.into_iter().enumerate() {
+///         position[a.index()] = i;
+///     }
+///     let mut edges = g.raw_edges().iter()
+///         .map(|e| (position[e.source().index()], position[e.target().index()]))
+///         .collect::<Vec<_>>();
+///     edges.sort();
+///     edges
+/// };
+/// assert_eq!(relabel(&g), relabel(&h));
+/// ```
+pub fn canonical_form<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>) -> Vec<NodeIndex<Ix>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    canonical_form_by(g, |_| ())
+}
+
+/// Compute a *canonical order* of the nodes of the graph, where nodes with
+/// different `node_key` are told apart.
+///
+/// Nodes are ordered by their key first: the canonical order puts the nodes
+/// with the smallest key first. Isomorphisms that preserve the key give
+/// identical renumbered graphs. Edge weights are ignored.
+///
+/// See [`canonical_form`](fn.canonical_form.html) for the details.
+pub fn canonical_form_by<N, E, Ty, Ix, F, K>(g: &Graph<N, E, Ty, Ix>, node_key: F)
+    -> Vec<NodeIndex<Ix>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+          F: FnMut(&N) -> K,
+          K: Ord,
+{
+    let mut search = Search::new(g, node_key);
+    let color = search.initial_color.clone();
+    search.run(color, &mut Vec::new());
+    search.best.unwrap().order.into_iter().map(NodeIndex::new).collect()
+}
+
+/// The automorphism group of a graph, see
+/// [`automorphisms`](fn.automorphisms.html).
+#[derive(Clone, Debug)]
+pub struct Automorphisms<Ix> {
+    /// Automorphisms that generate the group: each maps the node with index
+    /// `i` to the node `generator[i]`. The identity is left out.
+    pub generators: Vec<Vec<NodeIndex<Ix>>>,
+    /// The number of automorphisms, including the identity. It is a float
+    /// because it grows fast: the graph of *n* isolated nodes has *n!*
+    /// automorphisms.
+    pub group_size: f64,
+    /// The *orbits* of the group: the classes of nodes that some
+    /// automorphism maps to each other, sorted by their smallest node.
+    pub orbits: Vec<Vec<NodeIndex<Ix>>>,
+}
+
+/// Compute the *automorphism group* of the graph: the permutations of the
+/// nodes that map the edges onto the edges. Weights are ignored; see
+/// [`automorphisms_by`](fn.automorphisms_by.html) to only map nodes to nodes
+/// with the same weight.
+///
+/// Return generators of the group and its size. The generators are found by
+/// the search of [`canonical_form`](fn.canonical_form.html), as the
+/// permutations between the equivalent leaves it reaches.
+///
+/// A graph without symmetries has no generators, one automorphism, and an
+/// orbit for each node.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::automorphisms;
+/// use petgraph::graph::node_index as n;
+///
+/// // a square has the 8 symmetries of the dihedral group
+/// let square = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 0)]);
+/// assert_eq!(automorphisms(&square).group_size, 8.);
+///
+/// // in a path, the ends and the middle are two orbits
+/// let path = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2)]);
+/// let group = automorphisms(&path);
+/// assert_eq!(group.group_size, 2.);
+/// assert_eq!(group.orbits, vec![vec![n(0), n(2)], vec![n(1)]]);
+/// ```
+pub fn automorphisms<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>) -> Automorphisms<Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    automorphisms_by(g, |_| ())
+}
+
+/// Compute the automorphisms of the graph that map each node to a node with
+/// the same `node_key`.
+///
+/// See [`automorphisms`](fn.automorphisms.html) for the details.
+pub fn automorphisms_by<N, E, Ty, Ix, F, K>(g: &Graph<N, E, Ty, Ix>, node_key: F)
+    -> Automorphisms<Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+          F: FnMut(&N) -> K,
+          K: Ord,
+{
+    let mut search = Search::new(g, node_key);
+    let color = search.initial_color.clone();
+    search.run(color, &mut Vec::new());
+
+    // The size of the group is the product of the orbit sizes of the nodes
+    // on the path to the first leaf, each in the stabilizer of the nodes
+    // before it.
+    let first_path = search.first.as_ref().map_or(Vec::new(), |leaf| leaf.path.clone());
+    let mut group_size = 1.;
+    for (depth, &a) in first_path.iter().enumerate() {
+        let orbits = search.orbits(&first_path[..depth]);
+        group_size *= (0..search.n).filter(|&b| orbits.equiv(a, b)).count() as f64;
+    }
+    let all = search.orbits(&[]);
+    let mut class_of: Vec<Option<usize>> = vec![None; search.n];
+    let mut orbits: Vec<Vec<NodeIndex<Ix>>> = Vec::new();
+    for a in 0..search.n {
+        let root = all.find(a);
+        match class_of[root] {
+            Some(c) => orbits[c].push(NodeIndex::new(a)),
+            None => {
+                class_of[root] = Some(orbits.len());
+                orbits.push(vec![NodeIndex::new(a)]);
+            }
+        }
+    }
+    let generators = search.automorphisms.into_iter().map(|perm| {
+        perm.into_iter().map(NodeIndex::new).collect()
+    }).collect();
+    Automorphisms {
+        generators: generators,
+        group_size: group_size,
+        orbits: orbits,
+    }
+}
+
+/// A leaf of the search tree: the nodes individualized to reach it, the
+/// node order and the edges renumbered by it.
+#[derive(Clone)]
+struct Leaf {
+    path: Vec<usize>,
+    order: Vec<usize>,
+    certificate: Vec<(usize, usize, usize)>,
+}
+
+/// The state of the search for a canonical order.
+///
+/// A partition of the nodes into ordered classes is stored as the color of
+/// each node: the position of the first node of its class in the order.
+struct Search {
+    n: usize,
+    /// For each node, its out-neighbors (all neighbors, for an undirected
+    /// graph) with the number of edges to each.
+    out: Vec<Vec<(usize, usize)>>,
+    /// For each node of a directed graph, its in-neighbors with the number
+    /// of edges from each.
+    incoming: Vec<Vec<(usize, usize)>>,
+    initial_color: Vec<usize>,
+    first: Option<Leaf>,
+    best: Option<Leaf>,
+    /// The automorphisms found, as the image of each node.
+    automorphisms: Vec<Vec<usize>>,
+}
+
+impl Search {
+    fn new<N, E, Ty, Ix, F, K>(g: &Graph<N, E, Ty, Ix>, mut node_key: F) -> Self
+        where Ty: EdgeType,
+              Ix: IndexType,
+              F: FnMut(&N) -> K,
+              K: Ord,
+    {
+        let n = g.node_count();
+        let mut out = vec![Vec::new(); n];
+        let mut incoming = vec![Vec::new(); if g.is_directed() { n } else { 0 }];
+        for edge in g.raw_edges() {
+            let (a, b) = (edge.source().index(), edge.target().index());
+            out[a].push(b);
+            if g.is_directed() {
+                incoming[b].push(a);
+            } else if a != b {
+                out[b].push(a);
+            }
+        }
+        let count = |adj: Vec<Vec<usize>>| -> Vec<Vec<(usize, usize)>> {
+            adj.into_iter().map(|mut xs| {
+                xs.sort();
+                let mut counted: Vec<(usize, usize)> = Vec::new();
+                for x in xs {
+                    match counted.last_mut() {
+                        Some(&mut (y, ref mut m)) if y == x => *m += 1,
+                        _ => counted.push((x, 1)),
+                    }
+                }
+                counted
+            }).collect()
+        };
+
+        let keys = g.raw_nodes().iter().map(|a| node_key(&a.weight)).collect::<Vec<_>>();
+        let mut by_key = (0..n).collect::<Vec<_>>();
+        by_key.sort_by(|&a, &b| keys[a].cmp(&keys[b]));
+        let mut initial_color = vec![0; n];
+        for i in 1..n {
+            let (prev, a) = (by_key[i - 1], by_key[i]);
+            initial_color[a] = if keys[prev] == keys[a] { initial_color[prev] } else { i };
+        }
+        Search {
+            n: n,
+            out: count(out),
+            incoming: count(incoming),
+            initial_color: initial_color,
+            first: None,
+            best: None,
+            automorphisms: Vec::new(),
+        }
+    }
+
+    /// Split the classes of `color` by the number of edges of each node to
+    /// and from each class, until no class splits.
+    fn refine(&self, color: &mut [usize]) {
+        let mut classes = count_classes(color);
+        loop {
+            let signatures = (0..self.n).map(|a| {
+                let mut edges = self.out[a].iter().map(|&(b, m)| (color[b], 0, m)).collect::<Vec<_>>();
+                if !self.incoming.is_empty() {
+                    edges.extend(self.incoming[a].iter().map(|&(b, m)| (color[b], 1, m)));
+                }
+                edges.sort();
+                (color[a], edges)
+            }).collect::<Vec<_>>();
+            let mut order = (0..self.n).collect::<Vec<_>>();
+            order.sort_by(|&a, &b| signatures[a].cmp(&signatures[b]));
+            for i in 0..self.n {
+                let a = order[i];
+                color[a] = if i > 0 && signatures[order[i - 1]] == signatures[a] {
+                    color[order[i - 1]]
+                } else {
+                    i
+                };
+            }
+            let refined = count_classes(color);
+            if refined == classes {
+                return
+            }
+            classes = refined;
+        }
+    }
+
+    /// Explore the search tree below the partition `color`, reached by
+    /// individualizing the nodes in `path`.
+    ///
+    /// Return `Some(depth)` when an automorphism shows that the rest of the
+    /// subtree of the ancestor at `depth` was explored already.
+    fn run(&mut self, mut color: Vec<usize>, path: &mut Vec<usize>) -> Option<usize> {
+        self.refine(&mut color);
+
+        // the first smallest class with more than one node
+        let mut size = vec![0; self.n];
+        for &c in &color {
+            size[c] += 1;
+        }
+        let target = (0..self.n).filter(|&c| size[c] > 1).min_by_key(|&c| size[c]);
+        let target = match target {
+            None => return self.leaf(&color, path),
+            Some(c) => c,
+        };
+
+        let mut tried = Vec::new();
+        for a in 0..self.n {
+            if color[a] != target {
+                continue
+            }
+            // skip a node that an automorphism fixing the path maps to one
+            // already tried
+            let orbits = self.orbits(path);
+            if tried.iter().any(|&b| orbits.equiv(a, b)) {
+                continue
+            }
+            tried.push(a);
+            let mut child = color.clone();
+            for c in &mut child {
+                if *c == target {
+                    *c = target + 1;
+                }
+            }
+            child[a] = target;
+            path.push(a);
+            let jump = self.run(child, path);
+            path.pop();
+            match jump {
+                Some(depth) if depth < path.len() => return jump,
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Return the orbits of the group generated by the automorphisms found
+    /// that fix every node of `path`.
+    fn orbits(&self, path: &[usize]) -> UnionFind<usize> {
+        let mut orbits = UnionFind::new(self.n);
+        for perm in &self.automorphisms {
+            if path.iter().all(|&a| perm[a] == a) {
+                for (a, &b) in perm.iter().enumerate() {
+                    orbits.union(a, b);
+                }
+            }
+        }
+        orbits
+    }
+
+    /// Record the leaf with the discrete partition `color`.
+    ///
+    /// If it is equivalent to the first or the best leaf, return the depth
+    /// of their common ancestor: the subtree of its child leading here is
+    /// the image of the one leading there.
+    fn leaf(&mut self, color: &[usize], path: &[usize]) -> Option<usize> {
+        let mut order = vec![0; self.n];
+        for (a, &c) in color.iter().enumerate() {
+            order[c] = a;
+        }
+        let mut certificate = Vec::new();
+        for (a, adj) in self.out.iter().enumerate() {
+            certificate.extend(adj.iter().map(|&(b, m)| (color[a], color[b], m)));
+        }
+        certificate.sort();
+        let leaf = Leaf {
+            path: path.to_vec(),
+            order: order,
+            certificate: certificate,
+        };
+
+        let first = match self.first {
+            None => {
+                self.first = Some(leaf.clone());
+                self.best = Some(leaf);
+                return None
+            }
+            Some(ref first) => first.clone(),
+        };
+        let best = self.best.clone().unwrap();
+        let equivalent = if leaf.certificate == first.certificate {
+            first
+        } else if leaf.certificate == best.certificate {
+            best
+        } else {
+            if leaf.certificate > best.certificate {
+                self.best = Some(leaf);
+            }
+            return None
+        };
+        self.add_automorphism(&equivalent, &leaf);
+        Some(path.iter().zip(&equivalent.path).take_while(|&(a, b)| a == b).count())
+    }
+
+    /// Record the automorphism that maps the order of `from` to the order
+    /// of `to`.
+    fn add_automorphism(&mut self, from: &Leaf, to: &Leaf) {
+        let mut perm = vec![0; self.n];
+        for (&a, &b) in from.order.iter().zip(&to.order) {
+            perm[a] = b;
+        }
+        if perm.iter().enumerate().any(|(a, &b)| a != b) {
+            self.automorphisms.push(perm);
+        }
+    }
+}
+
+/// Return the number of distinct colors.
+fn count_classes(color: &[usize]) -> usize {
+    let mut seen = vec![false; color.len()];
+    let mut classes = 0;
+    for &c in color {
+        if !seen[c] {
+            seen[c] = true;
+            classes += 1;
+        }
+    }
+    classes
+}
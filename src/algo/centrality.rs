@@ -0,0 +1,125 @@
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+
+use super::super::{
+    EdgeType,
+    Graph,
+};
+use super::super::graph::{
+    IndexType,
+    NodeIndex,
+};
+
+/// Compute the betweenness centrality of every edge.
+///
+/// The betweenness centrality of an edge is the sum, over all pairs of nodes
+/// *s* and *t*, of the fraction of shortest *s*-*t* paths that use the edge.
+/// Edges are unweighted, and for an undirected graph each unordered pair is
+/// counted once. Self loops are on no shortest path, and parallel edges
+/// give distinct paths.
+///
+/// If `normalized` is `true`, the scores are scaled to lie between 0 and 1,
+/// by dividing by the number of pairs of nodes: **|V|(|V| - 1)** for a
+/// directed graph and half that for an undirected graph.
+///
+/// Uses Brandes' algorithm with one breadth first search per source node.
+/// Runtime **O(|V||E|)**.
+///
+/// Returns a vector of scores indexed by edge index.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::edge_betweenness_centrality;
+///
+/// // a path a - b - c: the pair (a, c) uses both edges
+/// let g = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2)]);
+/// assert_eq!(edge_betweenness_centrality(&g, false), [2., 2.]);
+/// assert_eq!(edge_betweenness_centrality(&g, true), [2. / 3., 2. / 3.]);
+/// ```
+pub fn edge_betweenness_centrality<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>, normalized: bool)
+    -> Vec<f64>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    let mut centrality = edge_betweenness(g, &vec![false; g.edge_count()]);
+    let n = g.node_count();
+    if normalized && n > 1 {
+        let pairs = (n * (n - 1)) as f64;
+        let scale = if g.is_directed() { 1. / pairs } else { 2. / pairs };
+        for c in &mut centrality {
+            *c *= scale;
+        }
+    }
+    centrality
+}
+
+/// Compute the betweenness of every edge that is not `removed`, by edge
+/// index: the sum, over all pairs of nodes *s* and *t*, of the fraction of
+/// shortest *s*-*t* paths that use the edge.
+///
+/// Edges are unweighted, self loops are on no shortest path, and parallel
+/// edges give distinct paths. For an undirected graph each unordered pair is
+/// counted once. Removed edges score zero.
+///
+/// Uses Brandes' algorithm with one breadth first search per source node, in
+/// **O(|V||E|)** time.
+pub fn edge_betweenness<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>, removed: &[bool]) -> Vec<f64>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    let n = g.node_count();
+    let mut centrality = vec![0.; g.edge_count()];
+    let mut stack = Vec::with_capacity(n);
+    // the edges into each node on shortest paths from the source, and the
+    // node at their other end
+    let mut predecessors = vec![Vec::new(); n];
+    let mut sigma = vec![0.; n];
+    let mut distance = vec![None; n];
+    let mut delta = vec![0.; n];
+    let mut queue = VecDeque::new();
+    for source in 0..n {
+        stack.clear();
+        for i in 0..n {
+            predecessors[i].clear();
+            sigma[i] = 0.;
+            distance[i] = None;
+            delta[i] = 0.;
+        }
+        sigma[source] = 1.;
+        distance[source] = Some(0);
+        queue.push_back(source);
+        while let Some(v) = queue.pop_front() {
+            stack.push(v);
+            let next_distance = distance[v].map(|d| d + 1);
+            let mut edges = g.neighbors(NodeIndex::new(v)).detach();
+            while let Some((e, w)) = edges.next(g) {
+                let w = w.index();
+                if removed[e.index()] || w == v {
+                    continue;
+                }
+                if distance[w].is_none() {
+                    distance[w] = next_distance;
+                    queue.push_back(w);
+                }
+                if distance[w] == next_distance {
+                    sigma[w] += sigma[v];
+                    predecessors[w].push((e.index(), v));
+                }
+            }
+        }
+        while let Some(w) = stack.pop() {
+            for &(e, v) in &predecessors[w] {
+                let c = sigma[v] / sigma[w] * (1. + delta[w]);
+                centrality[e] += c;
+                delta[v] += c;
+            }
+        }
+    }
+    if !g.is_directed() {
+        // each unordered pair was counted from both of its ends
+        for c in &mut centrality {
+            *c *= 0.5;
+        }
+    }
+    centrality
+}
@@ -0,0 +1,175 @@
+use core::cmp::Reverse;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use super::super::{
+    EdgeType,
+    Graph,
+    Undirected,
+};
+use super::super::graph::{
+    DefIndex,
+    IndexType,
+    NodeIndex,
+};
+
+/// One level of a coarsening hierarchy, see [`coarsen`](fn.coarsen.html).
+#[derive(Clone, Debug)]
+pub struct CoarseLevel<Ix: IndexType = DefIndex> {
+    /// The coarse graph. Each node weighs the number of original nodes it
+    /// stands for, and each edge the number of original edges.
+    pub graph: Graph<usize, usize, Undirected, Ix>,
+    /// The node of `graph` that each node of the finer level was
+    /// contracted into, indexed by the node index of the finer level.
+    pub projection: Vec<NodeIndex<Ix>>,
+}
+
+/// Coarsen `g` by contracting a heavy-edge matching, up to `levels` times.
+///
+/// Each level matches each node, in index order, to the unmatched neighbor
+/// it has the heaviest edge to, preferring the lightest such neighbor, and
+/// contracts the matched pairs. Edges that join the same pair of coarse
+/// nodes merge, adding their weights, and edges inside a coarse node
+/// disappear. The weights start at `1` for the nodes and edges of `g`.
+///
+/// Return the levels from finest to coarsest: the projection of the first
+/// level maps the nodes of `g`, and that of each next level the nodes of the
+/// previous graph. The hierarchy stops early when a level has no edges left
+/// to contract.
+///
+/// Edge directions are ignored and self loops disappear. This is the
+/// coarsening of [`partition`](partition/index.html). Each level takes
+/// **O(|V| + |E| log |E|)** time.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::coarsen;
+/// use petgraph::graph::node_index as n;
+///
+/// // a path of four nodes
+/// let g = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2), (2, 3)]);
+/// let levels = coarsen(&g, 5);
+/// assert_eq!(levels.len(), 2);
+///
+/// // 0 - 1 and 2 - 3 are contracted, joined by the edge 1 - 2
+/// assert_eq!(levels[0].projection, vec![n(0), n(0), n(1), n(1)]);
+/// assert_eq!(levels[0].graph.node_weight(n(0)), Some(&2));
+/// assert_eq!(levels[0].graph.edge_count(), 1);
+///
+/// // then the last edge
+/// assert_eq!(levels[1].projection, vec![n(0), n(0)]);
+/// assert_eq!(levels[1].graph.node_weight(n(0)), Some(&4));
+/// ```
+pub fn coarsen<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>, levels: usize) -> Vec<CoarseLevel<Ix>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    let mut hierarchy = Vec::new();
+    let mut fine = Weighted::new(g);
+    for _ in 0..levels {
+        let (coarse, map) = fine.coarsen();
+        if coarse.len() == fine.len() {
+            break
+        }
+        let mut graph = Graph::with_capacity(coarse.len(), 0);
+        for &w in &coarse.node_weight {
+            graph.add_node(w);
+        }
+        for (a, b, w) in coarse.edges() {
+            graph.add_edge(NodeIndex::new(a), NodeIndex::new(b), w);
+        }
+        hierarchy.push(CoarseLevel {
+            graph: graph,
+            projection: map.into_iter().map(NodeIndex::new).collect(),
+        });
+        fine = coarse;
+    }
+    hierarchy
+}
+
+/// A graph with node and edge weights, as sorted adjacency lists without
+/// self loops or parallel edges.
+pub struct Weighted {
+    pub node_weight: Vec<usize>,
+    /// The neighbors of each node, with the weight of the edge to them.
+    pub adjacency: Vec<Vec<(usize, usize)>>,
+}
+
+impl Weighted {
+    /// Create the weighted graph of `g`, with weight `1` for each node and
+    /// each edge.
+    pub fn new<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>) -> Self
+        where Ty: EdgeType,
+              Ix: IndexType,
+    {
+        let edges = g.raw_edges().iter().map(|edge| (edge.source().index(), edge.target().index(), 1));
+        Weighted::from_edges(vec![1; g.node_count()], edges)
+    }
+
+    /// Create the graph of the nodes of `node_weight`, merging parallel
+    /// edges by adding their weights.
+    pub fn from_edges<I>(node_weight: Vec<usize>, edges: I) -> Self
+        where I: IntoIterator<Item=(usize, usize, usize)>,
+    {
+        let mut adjacency = vec![Vec::new(); node_weight.len()];
+        for (a, b, w) in edges {
+            if a != b {
+                adjacency[a].push((b, w));
+                adjacency[b].push((a, w));
+            }
+        }
+        for list in &mut adjacency {
+            list.sort();
+            let mut merged: Vec<(usize, usize)> = Vec::with_capacity(list.len());
+            for &(b, w) in list.iter() {
+                match merged.last_mut() {
+                    Some(last) if last.0 == b => last.1 += w,
+                    _ => merged.push((b, w)),
+                }
+            }
+            *list = merged;
+        }
+        Weighted {
+            node_weight: node_weight,
+            adjacency: adjacency,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.node_weight.len()
+    }
+
+    /// Return the edges as `(a, b, weight)` with `a < b`.
+    pub fn edges<'a>(&'a self) -> Box<Iterator<Item=(usize, usize, usize)> + 'a> {
+        Box::new(self.adjacency.iter().enumerate().flat_map(|(a, list)| {
+            list.iter().filter(move |&&(b, _)| a < b).map(move |&(b, w)| (a, b, w))
+        }))
+    }
+
+    /// Contract a heavy-edge matching, see [`coarsen`](fn.coarsen.html).
+    ///
+    /// Return the coarse graph and the coarse node of each node.
+    pub fn coarsen(&self) -> (Weighted, Vec<usize>) {
+        let mut map = vec![None; self.len()];
+        let mut coarse_weight = Vec::new();
+        for a in 0..self.len() {
+            if map[a].is_some() {
+                continue
+            }
+            let mate = self.adjacency[a].iter()
+                                        .filter(|&&(b, _)| map[b].is_none())
+                                        .max_by_key(|&&(b, w)| (w, Reverse(self.node_weight[b])));
+            map[a] = Some(coarse_weight.len());
+            let mut weight = self.node_weight[a];
+            if let Some(&(b, _)) = mate {
+                map[b] = Some(coarse_weight.len());
+                weight += self.node_weight[b];
+            }
+            coarse_weight.push(weight);
+        }
+        let map = map.into_iter().map(|c| c.unwrap()).collect::<Vec<_>>();
+        let coarse = Weighted::from_edges(coarse_weight,
+                                          self.edges().map(|(a, b, w)| (map[a], map[b], w)));
+        (coarse, map)
+    }
+}
@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+
+use super::super::{
+    EdgeType,
+    Graph,
+};
+use super::super::graph::IndexType;
+
+/// Color the edges of the graph so that edges sharing a node get different
+/// colors.
+///
+/// Return the color of each edge, by edge index. Colors are numbered from
+/// zero. Edge directions are ignored.
+///
+/// For a simple graph (without self loops or several edges between the
+/// same two nodes) this uses the algorithm of Misra and Gries, which needs at
+/// most *Δ + 1* colors, where *Δ* is the maximum degree; at least *Δ* colors
+/// are always needed. Otherwise, each edge gets the smallest color not yet
+/// used next to it, which uses at most *2Δ - 1* colors.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::greedy_edge_coloring;
+///
+/// // a star and a triangle
+/// let g = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (0, 2), (0, 3), (1, 2)]);
+/// let colors = greedy_edge_coloring(&g);
+/// assert!(colors.iter().all(|&c| c <= 3));
+/// assert!(colors[0] != colors[1] && colors[1] != colors[2] && colors[0] != colors[2]);
+/// assert!(colors[3] != colors[0] && colors[3] != colors[1]);
+/// ```
+pub fn greedy_edge_coloring<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>) -> Vec<usize>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    let mut pairs = HashMap::with_capacity(g.edge_count());
+    let mut simple = true;
+    for (i, edge) in g.raw_edges().iter().enumerate() {
+        let (a, b) = (edge.source().index(), edge.target().index());
+        if a == b || pairs.insert((a.min(b), a.max(b)), i).is_some() {
+            simple = false;
+            break
+        }
+    }
+    if simple {
+        misra_gries(g, &pairs)
+    } else {
+        first_fit(g)
+    }
+}
+
+/// Give each edge the smallest color not used on an edge next to it.
+fn first_fit<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>) -> Vec<usize>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    const NONE: usize = !0;
+    let mut colors = vec![NONE; g.edge_count()];
+    let mut taken = Vec::new();
+    for (i, edge) in g.raw_edges().iter().enumerate() {
+        taken.clear();
+        for &a in &[edge.source(), edge.target()] {
+            let mut edges = g.neighbors_undirected(a).detach();
+            while let Some((e, _)) = edges.next(g) {
+                let c = colors[e.index()];
+                if c != NONE {
+                    if c >= taken.len() {
+                        taken.resize(c + 1, false);
+                    }
+                    taken[c] = true;
+                }
+            }
+        }
+        colors[i] = taken.iter().position(|&t| !t).unwrap_or(taken.len());
+    }
+    colors
+}
+
+/// Misra and Gries' edge coloring of a simple graph with *Δ + 1* colors.
+///
+/// `pairs` maps each pair of adjacent nodes, smaller first, to their edge.
+fn misra_gries<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>, pairs: &HashMap<(usize, usize), usize>)
+    -> Vec<usize>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    let neighbors = g.node_indices().map(|a| {
+        g.neighbors_undirected(a).map(|b| b.index()).collect::<Vec<_>>()
+    }).collect::<Vec<_>>();
+    let max_degree = neighbors.iter().map(|adj| adj.len()).max().unwrap_or(0);
+    let mut coloring = EdgeColors {
+        pairs: pairs,
+        colors: vec![None; g.edge_count()],
+        at: vec![vec![None; max_degree + 1]; g.node_count()],
+    };
+    let mut fan = Vec::new();
+    let mut path = Vec::new();
+    for edge in g.raw_edges() {
+        let (u, v) = (edge.source().index(), edge.target().index());
+
+        // a maximal fan of u starting at v: the color of each fan edge is
+        // free on the previous fan node
+        fan.clear();
+        fan.push(v);
+        loop {
+            let last = fan[fan.len() - 1];
+            let next = neighbors[u].iter().cloned().find(|&w| {
+                !fan.contains(&w) && match coloring.color(u, w) {
+                    Some(c) => coloring.is_free(last, c),
+                    None => false,
+                }
+            });
+            match next {
+                Some(w) => fan.push(w),
+                None => break,
+            }
+        }
+        let c = coloring.free_color(u);
+        let d = coloring.free_color(fan[fan.len() - 1]);
+
+        // invert the path from u of edges colored d, c, d, ...
+        path.clear();
+        let (mut x, mut want, mut other) = (u, d, c);
+        while let Some(y) = coloring.at[x][want] {
+            path.push((x, y));
+            x = y;
+            ::std::mem::swap(&mut want, &mut other);
+        }
+        let recolor = path.iter().map(|&(x, y)| {
+            let old = coloring.color(x, y).unwrap();
+            coloring.uncolor(x, y);
+            (x, y, if old == c { d } else { c })
+        }).collect::<Vec<_>>();
+        for (x, y, color) in recolor {
+            coloring.set_color(x, y, color);
+        }
+
+        // the first node of the fan where d is free, while the fan before it
+        // is still a fan
+        let mut end = 0;
+        while !coloring.is_free(fan[end], d) {
+            end += 1;
+            let prev = fan[end - 1];
+            debug_assert!(coloring.color(u, fan[end]).map_or(false, |c| coloring.is_free(prev, c)));
+        }
+
+        // rotate the fan up to there, and color the last edge with d
+        for i in 0..end {
+            let next_color = coloring.color(u, fan[i + 1]).unwrap();
+            coloring.uncolor(u, fan[i + 1]);
+            coloring.set_color(u, fan[i], next_color);
+        }
+        coloring.set_color(u, fan[end], d);
+    }
+    coloring.colors.into_iter().map(|c| c.unwrap()).collect()
+}
+
+/// Edge colors of a simple graph, with the edge of each color at each node.
+struct EdgeColors<'a> {
+    pairs: &'a HashMap<(usize, usize), usize>,
+    colors: Vec<Option<usize>>,
+    /// `at[x][c]` is the node joined to `x` by the edge of color `c`.
+    at: Vec<Vec<Option<usize>>>,
+}
+
+impl<'a> EdgeColors<'a> {
+    fn edge(&self, x: usize, y: usize) -> usize {
+        self.pairs[&(x.min(y), x.max(y))]
+    }
+
+    fn color(&self, x: usize, y: usize) -> Option<usize> {
+        self.colors[self.edge(x, y)]
+    }
+
+    fn is_free(&self, x: usize, c: usize) -> bool {
+        self.at[x][c].is_none()
+    }
+
+    fn free_color(&self, x: usize) -> usize {
+        self.at[x].iter().position(|y| y.is_none()).unwrap()
+    }
+
+    fn set_color(&mut self, x: usize, y: usize, c: usize) {
+        let e = self.edge(x, y);
+        self.colors[e] = Some(c);
+        self.at[x][c] = Some(y);
+        self.at[y][c] = Some(x);
+    }
+
+    fn uncolor(&mut self, x: usize, y: usize) {
+        let e = self.edge(x, y);
+        if let Some(c) = self.colors[e].take() {
+            self.at[x][c] = None;
+            self.at[y][c] = None;
+        }
+    }
+}
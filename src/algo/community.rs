@@ -0,0 +1,129 @@
+use alloc::vec::Vec;
+
+use super::super::{
+    EdgeType,
+    Graph,
+};
+use super::centrality::edge_betweenness;
+use super::super::graph::{
+    IndexType,
+    NodeIndex,
+};
+use super::super::unionfind::UnionFind;
+
+/// Detect communities with the Girvan–Newman algorithm.
+///
+/// The algorithm repeatedly removes the edge with the highest edge
+/// betweenness, the edge on the most shortest paths, which tends to be an
+/// edge between communities. The betweenness is computed again after each
+/// removal. Edge weights are ignored, and for a directed graph shortest
+/// paths follow the edge directions while components are weakly connected.
+///
+/// Return an iterator over the levels of the resulting dendrogram: each time
+/// a removal splits a component, it yields the partition of the nodes into
+/// the components of what is left of the graph. The last partition has each
+/// node on its own. The components are sorted by their smallest node, and
+/// the nodes of each component in index order.
+///
+/// Each level takes **O(|V||E|²)** time in the worst case, as it may remove
+/// many edges.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::girvan_newman;
+/// use petgraph::graph::node_index as n;
+///
+/// // two triangles joined by the edge 2 - 3
+/// let g = Graph::<(), (), Undirected>::from_edges(&[
+///     (0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 5), (5, 3)]);
+/// let mut levels = girvan_newman(&g);
+/// assert_eq!(levels.next(), Some(vec![vec![n(0), n(1), n(2)], vec![n(3), n(4), n(5)]]));
+/// assert_eq!(levels.last().map(|p| p.len()), Some(6));
+/// ```
+pub fn girvan_newman<'a, N, E, Ty, Ix>(g: &'a Graph<N, E, Ty, Ix>) -> GirvanNewman<'a, N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    // self loops never split a component
+    let removed = g.raw_edges().iter().map(|e| e.source() == e.target()).collect::<Vec<_>>();
+    let remaining = removed.iter().filter(|&&r| !r).count();
+    let mut levels = GirvanNewman {
+        g: g,
+        removed: removed,
+        remaining: remaining,
+        components: 0,
+    };
+    levels.components = levels.current_partition().len();
+    levels
+}
+
+/// An iterator over the levels of the Girvan–Newman dendrogram.
+///
+/// Created with [`girvan_newman`](fn.girvan_newman.html).
+pub struct GirvanNewman<'a, N: 'a, E: 'a, Ty: 'a, Ix: 'a + IndexType> {
+    g: &'a Graph<N, E, Ty, Ix>,
+    removed: Vec<bool>,
+    remaining: usize,
+    components: usize,
+}
+
+impl<'a, N, E, Ty, Ix> GirvanNewman<'a, N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    /// Return the components of the edges that are left.
+    fn current_partition(&self) -> Vec<Vec<NodeIndex<Ix>>> {
+        let mut sets = UnionFind::new(self.g.node_count());
+        for (edge, &removed) in self.g.raw_edges().iter().zip(&self.removed) {
+            if !removed {
+                sets.union(edge.source().index(), edge.target().index());
+            }
+        }
+        let mut component_of = vec![None; self.g.node_count()];
+        let mut components = Vec::new();
+        for a in self.g.node_indices() {
+            let root = sets.find_mut(a.index());
+            let c = match component_of[root] {
+                Some(c) => c,
+                None => {
+                    component_of[root] = Some(components.len());
+                    components.push(Vec::new());
+                    components.len() - 1
+                }
+            };
+            components[c].push(a);
+        }
+        components
+    }
+}
+
+impl<'a, N, E, Ty, Ix> Iterator for GirvanNewman<'a, N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    type Item = Vec<Vec<NodeIndex<Ix>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.remaining > 0 {
+            let betweenness = edge_betweenness(self.g, &self.removed);
+            let mut best = None;
+            for (e, &b) in betweenness.iter().enumerate() {
+                if self.removed[e] {
+                    continue;
+                }
+                if best.map_or(true, |(_, max)| b > max) {
+                    best = Some((e, b));
+                }
+            }
+            let (e, _) = best.unwrap();
+            self.removed[e] = true;
+            self.remaining -= 1;
+            let partition = self.current_partition();
+            if partition.len() > self.components {
+                self.components = partition.len();
+                return Some(partition);
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,162 @@
+use std::collections::{
+    HashMap,
+    HashSet,
+    VecDeque,
+};
+use std::hash::Hash;
+
+use super::super::{
+    EdgeType,
+    Graph,
+};
+use super::super::graph::{
+    EdgeIndex,
+    IndexType,
+    NodeIndex,
+};
+
+/// The differences between two versions of a graph, see
+/// [`diff`](fn.diff.html).
+///
+/// Indices of removed nodes and edges are in the old graph, indices of added
+/// ones in the new graph, and changed ones are pairs of an old and a new
+/// index. All lists are sorted by index.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GraphDiff<Ix: IndexType> {
+    /// Nodes of the new graph whose key is not in the old graph.
+    pub added_nodes: Vec<NodeIndex<Ix>>,
+    /// Nodes of the old graph whose key is not in the new graph.
+    pub removed_nodes: Vec<NodeIndex<Ix>>,
+    /// Nodes with the same key in both graphs, but different weights.
+    pub changed_nodes: Vec<(NodeIndex<Ix>, NodeIndex<Ix>)>,
+    /// Edges of the new graph without a match in the old graph.
+    pub added_edges: Vec<EdgeIndex<Ix>>,
+    /// Edges of the old graph without a match in the new graph.
+    pub removed_edges: Vec<EdgeIndex<Ix>>,
+    /// Matching edges with different weights.
+    pub changed_edges: Vec<(EdgeIndex<Ix>, EdgeIndex<Ix>)>,
+}
+
+impl<Ix: IndexType> GraphDiff<Ix> {
+    /// Return `true` if the two graphs have no differences.
+    pub fn is_empty(&self) -> bool {
+        self.added_nodes.is_empty() && self.removed_nodes.is_empty() &&
+            self.changed_nodes.is_empty() && self.added_edges.is_empty() &&
+            self.removed_edges.is_empty() && self.changed_edges.is_empty()
+    }
+}
+
+/// Compute the differences between the graphs `old` and `new`, matching
+/// nodes by the key `node_key` returns for their weights.
+///
+/// Node indices don't need to agree between the two graphs. An edge of
+/// `old` matches an edge of `new` between the nodes with the same keys (in
+/// either direction, if the graphs are undirected); parallel edges match in
+/// the order of their indices. Matching nodes or edges whose weights are not
+/// equal are reported as changed.
+///
+/// **Panics** if two nodes of the same graph have the same key.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::algo::diff;
+/// use petgraph::graph::{edge_index as e, node_index as n};
+///
+/// // crates and their dependencies, with version requirements
+/// let mut old = Graph::<&str, &str>::new();
+/// let app = old.add_node("app");
+/// let log = old.add_node("log");
+/// let time = old.add_node("time");
+/// old.add_edge(app, log, "0.3");
+/// old.add_edge(app, time, "0.1");
+///
+/// let mut new = Graph::<&str, &str>::new();
+/// let app = new.add_node("app");
+/// let log = new.add_node("log");
+/// let rand = new.add_node("rand");
+/// new.add_edge(app, log, "0.4");
+/// new.add_edge(app, rand, "0.3");
+///
+/// let d = diff(&old, &new, |&name| name);
+/// assert_eq!(d.added_nodes, vec![n(2)]);
+/// assert_eq!(d.removed_nodes, vec![n(2)]);
+/// assert!(d.changed_nodes.is_empty());
+/// assert_eq!(d.added_edges, vec![e(1)]);
+/// assert_eq!(d.removed_edges, vec![e(1)]);
+/// assert_eq!(d.changed_edges, vec![(e(0), e(0))]);
+/// ```
+pub fn diff<N, E, Ty, Ix, F, K>(old: &Graph<N, E, Ty, Ix>,
+                                new: &Graph<N, E, Ty, Ix>,
+                                mut node_key: F) -> GraphDiff<Ix>
+    where N: PartialEq,
+          E: PartialEq,
+          Ty: EdgeType,
+          Ix: IndexType,
+          F: FnMut(&N) -> K,
+          K: Hash + Eq,
+{
+    let mut new_index = HashMap::with_capacity(new.node_count());
+    for a in new.node_indices() {
+        let dup = new_index.insert(node_key(&new[a]), a);
+        assert!(dup.is_none(), "diff: two nodes of the new graph have the same key");
+    }
+
+    // the node of `new` matching each node of `old`
+    let mut matching = vec![None; old.node_count()];
+    let mut matched = vec![false; new.node_count()];
+    let mut seen = HashSet::with_capacity(old.node_count());
+    let mut d = GraphDiff {
+        added_nodes: Vec::new(),
+        removed_nodes: Vec::new(),
+        changed_nodes: Vec::new(),
+        added_edges: Vec::new(),
+        removed_edges: Vec::new(),
+        changed_edges: Vec::new(),
+    };
+    for a in old.node_indices() {
+        let key = node_key(&old[a]);
+        match new_index.get(&key) {
+            None => d.removed_nodes.push(a),
+            Some(&b) => {
+                matching[a.index()] = Some(b);
+                matched[b.index()] = true;
+                if old[a] != new[b] {
+                    d.changed_nodes.push((a, b));
+                }
+            }
+        }
+        assert!(seen.insert(key),
+                "diff: two nodes of the old graph have the same key");
+    }
+    d.added_nodes.extend(new.node_indices().filter(|b| !matched[b.index()]));
+
+    // the edges of `new` between each pair of nodes, in index order
+    let pair = |a: NodeIndex<Ix>, b: NodeIndex<Ix>| {
+        let (a, b) = (a.index(), b.index());
+        if new.is_directed() || a <= b { (a, b) } else { (b, a) }
+    };
+    let mut new_edges = HashMap::<_, VecDeque<_>>::with_capacity(new.edge_count());
+    for e in new.edge_indices() {
+        let (a, b) = new.edge_endpoints(e).unwrap();
+        new_edges.entry(pair(a, b)).or_insert_with(VecDeque::new).push_back(e);
+    }
+    let mut edge_matched = vec![false; new.edge_count()];
+    for e in old.edge_indices() {
+        let (a, b) = old.edge_endpoints(e).unwrap();
+        let found = match (matching[a.index()], matching[b.index()]) {
+            (Some(a), Some(b)) => new_edges.get_mut(&pair(a, b)).and_then(|es| es.pop_front()),
+            _ => None,
+        };
+        match found {
+            None => d.removed_edges.push(e),
+            Some(f) => {
+                edge_matched[f.index()] = true;
+                if old[e] != new[f] {
+                    d.changed_edges.push((e, f));
+                }
+            }
+        }
+    }
+    d.added_edges.extend(new.edge_indices().filter(|f| !edge_matched[f.index()]));
+    d
+}
@@ -0,0 +1,148 @@
+use core::hash::{
+    Hash,
+    Hasher,
+};
+use core::mem;
+use alloc::vec::Vec;
+
+use super::super::{
+    EdgeType,
+    Graph,
+};
+use super::super::graph::IndexType;
+
+/// The number of neighborhood refinement rounds.
+const ROUNDS: usize = 3;
+
+/// Compute a 64-bit *fingerprint* of the graph's structure and weights.
+///
+/// The fingerprint does not depend on the order of nodes and edges:
+/// isomorphic graphs with equal weights at matching nodes and edges have
+/// equal fingerprints. It is computed with three rounds of
+/// Weisfeiler–Lehman refinement, where each node label is combined with the
+/// sorted labels and edge weights of its neighborhood, and then the sorted
+/// node and edge labels are combined with the edge type and counts.
+///
+/// Different graphs usually have different fingerprints, but not always:
+/// use it as a hash key, and confirm a match with a full comparison, such as
+/// [`is_isomorphic_matching`](fn.is_isomorphic_matching.html).
+///
+/// **Stability:** the fingerprint only depends on the graph and on the data
+/// that the weights' `Hash` implementations feed to the hasher. It is the
+/// same between runs and on all platforms (integers are hashed little
+/// endian, and `usize` as 64 bits), and it will not change within the same
+/// minor version of petgraph. The hash of standard library types like
+/// `str` could change between Rust versions, in theory.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::algo::fingerprint;
+///
+/// let a = Graph::<&str, u32>::from_edges(&[(0, 1, 7), (1, 2, 8)]);
+/// // the same graph, with the nodes in a different order
+/// let mut b = Graph::<&str, u32>::new();
+/// let x = b.add_node("");
+/// let y = b.add_node("");
+/// let z = b.add_node("");
+/// b.add_edge(x, z, 8);
+/// b.add_edge(y, x, 7);
+/// assert_eq!(fingerprint(&a), fingerprint(&b));
+/// b.add_edge(y, z, 9);
+/// assert!(fingerprint(&a) != fingerprint(&b));
+/// ```
+pub fn fingerprint<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>) -> u64
+    where N: Hash,
+          E: Hash,
+          Ty: EdgeType,
+          Ix: IndexType,
+{
+    let edge_weights = g.raw_edges().iter().map(|e| stable_hash(&e.weight)).collect::<Vec<_>>();
+    let mut labels = g.raw_nodes().iter().map(|a| stable_hash(&a.weight)).collect::<Vec<_>>();
+    let mut neighborhood = Vec::new();
+    for _ in 0..ROUNDS {
+        labels = g.node_indices().map(|a| {
+            // each incident edge as its direction, weight and other end
+            neighborhood.clear();
+            let mut edges = g.neighbors_undirected(a).detach();
+            while let Some((e, b)) = edges.next(g) {
+                let outgoing = g.raw_edges()[e.index()].source() == a;
+                let direction = if !g.is_directed() { 0u8 } else if outgoing { 1 } else { 2 };
+                neighborhood.push((direction, edge_weights[e.index()], labels[b.index()]));
+            }
+            // a self loop is listed once, as outgoing
+            neighborhood.sort();
+            stable_hash(&(labels[a.index()], &neighborhood))
+        }).collect();
+    }
+
+    let mut edge_labels = g.raw_edges().iter().zip(&edge_weights).map(|(edge, &w)| {
+        let (mut a, mut b) = (labels[edge.source().index()], labels[edge.target().index()]);
+        if !g.is_directed() && b < a {
+            mem::swap(&mut a, &mut b);
+        }
+        (a, b, w)
+    }).collect::<Vec<_>>();
+    edge_labels.sort();
+    labels.sort();
+    stable_hash(&(g.is_directed(), g.node_count(), g.edge_count(), labels, edge_labels))
+}
+
+/// Hash `x` with 64-bit FNV-1a.
+fn stable_hash<T: ?Sized + Hash>(x: &T) -> u64 {
+    let mut hasher = StableHasher(0xcbf29ce484222325);
+    x.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// The FNV-1a hash, writing integers in little endian and `usize` as 64
+/// bits, so that the result is the same on all platforms.
+struct StableHasher(u64);
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write_u64(i as u64)
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write_u64(i as u64)
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        let mut bytes = [0; 8];
+        for (k, byte) in bytes.iter_mut().enumerate() {
+            *byte = (i >> (8 * k)) as u8;
+        }
+        self.write(&bytes)
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64)
+    }
+
+    fn write_i16(&mut self, i: i16) {
+        self.write_u64(i as u64)
+    }
+
+    fn write_i32(&mut self, i: i32) {
+        self.write_u64(i as u64)
+    }
+
+    fn write_i64(&mut self, i: i64) {
+        self.write_u64(i as u64)
+    }
+
+    fn write_isize(&mut self, i: isize) {
+        self.write_u64(i as u64)
+    }
+}
@@ -0,0 +1,160 @@
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+
+use super::super::{
+    EdgeDirection,
+    EdgeType,
+    Graph,
+    Incoming,
+    Outgoing,
+};
+use super::scc;
+use super::super::graph::{
+    IndexType,
+    NodeIndex,
+};
+
+/// The states at a fixed point of a dataflow analysis, see
+/// [`fixpoint`](fn.fixpoint.html).
+///
+/// Both vectors are indexed by node index.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fixpoint<L> {
+    /// The state flowing into each node: the join of the outputs of its
+    /// predecessors in the direction of the analysis.
+    pub input: Vec<L>,
+    /// The state flowing out of each node: its transfer function applied
+    /// to its input.
+    pub output: Vec<L>,
+}
+
+/// Solve a dataflow analysis over `g`: iterate to a fixed point where the
+/// input of each node is the join of the outputs of its predecessors, and
+/// its output is `transfer(node, &input)`.
+///
+/// The states flow along the edges for `direction` `Outgoing`, as in
+/// forward analyses like reaching definitions, and against them for
+/// `Incoming`, as in backward analyses like liveness. Nodes without
+/// predecessors have the input `bottom`, and `join` combines states, with
+/// `bottom` as its identity.
+///
+/// The strongly connected components are solved one at a time, in
+/// topological order along the flow, each with a worklist that revisits the
+/// successors of a node whenever its output changes. So an acyclic graph is
+/// solved with a single transfer per node.
+///
+/// The iteration ends if `transfer` is monotone and the lattice has no
+/// infinite ascending chains. For an undirected graph, states flow both
+/// ways along each edge.
+///
+/// ```
+/// use petgraph::{Graph, Outgoing};
+/// use petgraph::algo::fixpoint;
+///
+/// // which nodes reach each node, as bit sets
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 1), (3, 2)]);
+/// let reach = fixpoint(&g, Outgoing, 0u8, |a, b| a | b, |n, input| input | 1 << n.index());
+/// assert_eq!(reach.input, vec![0b0000, 0b1111, 0b1111, 0b0000]);
+/// assert_eq!(reach.output, vec![0b0001, 0b1111, 0b1111, 0b1000]);
+/// ```
+pub fn fixpoint<N, E, Ty, Ix, L, J, T>(g: &Graph<N, E, Ty, Ix>,
+                                       direction: EdgeDirection,
+                                       bottom: L,
+                                       mut join: J,
+                                       mut transfer: T) -> Fixpoint<L>
+    where Ty: EdgeType,
+          Ix: IndexType,
+          L: Clone + PartialEq,
+          J: FnMut(&L, &L) -> L,
+          T: FnMut(NodeIndex<Ix>, &L) -> L,
+{
+    let backward = direction.opposite();
+    let components = components_in_order(g, direction);
+    let mut position = vec![0; g.node_count()];
+    for (i, nodes) in components.iter().enumerate() {
+        for a in nodes {
+            position[a.index()] = i;
+        }
+    }
+    let mut input = vec![bottom.clone(); g.node_count()];
+    let mut output = vec![bottom.clone(); g.node_count()];
+    let mut queued = vec![false; g.node_count()];
+    let mut queue = VecDeque::new();
+    for (i, component) in components.iter().enumerate() {
+        for &a in component {
+            queued[a.index()] = true;
+            queue.push_back(a);
+        }
+        while let Some(a) = queue.pop_front() {
+            queued[a.index()] = false;
+            let mut state = bottom.clone();
+            for b in g.neighbors_directed(a, backward) {
+                state = join(&state, &output[b.index()]);
+            }
+            let state_out = transfer(a, &state);
+            input[a.index()] = state;
+            if state_out == output[a.index()] {
+                continue
+            }
+            output[a.index()] = state_out;
+            // later components are not queued yet, and earlier ones are done
+            for b in g.neighbors_directed(a, direction) {
+                if !queued[b.index()] && position[b.index()] == i {
+                    queued[b.index()] = true;
+                    queue.push_back(b);
+                }
+            }
+        }
+    }
+    Fixpoint {
+        input: input,
+        output: output,
+    }
+}
+
+/// Return the strongly connected components of `g` in topological order
+/// along the edges in `direction`, each sorted by node index.
+pub fn components_in_order<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>, direction: EdgeDirection)
+    -> Vec<Vec<NodeIndex<Ix>>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    let mut components = scc(g);
+    let mut component = vec![0; g.node_count()];
+    for (i, nodes) in components.iter_mut().enumerate() {
+        nodes.sort();
+        for a in nodes.iter() {
+            component[a.index()] = i;
+        }
+    }
+    // Kahn's algorithm on the condensation
+    let mut in_degree = vec![0; components.len()];
+    for edge in g.raw_edges() {
+        let (a, b) = (component[edge.source().index()], component[edge.target().index()]);
+        if a != b {
+            match direction {
+                Outgoing => in_degree[b] += 1,
+                Incoming => in_degree[a] += 1,
+            }
+        }
+    }
+    let mut order = (0..components.len()).filter(|&c| in_degree[c] == 0).collect::<Vec<_>>();
+    let mut i = 0;
+    while i < order.len() {
+        let c = order[i];
+        i += 1;
+        for &a in &components[c] {
+            for b in g.neighbors_directed(a, direction) {
+                let d = component[b.index()];
+                if d != c {
+                    in_degree[d] -= 1;
+                    if in_degree[d] == 0 {
+                        order.push(d);
+                    }
+                }
+            }
+        }
+    }
+    let mut components = components.into_iter().map(Some).collect::<Vec<_>>();
+    order.into_iter().map(|c| components[c].take().unwrap()).collect()
+}
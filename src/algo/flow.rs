@@ -0,0 +1,609 @@
+use alloc::collections::VecDeque;
+use core::cmp::min;
+use core::ops::{
+    Add,
+    Sub,
+};
+use alloc::vec::Vec;
+
+use super::super::{
+    Directed,
+    EdgeType,
+    Graph,
+};
+use super::super::graph::{
+    EdgeIndex,
+    IndexType,
+    NodeIndex,
+};
+
+/// A flow split into paths and cycles, see
+/// [`decompose_flow`](fn.decompose_flow.html).
+#[derive(Clone, Debug, PartialEq)]
+pub struct FlowDecomposition<K, Ix: IndexType> {
+    /// Paths from the source to the sink, as the flow they carry and their
+    /// edges in order.
+    pub paths: Vec<(K, Vec<EdgeIndex<Ix>>)>,
+    /// Cycles of flow that don't contribute to the flow value, as the flow
+    /// they carry and their edges in order.
+    pub cycles: Vec<(K, Vec<EdgeIndex<Ix>>)>,
+}
+
+/// Decompose a flow into source to sink paths and cycles.
+///
+/// `flow` holds the flow on each edge, by edge index; it must be
+/// non-negative and conserved at each node other than `source` and `sink`.
+/// Return paths and cycles whose flows add up to `flow` on every edge; the
+/// paths carry the flow value, the net flow out of `source`. Each path or
+/// cycle empties at least one edge, so there are at most *|E| + 1* of them.
+///
+/// With floating point flows, rounding errors can break conservation; use
+/// integers or values that add exactly.
+///
+/// **Panics** if `flow` does not have one value per edge, if the flow is
+/// not conserved, or if more flow enters `source` than leaves it.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::algo::decompose_flow;
+/// use petgraph::graph::{edge_index as e, node_index as n};
+///
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 2), (1, 3), (2, 3)]);
+/// let flow = decompose_flow(&g, &[3, 2, 1, 2, 3], n(0), n(3));
+/// assert_eq!(flow.paths, vec![(1, vec![e(0), e(2), e(4)]),
+///                             (2, vec![e(0), e(3)]),
+///                             (2, vec![e(1), e(4)])]);
+/// assert!(flow.cycles.is_empty());
+/// ```
+pub fn decompose_flow<N, E, Ix, K>(g: &Graph<N, E, Directed, Ix>,
+                                   flow: &[K],
+                                   source: NodeIndex<Ix>,
+                                   sink: NodeIndex<Ix>) -> FlowDecomposition<K, Ix>
+    where Ix: IndexType,
+          K: Copy + PartialOrd + Default + Add<Output=K> + Sub<Output=K>,
+{
+    assert_eq!(flow.len(), g.edge_count(), "decompose_flow: one flow value per edge");
+    let zero = K::default();
+
+    // Close the flow into a circulation with an extra edge from the sink
+    // back to the source, carrying the flow value. Cycles through it are
+    // the paths.
+    let back_edge = g.edge_count();
+    let mut rest = flow.to_vec();
+    // sum both ways before subtracting, so unsigned flows don't underflow
+    let mut outflow = zero;
+    let mut inflow = zero;
+    let mut outgoing = vec![Vec::new(); g.node_count()];
+    let mut target = Vec::with_capacity(g.edge_count() + 1);
+    for (i, edge) in g.raw_edges().iter().enumerate() {
+        outgoing[edge.source().index()].push(i);
+        target.push(edge.target());
+        if edge.source() == source {
+            outflow = outflow + flow[i];
+        }
+        if edge.target() == source {
+            inflow = inflow + flow[i];
+        }
+    }
+    assert!(!(outflow < inflow), "decompose_flow: net flow into the source");
+    rest.push(outflow - inflow);
+    outgoing[sink.index()].push(back_edge);
+    target.push(source);
+
+    // edges only ever run out of flow, so each node's edges before its
+    // cursor are empty
+    let mut cursor = vec![0; g.node_count()];
+    let mut next_edge = |rest: &[K], a: NodeIndex<Ix>| {
+        let out = &outgoing[a.index()];
+        let c = &mut cursor[a.index()];
+        while *c < out.len() && !(rest[out[*c]] > zero) {
+            *c += 1;
+        }
+        out.get(*c).cloned()
+    };
+    let mut decomposition = FlowDecomposition { paths: Vec::new(), cycles: Vec::new() };
+    let mut on_walk = vec![None; g.node_count()];
+    for start in g.node_indices() {
+        // walk along edges with flow until closing a cycle
+        let mut nodes = vec![start];
+        let mut edges = Vec::new();
+        on_walk[start.index()] = Some(0);
+        loop {
+            let a = nodes[nodes.len() - 1];
+            let e = match next_edge(&rest, a) {
+                Some(e) => e,
+                None => {
+                    assert!(nodes.len() == 1, "decompose_flow: flow is not conserved at {:?}", a);
+                    break
+                }
+            };
+            let b = target[e];
+            edges.push(e);
+            let pos = match on_walk[b.index()] {
+                None => {
+                    on_walk[b.index()] = Some(nodes.len());
+                    nodes.push(b);
+                    continue
+                }
+                Some(pos) => pos,
+            };
+            let value = take_flow(&mut rest, &edges[pos..]);
+            let mut cycle = edges.drain(pos..).collect::<Vec<_>>();
+            for x in nodes.drain(pos + 1..) {
+                on_walk[x.index()] = None;
+            }
+            match cycle.iter().position(|&e| e == back_edge) {
+                None => {
+                    let cycle = cycle.into_iter().map(EdgeIndex::new).collect();
+                    decomposition.cycles.push((value, cycle));
+                }
+                Some(i) => {
+                    let before = cycle.drain(..i + 1).take(i).collect::<Vec<_>>();
+                    let path = cycle.into_iter().chain(before).map(EdgeIndex::new).collect();
+                    decomposition.paths.push((value, path));
+                }
+            }
+        }
+        on_walk[start.index()] = None;
+    }
+    decomposition
+}
+
+/// Subtract the smallest flow on `edges` from each of them, and return it.
+fn take_flow<K>(rest: &mut [K], edges: &[usize]) -> K
+    where K: Copy + PartialOrd + Sub<Output=K>,
+{
+    let mut value = rest[edges[0]];
+    for &e in edges {
+        if rest[e] < value {
+            value = rest[e];
+        }
+    }
+    for &e in edges {
+        rest[e] = rest[e] - value;
+    }
+    value
+}
+
+/// Find a feasible *circulation* with lower and upper bounds on the edges.
+///
+/// A circulation assigns each edge a flow, between `lower` and `upper` for
+/// that edge by edge index, such that the flow into each node equals the
+/// flow out of it. Return the flow on each edge, or `None` if no
+/// circulation fits the bounds.
+///
+/// The lower bounds are sent first; the imbalance they leave is then
+/// repaired with a maximum flow from the nodes with excess to the nodes with
+/// deficit, found with the Edmonds–Karp algorithm in **O(|V| |E|²)** time.
+///
+/// **Panics** if `lower` or `upper` does not have one value per edge.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::algo::circulation;
+///
+/// // a cycle 0 -> 1 -> 2 -> 0 with a shortcut 0 -> 2
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (0, 2)]);
+/// assert_eq!(circulation(&g, &[0, 2, 0, 1], &[5, 5, 5, 1]), Some(vec![2, 2, 3, 1]));
+/// assert_eq!(circulation(&g, &[0, 2, 0, 1], &[5, 5, 2, 1]), None);
+///
+/// // unsigned bounds work too
+/// assert_eq!(circulation(&g, &[0u32, 2, 0, 1], &[5, 5, 5, 1]), Some(vec![2, 2, 3, 1]));
+/// ```
+pub fn circulation<N, E, Ix, K>(g: &Graph<N, E, Directed, Ix>, lower: &[K], upper: &[K])
+    -> Option<Vec<K>>
+    where Ix: IndexType,
+          K: Copy + PartialOrd + Default + Add<Output=K> + Sub<Output=K>,
+{
+    assert_eq!(lower.len(), g.edge_count(), "circulation: one lower bound per edge");
+    assert_eq!(upper.len(), g.edge_count(), "circulation: one upper bound per edge");
+    let zero = K::default();
+    let (source, sink) = (g.node_count(), g.node_count() + 1);
+    let mut residual = Residual::new(g.node_count() + 2);
+    // the lower bounds in and out of each node, kept apart so that unsigned
+    // bounds don't underflow
+    let mut inflow = vec![zero; g.node_count()];
+    let mut outflow = vec![zero; g.node_count()];
+    for (i, edge) in g.raw_edges().iter().enumerate() {
+        if upper[i] < lower[i] {
+            return None
+        }
+        let (a, b) = (edge.source().index(), edge.target().index());
+        residual.add_arc(a, b, upper[i] - lower[i]);
+        outflow[a] = outflow[a] + lower[i];
+        inflow[b] = inflow[b] + lower[i];
+    }
+    let mut supply_arcs = Vec::new();
+    for (a, (&x, &y)) in inflow.iter().zip(&outflow).enumerate() {
+        if x > y {
+            supply_arcs.push(residual.add_arc(source, a, x - y));
+        } else if y > x {
+            residual.add_arc(a, sink, y - x);
+        }
+    }
+    residual.max_flow(source, sink);
+    if supply_arcs.iter().any(|&arc| residual.cap[arc] > zero) {
+        return None
+    }
+    Some((0..g.edge_count()).map(|i| lower[i] + residual.cap[2 * i + 1]).collect())
+}
+
+/// Find a maximum set of *edge-disjoint paths* from `a` to `b`: paths that
+/// share no edge.
+///
+/// By Menger's theorem, their number is the least number of edges whose
+/// removal disconnects `b` from `a`. Return each path as its edges in
+/// order, from `a` to `b`; each path visits a node at most once. The edges
+/// of a directed graph are followed in their direction, those of an
+/// undirected graph either way. There are no paths if `a == b`.
+///
+/// The paths come from a maximum flow with unit capacities, found with
+/// augmenting paths in **O(k |E|)** time for *k* paths.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::edge_disjoint_paths;
+/// use petgraph::graph::node_index as n;
+///
+/// // two triangles sharing node 2
+/// let g = Graph::<(), (), Undirected>::from_edges(&[
+///     (0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 2),
+/// ]);
+/// assert_eq!(edge_disjoint_paths(&g, n(0), n(4)).len(), 2);
+/// ```
+pub fn edge_disjoint_paths<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>,
+                                         a: NodeIndex<Ix>,
+                                         b: NodeIndex<Ix>) -> Vec<Vec<EdgeIndex<Ix>>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    let (residual, count) = edge_flow(g, a, b);
+    flow_paths(g, &residual, 0, count, a, b)
+}
+
+/// Find a maximum set of *vertex-disjoint paths* from `a` to `b`: paths
+/// that share no node other than `a` and `b`.
+///
+/// By Menger's theorem, their number is the least number of nodes other
+/// than `a` and `b` whose removal disconnects `b` from `a`, when `a` and
+/// `b` are not adjacent. An edge from `a` to `b` is a path without inner
+/// nodes, so each such edge counts as one more path. Return the paths like
+/// [`edge_disjoint_paths`](fn.edge_disjoint_paths.html); there are no paths
+/// if `a == b`.
+///
+/// Each node other than `a` and `b` is split into an entry and an exit with
+/// an edge of capacity one between them, and the paths come from a maximum
+/// flow with unit capacities in **O(k (|V| + |E|))** time for *k* paths.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::vertex_disjoint_paths;
+/// use petgraph::graph::node_index as n;
+///
+/// // two triangles sharing node 2
+/// let g = Graph::<(), (), Undirected>::from_edges(&[
+///     (0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 2),
+/// ]);
+/// assert_eq!(vertex_disjoint_paths(&g, n(0), n(4)).len(), 1);
+/// assert_eq!(vertex_disjoint_paths(&g, n(0), n(2)).len(), 2);
+/// assert!(vertex_disjoint_paths(&g, n(2), n(2)).is_empty());
+/// ```
+pub fn vertex_disjoint_paths<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>,
+                                           a: NodeIndex<Ix>,
+                                           b: NodeIndex<Ix>) -> Vec<Vec<EdgeIndex<Ix>>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    let (residual, count) = vertex_flow(g, a, b);
+    flow_paths(g, &residual, 2 * g.node_count(), count, a, b)
+}
+
+/// Return the *edge connectivity* between `a` and `b`: the least number of
+/// edges whose removal disconnects `b` from `a`.
+///
+/// This is the number of [`edge_disjoint_paths`](fn.edge_disjoint_paths.html)
+/// from `a` to `b`, without building the paths. It is `0` if `a == b`.
+pub fn local_edge_connectivity<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>,
+                                             a: NodeIndex<Ix>,
+                                             b: NodeIndex<Ix>) -> usize
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    edge_flow(g, a, b).1
+}
+
+/// Return the *node connectivity* between `a` and `b`: the number of
+/// [`vertex_disjoint_paths`](fn.vertex_disjoint_paths.html) from `a` to
+/// `b`, without building the paths.
+///
+/// When there is no edge from `a` to `b`, this is the least number of other
+/// nodes whose removal disconnects `b` from `a`. It is `0` if `a == b`.
+pub fn local_node_connectivity<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>,
+                                             a: NodeIndex<Ix>,
+                                             b: NodeIndex<Ix>) -> usize
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    vertex_flow(g, a, b).1
+}
+
+/// Return the *edge connectivity* of `g`: the least number of edges whose
+/// removal disconnects it, strongly for a directed graph.
+///
+/// A graph is *k*-edge-connected if this is at least *k*; it is `0` for a
+/// disconnected graph and `1` for a connected graph with a bridge. Graphs
+/// of fewer than two nodes have edge connectivity `0`.
+///
+/// Every cut separates the first node from some other node, so this is the
+/// least [`local_edge_connectivity`](fn.local_edge_connectivity.html) from
+/// the first node to each other node, and back for a directed graph.
+/// Computes in **O(λ |V| |E|)** time for edge connectivity *λ*.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::edge_connectivity;
+///
+/// // a cycle of four nodes with a chord
+/// let mut g = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 0)]);
+/// assert_eq!(edge_connectivity(&g), 2);
+/// g.add_edge(0.into(), 2.into(), ());
+/// assert_eq!(edge_connectivity(&g), 2);
+/// ```
+pub fn edge_connectivity<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>) -> usize
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    let mut nodes = g.node_indices();
+    let first = match nodes.next() {
+        None => return 0,
+        Some(a) => a,
+    };
+    let mut best = None;
+    for b in nodes {
+        let mut cut = local_edge_connectivity(g, first, b);
+        if g.is_directed() {
+            cut = min(cut, local_edge_connectivity(g, b, first));
+        }
+        best = Some(best.map_or(cut, |best| min(best, cut)));
+    }
+    best.unwrap_or(0)
+}
+
+/// Return the *node connectivity* of `g`: the least number of nodes whose
+/// removal disconnects it, strongly for a directed graph, or leaves a
+/// single node.
+///
+/// A graph is *k*-connected if this is at least *k*; it is `0` for a
+/// disconnected graph, `1` for a connected graph with a cut vertex, and
+/// `|V| - 1` for a complete graph. Self loops and parallel edges make no
+/// difference.
+///
+/// This is the least [`local_node_connectivity`](fn.local_node_connectivity.html)
+/// between nodes without an edge between them. With Even's algorithm, only
+/// pairs that start at one of the first *κ + 1* nodes are tried, for node
+/// connectivity *κ*, so it computes in **O(κ² |V| (|V| + |E|))** time.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::node_connectivity;
+///
+/// // two triangles sharing node 2
+/// let g = Graph::<(), (), Undirected>::from_edges(&[
+///     (0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 2),
+/// ]);
+/// assert_eq!(node_connectivity(&g), 1);
+/// ```
+pub fn node_connectivity<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>) -> usize
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    let n = g.node_count();
+    let mut best = n.saturating_sub(1);
+    // some node among the first best + 1 is outside a smallest cut, and is
+    // separated by it from some other node
+    let mut i = 0;
+    while i <= best && i < n {
+        let a = NodeIndex::new(i);
+        for b in g.node_indices() {
+            if b == a {
+                continue
+            }
+            if g.find_edge(a, b).is_none() {
+                best = min(best, local_node_connectivity(g, a, b));
+            }
+            if g.is_directed() && g.find_edge(b, a).is_none() {
+                best = min(best, local_node_connectivity(g, b, a));
+            }
+        }
+        i += 1;
+    }
+    best
+}
+
+/// Compute a maximum flow from `a` to `b` with unit capacity on each edge.
+///
+/// Return the residual network and the flow value.
+fn edge_flow<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>, a: NodeIndex<Ix>, b: NodeIndex<Ix>)
+    -> (Residual<usize>, usize)
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    let mut residual = Residual::new(g.node_count());
+    add_edge_arcs(g, &mut residual, false);
+    let count = residual.max_flow(a.index(), b.index());
+    (residual, count)
+}
+
+/// Compute a maximum flow from `a` to `b` with unit capacity on each edge
+/// and on each node other than `a` and `b`.
+///
+/// Return the residual network, with the node arcs before the edge arcs,
+/// and the flow value.
+fn vertex_flow<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>, a: NodeIndex<Ix>, b: NodeIndex<Ix>)
+    -> (Residual<usize>, usize)
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    // node x enters at 2x and exits at 2x + 1
+    let mut residual = Residual::new(2 * g.node_count());
+    for x in 0..g.node_count() {
+        let cap = if x == a.index() || x == b.index() { g.edge_count() } else { 1 };
+        residual.add_arc(2 * x, 2 * x + 1, cap);
+    }
+    add_edge_arcs(g, &mut residual, true);
+    // the exit of a node reaches its own entry only through cycles
+    if a == b {
+        return (residual, 0)
+    }
+    let count = residual.max_flow(2 * a.index() + 1, 2 * b.index());
+    (residual, count)
+}
+
+/// Add an arc of capacity one for each edge of `g`, and one back for an
+/// undirected edge. With `split` nodes, node `x` enters at `2x` and exits
+/// at `2x + 1`, and the arcs go from exits to entries.
+fn add_edge_arcs<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>, residual: &mut Residual<usize>, split: bool)
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    let entry = |x: usize| if split { 2 * x } else { x };
+    let exit = |x: usize| if split { 2 * x + 1 } else { x };
+    for edge in g.raw_edges() {
+        let (s, t) = (edge.source().index(), edge.target().index());
+        residual.add_arc(exit(s), entry(t), 1);
+        if !g.is_directed() {
+            residual.add_arc(exit(t), entry(s), 1);
+        }
+    }
+}
+
+/// Split the unit flow on the edge arcs of `residual`, from `offset` on, into
+/// `count` paths from `a` to `b`, dropping the cycles on the way.
+fn flow_paths<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>,
+                            residual: &Residual<usize>,
+                            offset: usize,
+                            count: usize,
+                            a: NodeIndex<Ix>,
+                            b: NodeIndex<Ix>) -> Vec<Vec<EdgeIndex<Ix>>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    let stride = if g.is_directed() { 2 } else { 4 };
+    let mut outgoing = vec![Vec::new(); g.node_count()];
+    for (i, edge) in g.raw_edges().iter().enumerate() {
+        // the flow on an arc is the capacity of its reverse
+        let arc = offset + stride * i;
+        let forward = residual.cap[arc + 1];
+        let backward = if g.is_directed() { 0 } else { residual.cap[arc + 3] };
+        if forward > backward {
+            outgoing[edge.source().index()].push((i, edge.target().index()));
+        } else if backward > forward {
+            outgoing[edge.target().index()].push((i, edge.source().index()));
+        }
+    }
+    let mut paths = Vec::with_capacity(count);
+    let mut on_walk = vec![None; g.node_count()];
+    for _ in 0..count {
+        let mut nodes = vec![a.index()];
+        let mut edges = Vec::new();
+        on_walk[a.index()] = Some(0);
+        while nodes[nodes.len() - 1] != b.index() {
+            let (e, x) = outgoing[nodes[nodes.len() - 1]].pop().unwrap();
+            match on_walk[x] {
+                None => {
+                    on_walk[x] = Some(nodes.len());
+                    nodes.push(x);
+                    edges.push(EdgeIndex::new(e));
+                }
+                Some(pos) => {
+                    for y in nodes.drain(pos + 1..) {
+                        on_walk[y] = None;
+                    }
+                    edges.truncate(pos);
+                }
+            }
+        }
+        for &x in &nodes {
+            on_walk[x] = None;
+        }
+        paths.push(edges);
+    }
+    paths
+}
+
+/// A residual network for maximum flow computations.
+///
+/// Arcs are stored in pairs: arc `2i` and its reverse `2i + 1`.
+struct Residual<K> {
+    arcs: Vec<Vec<usize>>,
+    to: Vec<usize>,
+    cap: Vec<K>,
+}
+
+impl<K> Residual<K>
+    where K: Copy + PartialOrd + Default + Add<Output=K> + Sub<Output=K>,
+{
+    fn new(nodes: usize) -> Self {
+        Residual {
+            arcs: vec![Vec::new(); nodes],
+            to: Vec::new(),
+            cap: Vec::new(),
+        }
+    }
+
+    /// Add an arc with capacity `cap`, and return its index.
+    fn add_arc(&mut self, a: usize, b: usize, cap: K) -> usize {
+        let arc = self.to.len();
+        self.arcs[a].push(arc);
+        self.to.push(b);
+        self.cap.push(cap);
+        self.arcs[b].push(arc + 1);
+        self.to.push(a);
+        self.cap.push(K::default());
+        arc
+    }
+
+    /// Push a maximum flow from `source` to `sink` along shortest augmenting
+    /// paths, and return its value.
+    fn max_flow(&mut self, source: usize, sink: usize) -> K {
+        let zero = K::default();
+        let mut total = zero;
+        let mut pred = vec![None; self.arcs.len()];
+        let mut queue = VecDeque::new();
+        loop {
+            for p in &mut pred {
+                *p = None;
+            }
+            queue.clear();
+            queue.push_back(source);
+            while let Some(a) = queue.pop_front() {
+                for &arc in &self.arcs[a] {
+                    let b = self.to[arc];
+                    if b != source && pred[b].is_none() && self.cap[arc] > zero {
+                        pred[b] = Some(arc);
+                        queue.push_back(b);
+                    }
+                }
+            }
+            if pred[sink].is_none() {
+                return total
+            }
+            let mut bottleneck = None;
+            let mut b = sink;
+            while let Some(arc) = pred[b] {
+                if bottleneck.map_or(true, |x| self.cap[arc] < x) {
+                    bottleneck = Some(self.cap[arc]);
+                }
+                b = self.to[arc ^ 1];
+            }
+            let bottleneck = bottleneck.unwrap();
+            let mut b = sink;
+            while let Some(arc) = pred[b] {
+                self.cap[arc] = self.cap[arc] - bottleneck;
+                self.cap[arc ^ 1] = self.cap[arc ^ 1] + bottleneck;
+                b = self.to[arc ^ 1];
+            }
+            total = total + bottleneck;
+        }
+    }
+}
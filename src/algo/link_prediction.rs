@@ -0,0 +1,196 @@
+//! Link prediction scores for pairs of nodes.
+//!
+//! Each function scores node pairs by how likely they are to be adjacent,
+//! judging from their neighborhoods. The pairs are given as an iterator;
+//! use [`non_edges`](fn.non_edges.html) to score all pairs of nodes that are
+//! not yet adjacent.
+//!
+//! Edge directions are ignored, and self loops and parallel edges don't
+//! count: the neighborhood of a node is the set of other nodes adjacent to
+//! it. Neighborhoods are computed once per call as sorted lists, and
+//! common neighbors are found by merging them.
+
+use std::cmp::Ordering;
+
+use super::super::{
+    EdgeType,
+    Graph,
+};
+use super::super::graph::{
+    IndexType,
+    NodeIndex,
+};
+
+/// The sorted neighborhood of each node.
+struct Neighborhoods {
+    neighbors: Vec<Vec<usize>>,
+}
+
+impl Neighborhoods {
+    fn new<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>) -> Self
+        where Ty: EdgeType,
+              Ix: IndexType,
+    {
+        let mut neighbors = vec![Vec::new(); g.node_count()];
+        for edge in g.raw_edges() {
+            let (a, b) = (edge.source().index(), edge.target().index());
+            if a != b {
+                neighbors[a].push(b);
+                neighbors[b].push(a);
+            }
+        }
+        for adj in &mut neighbors {
+            adj.sort();
+            adj.dedup();
+        }
+        Neighborhoods {
+            neighbors: neighbors,
+        }
+    }
+
+    fn degree(&self, a: usize) -> usize {
+        self.neighbors[a].len()
+    }
+
+    /// Call `f` with each common neighbor of `a` and `b`.
+    fn common<F>(&self, a: usize, b: usize, mut f: F)
+        where F: FnMut(usize),
+    {
+        let (xs, ys) = (&self.neighbors[a], &self.neighbors[b]);
+        let (mut i, mut j) = (0, 0);
+        while i < xs.len() && j < ys.len() {
+            match xs[i].cmp(&ys[j]) {
+                Ordering::Less => i += 1,
+                Ordering::Greater => j += 1,
+                Ordering::Equal => {
+                    f(xs[i]);
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+    }
+}
+
+/// Score each pair with `score`.
+fn score_pairs<N, E, Ty, Ix, I, F>(g: &Graph<N, E, Ty, Ix>, pairs: I, mut score: F)
+    -> Vec<(NodeIndex<Ix>, NodeIndex<Ix>, f64)>
+    where Ty: EdgeType,
+          Ix: IndexType,
+          I: IntoIterator<Item=(NodeIndex<Ix>, NodeIndex<Ix>)>,
+          F: FnMut(&Neighborhoods, usize, usize) -> f64,
+{
+    let hoods = Neighborhoods::new(g);
+    pairs.into_iter().map(|(a, b)| (a, b, score(&hoods, a.index(), b.index()))).collect()
+}
+
+/// Return all unordered pairs of distinct nodes that are not adjacent, with
+/// the smaller node index first.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::link_prediction::non_edges;
+/// use petgraph::graph::node_index as n;
+///
+/// let g = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2)]);
+/// assert_eq!(non_edges(&g), vec![(n(0), n(2))]);
+/// ```
+pub fn non_edges<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>) -> Vec<(NodeIndex<Ix>, NodeIndex<Ix>)>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    let hoods = Neighborhoods::new(g);
+    let mut pairs = Vec::new();
+    for a in 0..g.node_count() {
+        let adj = &hoods.neighbors[a];
+        let mut k = 0;
+        for b in a + 1..g.node_count() {
+            while k < adj.len() && adj[k] < b {
+                k += 1;
+            }
+            if k == adj.len() || adj[k] != b {
+                pairs.push((NodeIndex::new(a), NodeIndex::new(b)));
+            }
+        }
+    }
+    pairs
+}
+
+/// Return the *Jaccard coefficient* of each pair: the number of common
+/// neighbors divided by the number of nodes adjacent to either.
+///
+/// Pairs where neither node has neighbors score zero.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::link_prediction::jaccard_coefficient;
+/// use petgraph::graph::node_index as n;
+///
+/// let g = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (0, 2), (3, 1), (3, 4)]);
+/// // 0 and 3 share 1, out of 1, 2 and 4
+/// let scores = jaccard_coefficient(&g, vec![(n(0), n(3))]);
+/// assert_eq!(scores, vec![(n(0), n(3), 1. / 3.)]);
+/// ```
+pub fn jaccard_coefficient<N, E, Ty, Ix, I>(g: &Graph<N, E, Ty, Ix>, pairs: I)
+    -> Vec<(NodeIndex<Ix>, NodeIndex<Ix>, f64)>
+    where Ty: EdgeType,
+          Ix: IndexType,
+          I: IntoIterator<Item=(NodeIndex<Ix>, NodeIndex<Ix>)>,
+{
+    score_pairs(g, pairs, |hoods, a, b| {
+        let mut common = 0;
+        hoods.common(a, b, |_| common += 1);
+        let union = hoods.degree(a) + hoods.degree(b) - common;
+        if union == 0 { 0. } else { common as f64 / union as f64 }
+    })
+}
+
+/// Return the *Adamic–Adar index* of each pair: the sum of *1 / ln(d)* over
+/// their common neighbors, where *d* is the degree of the common neighbor.
+///
+/// Common neighbors with few neighbors of their own count for more. The
+/// pair of a node with itself scores infinity if the node has a neighbor
+/// of degree one.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::link_prediction::adamic_adar_index;
+/// use petgraph::graph::node_index as n;
+///
+/// let g = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (0, 2), (3, 1), (3, 4)]);
+/// let scores = adamic_adar_index(&g, vec![(n(0), n(3))]);
+/// assert_eq!(scores, vec![(n(0), n(3), 1. / 2f64.ln())]);
+/// ```
+pub fn adamic_adar_index<N, E, Ty, Ix, I>(g: &Graph<N, E, Ty, Ix>, pairs: I)
+    -> Vec<(NodeIndex<Ix>, NodeIndex<Ix>, f64)>
+    where Ty: EdgeType,
+          Ix: IndexType,
+          I: IntoIterator<Item=(NodeIndex<Ix>, NodeIndex<Ix>)>,
+{
+    score_pairs(g, pairs, |hoods, a, b| {
+        let mut score = 0.;
+        hoods.common(a, b, |c| score += 1. / (hoods.degree(c) as f64).ln());
+        score
+    })
+}
+
+/// Return the *preferential attachment* score of each pair: the product of
+/// their degrees.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::link_prediction::preferential_attachment;
+/// use petgraph::graph::node_index as n;
+///
+/// let g = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (0, 2), (3, 1), (3, 4)]);
+/// let scores = preferential_attachment(&g, vec![(n(0), n(3)), (n(1), n(2))]);
+/// assert_eq!(scores, vec![(n(0), n(3), 4.), (n(1), n(2), 2.)]);
+/// ```
+pub fn preferential_attachment<N, E, Ty, Ix, I>(g: &Graph<N, E, Ty, Ix>, pairs: I)
+    -> Vec<(NodeIndex<Ix>, NodeIndex<Ix>, f64)>
+    where Ty: EdgeType,
+          Ix: IndexType,
+          I: IntoIterator<Item=(NodeIndex<Ix>, NodeIndex<Ix>)>,
+{
+    score_pairs(g, pairs, |hoods, a, b| (hoods.degree(a) * hoods.degree(b)) as f64)
+}
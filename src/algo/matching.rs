@@ -0,0 +1,126 @@
+use alloc::vec::Vec;
+
+use super::super::{
+    EdgeType,
+    Graph,
+};
+use super::super::graph::{
+    EdgeIndex,
+    IndexType,
+};
+
+/// Compute a *maximal matching* of the graph greedily: take each edge, in
+/// index order, whose endpoints are not matched yet.
+///
+/// A matching is a set of edges without common endpoints; it is maximal if
+/// no edge can be added to it. A maximal matching has at least half as many
+/// edges as a maximum matching. Edge directions are ignored and self loops
+/// are never taken. Computes in **O(|V| + |E|)** time.
+///
+/// Return the edges of the matching, in index order.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::{greedy_maximal_matching, is_maximal_matching};
+/// use petgraph::graph::edge_index as e;
+///
+/// // a path of four edges
+/// let g = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 4)]);
+/// let matching = greedy_maximal_matching(&g);
+/// assert_eq!(matching, vec![e(0), e(2)]);
+/// assert!(is_maximal_matching(&g, &matching));
+/// ```
+pub fn greedy_maximal_matching<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>) -> Vec<EdgeIndex<Ix>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    let mut matched = vec![false; g.node_count()];
+    let mut matching = Vec::new();
+    for (i, edge) in g.raw_edges().iter().enumerate() {
+        let (a, b) = (edge.source().index(), edge.target().index());
+        if a != b && !matched[a] && !matched[b] {
+            matched[a] = true;
+            matched[b] = true;
+            matching.push(EdgeIndex::new(i));
+        }
+    }
+    matching
+}
+
+/// Return `true` if `matching` is a matching of `g`: edges of `g`, none of
+/// them a self loop, and no two with a common endpoint.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::is_matching;
+/// use petgraph::graph::edge_index as e;
+///
+/// let g = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2), (2, 3)]);
+/// assert!(is_matching(&g, &[e(0), e(2)]));
+/// assert!(!is_matching(&g, &[e(0), e(1)]));
+/// assert!(!is_matching(&g, &[e(3)]));
+/// ```
+pub fn is_matching<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>, matching: &[EdgeIndex<Ix>]) -> bool
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    matched_nodes(g, matching).is_some()
+}
+
+/// Return `true` if `matching` is a matching of `g` that no edge of `g` can
+/// be added to.
+pub fn is_maximal_matching<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>,
+                                         matching: &[EdgeIndex<Ix>]) -> bool
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    let matched = match matched_nodes(g, matching) {
+        None => return false,
+        Some(matched) => matched,
+    };
+    g.raw_edges().iter().all(|edge| {
+        let (a, b) = (edge.source().index(), edge.target().index());
+        a == b || matched[a] || matched[b]
+    })
+}
+
+/// Return `true` if `matching` is a matching of `g` that covers every node.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::is_perfect_matching;
+/// use petgraph::graph::edge_index as e;
+///
+/// let g = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2), (2, 3)]);
+/// assert!(is_perfect_matching(&g, &[e(0), e(2)]));
+/// assert!(!is_perfect_matching(&g, &[e(1)]));
+/// ```
+pub fn is_perfect_matching<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>,
+                                         matching: &[EdgeIndex<Ix>]) -> bool
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    is_matching(g, matching) && 2 * matching.len() == g.node_count()
+}
+
+/// Return which nodes `matching` covers, or `None` if it is not a matching
+/// of `g`.
+fn matched_nodes<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>, matching: &[EdgeIndex<Ix>])
+    -> Option<Vec<bool>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    let mut matched = vec![false; g.node_count()];
+    for &e in matching {
+        let (a, b) = match g.edge_endpoints(e) {
+            None => return None,
+            Some((a, b)) => (a.index(), b.index()),
+        };
+        if a == b || matched[a] || matched[b] {
+            return None
+        }
+        matched[a] = true;
+        matched[b] = true;
+    }
+    Some(matched)
+}
@@ -0,0 +1,1910 @@
+//! Graph algorithms.
+//!
+//! It is a goal to gradually migrate the algorithms to be based on graph traits
+//! so that they are generally applicable. For now, most of these use only the
+//! **Graph** type.
+
+#[cfg(feature = "std")]
+pub mod link_prediction;
+pub mod partition;
+pub mod tree;
+mod all_pairs;
+mod assignment;
+mod canonical;
+mod centrality;
+mod coarsen;
+#[cfg(feature = "std")]
+mod coloring;
+mod community;
+#[cfg(feature = "std")]
+mod diff;
+mod fingerprint;
+mod fixpoint;
+mod flow;
+mod matching;
+mod planar;
+mod reachability;
+mod spanning;
+#[cfg(feature = "std")]
+mod spqr;
+#[cfg(feature = "std")]
+mod summary;
+
+use core::cmp::min;
+use core::mem;
+use core::ops::Add;
+use alloc::collections::{
+    BinaryHeap,
+    VecDeque,
+};
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::hash::Hash;
+
+use super::{
+    Directed,
+    Graph,
+    Undirected,
+    EdgeDirection,
+    EdgeType,
+    Outgoing,
+    Incoming,
+    Dfs,
+};
+use scored::MinScored;
+use super::visit::{
+    Externals,
+    NeighborIter,
+    NeighborsDirected,
+    NodeCompactIndexable,
+    NodeCount,
+    NodeIdentifiers,
+    Revisitable,
+    Topo,
+    Visitable,
+    VisitMap,
+};
+use super::unionfind::UnionFind;
+use super::graph::{
+    EdgeIndex,
+    NodeIndex,
+    IndexType,
+};
+
+pub use self::all_pairs::{
+    floyd_warshall,
+    floyd_warshall_paths,
+    DistanceMatrix,
+    Rows,
+};
+pub use self::assignment::{
+    hungarian,
+    hungarian_matching,
+};
+pub use self::canonical::{
+    automorphisms,
+    automorphisms_by,
+    canonical_form,
+    canonical_form_by,
+    Automorphisms,
+};
+pub use self::centrality::edge_betweenness_centrality;
+pub use self::coarsen::{
+    coarsen,
+    CoarseLevel,
+};
+#[cfg(feature = "std")]
+pub use self::coloring::greedy_edge_coloring;
+pub use self::community::{
+    girvan_newman,
+    GirvanNewman,
+};
+pub use self::fingerprint::fingerprint;
+pub use self::fixpoint::{
+    fixpoint,
+    Fixpoint,
+};
+pub use self::flow::{
+    circulation,
+    decompose_flow,
+    edge_connectivity,
+    edge_disjoint_paths,
+    local_edge_connectivity,
+    local_node_connectivity,
+    node_connectivity,
+    vertex_disjoint_paths,
+    FlowDecomposition,
+};
+pub use super::isomorphism::{
+    is_isomorphic,
+    is_isomorphic_matching,
+};
+#[cfg(feature = "std")]
+pub use self::diff::{
+    diff,
+    GraphDiff,
+};
+#[cfg(feature = "std")]
+pub use super::dijkstra::{
+    bfs_predecessors,
+    dijkstra,
+    dijkstra_bounded,
+    dijkstra_into,
+    dijkstra_path,
+    dijkstra_predecessors,
+    multi_source_dijkstra,
+    shortest_path,
+    widest_path,
+    ScoreMap,
+};
+pub use self::matching::{
+    greedy_maximal_matching,
+    is_matching,
+    is_maximal_matching,
+    is_perfect_matching,
+};
+pub use self::planar::{
+    dual_graph,
+    is_planar,
+    planar_embedding,
+    Faces,
+    PlanarEmbedding,
+    Rotation,
+};
+pub use self::reachability::Reachability;
+pub use self::spanning::{
+    spanning_tree_count,
+    spanning_tree_count_f64,
+    spanning_trees,
+    SpanningTrees,
+};
+#[cfg(feature = "std")]
+pub use self::spqr::{
+    spqr_tree,
+    SkeletonEdge,
+    SpqrKind,
+    SpqrNode,
+};
+#[cfg(feature = "std")]
+pub use self::summary::{
+    summary,
+    GraphSummary,
+};
+
+/// Return `true` if the input graph contains a cycle.
+///
+/// Always treats the input graph as if undirected.
+pub fn is_cyclic_undirected<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>) -> bool
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    let mut edge_sets = UnionFind::new(g.node_count());
+    for edge in g.raw_edges() {
+        let (a, b) = (edge.source(), edge.target());
+
+        // union the two vertices of the edge
+        //  -- if they were already the same, then we have a cycle
+        if !edge_sets.union(a.index(), b.index()) {
+            return true
+        }
+    }
+    false
+}
+
+/// **Deprecated: Renamed to `is_cyclic_undirected`.**
+pub fn is_cyclic<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>) -> bool
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    is_cyclic_undirected(g)
+}
+
+/// Return `true` if the input directed graph contains a cycle.
+///
+/// Using the topological sort algorithm.
+///
+/// Graphs with undirected edges are treated as if each edge was a cycle
+/// of length two.
+pub fn is_cyclic_directed<'a, G>(g: &'a G) -> bool
+    where G: Externals<'a> + NeighborsDirected<'a> + Visitable + NodeCount,
+{
+    let mut n_ordered = 0;
+    let mut topo = Topo::new(g);
+    while let Some(_) = topo.next(g) {
+        n_ordered += 1;
+    }
+    n_ordered != g.node_count()
+}
+
+/// Find a cycle in the graph, if there is one.
+///
+/// Return the cycle as a sequence of `(node, edge)` pairs, where `edge`
+/// leads from `node` to the node of the next pair, and the edge of the last
+/// pair leads back to the first node. Return `None` if the graph is acyclic.
+///
+/// For an undirected graph, an edge is never traversed back the way it was
+/// entered, so the shortest cycles are self loops and pairs of parallel
+/// edges.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::algo::find_cycle;
+/// use petgraph::graph::{edge_index as e, node_index as n};
+///
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 1)]);
+/// assert_eq!(find_cycle(&g), Some(vec![(n(1), e(1)), (n(2), e(2)), (n(3), e(3))]));
+/// ```
+pub fn find_cycle<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>)
+    -> Option<Vec<(NodeIndex<Ix>, EdgeIndex<Ix>)>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    // position of each node on the dfs stack, if it is on it
+    let mut stack_pos = vec![None; g.node_count()];
+    let mut finished = g.visit_map();
+    let mut stack = Vec::new();
+    for start in g.node_indices() {
+        if finished.is_visited(&start) {
+            continue
+        }
+        stack_pos[start.index()] = Some(0);
+        stack.push((start, EdgeIndex::end(), g.neighbors(start).detach()));
+        while !stack.is_empty() {
+            let top = stack.len() - 1;
+            let (a, entered_by) = (stack[top].0, stack[top].1);
+            match stack[top].2.next(g) {
+                None => {
+                    stack.pop();
+                    stack_pos[a.index()] = None;
+                    finished.visit(a);
+                }
+                Some((e, _)) if !g.is_directed() && e == entered_by => {}
+                Some((e, b)) => {
+                    if let Some(pos) = stack_pos[b.index()] {
+                        // the edges leading out of each node on the stack
+                        // are the edges that entered the node above it
+                        let mut cycle = Vec::with_capacity(top + 1 - pos);
+                        for i in pos..top {
+                            cycle.push((stack[i].0, stack[i + 1].1));
+                        }
+                        cycle.push((a, e));
+                        return Some(cycle);
+                    }
+                    if !finished.is_visited(&b) {
+                        stack_pos[b.index()] = Some(stack.len());
+                        stack.push((b, e, g.neighbors(b).detach()));
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Perform a topological sort of a directed graph.
+///
+/// Return a vector of nodes in topological order: each node is ordered
+/// before its successors.
+///
+/// If the returned vec contains less than all the nodes of the graph, then
+/// the graph was cyclic.
+pub fn toposort<'a, G>(g: &'a G) -> Vec<G::NodeId>
+    where G: Externals<'a> + NeighborsDirected<'a> + Visitable + NodeCount,
+{
+    let mut order = Vec::with_capacity(g.node_count());
+    let mut topo = Topo::new(g);
+    while let Some(nx) = topo.next(g) {
+        order.push(nx);
+    }
+    order
+}
+
+/// Group the nodes of a directed acyclic graph into *generations*: the
+/// first holds the nodes without predecessors, and each next one the nodes
+/// whose predecessors are all in earlier generations.
+///
+/// The nodes of a generation have no edges between them, so as tasks with
+/// edges as dependencies, each generation can run concurrently once the
+/// previous ones are done. The generation of a node is the length of the
+/// longest path that ends in it. Each generation lists its nodes in index
+/// order.
+///
+/// Return `None` if the graph has a cycle. Computes in **O(|V| + |E|)**
+/// time.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::algo::topological_generations;
+/// use petgraph::graph::node_index as n;
+///
+/// let g = Graph::<(), ()>::from_edges(&[(0, 2), (1, 2), (0, 3), (2, 4), (3, 4)]);
+/// assert_eq!(topological_generations(&g),
+///            Some(vec![vec![n(0), n(1)], vec![n(2), n(3)], vec![n(4)]]));
+/// ```
+pub fn topological_generations<N, E, Ix>(g: &Graph<N, E, Directed, Ix>)
+    -> Option<Vec<Vec<NodeIndex<Ix>>>>
+    where Ix: IndexType,
+{
+    let mut in_degree = vec![0; g.node_count()];
+    for edge in g.raw_edges() {
+        in_degree[edge.target().index()] += 1;
+    }
+    let mut generation = g.node_indices().filter(|a| in_degree[a.index()] == 0).collect::<Vec<_>>();
+    let mut generations = Vec::new();
+    let mut ordered = 0;
+    while !generation.is_empty() {
+        ordered += generation.len();
+        let mut next = Vec::new();
+        for &a in &generation {
+            for b in g.neighbors_directed(a, Outgoing) {
+                in_degree[b.index()] -= 1;
+                if in_degree[b.index()] == 0 {
+                    next.push(b);
+                }
+            }
+        }
+        next.sort();
+        generations.push(mem::replace(&mut generation, next));
+    }
+    if ordered == g.node_count() {
+        Some(generations)
+    } else {
+        None
+    }
+}
+
+/// Return `true` if the graph is a tree: connected and without cycles.
+///
+/// Edge directions are ignored, so a directed graph is a tree if its
+/// underlying undirected graph is. Self loops and parallel edges count as
+/// cycles. The empty graph is not a tree.
+///
+/// A tree with *n* nodes has exactly *n - 1* edges, and any graph with
+/// *n - 1* edges and no cycle is connected, so only that needs checking.
+pub fn is_tree<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>) -> bool
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    g.node_count() != 0 &&
+        g.edge_count() == g.node_count() - 1 &&
+        !is_cyclic_undirected(g)
+}
+
+/// Return `true` if the graph is a forest: every connected component is a
+/// tree.
+///
+/// Edge directions are ignored, like in [`is_tree`](fn.is_tree.html).
+/// The empty graph is a forest.
+pub fn is_forest<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>) -> bool
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    // a forest has at most n - 1 edges, one less per extra component
+    (g.edge_count() == 0 || g.edge_count() < g.node_count()) &&
+        !is_cyclic_undirected(g)
+}
+
+/// Return `true` if the graph is a directed acyclic graph.
+///
+/// Graphs with undirected edges are treated as if each edge was a cycle
+/// of length two, see [`is_cyclic_directed`](fn.is_cyclic_directed.html).
+pub fn is_dag<'a, G>(g: &'a G) -> bool
+    where G: Externals<'a> + NeighborsDirected<'a> + Visitable + NodeCount,
+{
+    !is_cyclic_directed(g)
+}
+
+/// Return the *descendants* of `node`: the nodes reachable from it by a
+/// path of one or more edges.
+///
+/// The set is the graph's visitor map: a `FixedBitSet` of node indices for
+/// `Graph`, a `HashSet` of nodes for `GraphMap`. The node itself is a
+/// descendant only if it is on a cycle, which in an undirected graph means
+/// that it has an edge. Computes in **O(|V| + |E|)** time.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::algo::{ancestors, descendants};
+///
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (3, 1)]);
+/// let below = descendants(&g, 1.into());
+/// assert_eq!(below.ones().collect::<Vec<_>>(), vec![2]);
+/// let above = ancestors(&g, 1.into());
+/// assert_eq!(above.ones().collect::<Vec<_>>(), vec![0, 3]);
+/// ```
+pub fn descendants<'a, G>(g: &'a G, node: G::NodeId) -> G::Map
+    where G: NeighborsDirected<'a> + Visitable,
+{
+    reachable(g, Some(node), Outgoing)
+}
+
+/// Return the *ancestors* of `node`: the nodes that reach it by a path of
+/// one or more edges.
+///
+/// These are the descendants of `node` in the graph with its edges
+/// reversed, see [`descendants`](fn.descendants.html), so the same as
+/// `descendants(&Reversed(&g), node)`.
+pub fn ancestors<'a, G>(g: &'a G, node: G::NodeId) -> G::Map
+    where G: NeighborsDirected<'a> + Visitable,
+{
+    reachable(g, Some(node), Incoming)
+}
+
+/// Return the nodes reachable from any of `nodes` by a path of one or more
+/// edges, see [`descendants`](fn.descendants.html).
+///
+/// Computes in **O(|V| + |E|)** time however many nodes there are.
+pub fn descendants_of_set<'a, G, I>(g: &'a G, nodes: I) -> G::Map
+    where G: NeighborsDirected<'a> + Visitable,
+          I: IntoIterator<Item=G::NodeId>,
+{
+    reachable(g, nodes, Outgoing)
+}
+
+fn reachable<'a, G, I>(g: &'a G, nodes: I, dir: EdgeDirection) -> G::Map
+    where G: NeighborsDirected<'a> + Visitable,
+          I: IntoIterator<Item=G::NodeId>,
+{
+    let mut reached = g.visit_map();
+    // the start nodes are only marked once reached themselves
+    let mut stack = nodes.into_iter().collect::<Vec<_>>();
+    while let Some(a) = stack.pop() {
+        for b in g.neighbors_directed(a, dir) {
+            if reached.visit(b.clone()) {
+                stack.push(b);
+            }
+        }
+    }
+    reached
+}
+
+/// Workspace for a depth first search, to reuse its stack and visitor map
+/// across calls to [`has_path_connecting`](fn.has_path_connecting.html).
+#[derive(Clone, Debug)]
+pub struct DfsSpace<N, VM> {
+    dfs: Dfs<N, VM>,
+}
+
+impl<N, VM> DfsSpace<N, VM>
+    where N: Clone,
+          VM: VisitMap<N>,
+{
+    /// Create a new `DfsSpace` for searches in `g`.
+    pub fn new<G>(g: &G) -> Self
+        where G: Visitable<NodeId=N, Map=VM>,
+    {
+        DfsSpace {
+            dfs: Dfs::empty(g),
+        }
+    }
+}
+
+/// Return `true` if there is a path from `from` to `to`, following the
+/// edges of a directed graph in their direction. Every node has a path to
+/// itself.
+///
+/// The search is a depth first search that stops when it reaches `to`. Pass
+/// a [`DfsSpace`](struct.DfsSpace.html) to reuse its allocations when
+/// checking many pairs; with `None`, a new workspace is allocated. For
+/// repeated queries on a graph that does not change, see
+/// [`Reachability`](struct.Reachability.html).
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::algo::{has_path_connecting, DfsSpace};
+/// use petgraph::graph::node_index as n;
+///
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (3, 2)]);
+/// assert!(has_path_connecting(&g, n(0), n(2), None));
+///
+/// let mut space = DfsSpace::new(&g);
+/// assert!(!has_path_connecting(&g, n(0), n(3), Some(&mut space)));
+/// assert!(has_path_connecting(&g, n(3), n(2), Some(&mut space)));
+/// ```
+pub fn has_path_connecting<'a, G>(g: &'a G,
+                                  from: G::NodeId,
+                                  to: G::NodeId,
+                                  space: Option<&mut DfsSpace<G::NodeId, G::Map>>) -> bool
+    where G: NeighborIter<'a> + Revisitable,
+          G::NodeId: PartialEq,
+{
+    let mut local;
+    let space = match space {
+        Some(space) => space,
+        None => {
+            local = DfsSpace::new(g);
+            &mut local
+        }
+    };
+    let dfs = &mut space.dfs;
+    g.reset_map(&mut dfs.discovered);
+    dfs.move_to(from);
+    while let Some(a) = dfs.next(g) {
+        if a == to {
+            return true
+        }
+    }
+    false
+}
+
+/// Compute the *strongly connected components* using Kosaraju's algorithm.
+///
+/// Return a vector where each element is an scc.
+///
+/// For an undirected graph, the sccs are simply the connected components.
+pub fn scc<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>) -> Vec<Vec<NodeIndex<Ix>>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    let mut dfs = Dfs::empty(g);
+
+    // First phase, reverse dfs pass, compute finishing times.
+    // http://stackoverflow.com/a/26780899/161659
+    let mut finished = g.visit_map();
+    let mut finish_order = Vec::new();
+    for i in 0..g.node_count() {
+        let nindex = NodeIndex::new(i);
+        if dfs.discovered.is_visited(&nindex) {
+            continue
+        }
+
+        dfs.stack.push(nindex);
+        while let Some(&nx) = dfs.stack.last() {
+            if dfs.discovered.visit(nx) {
+                // First time visiting `nx`: Push neighbors, don't pop `nx`
+                for succ in g.neighbors_directed(nx, EdgeDirection::Incoming) {
+                    if !dfs.discovered.is_visited(&succ) {
+                        dfs.stack.push(succ);
+                    }
+                }
+            } else {
+                dfs.stack.pop();
+                if finished.visit(nx) {
+                    // Second time: All reachable nodes must have been finished
+                    finish_order.push(nx);
+                }
+            }
+        }
+    }
+
+    dfs.discovered.clear();
+    let mut sccs = Vec::new();
+
+    // Second phase
+    // Process in decreasing finishing time order
+    for &nindex in finish_order.iter().rev() {
+        if dfs.discovered.is_visited(&nindex) {
+            continue;
+        }
+        // Move to the leader node.
+        dfs.move_to(nindex);
+        //let leader = nindex;
+        let mut scc = Vec::new();
+        while let Some(nx) = dfs.next(g) {
+            scc.push(nx);
+        }
+        sccs.push(scc);
+    }
+    sccs
+}
+
+/// Condense every strongly connected component into a single node and return the result.
+///
+/// If `make_acyclic` is true, self-loops and multi edges are ignored, guaranteeing that
+/// the output is acyclic.
+pub fn condensation<N, E, Ty, Ix>(g: Graph<N, E, Ty, Ix>, make_acyclic: bool) -> Graph<Vec<N>, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    let sccs = scc(&g);
+    let mut condensed: Graph<Vec<N>, E, Ty, Ix> = Graph::with_capacity(sccs.len(), g.edge_count());
+
+    // Build a map from old indices to new ones.
+    let mut node_map = vec![NodeIndex::end(); g.node_count()];
+    for comp in sccs {
+        let new_nix = condensed.add_node(Vec::new());
+        for nix in comp {
+            node_map[nix.index()] = new_nix;
+        }
+    }
+
+    // Consume nodes and edges of the old graph and insert them into the new one.
+    let (nodes, edges) = g.into_nodes_edges();
+    for (nix, node) in nodes.into_iter().enumerate() {
+        condensed[node_map[nix]].push(node.weight);
+    }
+    for edge in edges {
+        let source = node_map[edge.source().index()];
+        let target = node_map[edge.target().index()];
+        if make_acyclic {
+            if source != target {
+                condensed.update_edge(source, target, edge.weight);
+            }
+        } else {
+            condensed.add_edge(source, target, edge.weight);
+        }
+    }
+    condensed
+}
+
+/// Return the number of connected components of the graph.
+///
+/// For a directed graph, this is the *weakly* connected components.
+pub fn connected_components<'a, G>(g: &'a G) -> usize
+    where G: NodeIdentifiers<'a> + NeighborsDirected<'a> + Visitable,
+{
+    let mut discovered = g.visit_map();
+    let mut stack = Vec::new();
+    let mut components = 0;
+    for node in g.node_identifiers() {
+        if !discovered.visit(node.clone()) {
+            continue;
+        }
+        components += 1;
+        stack.push(node);
+        // walk edges in both directions to find the weakly connected component
+        while let Some(nx) = stack.pop() {
+            for &dir in &[Outgoing, Incoming] {
+                for succ in g.neighbors_directed(nx.clone(), dir) {
+                    if discovered.visit(succ.clone()) {
+                        stack.push(succ);
+                    }
+                }
+            }
+        }
+    }
+    components
+}
+
+/// Return `true` if the graph is connected.
+///
+/// For a directed graph, this is whether it is *weakly* connected, see
+/// [`is_weakly_connected`](fn.is_weakly_connected.html). The empty graph is
+/// connected.
+pub fn is_connected<'a, G>(g: &'a G) -> bool
+    where G: NodeCount + NodeIdentifiers<'a> + NeighborsDirected<'a> + Visitable,
+{
+    is_weakly_connected(g)
+}
+
+/// Return `true` if the graph is weakly connected: if every node can be
+/// reached from every other node when ignoring edge directions.
+///
+/// Stops at the first node that can't be reached. The empty graph is
+/// connected.
+pub fn is_weakly_connected<'a, G>(g: &'a G) -> bool
+    where G: NodeCount + NodeIdentifiers<'a> + NeighborsDirected<'a> + Visitable,
+{
+    reaches_all(g, &[Outgoing, Incoming])
+}
+
+/// Return `true` if the graph is strongly connected: if every node can be
+/// reached from every other node along the edge directions.
+///
+/// This holds if every node is reachable from one node, both along and
+/// against the edges, so two searches suffice. The empty graph is connected.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::algo::{is_strongly_connected, is_weakly_connected};
+///
+/// let mut g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2)]);
+/// assert!(is_weakly_connected(&g));
+/// assert!(!is_strongly_connected(&g));
+/// g.extend_with_edges(&[(2, 0)]);
+/// assert!(is_strongly_connected(&g));
+/// ```
+pub fn is_strongly_connected<'a, G>(g: &'a G) -> bool
+    where G: NodeCount + NodeIdentifiers<'a> + NeighborsDirected<'a> + Visitable,
+{
+    reaches_all(g, &[Outgoing]) && reaches_all(g, &[Incoming])
+}
+
+/// Return `true` if all nodes can be reached from the first node, following
+/// edges in the directions `dirs`.
+fn reaches_all<'a, G>(g: &'a G, dirs: &[EdgeDirection]) -> bool
+    where G: NodeCount + NodeIdentifiers<'a> + NeighborsDirected<'a> + Visitable,
+{
+    let start = match g.node_identifiers().next() {
+        None => return true,
+        Some(start) => start,
+    };
+    let mut discovered = g.visit_map();
+    let mut stack = Vec::new();
+    let mut reached = 1;
+    discovered.visit(start.clone());
+    stack.push(start);
+    while let Some(nx) = stack.pop() {
+        for &dir in dirs {
+            for succ in g.neighbors_directed(nx.clone(), dir) {
+                if discovered.visit(succ.clone()) {
+                    reached += 1;
+                    stack.push(succ);
+                }
+            }
+        }
+    }
+    reached == g.node_count()
+}
+
+/// Label each node with the id of its connected component.
+///
+/// For a directed graph, this is the *weakly* connected components.
+///
+/// Returns a vector indexed by node index. The components are numbered from
+/// `0` in the order their first node appears in `g.node_identifiers()`.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::algo::connected_component_labels;
+///
+/// let mut g = Graph::<(), ()>::from_edges(&[(0, 1), (2, 3), (3, 1)]);
+/// g.add_node(());
+/// assert_eq!(connected_component_labels(&g), [0, 0, 0, 0, 1]);
+/// ```
+pub fn connected_component_labels<'a, G>(g: &'a G) -> Vec<usize>
+    where G: NodeCompactIndexable + NodeIdentifiers<'a> + NeighborsDirected<'a>,
+{
+    // unlabeled nodes are marked with !0
+    let mut labels = vec![!0; g.node_count()];
+    let mut stack = Vec::new();
+    let mut components = 0;
+    for node in g.node_identifiers() {
+        if labels[g.to_index(node.clone())] != !0 {
+            continue;
+        }
+        labels[g.to_index(node.clone())] = components;
+        stack.push(node);
+        // walk edges in both directions to find the weakly connected component
+        while let Some(nx) = stack.pop() {
+            for &dir in &[Outgoing, Incoming] {
+                for succ in g.neighbors_directed(nx.clone(), dir) {
+                    let i = g.to_index(succ.clone());
+                    if labels[i] == !0 {
+                        labels[i] = components;
+                        stack.push(succ);
+                    }
+                }
+            }
+        }
+        components += 1;
+    }
+    labels
+}
+
+/// Return the connected components of the graph, each as a vector of its
+/// nodes.
+///
+/// For a directed graph, this is the *weakly* connected components.
+///
+/// The components are in the same order as their labels from
+/// [`connected_component_labels`](fn.connected_component_labels.html), and
+/// the nodes of each component are in node index order.
+pub fn connected_component_groups<'a, G>(g: &'a G) -> Vec<Vec<G::NodeId>>
+    where G: NodeCompactIndexable + NodeIdentifiers<'a> + NeighborsDirected<'a>,
+{
+    let labels = connected_component_labels(g);
+    let count = labels.iter().max().map_or(0, |&label| label + 1);
+    let mut groups = vec![Vec::new(); count];
+    for (i, &label) in labels.iter().enumerate() {
+        groups[label].push(g.from_index(i));
+    }
+    groups
+}
+
+/// Compute the *biconnected components* (blocks) of the graph.
+///
+/// A block is a maximal subgraph that stays connected when any one of its
+/// nodes is removed. Every edge belongs to exactly one block, so each block
+/// is returned as the list of its edges. Blocks share nodes only at
+/// articulation points. Nodes without edges belong to no block, and neither
+/// do self loops.
+///
+/// Edge directions are ignored.
+///
+/// Uses Hopcroft and Tarjan's depth first search algorithm.
+pub fn biconnected_components<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>)
+    -> Vec<Vec<EdgeIndex<Ix>>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    const UNVISITED: usize = !0;
+    let mut disc = vec![UNVISITED; g.node_count()];
+    let mut low = vec![UNVISITED; g.node_count()];
+    let mut time = 0;
+    let mut edge_stack = Vec::new();
+    let mut stack = Vec::new();
+    let mut blocks = Vec::new();
+    for root in g.node_indices() {
+        if disc[root.index()] != UNVISITED {
+            continue
+        }
+        disc[root.index()] = time;
+        low[root.index()] = time;
+        time += 1;
+        stack.push((root, EdgeIndex::end(), g.neighbors_undirected(root).detach()));
+        while !stack.is_empty() {
+            let top = stack.len() - 1;
+            let (a, entered_by) = (stack[top].0, stack[top].1);
+            match stack[top].2.next(g) {
+                Some((e, b)) => {
+                    if e == entered_by || b == a {
+                        continue
+                    }
+                    if disc[b.index()] == UNVISITED {
+                        disc[b.index()] = time;
+                        low[b.index()] = time;
+                        time += 1;
+                        edge_stack.push(e);
+                        stack.push((b, e, g.neighbors_undirected(b).detach()));
+                    } else if disc[b.index()] < disc[a.index()] {
+                        // back edge to an ancestor; edges to descendants were
+                        // already seen from the other end
+                        low[a.index()] = min(low[a.index()], disc[b.index()]);
+                        edge_stack.push(e);
+                    }
+                }
+                None => {
+                    stack.pop();
+                    if let Some(&(parent, _, _)) = stack.last() {
+                        low[parent.index()] = min(low[parent.index()], low[a.index()]);
+                        if low[a.index()] >= disc[parent.index()] {
+                            // parent separates the subtree of a: pop its block
+                            let mut block = Vec::new();
+                            while let Some(e) = edge_stack.pop() {
+                                block.push(e);
+                                if e == entered_by {
+                                    break
+                                }
+                            }
+                            blocks.push(block);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    blocks
+}
+
+/// A node of a block-cut tree, see [`block_cut_tree`](fn.block_cut_tree.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BlockCutNode<Ix> {
+    /// A biconnected block, with its nodes in index order.
+    Block(Vec<NodeIndex<Ix>>),
+    /// A cut vertex (articulation point): a node that is part of several
+    /// blocks.
+    Cut(NodeIndex<Ix>),
+}
+
+/// Build the *block-cut tree* of the graph.
+///
+/// The tree has a node for each biconnected block and for each cut vertex,
+/// and an edge between each cut vertex and the blocks that contain it. If
+/// the graph is disconnected, the result is a forest with one tree per
+/// component. Nodes without edges (other than self loops) form blocks of
+/// their own. Edge directions are ignored.
+///
+/// Return the tree and, for each node of `g` by index, the tree node it maps
+/// to: its cut vertex node, or else the single block it belongs to. Blocks
+/// come first in the tree, followed by the cut vertices.
+///
+/// Nodes `a` and `b` share a block if they map to the same block, or if a
+/// cut vertex is adjacent to the block of the other, or if both are cut
+/// vertices with a common neighbor in the tree.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::{block_cut_tree, BlockCutNode};
+/// use petgraph::graph::node_index as n;
+///
+/// // two triangles sharing node 2
+/// let g = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2), (2, 0),
+///                                                   (2, 3), (3, 4), (4, 2)]);
+/// let (tree, map) = block_cut_tree(&g);
+/// assert_eq!(tree.node_count(), 3);
+/// assert_eq!(tree[map[2]], BlockCutNode::Cut(n(2)));
+/// assert!(tree.find_edge(map[2], map[0]).is_some());
+/// assert!(tree.find_edge(map[2], map[4]).is_some());
+/// assert!(map[0] != map[4]);
+/// ```
+pub fn block_cut_tree<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>)
+    -> (Graph<BlockCutNode<Ix>, (), Undirected, Ix>, Vec<NodeIndex<Ix>>)
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    let mut block_nodes = Vec::new();
+    // number of blocks of each node
+    let mut block_count = vec![0; g.node_count()];
+    let mut in_block = g.visit_map();
+    for block in biconnected_components(g) {
+        let mut nodes = Vec::with_capacity(block.len() + 1);
+        for e in block {
+            let (a, b) = g.edge_endpoints(e).unwrap();
+            nodes.push(a);
+            nodes.push(b);
+        }
+        nodes.sort();
+        nodes.dedup();
+        for &a in &nodes {
+            block_count[a.index()] += 1;
+            in_block.visit(a);
+        }
+        block_nodes.push(nodes);
+    }
+    for a in g.node_indices() {
+        if !in_block.is_visited(&a) {
+            block_count[a.index()] = 1;
+            block_nodes.push(vec![a]);
+        }
+    }
+    block_nodes.sort();
+
+    let mut tree = Graph::with_capacity(block_nodes.len(), 0);
+    let mut map = vec![NodeIndex::end(); g.node_count()];
+    for nodes in &block_nodes {
+        let block = tree.add_node(BlockCutNode::Block(nodes.clone()));
+        for &a in nodes {
+            if block_count[a.index()] == 1 {
+                map[a.index()] = block;
+            }
+        }
+    }
+    for (i, nodes) in block_nodes.iter().enumerate() {
+        for &a in nodes {
+            if block_count[a.index()] > 1 {
+                if map[a.index()] == NodeIndex::end() {
+                    map[a.index()] = tree.add_node(BlockCutNode::Cut(a));
+                }
+                tree.add_edge(map[a.index()], NodeIndex::new(i), ());
+            }
+        }
+    }
+    (tree, map)
+}
+
+/// Compute the *2-edge-connected components* of the graph.
+///
+/// Two nodes are in the same component if they stay connected when any one
+/// edge is removed, which is the same as being connected without using any
+/// *bridge*. Return the components as lists of nodes in index order; the
+/// components are ordered by their first node.
+///
+/// Edge directions are ignored.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::algo::two_edge_connected_components;
+/// use petgraph::graph::node_index as n;
+///
+/// // two triangles joined by the bridge 2 - 3
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (2, 3),
+///                                       (3, 4), (4, 5), (5, 3)]);
+/// assert_eq!(two_edge_connected_components(&g),
+///            vec![vec![n(0), n(1), n(2)], vec![n(3), n(4), n(5)]]);
+/// ```
+pub fn two_edge_connected_components<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>)
+    -> Vec<Vec<NodeIndex<Ix>>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    // a bridge is a block of a single edge; parallel edges always share
+    // their block
+    let mut vertex_sets = UnionFind::new(g.node_count());
+    for block in biconnected_components(g) {
+        if block.len() > 1 {
+            for e in block {
+                let (a, b) = g.edge_endpoints(e).unwrap();
+                vertex_sets.union(a.index(), b.index());
+            }
+        }
+    }
+    let mut group_of = vec![None; g.node_count()];
+    let mut groups = Vec::<Vec<_>>::new();
+    for a in g.node_indices() {
+        let root = vertex_sets.find_mut(a.index());
+        let group = match group_of[root] {
+            Some(group) => group,
+            None => {
+                group_of[root] = Some(groups.len());
+                groups.push(Vec::new());
+                groups.len() - 1
+            }
+        };
+        groups[group].push(a);
+    }
+    groups
+}
+
+/// Return the subgraph induced by the nodes within `radius` hops of `center`,
+/// the *ego graph* of `center`.
+///
+/// For a directed graph, `dir` selects whether the hops follow the edges
+/// (`Outgoing`) or go against them (`Incoming`). For an undirected graph it
+/// has no effect.
+///
+/// The subgraph has all edges of `g` between its nodes. Also returns the
+/// original node index of each node of the subgraph: node `i` of the
+/// subgraph is node `nodes[i]` of `g`. The nodes keep their relative order,
+/// so the subgraph's node indices are compatible with `g` when all nodes are
+/// within `radius`.
+///
+/// ```
+/// use petgraph::{Graph, Outgoing};
+/// use petgraph::algo::ego_graph;
+/// use petgraph::graph::node_index as n;
+///
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 1), (4, 0)]);
+/// let (ego, nodes) = ego_graph(&g, n(1), 1, Outgoing);
+/// assert_eq!(nodes, [n(1), n(2)]);
+/// assert_eq!(ego.edge_count(), 1);
+/// ```
+pub fn ego_graph<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>,
+                               center: NodeIndex<Ix>,
+                               radius: usize,
+                               dir: EdgeDirection)
+    -> (Graph<N, E, Ty, Ix>, Vec<NodeIndex<Ix>>)
+    where N: Clone,
+          E: Clone,
+          Ty: EdgeType,
+          Ix: IndexType,
+{
+    // breadth first search, stopping at the given radius
+    let mut distance = vec![None; g.node_count()];
+    let mut queue = VecDeque::new();
+    distance[center.index()] = Some(0);
+    queue.push_back(center);
+    while let Some(a) = queue.pop_front() {
+        let d = match distance[a.index()] {
+            Some(d) if d < radius => d,
+            _ => continue,
+        };
+        for b in g.neighbors_directed(a, dir) {
+            if distance[b.index()].is_none() {
+                distance[b.index()] = Some(d + 1);
+                queue.push_back(b);
+            }
+        }
+    }
+
+    let ego = g.filter_map(|a, w| distance[a.index()].map(|_| w.clone()),
+                           |_, w| Some(w.clone()));
+    let nodes = g.node_indices().filter(|a| distance[a.index()].is_some()).collect();
+    (ego, nodes)
+}
+
+/// The tree found by a breadth first search, see [`bfs_tree`](fn.bfs_tree.html).
+///
+/// The vectors are indexed by node index, and have `None` for the nodes that
+/// were not reached.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BfsTree<Ix: IndexType> {
+    /// The nodes reached, in breadth first order, starting with the root.
+    pub order: Vec<NodeIndex<Ix>>,
+    /// The parent of each node; `None` for the root too.
+    pub parent: Vec<Option<NodeIndex<Ix>>>,
+    /// The edge from the parent of each node.
+    pub parent_edge: Vec<Option<EdgeIndex<Ix>>>,
+    /// The depth of each node: the number of edges on the shortest path from
+    /// the root.
+    pub depth: Vec<Option<usize>>,
+}
+
+impl<Ix: IndexType> BfsTree<Ix> {
+    /// Return the tree as a graph, with edges from parents to children.
+    ///
+    /// The graph has all the nodes of the searched graph, with identical node
+    /// indices; the weight of a node is its depth, and the weight of an edge
+    /// is the index of the edge of the searched graph it comes from.
+    pub fn to_graph(&self) -> Graph<Option<usize>, EdgeIndex<Ix>, Directed, Ix> {
+        let n = self.depth.len();
+        let mut tree = Graph::with_capacity(n, self.order.len().saturating_sub(1));
+        for &depth in &self.depth {
+            tree.add_node(depth);
+        }
+        // add the edges in breadth first order
+        for &b in &self.order {
+            if let (Some(a), Some(e)) = (self.parent[b.index()], self.parent_edge[b.index()]) {
+                tree.add_edge(a, b, e);
+            }
+        }
+        tree
+    }
+}
+
+/// Compute the tree of a breadth first search from `root`: a shortest path
+/// tree, by number of edges, of the nodes reachable from `root`.
+///
+/// Edges are followed in their direction, or both ways in an undirected
+/// graph. Computes in **O(|V| + |E|)** time.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::algo::bfs_tree;
+/// use petgraph::graph::node_index as n;
+///
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 3), (2, 3), (4, 0)]);
+/// let tree = bfs_tree(&g, n(0));
+/// assert_eq!(tree.depth, vec![Some(0), Some(1), Some(1), Some(2), None]);
+/// assert_eq!(tree.parent[0], None);
+/// assert!(tree.parent[3] == Some(n(1)) || tree.parent[3] == Some(n(2)));
+///
+/// let t = tree.to_graph();
+/// assert_eq!(t.node_count(), 5);
+/// assert_eq!(t.edge_count(), 3);
+/// ```
+pub fn bfs_tree<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>, root: NodeIndex<Ix>) -> BfsTree<Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    let mut tree = BfsTree {
+        order: Vec::new(),
+        parent: vec![None; g.node_count()],
+        parent_edge: vec![None; g.node_count()],
+        depth: vec![None; g.node_count()],
+    };
+    tree.depth[root.index()] = Some(0);
+    tree.order.push(root);
+    let mut i = 0;
+    while i < tree.order.len() {
+        let a = tree.order[i];
+        i += 1;
+        let depth = tree.depth[a.index()].map(|d| d + 1);
+        let mut edges = g.neighbors(a).detach();
+        while let Some((e, b)) = edges.next(g) {
+            if tree.depth[b.index()].is_none() {
+                tree.depth[b.index()] = depth;
+                tree.parent[b.index()] = Some(a);
+                tree.parent_edge[b.index()] = Some(e);
+                tree.order.push(b);
+            }
+        }
+    }
+    tree
+}
+
+/// Compute a *minimum spanning tree* of a graph.
+///
+/// Treat the input graph as undirected.
+///
+/// Using Kruskal's algorithm with runtime **O(|E| log |E|)**. We actually
+/// return a minimum spanning forest, i.e. a minimum spanning tree for each connected
+/// component of the graph.
+///
+/// The resulting graph has all the vertices of the input graph (with identical node indices),
+/// and **|V| - c** edges, where **c** is the number of connected components in `g`.
+///
+/// Only the weights of the tree edges are cloned; see
+/// [`min_spanning_tree_edges`](fn.min_spanning_tree_edges.html) to avoid
+/// cloning altogether.
+pub fn min_spanning_tree<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>)
+    -> Graph<N, E, Undirected, Ix>
+    where N: Clone,
+          E: Clone + PartialOrd,
+          Ty: EdgeType,
+          Ix: IndexType,
+{
+    let tree_edges = min_spanning_tree_edges(g, |weight| weight);
+
+    // Create a mst skeleton by copying all nodes
+    let mut mst = Graph::with_capacity(g.node_count(), tree_edges.len());
+    for node in g.raw_nodes() {
+        mst.add_node(node.weight.clone());
+    }
+    for e in tree_edges {
+        let edge = &g.raw_edges()[e.index()];
+        mst.add_edge(edge.source(), edge.target(), edge.weight.clone());
+    }
+
+    debug_assert!(mst.node_count() == g.node_count());
+    debug_assert!(mst.edge_count() < g.node_count() || g.node_count() == 0);
+    mst
+}
+
+/// Compute the edges of a *minimum spanning tree* of a graph, by the cost
+/// `edge_cost` of each edge weight.
+///
+/// Treat the input graph as undirected. Like
+/// [`min_spanning_tree`](fn.min_spanning_tree.html), this is a minimum
+/// spanning forest, with a tree for each connected component, but edge
+/// weights are only borrowed: they need not be `Clone`, and the cost can be
+/// a reference into the weight, or computed from it.
+///
+/// Return the indices of the tree edges, in the order they are picked: by
+/// increasing cost, ties in edge index order. Costs that are not comparable
+/// to themselves (NaN) are picked last.
+///
+/// Using Kruskal's algorithm with runtime **O(|E| log |E|)**.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::min_spanning_tree_edges;
+/// use petgraph::graph::edge_index as e;
+///
+/// // a weight that is not `Clone`
+/// struct Cable {
+///     length: f64,
+/// }
+///
+/// let mut g = Graph::<(), Cable, Undirected>::new_undirected();
+/// let a = g.add_node(());
+/// let b = g.add_node(());
+/// let c = g.add_node(());
+/// g.add_edge(a, b, Cable { length: 3. });
+/// g.add_edge(b, c, Cable { length: 1. });
+/// g.add_edge(c, a, Cable { length: 2. });
+///
+/// let tree = min_spanning_tree_edges(&g, |cable| cable.length);
+/// assert_eq!(tree, vec![e(1), e(2)]);
+/// ```
+pub fn min_spanning_tree_edges<'a, N, E, Ty, Ix, F, K>(g: &'a Graph<N, E, Ty, Ix>,
+                                                      mut edge_cost: F)
+    -> Vec<EdgeIndex<Ix>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+          F: FnMut(&'a E) -> K,
+          K: PartialOrd,
+{
+    let costs = g.raw_edges().iter().map(|edge| edge_cost(&edge.weight)).collect::<Vec<_>>();
+    let mut order = (0..costs.len()).collect::<Vec<_>>();
+    // MinScored orders by decreasing cost, with NaN least
+    order.sort_by(|&i, &j| MinScored(&costs[j], ()).cmp(&MinScored(&costs[i], ())));
+
+    // Kruskal's algorithm: pick the edges by increasing cost, if they
+    // connect two disjoint trees of the forest so far.
+    let mut subgraphs = UnionFind::new(g.node_count());
+    let mut tree_edges = Vec::with_capacity(g.node_count().saturating_sub(1));
+    for i in order {
+        let edge = &g.raw_edges()[i];
+        if subgraphs.union(edge.source().index(), edge.target().index()) {
+            tree_edges.push(EdgeIndex::new(i));
+        }
+    }
+    tree_edges
+}
+
+/// Compute a *minimum cycle basis* of the graph.
+///
+/// A cycle basis is a set of cycles from which every cycle (every edge set
+/// with even degree at each node) is obtained as a symmetric difference.
+/// A minimum cycle basis has the least total weight, where the weight of a
+/// cycle is the sum of `edge_cost` over its edges. Costs must be
+/// non-negative.
+///
+/// Edge directions are ignored. Return each cycle as its edges in index
+/// order; there are *|E| - |V| + c* cycles, where *c* is the number of
+/// connected components.
+///
+/// Uses De Pina's algorithm, with a shortest path search per witness
+/// edge in a two-layered copy of the graph:
+/// **O(|E|² |V| log |V|)** time.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::minimum_cycle_basis;
+/// use petgraph::graph::edge_index as e;
+///
+/// // a square with a diagonal, which is shorter than going around
+/// let g = Graph::<(), u32, Undirected>::from_edges(&[
+///     (0, 1, 1), (1, 2, 1), (2, 3, 1), (3, 0, 1), (0, 2, 1)]);
+/// let mut basis = minimum_cycle_basis(&g, |w| *w);
+/// basis.sort();
+/// assert_eq!(basis, vec![vec![e(0), e(1), e(4)], vec![e(2), e(3), e(4)]]);
+/// ```
+pub fn minimum_cycle_basis<N, E, Ty, Ix, F, K>(g: &Graph<N, E, Ty, Ix>, mut edge_cost: F)
+    -> Vec<Vec<EdgeIndex<Ix>>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+          F: FnMut(&E) -> K,
+          K: Default + Add<Output=K> + Copy + PartialOrd,
+{
+    let costs = g.raw_edges().iter().map(|edge| edge_cost(&edge.weight)).collect::<Vec<_>>();
+
+    // The edges outside a spanning forest each close one independent cycle;
+    // witnesses are subsets of them.
+    let mut forest = UnionFind::new(g.node_count());
+    let mut position = vec![None; g.edge_count()];
+    let mut dim = 0;
+    for (i, edge) in g.raw_edges().iter().enumerate() {
+        if !forest.union(edge.source().index(), edge.target().index()) {
+            position[i] = Some(dim);
+            dim += 1;
+        }
+    }
+    let mut witnesses = (0..dim).map(|i| {
+        let mut witness = vec![false; dim];
+        witness[i] = true;
+        witness
+    }).collect::<Vec<_>>();
+
+    let mut basis = Vec::with_capacity(dim);
+    for i in 0..dim {
+        let cycle = shortest_odd_cycle(g, &costs, &position, &witnesses[i]);
+        let (done, rest) = witnesses.split_at_mut(i + 1);
+        for witness in rest {
+            let odd = cycle.iter().filter(|&&e| {
+                position[e].map_or(false, |p| witness[p])
+            }).count() % 2 == 1;
+            if odd {
+                for (w, &s) in witness.iter_mut().zip(&done[i]) {
+                    *w ^= s;
+                }
+            }
+        }
+        basis.push(cycle.into_iter().map(EdgeIndex::new).collect());
+    }
+    basis
+}
+
+/// Compute the *fundamental cycles* of a spanning forest.
+///
+/// `tree_edges` are the edges of a spanning forest of `g`. Each other edge
+/// closes exactly one cycle with the tree edges; together these cycles form
+/// a cycle basis. Return, for each non-tree edge in index order, its cycle
+/// as a list of edges in cycle order, starting with the non-tree edge.
+/// Non-tree edges joining two different trees close no cycle and are
+/// skipped.
+///
+/// Edge directions are ignored. The cycles are found by walking up from both
+/// endpoints to their lowest common ancestor in the tree, so this takes time
+/// proportional to the total length of the cycles.
+///
+/// **Panics** if the tree edges contain a cycle.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::fundamental_cycles;
+/// use petgraph::graph::edge_index as e;
+///
+/// // a square with a diagonal, and the spanning tree 0 - 1 - 2 - 3
+/// let g = Graph::<(), (), Undirected>::from_edges(&[
+///     (0, 1), (1, 2), (2, 3), (3, 0), (0, 2)]);
+/// let cycles = fundamental_cycles(&g, vec![e(0), e(1), e(2)]);
+/// assert_eq!(cycles, vec![vec![e(3), e(0), e(1), e(2)],
+///                         vec![e(4), e(1), e(0)]]);
+/// ```
+pub fn fundamental_cycles<N, E, Ty, Ix, I>(g: &Graph<N, E, Ty, Ix>, tree_edges: I)
+    -> Vec<Vec<EdgeIndex<Ix>>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+          I: IntoIterator<Item=EdgeIndex<Ix>>,
+{
+    let mut in_tree = vec![false; g.edge_count()];
+    let mut tree_adj = vec![Vec::new(); g.node_count()];
+    for e in tree_edges {
+        let (a, b) = g.edge_endpoints(e).unwrap();
+        in_tree[e.index()] = true;
+        tree_adj[a.index()].push((e, b));
+        tree_adj[b.index()].push((e, a));
+    }
+
+    // root each tree at its first node, and record parent links and depths
+    let mut parent = vec![None; g.node_count()];
+    let mut depth = vec![0; g.node_count()];
+    let mut root = vec![NodeIndex::end(); g.node_count()];
+    let mut queue = VecDeque::new();
+    for start in g.node_indices() {
+        if root[start.index()] != NodeIndex::end() {
+            continue
+        }
+        root[start.index()] = start;
+        queue.push_back(start);
+        while let Some(a) = queue.pop_front() {
+            for &(e, b) in &tree_adj[a.index()] {
+                if parent[a.index()] == Some((e, b)) {
+                    continue
+                }
+                assert!(root[b.index()] == NodeIndex::end(),
+                        "fundamental_cycles: the tree edges contain a cycle");
+                root[b.index()] = start;
+                parent[b.index()] = Some((e, a));
+                depth[b.index()] = depth[a.index()] + 1;
+                queue.push_back(b);
+            }
+        }
+    }
+
+    let mut cycles = Vec::new();
+    for (i, edge) in g.raw_edges().iter().enumerate() {
+        let (mut a, mut b) = (edge.source(), edge.target());
+        if in_tree[i] || root[a.index()] != root[b.index()] {
+            continue
+        }
+        // the path from b up to the common ancestor, and from a up to it
+        let mut up_from_b = Vec::new();
+        let mut up_from_a = Vec::new();
+        while a != b {
+            if depth[b.index()] >= depth[a.index()] {
+                let (e, next) = parent[b.index()].unwrap();
+                up_from_b.push(e);
+                b = next;
+            } else {
+                let (e, next) = parent[a.index()].unwrap();
+                up_from_a.push(e);
+                a = next;
+            }
+        }
+        let mut cycle = Vec::with_capacity(1 + up_from_b.len() + up_from_a.len());
+        cycle.push(EdgeIndex::new(i));
+        cycle.extend(up_from_b);
+        cycle.extend(up_from_a.into_iter().rev());
+        cycles.push(cycle);
+    }
+    cycles
+}
+
+/// Return the edges of a shortest cycle that uses an odd number of the
+/// edges in `witness`.
+///
+/// Walks from a node to its copy in a graph of two layers, where the edges
+/// of the witness cross between the layers.
+fn shortest_odd_cycle<N, E, Ty, Ix, K>(g: &Graph<N, E, Ty, Ix>,
+                                       costs: &[K],
+                                       position: &[Option<usize>],
+                                       witness: &[bool]) -> Vec<usize>
+    where Ty: EdgeType,
+          Ix: IndexType,
+          K: Default + Add<Output=K> + Copy + PartialOrd,
+{
+    let odd = |e: EdgeIndex<Ix>| position[e.index()].map_or(false, |p| witness[p]);
+    let mut best: Option<(K, Vec<usize>)> = None;
+    let mut tried = g.visit_map();
+    for (i, edge) in g.raw_edges().iter().enumerate() {
+        // every odd cycle passes through the start of some witness edge
+        let start = edge.source();
+        if !odd(EdgeIndex::new(i)) || !tried.visit(start) {
+            continue
+        }
+        let (source, target) = (2 * start.index(), 2 * start.index() + 1);
+        let mut dist = vec![None; 2 * g.node_count()];
+        let mut pred = vec![(0, 0); 2 * g.node_count()];
+        let mut finished = vec![false; 2 * g.node_count()];
+        let mut visit_next = BinaryHeap::new();
+        dist[source] = Some(K::default());
+        visit_next.push(MinScored(K::default(), source));
+        while let Some(MinScored(d, state)) = visit_next.pop() {
+            if finished[state] {
+                continue
+            }
+            if let Some((ref best_cost, _)) = best {
+                if !(d < *best_cost) {
+                    break
+                }
+            }
+            finished[state] = true;
+            if state == target {
+                // keep the edges walked an odd number of times
+                let mut walk = Vec::new();
+                let mut s = target;
+                while s != source {
+                    walk.push(pred[s].0);
+                    s = pred[s].1;
+                }
+                walk.sort();
+                let mut cycle = Vec::with_capacity(walk.len());
+                for e in walk {
+                    if cycle.last() == Some(&e) {
+                        cycle.pop();
+                    } else {
+                        cycle.push(e);
+                    }
+                }
+                best = Some((d, cycle));
+                break
+            }
+            let a = NodeIndex::<Ix>::new(state / 2);
+            let mut edges = g.neighbors_undirected(a).detach();
+            while let Some((e, b)) = edges.next(g) {
+                let next = 2 * b.index() + ((state % 2) ^ odd(e) as usize);
+                let next_dist = d + costs[e.index()];
+                let shorter = match dist[next] {
+                    None => true,
+                    Some(old) => next_dist < old,
+                };
+                if !finished[next] && shorter {
+                    dist[next] = Some(next_dist);
+                    pred[next] = (e.index(), state);
+                    visit_next.push(MinScored(next_dist, next));
+                }
+            }
+        }
+    }
+    best.map(|(_, cycle)| cycle).unwrap_or_default()
+}
+
+/// Return the adjacency matrix of the graph as a row-major `n × n` matrix,
+/// where `n` is the number of nodes.
+///
+/// The entry at row `i` and column `j` (at index `i * n + j`) counts the
+/// edges from the node with index `i` to the node with index `j`. For an
+/// undirected graph the matrix is symmetric.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::algo::adjacency_matrix;
+///
+/// let mut g = Graph::<(), ()>::new();
+/// let a = g.add_node(());
+/// let b = g.add_node(());
+/// g.add_edge(a, b, ());
+/// g.add_edge(a, b, ());
+///
+/// assert_eq!(adjacency_matrix(&g), [0., 2.,
+///                                   0., 0.]);
+/// ```
+pub fn adjacency_matrix<'a, G>(g: &'a G) -> Vec<f64>
+    where G: NodeCompactIndexable + NodeIdentifiers<'a> + NeighborIter<'a>,
+{
+    let n = g.node_count();
+    let mut matrix = vec![0.; n * n];
+    for a in g.node_identifiers() {
+        let i = g.to_index(a.clone());
+        for b in g.neighbors(a) {
+            matrix[i * n + g.to_index(b)] += 1.;
+        }
+    }
+    matrix
+}
+
+/// Return the Laplacian matrix *L = D - W* of the graph as a row-major
+/// `n × n` matrix, where `n` is the number of nodes.
+///
+/// *W* is the weighted adjacency matrix, where each edge from `a` to `b`
+/// adds `weight_fn(a, b)` to its entry, and *D* is the diagonal matrix of
+/// the row sums of *W*. Self loops are left out. For a directed graph this
+/// is the out-degree Laplacian.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::algo::laplacian_matrix;
+///
+/// let mut g = Graph::new_undirected();
+/// let a = g.add_node(());
+/// let b = g.add_node(());
+/// let c = g.add_node(());
+/// g.add_edge(a, b, 2.);
+/// g.add_edge(b, c, 1.);
+///
+/// let l = laplacian_matrix(&g, |a, b| g[g.find_edge(a, b).unwrap()]);
+/// assert_eq!(l, [ 2., -2.,  0.,
+///                -2.,  3., -1.,
+///                 0., -1.,  1.]);
+/// ```
+pub fn laplacian_matrix<'a, G, F>(g: &'a G, mut weight_fn: F) -> Vec<f64>
+    where G: NodeCompactIndexable + NodeIdentifiers<'a> + NeighborIter<'a>,
+          F: FnMut(G::NodeId, G::NodeId) -> f64,
+{
+    let n = g.node_count();
+    let mut matrix = vec![0.; n * n];
+    for a in g.node_identifiers() {
+        let i = g.to_index(a.clone());
+        for b in g.neighbors(a.clone()) {
+            let j = g.to_index(b.clone());
+            if i != j {
+                let w = weight_fn(a.clone(), b);
+                matrix[i * n + j] -= w;
+                matrix[i * n + i] += w;
+            }
+        }
+    }
+    matrix
+}
+
+/// Return the *degree sequence* of the graph: the degrees of its nodes, in
+/// non-increasing order.
+///
+/// A node's degree counts the edges it is an endpoint of, in either
+/// direction; a self loop counts twice.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::degree_sequence;
+///
+/// let g = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (0, 2), (0, 3), (2, 3)]);
+/// assert_eq!(degree_sequence(&g), vec![3, 2, 2, 1]);
+/// ```
+pub fn degree_sequence<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>) -> Vec<usize>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    let mut degrees = vec![0; g.node_count()];
+    for edge in g.raw_edges() {
+        degrees[edge.source().index()] += 1;
+        degrees[edge.target().index()] += 1;
+    }
+    degrees.sort_by(|a, b| b.cmp(a));
+    degrees
+}
+
+/// Return `true` if `seq` is *graphical*: the degrees of the nodes of some
+/// simple undirected graph, in any order.
+///
+/// Uses the Erdős–Gallai theorem, in **O(n log n)** time.
+///
+/// ```
+/// use petgraph::algo::is_graphical;
+///
+/// assert!(is_graphical(&[3, 3, 2, 2, 2]));
+/// assert!(!is_graphical(&[3, 3, 1, 1]));
+/// assert!(!is_graphical(&[2, 1, 1, 1]));
+/// ```
+pub fn is_graphical(seq: &[usize]) -> bool {
+    let mut degrees = seq.to_vec();
+    degrees.sort_by(|a, b| b.cmp(a));
+    let n = degrees.len();
+    if degrees.iter().sum::<usize>() % 2 != 0 || degrees.first().map_or(false, |&d| d >= n) {
+        return false
+    }
+    // suffix[i] is the sum of the degrees from i on
+    let mut suffix = vec![0; n + 1];
+    for i in (0..n).rev() {
+        suffix[i] = suffix[i + 1] + degrees[i];
+    }
+    // For each k, the k largest degrees must fit in the edges among those
+    // nodes and the edges to the others: the nodes in k..split have degree
+    // at least k and take k edges each, the rest take their degree.
+    let mut split = n;
+    for k in 1..n + 1 {
+        while split > k && degrees[split - 1] < k {
+            split -= 1;
+        }
+        let split = split.max(k);
+        let head = suffix[0] - suffix[k];
+        if head > k * (k - 1) + k * (split - k) + suffix[split] {
+            return false
+        }
+    }
+    true
+}
+
+/// Compute the *degree assortativity* of the graph: the Pearson correlation
+/// of the degrees at the two ends of the edges.
+///
+/// It is positive when nodes tend to be adjacent to nodes of similar
+/// degree, and negative when high degree nodes tend to be adjacent to low
+/// degree ones. For an undirected graph, each edge is counted in both
+/// directions and degrees are as in [`degree_sequence`](fn.degree_sequence.html).
+/// For a directed graph, the out-degree of each edge's source is correlated
+/// with the in-degree of its target.
+///
+/// Return `None` if the graph has no edges or if the degrees at one end
+/// are all equal, as in a regular graph.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::degree_assortativity;
+///
+/// // in a star, the center is only adjacent to leaves
+/// let star = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (0, 2), (0, 3)]);
+/// assert_eq!(degree_assortativity(&star), Some(-1.));
+/// ```
+#[cfg(feature = "std")]
+pub fn degree_assortativity<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>) -> Option<f64>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    let mut out_degree = vec![0; g.node_count()];
+    let mut in_degree = vec![0; g.node_count()];
+    for edge in g.raw_edges() {
+        out_degree[edge.source().index()] += 1;
+        in_degree[edge.target().index()] += 1;
+    }
+    if !g.is_directed() {
+        for (d, i) in out_degree.iter_mut().zip(&in_degree) {
+            *d += *i;
+        }
+        in_degree.clone_from(&out_degree);
+    }
+    let pairs = g.raw_edges().iter().flat_map(|edge| {
+        let (a, b) = (edge.source().index(), edge.target().index());
+        let forward = Some((out_degree[a] as f64, in_degree[b] as f64));
+        let backward = if g.is_directed() {
+            None
+        } else {
+            Some((out_degree[b] as f64, in_degree[a] as f64))
+        };
+        forward.into_iter().chain(backward)
+    });
+    pearson(pairs)
+}
+
+/// Compute the *attribute assortativity* of the graph, for the class of
+/// each node given by `classify`.
+///
+/// This is Newman's assortativity coefficient for categories: 1 when edges
+/// only join nodes of the same class, 0 when classes are mixed as much as
+/// at random, and negative when edges tend to join different classes. For
+/// an undirected graph, each edge is counted in both directions.
+///
+/// Return `None` if the graph has no edges or if the edge ends all belong
+/// to the same class.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::attribute_assortativity;
+///
+/// // two triangles of different colors, joined by one edge
+/// let g = Graph::<(), (), Undirected>::from_edges(&[
+///     (0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (0, 3)]);
+/// let r = attribute_assortativity(&g, |a| a.index() < 3).unwrap();
+/// assert!((r - 5. / 7.).abs() < 1e-9);
+/// ```
+#[cfg(feature = "std")]
+pub fn attribute_assortativity<N, E, Ty, Ix, F, T>(g: &Graph<N, E, Ty, Ix>, mut classify: F)
+    -> Option<f64>
+    where Ty: EdgeType,
+          Ix: IndexType,
+          F: FnMut(NodeIndex<Ix>) -> T,
+          T: Eq + Hash,
+{
+    let mut class_ids = HashMap::new();
+    let class = g.node_indices().map(|a| {
+        let next = class_ids.len();
+        *class_ids.entry(classify(a)).or_insert(next)
+    }).collect::<Vec<_>>();
+    let k = class_ids.len();
+
+    // the fraction of edge ends of each class at the source and the target,
+    // and of edges within a class
+    let mut source = vec![0.; k];
+    let mut target = vec![0.; k];
+    let mut within = 0.;
+    let mut total = 0.;
+    for edge in g.raw_edges() {
+        let (a, b) = (class[edge.source().index()], class[edge.target().index()]);
+        let count = if g.is_directed() { 1. } else { 2. };
+        source[a] += 1.;
+        target[b] += 1.;
+        if !g.is_directed() {
+            source[b] += 1.;
+            target[a] += 1.;
+        }
+        if a == b {
+            within += count;
+        }
+        total += count;
+    }
+    if total == 0. {
+        return None
+    }
+    let expected = source.iter().zip(&target).map(|(s, t)| s * t).sum::<f64>() / (total * total);
+    if expected == 1. {
+        return None
+    }
+    Some((within / total - expected) / (1. - expected))
+}
+
+/// Return the Pearson correlation coefficient of the pairs, or `None` if
+/// there are none or one side has no variance.
+#[cfg(feature = "std")]
+fn pearson<I>(pairs: I) -> Option<f64>
+    where I: IntoIterator<Item=(f64, f64)>,
+{
+    let (mut n, mut sx, mut sy, mut sxx, mut syy, mut sxy) = (0., 0., 0., 0., 0., 0.);
+    for (x, y) in pairs {
+        n += 1.;
+        sx += x;
+        sy += y;
+        sxx += x * x;
+        syy += y * y;
+        sxy += x * y;
+    }
+    let var_x = n * sxx - sx * sx;
+    let var_y = n * syy - sy * sy;
+    if n == 0. || var_x <= 0. || var_y <= 0. {
+        return None
+    }
+    Some((n * sxy - sx * sy) / (var_x * var_y).sqrt())
+}
+
+/// Compute the *SimRank* similarity of every pair of nodes, as a row-major
+/// `n × n` matrix, where `n` is the number of nodes.
+///
+/// Two nodes are similar if their in-neighbors are similar: a node has
+/// similarity 1 with itself, and otherwise the similarity of `a` and `b` is
+/// `decay` times the average similarity of the pairs of an in-neighbor of
+/// `a` and an in-neighbor of `b`. A node without in-neighbors has
+/// similarity 0 with the other nodes. For an undirected graph, the
+/// in-neighbors are all the neighbors.
+///
+/// The scores are computed by iterating from the identity matrix, until no
+/// score changes by more than `tol` or after `max_iter` iterations. Each
+/// iteration takes **O(n |E|)** time.
+///
+/// **Panics** if `decay` is not in the range `0..1` (inclusive).
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::algo::simrank;
+///
+/// // 1 and 2 are both cited by 0 only
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (0, 2)]);
+/// let s = simrank(&g, 0.8, 100, 1e-6);
+/// assert_eq!(s[1 * 3 + 2], 0.8);
+/// assert_eq!(s[0 * 3 + 1], 0.);
+/// ```
+pub fn simrank<'a, G>(g: &'a G, decay: f64, max_iter: usize, tol: f64) -> Vec<f64>
+    where G: NodeCompactIndexable + NodeIdentifiers<'a> + NeighborsDirected<'a>,
+{
+    assert!(decay >= 0. && decay <= 1., "simrank: decay must be in the range 0..1");
+    let n = g.node_count();
+    let mut incoming = vec![Vec::new(); n];
+    for a in g.node_identifiers() {
+        let i = g.to_index(a.clone());
+        incoming[i].extend(g.neighbors_directed(a, Incoming).map(|b| g.to_index(b)));
+    }
+    let mut scores = vec![0.; n * n];
+    for i in 0..n {
+        scores[i * n + i] = 1.;
+    }
+    // partial[i * n + b] sums the scores of i with the in-neighbors of b
+    let mut partial = vec![0.; n * n];
+    let mut next = vec![0.; n * n];
+    for _ in 0..max_iter {
+        for i in 0..n {
+            for b in 0..n {
+                partial[i * n + b] = incoming[b].iter().map(|&j| scores[i * n + j]).sum();
+            }
+        }
+        let mut change: f64 = 0.;
+        for a in 0..n {
+            next[a * n + a] = 1.;
+            for b in a + 1..n {
+                let x = if incoming[a].is_empty() || incoming[b].is_empty() {
+                    0.
+                } else {
+                    let sum = incoming[a].iter().map(|&i| partial[i * n + b]).sum::<f64>();
+                    decay * sum / (incoming[a].len() * incoming[b].len()) as f64
+                };
+                change = change.max((x - scores[a * n + b]).abs());
+                next[a * n + b] = x;
+                next[b * n + a] = x;
+            }
+        }
+        mem::swap(&mut scores, &mut next);
+        if change <= tol {
+            break
+        }
+    }
+    scores
+}
+
+/// Return `true` if the two graphs are equal: they have the same node
+/// weights at the same node indices, and the same edges, with the same
+/// endpoints and weights, at the same edge indices.
+///
+/// This is not an isomorphism test; see
+/// [`is_isomorphic`](fn.is_isomorphic.html) for that. In an undirected
+/// graph, an edge `a - b` equals an edge `b - a`.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::algo::graph_eq;
+///
+/// let g = Graph::<(), i32>::from_edges(&[(0, 1, 1), (1, 2, 2)]);
+/// let mut h = g.clone();
+/// assert!(graph_eq(&g, &h));
+///
+/// let e = h.find_edge(1.into(), 2.into()).unwrap();
+/// h[e] = 3;
+/// assert!(!graph_eq(&g, &h));
+/// ```
+pub fn graph_eq<N, E, Ty, Ix>(a: &Graph<N, E, Ty, Ix>, b: &Graph<N, E, Ty, Ix>) -> bool
+    where N: PartialEq,
+          E: PartialEq,
+          Ty: EdgeType,
+          Ix: IndexType,
+{
+    if a.node_count() != b.node_count() || a.edge_count() != b.edge_count() {
+        return false
+    }
+    let nodes_eq = a.raw_nodes().iter().zip(b.raw_nodes())
+                    .all(|(x, y)| x.weight == y.weight);
+    nodes_eq && a.raw_edges().iter().zip(b.raw_edges()).all(|(x, y)| {
+        let same_ends = (x.source(), x.target()) == (y.source(), y.target()) ||
+            !a.is_directed() && (x.source(), x.target()) == (y.target(), y.source());
+        same_ends && x.weight == y.weight
+    })
+}
@@ -0,0 +1,338 @@
+//! Balanced graph partitioning.
+//!
+//! The partitions split the nodes into parts of equal size while keeping
+//! the *cut*, the number of edges between different parts, small. They are
+//! found by multilevel bisection: the graph is coarsened by contracting
+//! heavy-edge matchings, as in [`coarsen`](../fn.coarsen.html), the
+//! coarsest graph is split by growing one part breadth first, and the split
+//! is projected back level by level, improved each time by
+//! Fiduccia–Mattheyses refinement. More than two parts are found by
+//! bisecting recursively.
+//!
+//! Edge directions are ignored, self loops are never cut, and parallel
+//! edges count once each. The partitions are heuristic: balanced, but not
+//! always with the smallest possible cut.
+//!
+//! Results are vectors of length `g.node_count()`, the part of each node.
+
+use core::cmp::{
+    max,
+    min,
+};
+use alloc::collections::{
+    BinaryHeap,
+    VecDeque,
+};
+use alloc::vec::Vec;
+
+use super::super::{
+    EdgeType,
+    Graph,
+};
+use super::super::graph::IndexType;
+use super::coarsen::Weighted;
+
+/// Graphs of at most this many nodes are not coarsened further.
+const COARSEST: usize = 32;
+
+/// The maximum number of refinement passes per level.
+const PASSES: usize = 8;
+
+/// Refinement may unbalance the parts by 1/SLACK of the total weight
+/// between balanced states, so that it can move a node at a time.
+const SLACK: usize = 20;
+
+impl Weighted {
+    /// Return the subgraph induced by `nodes`, with node `i` of the subgraph
+    /// for `nodes[i]`.
+    fn induced(&self, nodes: &[usize]) -> Weighted {
+        let mut index = vec![None; self.len()];
+        for (i, &a) in nodes.iter().enumerate() {
+            index[a] = Some(i);
+        }
+        let node_weight = nodes.iter().map(|&a| self.node_weight[a]).collect();
+        let edges = self.edges().filter_map(|(a, b, w)| match (index[a], index[b]) {
+            (Some(i), Some(j)) => Some((i, j, w)),
+            _ => None,
+        });
+        Weighted::from_edges(node_weight, edges)
+    }
+
+    /// Return the total weight of the edges between the two sides.
+    fn cut(&self, side: &[bool]) -> usize {
+        self.edges().filter(|&(a, b, _)| side[a] != side[b]).map(|(_, _, w)| w).sum()
+    }
+
+    /// Return the total weight of the nodes on side `false`.
+    fn weight0(&self, side: &[bool]) -> usize {
+        self.node_weight.iter().zip(side).filter(|&(_, &s)| !s).map(|(&w, _)| w).sum()
+    }
+
+    /// Return how much the cut shrinks if `a` changes sides.
+    fn gain(&self, side: &[bool], a: usize) -> isize {
+        self.adjacency[a].iter().fold(0, |gain, &(b, w)| {
+            if side[b] != side[a] { gain + w as isize } else { gain - w as isize }
+        })
+    }
+}
+
+/// Return a balanced bisection of `g` with a small cut: each node is in
+/// part `0` or `1`, and the parts differ in size by at most one node.
+///
+/// See the [module documentation](index.html) for the method. Computes in
+/// about **O((|V| + |E|) log |V|)** time.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::partition::{bisect, cut_size};
+///
+/// // two triangles joined by the edge 2 - 3
+/// let g = Graph::<(), (), Undirected>::from_edges(&[
+///     (0, 1), (1, 2), (2, 0),
+///     (3, 4), (4, 5), (5, 3),
+///     (2, 3),
+/// ]);
+/// let parts = bisect(&g);
+/// assert_eq!(cut_size(&g, &parts), 1);
+/// assert_eq!(parts[0], parts[1]);
+/// assert_ne!(parts[0], parts[5]);
+/// ```
+pub fn bisect<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>) -> Vec<usize>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    k_way(g, 2)
+}
+
+/// Return a balanced partition of `g` into `k` parts with a small cut: each
+/// node is in a part `0..k`, of `g.node_count() / k` nodes, rounded up or
+/// down.
+///
+/// The graph is bisected recursively, in proportion to the number of parts
+/// on each side, see [`bisect`](fn.bisect.html).
+///
+/// **Panics** if `k` is zero.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::partition::{k_way, cut_size};
+///
+/// // a cycle of 12 nodes
+/// let edges = (0..12).map(|i| (i, (i + 1) % 12)).collect::<Vec<_>>();
+/// let g = Graph::<(), (), Undirected, u32>::from_edges(&edges);
+/// let parts = k_way(&g, 3);
+/// assert_eq!(cut_size(&g, &parts), 3);
+/// for part in 0..3 {
+///     assert_eq!(parts.iter().filter(|&&p| p == part).count(), 4);
+/// }
+/// ```
+pub fn k_way<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>, k: usize) -> Vec<usize>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    assert!(k > 0, "k_way: the number of parts must be positive");
+    let mut parts = vec![0; g.node_count()];
+    let nodes = (0..g.node_count()).collect::<Vec<_>>();
+    split(&Weighted::new(g), &nodes, k, 0, &mut parts);
+    parts
+}
+
+/// Return the number of edges of `g` between different parts, where
+/// `parts[i]` is the part of node `i`.
+///
+/// **Panics** if `parts` is shorter than the number of nodes.
+pub fn cut_size<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>, parts: &[usize]) -> usize
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    g.raw_edges().iter()
+     .filter(|edge| parts[edge.source().index()] != parts[edge.target().index()])
+     .count()
+}
+
+/// Split `g`, the subgraph of the nodes `nodes`, into the `k` parts from
+/// `first` on.
+fn split(g: &Weighted, nodes: &[usize], k: usize, first: usize, parts: &mut [usize]) {
+    if k == 1 || nodes.is_empty() {
+        for &a in nodes {
+            parts[a] = first;
+        }
+        return
+    }
+    let k0 = k / 2;
+    let total = g.node_weight.iter().sum::<usize>();
+    let side = multilevel(g, total * k0 / k, (total * k0 + k - 1) / k);
+    let (mut left, mut right) = (Vec::new(), Vec::new());
+    for (i, &s) in side.iter().enumerate() {
+        if s { right.push(i) } else { left.push(i) }
+    }
+    let original = |sub: &[usize]| sub.iter().map(|&i| nodes[i]).collect::<Vec<_>>();
+    split(&g.induced(&left), &original(&left), k0, first, parts);
+    split(&g.induced(&right), &original(&right), k - k0, first + k0, parts);
+}
+
+/// Bisect `g` so that the weight of side `false` is in `lo..=hi`, give or
+/// take a node of the coarser levels.
+fn multilevel(g: &Weighted, lo: usize, hi: usize) -> Vec<bool> {
+    let mut side = None;
+    if g.len() > COARSEST {
+        let (coarse, map) = g.coarsen();
+        // stop when the matching is too small to make progress, as in a star
+        if coarse.len() * 10 <= g.len() * 9 {
+            let coarse_side = multilevel(&coarse, lo, hi);
+            side = Some(map.iter().map(|&c| coarse_side[c]).collect::<Vec<_>>());
+        }
+    }
+    let mut side = match side {
+        None => return initial(g, lo, hi),
+        Some(side) => side,
+    };
+    refine(g, &mut side, lo, hi);
+    side
+}
+
+/// Bisect `g` from scratch: grow side `false` breadth first from a few
+/// start nodes, refine each, and keep the smallest cut.
+fn initial(g: &Weighted, lo: usize, hi: usize) -> Vec<bool> {
+    let n = g.len();
+    let mut best: Option<(usize, Vec<bool>)> = None;
+    let tries = min(n, 8);
+    for t in 0..tries {
+        let mut side = grow(g, t * n / tries, lo);
+        refine(g, &mut side, lo, hi);
+        let cut = g.cut(&side);
+        if best.as_ref().map_or(true, |&(best_cut, _)| cut < best_cut) {
+            best = Some((cut, side));
+        }
+    }
+    match best {
+        None => Vec::new(),
+        Some((_, side)) => side,
+    }
+}
+
+/// Move nodes to side `false` breadth first from `start` until it weighs at
+/// least `lo`, starting over from the next node on side `true` whenever
+/// the search runs out.
+fn grow(g: &Weighted, start: usize, lo: usize) -> Vec<bool> {
+    let n = g.len();
+    let mut side = vec![true; n];
+    let mut weight = 0;
+    let mut queue = VecDeque::new();
+    for root in (start..n).chain(0..start) {
+        if weight >= lo {
+            break
+        }
+        if !side[root] {
+            continue
+        }
+        side[root] = false;
+        weight += g.node_weight[root];
+        queue.push_back(root);
+        while let Some(a) = queue.pop_front() {
+            for &(b, _) in &g.adjacency[a] {
+                if weight >= lo {
+                    return side
+                }
+                if side[b] {
+                    side[b] = false;
+                    weight += g.node_weight[b];
+                    queue.push_back(b);
+                }
+            }
+        }
+    }
+    side
+}
+
+/// Balance the bisection, then improve its cut with Fiduccia–Mattheyses
+/// passes.
+///
+/// Side `false` may weigh a node less than `lo` or a node more than `hi`
+/// (both minus one), since the nodes of a coarse graph are too heavy to
+/// balance exactly.
+fn refine(g: &Weighted, side: &mut [bool], lo: usize, hi: usize) {
+    let max_weight = match g.node_weight.iter().max() {
+        None => return,
+        Some(&w) => w,
+    };
+    let min0 = lo.saturating_sub(max_weight - 1);
+    let max0 = hi + max_weight - 1;
+    let total = g.node_weight.iter().sum::<usize>();
+    let slack = max(max_weight, total / SLACK);
+    rebalance(g, side, min0, max0);
+    for _ in 0..PASSES {
+        if !pass(g, side, min0, max0, slack) {
+            break
+        }
+    }
+}
+
+/// Move the nodes of highest gain off the heavier side until side `false`
+/// weighs `min0..=max0`.
+fn rebalance(g: &Weighted, side: &mut [bool], min0: usize, max0: usize) {
+    let mut weight0 = g.weight0(side);
+    while weight0 < min0 || weight0 > max0 {
+        // the heavier side has a node, and none outweighs the slack
+        let from = weight0 < min0;
+        let a = (0..g.len()).filter(|&a| side[a] == from)
+                            .max_by_key(|&a| g.gain(side, a))
+                            .unwrap();
+        side[a] = !from;
+        if from {
+            weight0 += g.node_weight[a];
+        } else {
+            weight0 -= g.node_weight[a];
+        }
+    }
+}
+
+/// Run one Fiduccia–Mattheyses pass: move each node at most once, highest
+/// gain first and within `slack` of the balance, then keep the balanced
+/// prefix of the moves with the smallest cut.
+///
+/// Return `true` if the cut shrank.
+fn pass(g: &Weighted, side: &mut [bool], min0: usize, max0: usize, slack: usize) -> bool {
+    let n = g.len();
+    let mut gain = (0..n).map(|a| g.gain(side, a)).collect::<Vec<_>>();
+    let mut heap = gain.iter().cloned().zip(0..n).collect::<BinaryHeap<_>>();
+    let mut locked = vec![false; n];
+    let mut weight0 = g.weight0(side);
+    let start_cut = g.cut(side) as isize;
+    let mut cut = start_cut;
+    let mut best = (cut, 0);
+    let mut moves = Vec::new();
+    while let Some((node_gain, a)) = heap.pop() {
+        // skip stale entries
+        if locked[a] || node_gain != gain[a] {
+            continue
+        }
+        let new0 = if side[a] { weight0 + g.node_weight[a] } else { weight0 - g.node_weight[a] };
+        if new0 + slack < min0 || new0 > max0 + slack {
+            continue
+        }
+        locked[a] = true;
+        side[a] = !side[a];
+        weight0 = new0;
+        cut -= node_gain;
+        moves.push(a);
+        for &(b, w) in &g.adjacency[a] {
+            if locked[b] {
+                continue
+            }
+            if side[b] == side[a] {
+                gain[b] -= 2 * w as isize;
+            } else {
+                gain[b] += 2 * w as isize;
+            }
+            heap.push((gain[b], b));
+        }
+        if min0 <= weight0 && weight0 <= max0 && cut < best.0 {
+            best = (cut, moves.len());
+        }
+    }
+    for &a in &moves[best.1..] {
+        side[a] = !side[a];
+    }
+    best.0 < start_cut
+}
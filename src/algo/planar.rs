@@ -0,0 +1,783 @@
+use core::cmp::{
+    max,
+    min,
+};
+use core::mem;
+use alloc::vec::Vec;
+
+use super::super::{
+    Directed,
+    EdgeType,
+    Graph,
+};
+use super::super::graph::{
+    DefIndex,
+    EdgeIndex,
+    IndexType,
+    NodeIndex,
+};
+
+/// A combinatorial embedding of a planar graph in the plane: the clockwise
+/// order of the edges around each node, its *rotation system*. See
+/// [`planar_embedding`](fn.planar_embedding.html).
+///
+/// Each edge has two *half-edges*, one leaving each endpoint. A self loop
+/// leaves its node twice.
+#[derive(Clone, Debug)]
+pub struct PlanarEmbedding<Ix: IndexType = DefIndex> {
+    /// The endpoints of each edge.
+    ends: Vec<(NodeIndex<Ix>, NodeIndex<Ix>)>,
+    /// A half-edge leaving each node, if any.
+    first: Vec<Option<usize>>,
+    /// The next half-edge clockwise and counterclockwise around its node.
+    /// Half-edge `2 * e` leaves the source of edge `e`, `2 * e + 1` its
+    /// target.
+    cw: Vec<usize>,
+    ccw: Vec<usize>,
+}
+
+impl<Ix: IndexType> PlanarEmbedding<Ix> {
+    fn new(node_count: usize, ends: Vec<(NodeIndex<Ix>, NodeIndex<Ix>)>) -> Self {
+        let half_edges = 2 * ends.len();
+        PlanarEmbedding {
+            ends: ends,
+            first: vec![None; node_count],
+            cw: (0..half_edges).collect(),
+            ccw: (0..half_edges).collect(),
+        }
+    }
+
+    /// Return the number of nodes.
+    pub fn node_count(&self) -> usize {
+        self.first.len()
+    }
+
+    /// Return the number of edges.
+    pub fn edge_count(&self) -> usize {
+        self.ends.len()
+    }
+
+    /// Return the node that half-edge `h` leaves.
+    fn origin(&self, h: usize) -> NodeIndex<Ix> {
+        let (a, b) = self.ends[h / 2];
+        if h % 2 == 0 { a } else { b }
+    }
+
+    /// Return the half-edge of `e` that leaves `a`.
+    fn half_edge(&self, e: usize, a: usize) -> usize {
+        if self.ends[e].0.index() == a { 2 * e } else { 2 * e + 1 }
+    }
+
+    /// Add `h` around its node, clockwise after `reference` or as its only
+    /// half-edge.
+    fn add_cw(&mut self, h: usize, reference: Option<usize>) {
+        match reference {
+            None => self.add_alone(h),
+            Some(r) => {
+                let next = self.cw[r];
+                self.cw[r] = h;
+                self.ccw[h] = r;
+                self.cw[h] = next;
+                self.ccw[next] = h;
+            }
+        }
+    }
+
+    /// Add `h` around its node, counterclockwise before `reference` or as its
+    /// only half-edge; it becomes the first if `reference` was.
+    fn add_ccw(&mut self, h: usize, reference: Option<usize>) {
+        match reference {
+            None => self.add_alone(h),
+            Some(r) => {
+                let prev = self.ccw[r];
+                self.add_cw(h, Some(prev));
+                let a = self.origin(h).index();
+                if self.first[a] == Some(r) {
+                    self.first[a] = Some(h);
+                }
+            }
+        }
+    }
+
+    /// Add `h` as the first half-edge around its node.
+    fn add_first(&mut self, h: usize) {
+        let a = self.origin(h).index();
+        let first = self.first[a];
+        self.add_ccw(h, first);
+    }
+
+    fn add_alone(&mut self, h: usize) {
+        self.cw[h] = h;
+        self.ccw[h] = h;
+        let a = self.origin(h).index();
+        self.first[a] = Some(h);
+    }
+
+    /// Return the edges around `a` in clockwise order, each with the node
+    /// at its other end.
+    ///
+    /// **Panics** if `a` is out of bounds.
+    pub fn rotation(&self, a: NodeIndex<Ix>) -> Rotation<Ix> {
+        Rotation {
+            embedding: self,
+            first: self.first[a.index()],
+            next: self.first[a.index()],
+        }
+    }
+
+    /// Return the half-edge after `h` along its face: the one clockwise
+    /// after its twin.
+    fn face_next(&self, h: usize) -> usize {
+        self.cw[h ^ 1]
+    }
+
+    /// Return an iterator of the faces of the embedding.
+    ///
+    /// Each face is the closed walk around its boundary, as the node and the
+    /// edge leaving it for each step; an edge with the same face on both
+    /// sides is walked twice. Every connected component with edges has its
+    /// own outer face, and nodes without edges are on no face.
+    pub fn faces(&self) -> Faces<Ix> {
+        Faces {
+            embedding: self,
+            visited: vec![false; self.cw.len()],
+            next: 0,
+        }
+    }
+
+    /// Return the face of each half-edge, numbered in the order of
+    /// [`faces`](#method.faces), and the number of faces.
+    fn face_numbers(&self) -> (Vec<usize>, usize) {
+        let mut face = vec![None; self.cw.len()];
+        let mut count = 0;
+        for start in 0..self.cw.len() {
+            if face[start].is_some() {
+                continue
+            }
+            let mut h = start;
+            while face[h].is_none() {
+                face[h] = Some(count);
+                h = self.face_next(h);
+            }
+            count += 1;
+        }
+        (face.into_iter().map(|f| f.unwrap()).collect(), count)
+    }
+}
+
+/// An iterator of the edges around a node, see
+/// [`PlanarEmbedding::rotation`](struct.PlanarEmbedding.html#method.rotation).
+pub struct Rotation<'a, Ix: 'a + IndexType> {
+    embedding: &'a PlanarEmbedding<Ix>,
+    first: Option<usize>,
+    next: Option<usize>,
+}
+
+impl<'a, Ix: IndexType> Iterator for Rotation<'a, Ix> {
+    type Item = (EdgeIndex<Ix>, NodeIndex<Ix>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let h = match self.next {
+            None => return None,
+            Some(h) => h,
+        };
+        let next = self.embedding.cw[h];
+        self.next = if Some(next) == self.first { None } else { Some(next) };
+        Some((EdgeIndex::new(h / 2), self.embedding.origin(h ^ 1)))
+    }
+}
+
+/// An iterator of the faces of an embedding, see
+/// [`PlanarEmbedding::faces`](struct.PlanarEmbedding.html#method.faces).
+pub struct Faces<'a, Ix: 'a + IndexType> {
+    embedding: &'a PlanarEmbedding<Ix>,
+    visited: Vec<bool>,
+    next: usize,
+}
+
+impl<'a, Ix: IndexType> Iterator for Faces<'a, Ix> {
+    type Item = Vec<(NodeIndex<Ix>, EdgeIndex<Ix>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.next < self.visited.len() && self.visited[self.next] {
+            self.next += 1;
+        }
+        if self.next == self.visited.len() {
+            return None
+        }
+        let mut walk = Vec::new();
+        let mut h = self.next;
+        while !self.visited[h] {
+            self.visited[h] = true;
+            walk.push((self.embedding.origin(h), EdgeIndex::new(h / 2)));
+            h = self.embedding.face_next(h);
+        }
+        Some(walk)
+    }
+}
+
+/// Return the dual graph of a planar embedding: a node for each face, and
+/// an edge across each edge of the embedding.
+///
+/// Node `i` of the dual is face `i` of
+/// [`PlanarEmbedding::faces`](struct.PlanarEmbedding.html#method.faces),
+/// with the face as its weight, and edge `e` of the dual crosses edge `e`
+/// of the embedding, with its index as the weight. The dual edge goes from
+/// the face to the left of the edge, seen from its source to its target, to
+/// the face to its right, with left and right as the clockwise rotations
+/// draw them. A bridge, with the same face on both sides, becomes a self
+/// loop.
+///
+/// The dual of a connected planar graph is connected and planar, and a
+/// cycle of the graph is a cut of its dual. This turns, for example,
+/// minimum cuts of the graph into shortest cycles of the dual. Computes in
+/// **O(|V| + |E|)** time.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::{dual_graph, planar_embedding};
+///
+/// // a triangle: an inner and an outer face, and three edges between them
+/// let g = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2), (2, 0)]);
+/// let dual = dual_graph(&planar_embedding(&g).unwrap());
+/// assert_eq!(dual.node_count(), 2);
+/// assert_eq!(dual.edge_count(), 3);
+/// for edge in dual.raw_edges() {
+///     assert!(edge.source() != edge.target());
+/// }
+/// ```
+pub fn dual_graph<Ix>(embedding: &PlanarEmbedding<Ix>)
+    -> Graph<Vec<(NodeIndex<Ix>, EdgeIndex<Ix>)>, EdgeIndex<Ix>, Directed, Ix>
+    where Ix: IndexType,
+{
+    let (face, face_count) = embedding.face_numbers();
+    let mut dual = Graph::with_capacity(face_count, embedding.edge_count());
+    for walk in embedding.faces() {
+        dual.add_node(walk);
+    }
+    for e in 0..embedding.edge_count() {
+        dual.add_edge(NodeIndex::new(face[2 * e]), NodeIndex::new(face[2 * e + 1]), EdgeIndex::new(e));
+    }
+    dual
+}
+
+/// Return `true` if `g` is planar: it can be drawn in the plane without
+/// crossing edges.
+///
+/// Edge directions are ignored. See
+/// [`planar_embedding`](fn.planar_embedding.html) for the method.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::is_planar;
+///
+/// // the complete graph on four nodes is planar, on five it is not
+/// let mut k5 = Graph::<(), (), Undirected>::from_edges(&[
+///     (0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3),
+/// ]);
+/// assert!(is_planar(&k5));
+/// let e = k5.add_node(());
+/// for a in 0..4 {
+///     k5.add_edge(a.into(), e, ());
+/// }
+/// assert!(!is_planar(&k5));
+/// ```
+pub fn is_planar<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>) -> bool
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    planar_embedding(g).is_some()
+}
+
+/// Return a planar embedding of `g`, or `None` if it is not planar.
+///
+/// Edge directions are ignored. Parallel edges are embedded next to each
+/// other, and self loops enclose a face of their own.
+///
+/// Uses the left-right planarity test of de Fraysseix and Rosenstiehl, as
+/// described by Brandes, in **O(|V| + |E| log |E|)** time.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::planar_embedding;
+///
+/// // a square with a diagonal
+/// let g = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 0), (0, 2)]);
+/// let embedding = planar_embedding(&g).unwrap();
+/// assert_eq!(embedding.rotation(0.into()).count(), 3);
+///
+/// // Euler's formula: two triangles and the outer face
+/// let faces = embedding.faces().collect::<Vec<_>>();
+/// assert_eq!(faces.len(), 3);
+/// assert_eq!(faces.iter().map(|face| face.len()).sum::<usize>(), 10);
+/// ```
+pub fn planar_embedding<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>) -> Option<PlanarEmbedding<Ix>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    let n = g.node_count();
+    let ends = g.raw_edges().iter().map(|edge| (edge.source(), edge.target())).collect();
+    let mut embedding = PlanarEmbedding::new(n, ends);
+
+    // the simple graph: the first edge of each set of parallel edges, and
+    // no loops
+    let mut pairs = g.raw_edges().iter().enumerate().filter_map(|(e, edge)| {
+        let (a, b) = (edge.source().index(), edge.target().index());
+        if a == b { None } else { Some((min(a, b), max(a, b), e)) }
+    }).collect::<Vec<_>>();
+    pairs.sort();
+    let mut first = Vec::with_capacity(pairs.len());
+    let mut simple = Vec::new();
+    for (i, &(a, b, e)) in pairs.iter().enumerate() {
+        if i == 0 || (pairs[i - 1].0, pairs[i - 1].1) != (a, b) {
+            simple.push((e, a, b));
+        }
+        first.push((e, simple[simple.len() - 1].0));
+    }
+    if n > 2 && simple.len() > 3 * n - 6 {
+        return None
+    }
+    simple.sort();
+    let mut lr = LeftRight::new(n, simple.iter().map(|&(_, a, b)| (a, b)).collect());
+    if !lr.run() {
+        return None
+    }
+    let simple = simple.iter().map(|&(e, _, _)| e).collect::<Vec<_>>();
+    lr.embed(&mut embedding, &simple);
+
+    // the parallel edges next to the first of their set, and the loops
+    for &(e, rep) in &first {
+        if e != rep {
+            let (a, b) = (g.raw_edges()[e].source().index(), g.raw_edges()[e].target().index());
+            let (ra, rb) = (embedding.half_edge(rep, a), embedding.half_edge(rep, b));
+            let (ha, hb) = (embedding.half_edge(e, a), embedding.half_edge(e, b));
+            embedding.add_cw(ha, Some(ra));
+            embedding.add_ccw(hb, Some(rb));
+        }
+    }
+    for (e, edge) in g.raw_edges().iter().enumerate() {
+        let a = edge.source().index();
+        if a == edge.target().index() {
+            let first = embedding.first[a];
+            embedding.add_cw(2 * e, first);
+            embedding.add_cw(2 * e + 1, Some(2 * e));
+        }
+    }
+    Some(embedding)
+}
+
+/// An interval of return edges on one side, from its lowest to its highest.
+#[derive(Copy, Clone, Default)]
+struct Interval {
+    low: Option<usize>,
+    high: Option<usize>,
+}
+
+impl Interval {
+    fn is_empty(&self) -> bool {
+        self.low.is_none() && self.high.is_none()
+    }
+}
+
+/// The return edges that must be on different sides.
+#[derive(Copy, Clone, Default)]
+struct ConflictPair {
+    left: Interval,
+    right: Interval,
+}
+
+impl ConflictPair {
+    fn swap(&mut self) {
+        mem::swap(&mut self.left, &mut self.right);
+    }
+}
+
+/// The state of the left-right planarity test, on a simple graph whose
+/// edges are oriented by a depth first search.
+struct LeftRight {
+    edges: Vec<(usize, usize)>,
+    /// The edges at each node, with the node at their other end.
+    adjacency: Vec<Vec<(usize, usize)>>,
+    /// The oriented edges leaving each node, later ordered by nesting depth.
+    out: Vec<Vec<usize>>,
+    /// The orientation of each edge, once it has one.
+    oriented: Vec<Option<(usize, usize)>>,
+    height: Vec<Option<usize>>,
+    parent_edge: Vec<Option<usize>>,
+    roots: Vec<usize>,
+    lowpt: Vec<usize>,
+    lowpt2: Vec<usize>,
+    nesting_depth: Vec<isize>,
+    reference: Vec<Option<usize>>,
+    side: Vec<isize>,
+    stack: Vec<ConflictPair>,
+    stack_bottom: Vec<usize>,
+    lowpt_edge: Vec<usize>,
+}
+
+impl LeftRight {
+    fn new(node_count: usize, edges: Vec<(usize, usize)>) -> Self {
+        let m = edges.len();
+        let mut adjacency = vec![Vec::new(); node_count];
+        for (s, &(a, b)) in edges.iter().enumerate() {
+            adjacency[a].push((b, s));
+            adjacency[b].push((a, s));
+        }
+        LeftRight {
+            edges: edges,
+            adjacency: adjacency,
+            out: vec![Vec::new(); node_count],
+            oriented: vec![None; m],
+            height: vec![None; node_count],
+            parent_edge: vec![None; node_count],
+            roots: Vec::new(),
+            lowpt: vec![0; m],
+            lowpt2: vec![0; m],
+            nesting_depth: vec![0; m],
+            reference: vec![None; m],
+            side: vec![1; m],
+            stack: Vec::new(),
+            stack_bottom: vec![0; m],
+            lowpt_edge: vec![0; m],
+        }
+    }
+
+    fn source(&self, s: usize) -> usize {
+        self.oriented[s].unwrap().0
+    }
+
+    fn target(&self, s: usize) -> usize {
+        self.oriented[s].unwrap().1
+    }
+
+    /// Orient the graph and test it; return `true` if it is planar.
+    fn run(&mut self) -> bool {
+        for v in 0..self.height.len() {
+            if self.height[v].is_none() {
+                self.height[v] = Some(0);
+                self.roots.push(v);
+                self.orient(v);
+            }
+        }
+        let depth = &self.nesting_depth;
+        for out in &mut self.out {
+            out.sort_by_key(|&s| depth[s]);
+        }
+        let roots = self.roots.clone();
+        roots.into_iter().all(|root| self.test(root))
+    }
+
+    fn height(&self, v: usize) -> usize {
+        self.height[v].unwrap()
+    }
+
+    /// Orient the edges by a depth first search from `root`, computing the
+    /// lowpoints and the nesting depth of each edge.
+    fn orient(&mut self, root: usize) {
+        let mut stack = vec![(root, 0)];
+        while let Some(&(v, i)) = stack.last() {
+            if i == self.adjacency[v].len() {
+                stack.pop();
+                if let Some(e) = self.parent_edge[v] {
+                    let u = self.source(e);
+                    self.finish_edge(u, e);
+                }
+                continue
+            }
+            stack.last_mut().unwrap().1 += 1;
+            let (w, s) = self.adjacency[v][i];
+            if self.oriented[s].is_some() {
+                continue
+            }
+            self.oriented[s] = Some((v, w));
+            self.out[v].push(s);
+            self.lowpt[s] = self.height(v);
+            self.lowpt2[s] = self.height(v);
+            match self.height[w] {
+                None => {
+                    // a tree edge, finished when `w` is
+                    self.parent_edge[w] = Some(s);
+                    self.height[w] = Some(self.height(v) + 1);
+                    stack.push((w, 0));
+                }
+                Some(hw) => {
+                    // a back edge
+                    self.lowpt[s] = hw;
+                    self.finish_edge(v, s);
+                }
+            }
+        }
+    }
+
+    /// Compute the nesting depth of `s`, leaving `v`, and update the
+    /// lowpoints of the parent edge of `v`.
+    fn finish_edge(&mut self, v: usize, s: usize) {
+        let hv = self.height(v);
+        self.nesting_depth[s] = 2 * self.lowpt[s] as isize;
+        if self.lowpt2[s] < hv {
+            // chordal
+            self.nesting_depth[s] += 1;
+        }
+        if let Some(e) = self.parent_edge[v] {
+            if self.lowpt[s] < self.lowpt[e] {
+                self.lowpt2[e] = min(self.lowpt[e], self.lowpt2[s]);
+                self.lowpt[e] = self.lowpt[s];
+            } else if self.lowpt[s] > self.lowpt[e] {
+                self.lowpt2[e] = min(self.lowpt2[e], self.lowpt[s]);
+            } else {
+                self.lowpt2[e] = min(self.lowpt2[e], self.lowpt2[s]);
+            }
+        }
+    }
+
+    /// Test the constraints of the depth first tree from `root`.
+    fn test(&mut self, root: usize) -> bool {
+        // each frame is a node, the position in its edges, and whether the
+        // search just returned from the tree edge there
+        let mut stack = vec![(root, 0, false)];
+        while let Some(&(v, i, returned)) = stack.last() {
+            let e = self.parent_edge[v];
+            if i == self.out[v].len() {
+                stack.pop();
+                if let Some(e) = e {
+                    self.remove_back_edges(e);
+                }
+                if let Some(frame) = stack.last_mut() {
+                    frame.2 = true;
+                }
+                continue
+            }
+            let s = self.out[v][i];
+            if !returned {
+                self.stack_bottom[s] = self.stack.len();
+                let w = self.target(s);
+                if self.parent_edge[w] == Some(s) {
+                    stack.push((w, 0, false));
+                    continue
+                }
+                self.lowpt_edge[s] = s;
+                self.stack.push(ConflictPair {
+                    left: Interval::default(),
+                    right: Interval { low: Some(s), high: Some(s) },
+                });
+            }
+            // integrate the new return edges
+            if self.lowpt[s] < self.height(v) {
+                let e = e.unwrap();
+                if i == 0 {
+                    self.lowpt_edge[e] = self.lowpt_edge[s];
+                } else if !self.add_constraints(s, e) {
+                    return false
+                }
+            }
+            let frame = stack.last_mut().unwrap();
+            frame.1 += 1;
+            frame.2 = false;
+        }
+        true
+    }
+
+    fn conflicting(&self, interval: &Interval, s: usize) -> bool {
+        match interval.high {
+            Some(high) => self.lowpt[high] > self.lowpt[s],
+            None => false,
+        }
+    }
+
+    fn lowest(&self, pair: &ConflictPair) -> usize {
+        match (pair.left.low, pair.right.low) {
+            (None, Some(r)) => self.lowpt[r],
+            (Some(l), None) => self.lowpt[l],
+            (Some(l), Some(r)) => min(self.lowpt[l], self.lowpt[r]),
+            (None, None) => unreachable!(),
+        }
+    }
+
+    /// Merge the return edges of `s`, the latest edge out of the head of
+    /// `e`, with the constraints of the earlier ones.
+    fn add_constraints(&mut self, s: usize, e: usize) -> bool {
+        let mut p = ConflictPair::default();
+        // merge the return edges of `s` into the right interval
+        loop {
+            let mut q = self.stack.pop().unwrap();
+            if !q.left.is_empty() {
+                q.swap();
+            }
+            if !q.left.is_empty() {
+                return false
+            }
+            let q_low = q.right.low.unwrap();
+            if self.lowpt[q_low] > self.lowpt[e] {
+                // merge the intervals
+                if p.right.is_empty() {
+                    p.right.high = q.right.high;
+                } else {
+                    self.reference[p.right.low.unwrap()] = q.right.high;
+                }
+                p.right.low = q.right.low;
+            } else {
+                // align
+                self.reference[q_low] = Some(self.lowpt_edge[e]);
+            }
+            if self.stack.len() == self.stack_bottom[s] {
+                break
+            }
+        }
+        // merge the conflicting return edges of the earlier edges into the
+        // left interval
+        while let Some(&top) = self.stack.last() {
+            if !self.conflicting(&top.left, s) && !self.conflicting(&top.right, s) {
+                break
+            }
+            let mut q = self.stack.pop().unwrap();
+            if self.conflicting(&q.right, s) {
+                q.swap();
+            }
+            if self.conflicting(&q.right, s) {
+                return false
+            }
+            // merge the interval below the lowpoint of `s` into the right
+            if let Some(low) = p.right.low {
+                self.reference[low] = q.right.high;
+            }
+            if q.right.low.is_some() {
+                p.right.low = q.right.low;
+            }
+            if p.left.is_empty() {
+                p.left.high = q.left.high;
+            } else {
+                self.reference[p.left.low.unwrap()] = q.left.high;
+            }
+            p.left.low = q.left.low;
+        }
+        if !p.left.is_empty() || !p.right.is_empty() {
+            self.stack.push(p);
+        }
+        true
+    }
+
+    /// Remove the back edges that return to the tail of `e`, as the search
+    /// goes back over it.
+    fn remove_back_edges(&mut self, e: usize) {
+        let u = self.source(e);
+        let hu = self.height(u);
+        // drop entire conflict pairs
+        while let Some(&top) = self.stack.last() {
+            if self.lowest(&top) != hu {
+                break
+            }
+            self.stack.pop();
+            if let Some(low) = top.left.low {
+                self.side[low] = -1;
+            }
+        }
+        // one more conflict pair to consider
+        if let Some(mut p) = self.stack.pop() {
+            // trim the left interval
+            while let Some(high) = p.left.high {
+                if self.target(high) != u {
+                    break
+                }
+                p.left.high = self.reference[high];
+            }
+            if p.left.high.is_none() {
+                if let Some(low) = p.left.low {
+                    // just emptied
+                    self.reference[low] = p.right.low;
+                    self.side[low] = -1;
+                    p.left.low = None;
+                }
+            }
+            // trim the right interval
+            while let Some(high) = p.right.high {
+                if self.target(high) != u {
+                    break
+                }
+                p.right.high = self.reference[high];
+            }
+            if p.right.high.is_none() {
+                if let Some(low) = p.right.low {
+                    // just emptied
+                    self.reference[low] = p.left.low;
+                    self.side[low] = -1;
+                    p.right.low = None;
+                }
+            }
+            self.stack.push(p);
+        }
+        // the side of `e` is the side of a highest return edge
+        if self.lowpt[e] < hu {
+            let top = *self.stack.last().unwrap();
+            let (hl, hr) = (top.left.high, top.right.high);
+            self.reference[e] = match (hl, hr) {
+                (Some(l), Some(r)) if self.lowpt[l] > self.lowpt[r] => hl,
+                (Some(_), None) => hl,
+                _ => hr,
+            };
+        }
+    }
+
+    /// Return the final side of `s`, resolving its chain of references.
+    fn sign(&mut self, s: usize) -> isize {
+        let mut chain = Vec::new();
+        let mut t = s;
+        while let Some(r) = self.reference[t] {
+            chain.push(t);
+            t = r;
+        }
+        for &c in chain.iter().rev() {
+            let r = self.reference[c].take().unwrap();
+            self.side[c] *= self.side[r];
+        }
+        self.side[s]
+    }
+
+    /// Embed the simple graph, whose edge `s` is edge `simple[s]` of the
+    /// embedding.
+    fn embed<Ix: IndexType>(&mut self, embedding: &mut PlanarEmbedding<Ix>, simple: &[usize]) {
+        for s in 0..self.edges.len() {
+            self.nesting_depth[s] *= self.sign(s);
+        }
+        let half = |embedding: &PlanarEmbedding<Ix>, s: usize, a: usize| {
+            embedding.half_edge(simple[s], a)
+        };
+        let depth = &self.nesting_depth;
+        for (v, out) in self.out.iter_mut().enumerate() {
+            out.sort_by_key(|&s| depth[s]);
+            let mut previous = None;
+            for &s in out.iter() {
+                let h = half(embedding, s, v);
+                embedding.add_cw(h, previous);
+                previous = Some(h);
+            }
+        }
+        // the half-edges around each node, to the left and right of the
+        // tree edges to its children
+        let mut left_ref = vec![None; self.out.len()];
+        let mut right_ref = vec![None; self.out.len()];
+        for &root in &self.roots {
+            let mut stack = vec![(root, 0)];
+            while let Some(&(v, i)) = stack.last() {
+                if i == self.out[v].len() {
+                    stack.pop();
+                    continue
+                }
+                stack.last_mut().unwrap().1 += 1;
+                let s = self.out[v][i];
+                let w = self.target(s);
+                if self.parent_edge[w] == Some(s) {
+                    embedding.add_first(half(embedding, s, w));
+                    left_ref[v] = Some(half(embedding, s, v));
+                    right_ref[v] = left_ref[v];
+                    stack.push((w, 0));
+                } else if self.side[s] == 1 {
+                    embedding.add_cw(half(embedding, s, w), right_ref[w]);
+                } else {
+                    let h = half(embedding, s, w);
+                    embedding.add_ccw(h, left_ref[w]);
+                    left_ref[w] = Some(h);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use core::cmp::min;
+use core::marker::PhantomData;
+use core::usize;
+use alloc::vec::Vec;
+
+use super::super::{
+    EdgeType,
+    Graph,
+    Outgoing,
+};
+use super::fixpoint::components_in_order;
+use super::super::graph::{
+    DefIndex,
+    IndexType,
+    NodeIndex,
+};
+
+/// `Reachability` answers whether one node of a graph reaches another in
+/// constant time, after preprocessing the graph once.
+///
+/// The strongly connected components are contracted, and the resulting
+/// directed acyclic graph is covered by *k* chains, paths found greedily in
+/// topological order. Each component stores, for each chain, the first
+/// position on it that it reaches, so `a` reaches `b` if that position on
+/// the chain of `b` is no later than `b`.
+///
+/// The number of chains *k* is at least the largest number of pairwise
+/// unreachable components, so the index suits graphs that are long rather
+/// than wide, like most dependency graphs. Building takes
+/// **O(k (|V| + |E|))** time and **O(k |V|)** space.
+///
+/// Edge directions are followed; an undirected graph reaches within its
+/// connected components.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::algo::Reachability;
+/// use petgraph::graph::node_index as n;
+///
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 1), (0, 3)]);
+/// let reach = Reachability::new(&g);
+/// assert!(reach.can_reach(n(0), n(2)));
+/// assert!(reach.can_reach(n(2), n(1)));
+/// assert!(!reach.can_reach(n(3), n(1)));
+/// assert!(reach.can_reach(n(3), n(3)));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Reachability<Ix = DefIndex> {
+    // For each node, its component, in topological order.
+    component: Vec<usize>,
+    // For each component, its chain and position on it.
+    chain: Vec<usize>,
+    position: Vec<usize>,
+    chain_count: usize,
+    // For each component and chain, the first position reached on the chain,
+    // at `component * chain_count + chain`.
+    first: Vec<usize>,
+    ty: PhantomData<Ix>,
+}
+
+impl<Ix> Reachability<Ix>
+    where Ix: IndexType,
+{
+    /// Create the reachability index of `g`.
+    pub fn new<N, E, Ty>(g: &Graph<N, E, Ty, Ix>) -> Self
+        where Ty: EdgeType,
+    {
+        let components = components_in_order(g, Outgoing);
+        let mut component = vec![0; g.node_count()];
+        for (c, nodes) in components.iter().enumerate() {
+            for a in nodes {
+                component[a.index()] = c;
+            }
+        }
+        let mut successors = vec![Vec::new(); components.len()];
+        for edge in g.raw_edges() {
+            let (c, d) = (component[edge.source().index()], component[edge.target().index()]);
+            // undirected edges never join two components
+            if c != d {
+                successors[c].push(d);
+            }
+        }
+        for list in &mut successors {
+            list.sort();
+            list.dedup();
+        }
+
+        // follow a path from each component not on a chain yet
+        let mut chain = vec![usize::MAX; components.len()];
+        let mut position = vec![0; components.len()];
+        let mut chain_count = 0;
+        for start in 0..components.len() {
+            if chain[start] != usize::MAX {
+                continue
+            }
+            let mut c = start;
+            let mut length = 0;
+            loop {
+                chain[c] = chain_count;
+                position[c] = length;
+                length += 1;
+                match successors[c].iter().find(|&&d| chain[d] == usize::MAX) {
+                    None => break,
+                    Some(&d) => c = d,
+                }
+            }
+            chain_count += 1;
+        }
+
+        // successors come later in topological order, so fill in backwards
+        let mut first = vec![usize::MAX; components.len() * chain_count];
+        for c in (0..components.len()).rev() {
+            for &d in &successors[c] {
+                for k in 0..chain_count {
+                    first[c * chain_count + k] = min(first[c * chain_count + k],
+                                                     first[d * chain_count + k]);
+                }
+            }
+            first[c * chain_count + chain[c]] = position[c];
+        }
+        Reachability {
+            component: component,
+            chain: chain,
+            position: position,
+            chain_count: chain_count,
+            first: first,
+            ty: PhantomData,
+        }
+    }
+
+    /// Return the number of nodes.
+    pub fn node_count(&self) -> usize {
+        self.component.len()
+    }
+
+    /// Return the number of chains *k* the index uses.
+    pub fn chain_count(&self) -> usize {
+        self.chain_count
+    }
+
+    /// Return `true` if there is a path from `a` to `b`. Every node reaches
+    /// itself.
+    ///
+    /// **Panics** if `a` or `b` is out of bounds.
+    pub fn can_reach(&self, a: NodeIndex<Ix>, b: NodeIndex<Ix>) -> bool {
+        let c = self.component[a.index()];
+        let d = self.component[b.index()];
+        self.first[c * self.chain_count + self.chain[d]] <= self.position[d]
+    }
+}
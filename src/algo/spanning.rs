@@ -0,0 +1,306 @@
+use core::ops::{
+    AddAssign,
+    SubAssign,
+};
+use alloc::vec::Vec;
+
+use super::super::{
+    EdgeType,
+    Graph,
+};
+use super::super::graph::{
+    EdgeIndex,
+    IndexType,
+};
+use super::super::unionfind::UnionFind;
+
+/// An iterator of all the spanning trees of a graph, see
+/// [`spanning_trees`](fn.spanning_trees.html).
+#[derive(Clone)]
+pub struct SpanningTrees<Ix> {
+    node_count: usize,
+    /// The endpoints and index of each edge that is not a self loop.
+    edges: Vec<(usize, usize, EdgeIndex<Ix>)>,
+    /// The partial trees left to extend: the next edge to decide, the edges
+    /// chosen so far, and the edges left out.
+    stack: Vec<(usize, Vec<usize>, Vec<bool>)>,
+    started: bool,
+}
+
+/// Return an iterator of all the spanning trees of `g`, each as its edges
+/// in index order.
+///
+/// Edge directions are ignored, self loops are never in a tree, and
+/// parallel edges are distinct, so they give distinct trees. A graph that
+/// is not connected, or has no nodes, has no spanning trees.
+///
+/// The trees are found by deciding for each edge in turn whether it is in
+/// the tree, only going on while the chosen edges have no cycle and the
+/// edges not left out still connect the graph; so every branch ends in a
+/// tree, and each tree takes **O(|E|² α(|V|))** time.
+///
+/// The number of trees grows exponentially with the size of the graph;
+/// `count()` on a fresh iterator uses Kirchhoff's matrix tree theorem
+/// instead, like [`spanning_tree_count`](fn.spanning_tree_count.html), when
+/// the count fits in its exact integer arithmetic.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::spanning_trees;
+/// use petgraph::graph::edge_index as e;
+///
+/// // a triangle with a tail
+/// let g = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2), (2, 0), (2, 3)]);
+/// let trees = spanning_trees(&g).collect::<Vec<_>>();
+/// assert_eq!(trees, vec![vec![e(0), e(1), e(3)],
+///                        vec![e(0), e(2), e(3)],
+///                        vec![e(1), e(2), e(3)]]);
+/// assert_eq!(spanning_trees(&g).count(), 3);
+/// ```
+pub fn spanning_trees<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>) -> SpanningTrees<Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    let edges = g.raw_edges().iter().enumerate()
+                 .map(|(i, edge)| (edge.source().index(), edge.target().index(), EdgeIndex::new(i)))
+                 .filter(|&(a, b, _)| a != b)
+                 .collect::<Vec<_>>();
+    let mut trees = SpanningTrees {
+        node_count: g.node_count(),
+        edges: edges,
+        stack: Vec::new(),
+        started: false,
+    };
+    let removed = vec![false; trees.edges.len()];
+    if trees.node_count > 0 && trees.connects(&removed) {
+        trees.stack.push((0, Vec::new(), removed));
+    }
+    trees
+}
+
+impl<Ix: IndexType> SpanningTrees<Ix> {
+    /// Return `true` if the edges not in `removed` connect all nodes.
+    fn connects(&self, removed: &[bool]) -> bool {
+        let mut sets = UnionFind::new(self.node_count);
+        let mut components = self.node_count;
+        for (i, &(a, b, _)) in self.edges.iter().enumerate() {
+            if !removed[i] && sets.union(a, b) {
+                components -= 1;
+            }
+        }
+        components <= 1
+    }
+}
+
+impl<Ix: IndexType> Iterator for SpanningTrees<Ix> {
+    type Item = Vec<EdgeIndex<Ix>>;
+
+    fn next(&mut self) -> Option<Vec<EdgeIndex<Ix>>> {
+        self.started = true;
+        while let Some((mut pos, mut chosen, removed)) = self.stack.pop() {
+            loop {
+                if chosen.len() + 1 >= self.node_count {
+                    let mut tree = chosen.iter().map(|&i| self.edges[i].2).collect::<Vec<_>>();
+                    tree.sort();
+                    return Some(tree)
+                }
+                // the edges not left out connect the graph, so there are
+                // edges left to decide
+                let (a, b, _) = self.edges[pos];
+                let mut out = removed.clone();
+                out[pos] = true;
+                if self.connects(&out) {
+                    self.stack.push((pos + 1, chosen.clone(), out));
+                }
+                let mut sets = UnionFind::new(self.node_count);
+                for &i in &chosen {
+                    sets.union(self.edges[i].0, self.edges[i].1);
+                }
+                if sets.equiv(a, b) {
+                    break
+                }
+                chosen.push(pos);
+                pos += 1;
+            }
+        }
+        None
+    }
+
+    fn count(self) -> usize {
+        if !self.started {
+            let edges = self.edges.iter().map(|&(a, b, _)| (a, b));
+            match kirchhoff_count(self.node_count, edges) {
+                Some(count) if count <= usize::MAX as u64 => return count as usize,
+                _ => {}
+            }
+        }
+        self.fold(0, |count, _| count + 1)
+    }
+}
+
+/// Return the number of spanning trees of `g`, by Kirchhoff's matrix tree
+/// theorem: the determinant of its Laplacian matrix without one row and
+/// column.
+///
+/// Edge directions are ignored, self loops are never in a tree, and
+/// parallel edges are distinct, as in [`spanning_trees`](fn.spanning_trees.html).
+/// A graph that is not connected, or has no nodes, has no spanning trees.
+///
+/// The count is exact, with the fraction-free Bareiss elimination in
+/// **O(|V|³)** time. Return `None` if the count or an intermediate value
+/// of the elimination overflows; see
+/// [`spanning_tree_count_f64`](fn.spanning_tree_count_f64.html) for the
+/// magnitude of larger counts.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::spanning_tree_count;
+///
+/// // the complete graph on n nodes has n^(n - 2) spanning trees
+/// let mut k6 = Graph::<(), (), Undirected>::new_undirected();
+/// let nodes = (0..6).map(|_| k6.add_node(())).collect::<Vec<_>>();
+/// for (i, &a) in nodes.iter().enumerate() {
+///     for &b in &nodes[i + 1..] {
+///         k6.add_edge(a, b, ());
+///     }
+/// }
+/// assert_eq!(spanning_tree_count(&k6), Some(1296));
+/// ```
+pub fn spanning_tree_count<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>) -> Option<u64>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    kirchhoff_count(g.node_count(),
+                    g.raw_edges().iter().map(|edge| (edge.source().index(), edge.target().index())))
+}
+
+/// Return the number of spanning trees of `g`, by Kirchhoff's matrix tree
+/// theorem in floating point.
+///
+/// This is [`spanning_tree_count`](fn.spanning_tree_count.html) with
+/// Gaussian elimination in `f64`, which does not overflow for counts up to
+/// about 10<sup>308</sup> but is only approximate: the relative error grows
+/// with the size of the graph. Computes in **O(|V|³)** time.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::spanning_tree_count_f64;
+///
+/// let g = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 0)]);
+/// assert!((spanning_tree_count_f64(&g) - 4.).abs() < 1e-9);
+/// ```
+pub fn spanning_tree_count_f64<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>) -> f64
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    if g.node_count() == 0 {
+        return 0.
+    }
+    let n = g.node_count() - 1;
+    let mut m = vec![0f64; n * n];
+    for edge in g.raw_edges() {
+        add_edge(&mut m, n, edge.source().index(), edge.target().index(), 1.);
+    }
+    // Gaussian elimination with partial pivoting
+    let mut det = 1.;
+    for k in 0..n {
+        // abs is not in core
+        let abs = |x: f64| if x < 0. { -x } else { x };
+        let pivot = (k..n).fold(k, |p, i| if abs(m[i * n + k]) > abs(m[p * n + k]) { i } else { p });
+        if m[pivot * n + k] == 0. {
+            return 0.
+        }
+        if pivot != k {
+            for j in 0..n {
+                m.swap(k * n + j, pivot * n + j);
+            }
+            det = -det;
+        }
+        det *= m[k * n + k];
+        for i in k + 1..n {
+            let factor = m[i * n + k] / m[k * n + k];
+            for j in k + 1..n {
+                m[i * n + j] -= factor * m[k * n + j];
+            }
+        }
+    }
+    det
+}
+
+/// Add the edge from `a` to `b` to the Laplacian `m` of nodes `0..n`, which
+/// leaves out the last node. Self loops are skipped.
+fn add_edge<K>(m: &mut [K], n: usize, a: usize, b: usize, one: K)
+    where K: Copy + AddAssign + SubAssign,
+{
+    if a == b {
+        return
+    }
+    if a < n {
+        m[a * n + a] += one;
+    }
+    if b < n {
+        m[b * n + b] += one;
+    }
+    if a < n && b < n {
+        m[a * n + b] -= one;
+        m[b * n + a] -= one;
+    }
+}
+
+/// Return the number of spanning trees of the graph of `node_count` nodes
+/// and the edges `edges`, by the matrix tree theorem, or `None` if it
+/// overflows.
+fn kirchhoff_count<I>(node_count: usize, edges: I) -> Option<u64>
+    where I: IntoIterator<Item=(usize, usize)>,
+{
+    if node_count == 0 {
+        return Some(0)
+    }
+    let n = node_count - 1;
+    let mut m = vec![0i128; n * n];
+    for (a, b) in edges {
+        add_edge(&mut m, n, a, b, 1);
+    }
+    match determinant(&mut m, n) {
+        Some(d) if d <= u64::MAX as i128 => Some(d as u64),
+        _ => None,
+    }
+}
+
+/// Compute the determinant of the `n × n` row-major matrix `m`, with the
+/// fraction-free Bareiss elimination, or `None` if an intermediate value
+/// overflows.
+fn determinant(m: &mut [i128], n: usize) -> Option<i128> {
+    let mut sign = 1;
+    let mut prev = 1;
+    for k in 0..n {
+        if m[k * n + k] == 0 {
+            let pivot = match (k + 1..n).find(|&i| m[i * n + k] != 0) {
+                None => return Some(0),
+                Some(i) => i,
+            };
+            for j in 0..n {
+                m.swap(k * n + j, pivot * n + j);
+            }
+            sign = -sign;
+        }
+        for i in k + 1..n {
+            for j in k + 1..n {
+                let x = match m[i * n + j].checked_mul(m[k * n + k]) {
+                    None => return None,
+                    Some(x) => x,
+                };
+                let y = match m[i * n + k].checked_mul(m[k * n + j]) {
+                    None => return None,
+                    Some(y) => y,
+                };
+                m[i * n + j] = match x.checked_sub(y) {
+                    None => return None,
+                    Some(d) => d / prev,
+                };
+            }
+        }
+        prev = m[k * n + k];
+    }
+    Some(if n == 0 { 1 } else { sign * m[n * n - 1] })
+}
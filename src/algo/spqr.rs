@@ -0,0 +1,330 @@
+use std::collections::HashMap;
+
+use super::super::{
+    EdgeType,
+    Graph,
+    Undirected,
+};
+use super::super::graph::{
+    EdgeIndex,
+    IndexType,
+    NodeIndex,
+};
+use super::super::unionfind::UnionFind;
+use super::{
+    biconnected_components,
+    is_connected,
+};
+
+/// The kind of a node of an SPQR tree.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SpqrKind {
+    /// An *S-node*: the skeleton is a cycle.
+    Series,
+    /// A *P-node*: the skeleton is two nodes joined by parallel edges.
+    Parallel,
+    /// An *R-node*: the skeleton is a triconnected simple graph.
+    Rigid,
+}
+
+/// An edge of the skeleton of an SPQR tree node.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SkeletonEdge<Ix: IndexType> {
+    /// An edge of the original graph.
+    Real(EdgeIndex<Ix>),
+    /// A virtual edge, standing in for the part of the graph on the other
+    /// side of the tree edge with this index.
+    Virtual(EdgeIndex<Ix>),
+}
+
+/// A node of an SPQR tree: one triconnected component of the graph.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SpqrNode<Ix: IndexType> {
+    /// The kind of the node.
+    pub kind: SpqrKind,
+    /// The edges of the skeleton, as `(a, b, edge)` where `a` and `b` are
+    /// nodes of the original graph.
+    ///
+    /// For an S-node, the edges are listed in cycle order with each edge
+    /// starting where the previous one ends.
+    pub skeleton: Vec<(NodeIndex<Ix>, NodeIndex<Ix>, SkeletonEdge<Ix>)>,
+}
+
+#[derive(Copy, Clone, Debug)]
+enum Ref {
+    Real(usize),
+    Virtual(usize),
+}
+
+type Skeleton = Vec<(usize, usize, Ref)>;
+
+/// Build the *SPQR tree* of a biconnected graph.
+///
+/// The SPQR tree describes all the ways the graph can be split at
+/// separation pairs (two nodes whose removal disconnects it). Each tree node
+/// is a triconnected component: a cycle (S), a bundle of parallel edges
+/// between two nodes (P) or a triconnected graph (R). No two adjacent tree
+/// nodes are both S or both P. Neighboring components share a *virtual
+/// edge* between the nodes of their separation pair, and the tree edge
+/// joining them is weighted with that pair.
+///
+/// Edge directions are ignored. Return `None` if the graph is not
+/// biconnected: if it has fewer than two nodes, is disconnected, has an
+/// articulation point or has a self loop.
+///
+/// This implementation splits the graph at one separation pair at a time and
+/// merges the pieces afterwards, which takes **O(|E|·|V|·(|V| + |E|))**
+/// time; the linear time algorithm of Hopcroft, Tarjan, Gutwenger and
+/// Mutzel is much more involved.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::{spqr_tree, SpqrKind};
+///
+/// // a square with one diagonal: two triangles sharing the edge 0 - 2
+/// let g = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2), (2, 3),
+///                                                   (3, 0), (0, 2)]);
+/// let tree = spqr_tree(&g).unwrap();
+/// let kinds = tree.node_indices().map(|a| tree[a].kind).collect::<Vec<_>>();
+/// assert_eq!(kinds.iter().filter(|&&k| k == SpqrKind::Series).count(), 2);
+/// assert_eq!(kinds.iter().filter(|&&k| k == SpqrKind::Parallel).count(), 1);
+/// assert_eq!(tree.edge_count(), 2);
+/// ```
+pub fn spqr_tree<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>)
+    -> Option<Graph<SpqrNode<Ix>, (NodeIndex<Ix>, NodeIndex<Ix>), Undirected, Ix>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    if g.node_count() < 2 || !is_connected(g) || biconnected_components(g).len() != 1 {
+        return None
+    }
+    let mut edges = Vec::with_capacity(g.edge_count());
+    for (i, edge) in g.raw_edges().iter().enumerate() {
+        let (a, b) = (edge.source().index(), edge.target().index());
+        if a == b {
+            return None
+        }
+        edges.push((a, b, Ref::Real(i)));
+    }
+    let (comps, virtuals) = split_components(edges);
+
+    // Merge adjacent bonds and adjacent polygons; what remains is unique.
+    let mut owners = vec![Vec::with_capacity(2); virtuals.len()];
+    for (i, &(_, ref edges)) in comps.iter().enumerate() {
+        for &(_, _, r) in edges {
+            if let Ref::Virtual(v) = r {
+                owners[v].push(i);
+            }
+        }
+    }
+    let mut sets = UnionFind::new(comps.len());
+    let mut tree_edge = vec![None; virtuals.len()];
+    let mut n_tree_edges = 0;
+    for (v, owner) in owners.iter().enumerate() {
+        let kind = comps[owner[0]].0;
+        if kind != SpqrKind::Rigid && kind == comps[owner[1]].0 {
+            sets.union(owner[0], owner[1]);
+        } else {
+            tree_edge[v] = Some(n_tree_edges);
+            n_tree_edges += 1;
+        }
+    }
+
+    let mut tree_node = vec![None; comps.len()];
+    let mut skeletons = Vec::<(SpqrKind, Skeleton)>::new();
+    for (i, &(kind, ref edges)) in comps.iter().enumerate() {
+        let root = sets.find_mut(i);
+        let t = match tree_node[root] {
+            Some(t) => t,
+            None => {
+                tree_node[root] = Some(skeletons.len());
+                skeletons.push((kind, Vec::new()));
+                skeletons.len() - 1
+            }
+        };
+        skeletons[t].1.extend(edges.iter().cloned().filter(|&(_, _, r)| match r {
+            Ref::Real(_) => true,
+            Ref::Virtual(v) => tree_edge[v].is_some(),
+        }));
+    }
+
+    let mut tree = Graph::with_capacity(skeletons.len(), n_tree_edges);
+    for (kind, mut edges) in skeletons {
+        if kind == SpqrKind::Series {
+            edges = cycle_order(edges);
+        }
+        let skeleton = edges.into_iter().map(|(a, b, r)| {
+            let edge = match r {
+                Ref::Real(e) => SkeletonEdge::Real(EdgeIndex::new(e)),
+                Ref::Virtual(v) => SkeletonEdge::Virtual(EdgeIndex::new(tree_edge[v].unwrap())),
+            };
+            (NodeIndex::new(a), NodeIndex::new(b), edge)
+        }).collect();
+        tree.add_node(SpqrNode { kind: kind, skeleton: skeleton });
+    }
+    for (v, &(a, b)) in virtuals.iter().enumerate() {
+        if tree_edge[v].is_some() {
+            let t0 = tree_node[sets.find_mut(owners[v][0])].unwrap();
+            let t1 = tree_node[sets.find_mut(owners[v][1])].unwrap();
+            tree.add_edge(NodeIndex::new(t0), NodeIndex::new(t1),
+                          (NodeIndex::new(a), NodeIndex::new(b)));
+        }
+    }
+    Some(tree)
+}
+
+/// Split a biconnected multigraph into bonds, triangles and triconnected
+/// simple graphs.
+///
+/// Return the pieces and the endpoints of each virtual edge; every virtual
+/// edge is shared by exactly two pieces.
+fn split_components(edges: Skeleton) -> (Vec<(SpqrKind, Skeleton)>, Vec<(usize, usize)>) {
+    let mut comps = Vec::new();
+    let mut virtuals = Vec::new();
+    let mut work = vec![edges];
+    while let Some(edges) = work.pop() {
+        let nodes = skeleton_nodes(&edges);
+        if nodes.len() == 2 {
+            comps.push((SpqrKind::Parallel, edges));
+            continue
+        }
+        let (is_bond, a, b, mut part, mut rest) = match find_multiple_edge(&edges) {
+            Some((a, b)) => {
+                // split off the bundle of parallel edges as a bond
+                let (bond, rest): (Skeleton, Skeleton) =
+                    edges.into_iter().partition(|&(x, y, _)| {
+                        (x, y) == (a, b) || (y, x) == (a, b)
+                    });
+                (true, a, b, bond, rest)
+            }
+            None if edges.len() == 3 => {
+                comps.push((SpqrKind::Series, edges));
+                continue
+            }
+            None => match find_split(&nodes, &edges) {
+                None => {
+                    comps.push((SpqrKind::Rigid, edges));
+                    continue
+                }
+                Some((a, b, side)) => {
+                    let in_side = |x: usize| side.binary_search(&x).is_ok();
+                    let (part, rest): (Skeleton, Skeleton) =
+                        edges.into_iter().partition(|&(x, y, _)| in_side(x) || in_side(y));
+                    (false, a, b, part, rest)
+                }
+            }
+        };
+        let v = virtuals.len();
+        virtuals.push((a, b));
+        part.push((a, b, Ref::Virtual(v)));
+        rest.push((a, b, Ref::Virtual(v)));
+        if is_bond {
+            comps.push((SpqrKind::Parallel, part));
+        } else {
+            work.push(part);
+        }
+        work.push(rest);
+    }
+    (comps, virtuals)
+}
+
+/// Return the sorted nodes of the skeleton.
+fn skeleton_nodes(edges: &Skeleton) -> Vec<usize> {
+    let mut nodes = Vec::with_capacity(2 * edges.len());
+    for &(a, b, _) in edges {
+        nodes.push(a);
+        nodes.push(b);
+    }
+    nodes.sort();
+    nodes.dedup();
+    nodes
+}
+
+/// Return a pair of nodes joined by more than one edge, if any.
+fn find_multiple_edge(edges: &Skeleton) -> Option<(usize, usize)> {
+    let mut pairs = edges.iter().map(|&(a, b, _)| if a < b { (a, b) } else { (b, a) })
+                         .collect::<Vec<_>>();
+    pairs.sort();
+    pairs.windows(2).find(|w| w[0] == w[1]).map(|w| w[0])
+}
+
+/// Find a separation pair `(a, b)` of a biconnected simple graph, together
+/// with the sorted nodes of one of the components left when removing it.
+///
+/// `b` separates the graph once `a` is removed exactly when it is an
+/// articulation point of the rest.
+fn find_split(nodes: &[usize], edges: &Skeleton) -> Option<(usize, usize, Vec<usize>)> {
+    let local = |x: usize| nodes.binary_search(&x).unwrap();
+    for a in 0..nodes.len() {
+        let mut h = Graph::<(), (), Undirected, usize>::with_capacity(nodes.len(), edges.len());
+        for _ in nodes {
+            h.add_node(());
+        }
+        for &(x, y, _) in edges {
+            let (x, y) = (local(x), local(y));
+            if x != a && y != a {
+                h.add_edge(NodeIndex::new(x), NodeIndex::new(y), ());
+            }
+        }
+        let mut block_count = vec![0; nodes.len()];
+        for block in biconnected_components(&h) {
+            let mut block_nodes = Vec::with_capacity(2 * block.len());
+            for e in block {
+                let (x, y) = h.edge_endpoints(e).unwrap();
+                block_nodes.push(x.index());
+                block_nodes.push(y.index());
+            }
+            block_nodes.sort();
+            block_nodes.dedup();
+            for x in block_nodes {
+                block_count[x] += 1;
+            }
+        }
+        let b = match block_count.iter().position(|&count| count > 1) {
+            None => continue,
+            Some(b) => b,
+        };
+        // collect the component of some node other than a and b
+        let start = (0..nodes.len()).find(|&x| x != a && x != b).unwrap();
+        let mut seen = vec![false; nodes.len()];
+        seen[start] = true;
+        let mut stack = vec![start];
+        let mut side = Vec::new();
+        while let Some(x) = stack.pop() {
+            side.push(nodes[x]);
+            for y in h.neighbors(NodeIndex::new(x)) {
+                if y.index() != b && !seen[y.index()] {
+                    seen[y.index()] = true;
+                    stack.push(y.index());
+                }
+            }
+        }
+        side.sort();
+        return Some((nodes[a], nodes[b], side));
+    }
+    None
+}
+
+/// Order the edges of a cycle so that each edge starts where the previous
+/// one ends.
+fn cycle_order(edges: Skeleton) -> Skeleton {
+    let mut incident = HashMap::<usize, Vec<usize>>::new();
+    for (i, &(a, b, _)) in edges.iter().enumerate() {
+        incident.entry(a).or_insert_with(Vec::new).push(i);
+        incident.entry(b).or_insert_with(Vec::new).push(i);
+    }
+    let mut used = vec![false; edges.len()];
+    let mut ordered = Vec::with_capacity(edges.len());
+    used[0] = true;
+    ordered.push(edges[0]);
+    let mut current = edges[0].1;
+    while ordered.len() < edges.len() {
+        let i = *incident[&current].iter().find(|&&i| !used[i]).unwrap();
+        used[i] = true;
+        let (a, b, r) = edges[i];
+        let next = if a == current { b } else { a };
+        ordered.push((current, next, r));
+        current = next;
+    }
+    ordered
+}
@@ -0,0 +1,85 @@
+use std::collections::HashSet;
+
+use super::super::{
+    EdgeType,
+    Graph,
+};
+use super::connected_components;
+use super::super::graph::IndexType;
+
+/// Basic statistics of a graph, see [`summary`](fn.summary.html).
+#[derive(Clone, Debug, PartialEq)]
+pub struct GraphSummary {
+    /// The number of nodes.
+    pub node_count: usize,
+    /// The number of edges, including self loops and parallel edges.
+    pub edge_count: usize,
+    /// The number of edges divided by the number of possible edges between
+    /// distinct nodes; zero with fewer than two nodes.
+    pub density: f64,
+    /// The smallest node degree, or zero for the empty graph.
+    pub min_degree: usize,
+    /// The largest node degree, or zero for the empty graph.
+    pub max_degree: usize,
+    /// The average node degree, or zero for the empty graph.
+    pub mean_degree: f64,
+    /// The number of edges from a node to itself.
+    pub self_loops: usize,
+    /// The number of edges that repeat an earlier edge between the same
+    /// nodes (in the same direction, for a directed graph).
+    pub parallel_edges: usize,
+    /// The number of (weakly) connected components.
+    pub connected_components: usize,
+}
+
+/// Compute basic statistics of the graph.
+///
+/// A node's degree counts the edges it is an endpoint of, in either
+/// direction; a self loop counts twice.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::summary;
+///
+/// let g = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2), (2, 1), (3, 3)]);
+/// let s = summary(&g);
+/// assert_eq!((s.node_count, s.edge_count), (4, 4));
+/// assert_eq!((s.min_degree, s.max_degree, s.mean_degree), (1, 3, 2.));
+/// assert_eq!((s.self_loops, s.parallel_edges), (1, 1));
+/// assert_eq!(s.connected_components, 2);
+/// ```
+pub fn summary<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>) -> GraphSummary
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    let n = g.node_count();
+    let m = g.edge_count();
+    let mut degrees = vec![0; n];
+    let mut self_loops = 0;
+    let mut pairs = HashSet::with_capacity(m);
+    let mut parallel_edges = 0;
+    for edge in g.raw_edges() {
+        let (a, b) = (edge.source().index(), edge.target().index());
+        degrees[a] += 1;
+        degrees[b] += 1;
+        if a == b {
+            self_loops += 1;
+        }
+        let pair = if g.is_directed() || a <= b { (a, b) } else { (b, a) };
+        if !pairs.insert(pair) {
+            parallel_edges += 1;
+        }
+    }
+    let possible = n * n.saturating_sub(1) / if g.is_directed() { 1 } else { 2 };
+    GraphSummary {
+        node_count: n,
+        edge_count: m,
+        density: if possible == 0 { 0. } else { m as f64 / possible as f64 },
+        min_degree: degrees.iter().cloned().min().unwrap_or(0),
+        max_degree: degrees.iter().cloned().max().unwrap_or(0),
+        mean_degree: if n == 0 { 0. } else { 2. * m as f64 / n as f64 },
+        self_loops: self_loops,
+        parallel_edges: parallel_edges,
+        connected_components: connected_components(g),
+    }
+}
@@ -0,0 +1,461 @@
+//! Algorithms for rooted trees stored in a `Graph`.
+//!
+//! The tree is the connected component of the given root, and edge
+//! directions are ignored. If the component is not a tree, the functions
+//! work on the spanning tree found by a depth first search from the root.
+//!
+//! Results that are indexed by node are vectors of length
+//! `g.node_count()`; nodes outside the tree get a default value.
+
+use core::cmp::{
+    max,
+    min,
+};
+use alloc::collections::VecDeque;
+use core::mem;
+use core::ops::Range;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use super::super::{
+    EdgeType,
+    Graph,
+};
+use super::super::graph::{
+    IndexType,
+    NodeIndex,
+};
+
+/// The tree structure found by a depth first search from the root.
+struct Rooted<Ix> {
+    /// The nodes of the tree in preorder.
+    order: Vec<NodeIndex<Ix>>,
+    parent: Vec<Option<NodeIndex<Ix>>>,
+    children: Vec<Vec<NodeIndex<Ix>>>,
+}
+
+impl<Ix: IndexType> Rooted<Ix> {
+    fn new<N, E, Ty>(g: &Graph<N, E, Ty, Ix>, root: NodeIndex<Ix>) -> Self
+        where Ty: EdgeType,
+    {
+        let mut discovered = vec![false; g.node_count()];
+        let mut parent = vec![None; g.node_count()];
+        let mut children = vec![Vec::new(); g.node_count()];
+        let mut order = Vec::new();
+        discovered[root.index()] = true;
+        let mut stack = vec![root];
+        while let Some(a) = stack.pop() {
+            order.push(a);
+            for b in g.neighbors_undirected(a) {
+                if !discovered[b.index()] {
+                    discovered[b.index()] = true;
+                    parent[b.index()] = Some(a);
+                    children[a.index()].push(b);
+                }
+            }
+            stack.extend(children[a.index()].iter().rev());
+        }
+        Rooted {
+            order: order,
+            parent: parent,
+            children: children,
+        }
+    }
+
+    /// Return the tree neighbors of `a`: its children and its parent.
+    fn neighbors<'a>(&'a self, a: NodeIndex<Ix>) -> Box<Iterator<Item=NodeIndex<Ix>> + 'a> {
+        Box::new(self.children[a.index()].iter().cloned().chain(self.parent[a.index()]))
+    }
+
+    /// Return the path from `start` to a node farthest from it in the tree.
+    fn farthest(&self, start: NodeIndex<Ix>) -> Vec<NodeIndex<Ix>> {
+        let mut pred = vec![None; self.parent.len()];
+        let mut seen = vec![false; self.parent.len()];
+        let mut queue = VecDeque::new();
+        let mut last = start;
+        seen[start.index()] = true;
+        queue.push_back(start);
+        while let Some(a) = queue.pop_front() {
+            last = a;
+            for b in self.neighbors(a) {
+                if !seen[b.index()] {
+                    seen[b.index()] = true;
+                    pred[b.index()] = Some(a);
+                    queue.push_back(b);
+                }
+            }
+        }
+        let mut path = vec![last];
+        while let Some(a) = pred[path[path.len() - 1].index()] {
+            path.push(a);
+        }
+        path.reverse();
+        path
+    }
+}
+
+/// Return the parent of each node in the tree rooted at `root`.
+///
+/// The root and the nodes outside the tree have no parent.
+pub fn parents<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>, root: NodeIndex<Ix>)
+    -> Vec<Option<NodeIndex<Ix>>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    Rooted::new(g, root).parent
+}
+
+/// Return the *Euler tour* of the tree rooted at `root`.
+///
+/// The tour walks around the tree depth first and lists a node each time
+/// it is reached: when it is first entered, and again after returning from
+/// each of its children. A tree of *n* nodes has a tour of *2n - 1* nodes.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::tree::euler_tour;
+/// use petgraph::graph::node_index as n;
+///
+/// let g = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2)]);
+/// assert_eq!(euler_tour(&g, n(0)), vec![n(0), n(1), n(2), n(1), n(0)]);
+/// ```
+pub fn euler_tour<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>, root: NodeIndex<Ix>)
+    -> Vec<NodeIndex<Ix>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    let tree = Rooted::new(g, root);
+    let mut tour = Vec::with_capacity(2 * tree.order.len() - 1);
+    // each stack entry is a node and the number of its children visited
+    let mut stack = vec![(root, 0)];
+    tour.push(root);
+    while let Some(&mut (a, ref mut next_child)) = stack.last_mut() {
+        match tree.children[a.index()].get(*next_child) {
+            Some(&child) => {
+                *next_child += 1;
+                tour.push(child);
+                stack.push((child, 0));
+            }
+            None => {
+                stack.pop();
+                if let Some(&(parent, _)) = stack.last() {
+                    tour.push(parent);
+                }
+            }
+        }
+    }
+    tour
+}
+
+/// Return the number of nodes in the subtree of each node, in the tree
+/// rooted at `root`.
+///
+/// Nodes outside the tree have size zero.
+pub fn subtree_sizes<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>, root: NodeIndex<Ix>)
+    -> Vec<usize>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    let tree = Rooted::new(g, root);
+    let mut sizes = vec![0; g.node_count()];
+    for &a in tree.order.iter().rev() {
+        sizes[a.index()] += 1;
+        if let Some(p) = tree.parent[a.index()] {
+            sizes[p.index()] += sizes[a.index()];
+        }
+    }
+    sizes
+}
+
+/// Return a longest path in the tree containing `root`, counted in edges.
+///
+/// The path is found with two breadth first searches: the node farthest
+/// from any node is an end of a longest path.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::tree::diameter;
+/// use petgraph::graph::node_index as n;
+///
+/// let g = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 4), (2, 5)]);
+/// assert_eq!(diameter(&g, n(4)), vec![n(0), n(1), n(2), n(3), n(4)]);
+/// ```
+pub fn diameter<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>, root: NodeIndex<Ix>)
+    -> Vec<NodeIndex<Ix>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    let tree = Rooted::new(g, root);
+    let end = tree.farthest(root).pop().unwrap();
+    tree.farthest(end)
+}
+
+/// Return the center of the tree containing `root`: the one or two nodes
+/// that minimize the distance to the farthest node.
+///
+/// The center is the middle of any longest path; two center nodes are
+/// adjacent and returned in path order.
+pub fn center<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>, root: NodeIndex<Ix>)
+    -> Vec<NodeIndex<Ix>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    let path = diameter(g, root);
+    let mid = path.len() / 2;
+    if path.len() % 2 == 1 {
+        vec![path[mid]]
+    } else {
+        vec![path[mid - 1], path[mid]]
+    }
+}
+
+/// Compute the *centroid decomposition* of the tree containing `root`.
+///
+/// A centroid of a tree is a node whose removal leaves no part with more
+/// than half of the nodes. The decomposition picks a centroid, removes it,
+/// and decomposes each remaining part in turn, which builds a new tree of
+/// depth at most *log₂ n*.
+///
+/// Return the parent of each node in the centroid tree. The top centroid and
+/// the nodes outside the tree have no parent.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::tree::centroid_decomposition;
+/// use petgraph::graph::node_index as n;
+///
+/// // the path 0 - 1 - 2 - 3 - 4 is split at 2, then at 1 and 3
+/// let g = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 4)]);
+/// assert_eq!(centroid_decomposition(&g, n(0)),
+///            vec![Some(n(1)), Some(n(2)), None, Some(n(2)), Some(n(3))]);
+/// ```
+pub fn centroid_decomposition<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>, root: NodeIndex<Ix>)
+    -> Vec<Option<NodeIndex<Ix>>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    let tree = Rooted::new(g, root);
+    let mut removed = vec![false; g.node_count()];
+    let mut size = vec![0; g.node_count()];
+    let mut centroid_parent = vec![None; g.node_count()];
+    // the parts left to decompose, as a node in the part and the centroid
+    // whose removal left it
+    let mut parts = vec![(root, None)];
+    let mut order = Vec::new();
+    let mut pred = vec![None; g.node_count()];
+    while let Some((start, parent)) = parts.pop() {
+        // compute subtree sizes within the part, rooted at start
+        order.clear();
+        order.push(start);
+        pred[start.index()] = None;
+        let mut i = 0;
+        while i < order.len() {
+            let a = order[i];
+            i += 1;
+            for b in tree.neighbors(a) {
+                if !removed[b.index()] && Some(b) != pred[a.index()] {
+                    pred[b.index()] = Some(a);
+                    order.push(b);
+                }
+            }
+        }
+        for &a in order.iter().rev() {
+            size[a.index()] = 1 + tree.neighbors(a)
+                .filter(|&b| !removed[b.index()] && pred[b.index()] == Some(a))
+                .map(|b| size[b.index()])
+                .sum::<usize>();
+        }
+
+        // walk towards the heavy side until no part is too big
+        let total = order.len();
+        let mut centroid = start;
+        loop {
+            let heavy = tree.neighbors(centroid).find(|&b| {
+                !removed[b.index()] && pred[b.index()] == Some(centroid) &&
+                    2 * size[b.index()] > total
+            });
+            match heavy {
+                Some(b) => centroid = b,
+                None => break,
+            }
+        }
+        centroid_parent[centroid.index()] = parent;
+        removed[centroid.index()] = true;
+        for b in tree.neighbors(centroid) {
+            if !removed[b.index()] {
+                parts.push((b, Some(centroid)));
+            }
+        }
+    }
+    centroid_parent
+}
+
+/// The *heavy-light decomposition* of a rooted tree.
+///
+/// The tree is split into chains: each node continues the chain of its
+/// child with the largest subtree (its *heavy* child), and its other
+/// children start new chains. Any path in the tree then crosses
+/// *O(log n)* chains.
+///
+/// Each node gets a position such that every chain occupies a contiguous
+/// range of positions, ordered from its top node down. Store per-node
+/// values in a segment tree (or any range structure) by position, and use
+/// [`.path_segments()`](#method.path_segments) to answer path aggregate
+/// queries like the maximum or sum along a path.
+///
+/// Created with [`heavy_light_decomposition`](fn.heavy_light_decomposition.html).
+#[derive(Clone, Debug)]
+pub struct HeavyLight<Ix> {
+    parent: Vec<Option<NodeIndex<Ix>>>,
+    depth: Vec<usize>,
+    head: Vec<NodeIndex<Ix>>,
+    chain: Vec<Option<usize>>,
+    position: Vec<Option<usize>>,
+    chain_count: usize,
+}
+
+/// Compute the heavy-light decomposition of the tree rooted at `root`.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::algo::tree::heavy_light_decomposition;
+/// use petgraph::graph::node_index as n;
+///
+/// // node values, and the maximum along the path from 3 to 4
+/// let values = [5, 1, 7, 2, 3];
+/// let g = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2), (2, 3), (1, 4)]);
+/// let hld = heavy_light_decomposition(&g, n(0));
+/// let mut by_position = vec![0; 5];
+/// for a in g.node_indices() {
+///     by_position[hld.position(a).unwrap()] = values[a.index()];
+/// }
+/// let max = hld.path_segments(n(3), n(4)).into_iter()
+///              .flat_map(|range| by_position[range].to_vec())
+///              .max();
+/// assert_eq!(max, Some(7));
+/// ```
+pub fn heavy_light_decomposition<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>, root: NodeIndex<Ix>)
+    -> HeavyLight<Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    let tree = Rooted::new(g, root);
+    let sizes = subtree_sizes(g, root);
+    let mut depth = vec![0; g.node_count()];
+    for &a in &tree.order {
+        if let Some(p) = tree.parent[a.index()] {
+            depth[a.index()] = depth[p.index()] + 1;
+        }
+    }
+
+    let mut head = vec![NodeIndex::end(); g.node_count()];
+    let mut chain = vec![None; g.node_count()];
+    let mut position = vec![None; g.node_count()];
+    let mut chain_count = 0;
+    let mut next_position = 0;
+    head[root.index()] = root;
+    let mut stack = vec![root];
+    while let Some(a) = stack.pop() {
+        let h = head[a.index()];
+        if h == a {
+            chain[a.index()] = Some(chain_count);
+            chain_count += 1;
+        } else {
+            chain[a.index()] = chain[h.index()];
+        }
+        position[a.index()] = Some(next_position);
+        next_position += 1;
+
+        // visit the heavy child right after its parent, to continue the chain
+        let children = &tree.children[a.index()];
+        let heavy = children.iter().cloned().max_by_key(|b| sizes[b.index()]);
+        for &b in children {
+            if Some(b) != heavy {
+                head[b.index()] = b;
+                stack.push(b);
+            }
+        }
+        if let Some(b) = heavy {
+            head[b.index()] = h;
+            stack.push(b);
+        }
+    }
+    HeavyLight {
+        parent: tree.parent,
+        depth: depth,
+        head: head,
+        chain: chain,
+        position: position,
+        chain_count: chain_count,
+    }
+}
+
+impl<Ix: IndexType> HeavyLight<Ix> {
+    /// Return the number of chains.
+    pub fn chain_count(&self) -> usize {
+        self.chain_count
+    }
+
+    /// Return the chain of `a`, or `None` if it is not in the tree.
+    pub fn chain(&self, a: NodeIndex<Ix>) -> Option<usize> {
+        self.chain[a.index()]
+    }
+
+    /// Return the position of `a`, or `None` if it is not in the tree.
+    pub fn position(&self, a: NodeIndex<Ix>) -> Option<usize> {
+        self.position[a.index()]
+    }
+
+    /// Return the top node of the chain of `a`.
+    ///
+    /// **Panics** if `a` is not in the tree.
+    pub fn head(&self, a: NodeIndex<Ix>) -> NodeIndex<Ix> {
+        assert!(self.chain[a.index()].is_some(), "HeavyLight: node not in the tree");
+        self.head[a.index()]
+    }
+
+    /// Return the parent of `a` in the tree.
+    pub fn parent(&self, a: NodeIndex<Ix>) -> Option<NodeIndex<Ix>> {
+        self.parent[a.index()]
+    }
+
+    /// Return the depth of `a` in the tree; the root has depth zero.
+    pub fn depth(&self, a: NodeIndex<Ix>) -> usize {
+        self.depth[a.index()]
+    }
+
+    /// Return the lowest common ancestor of `a` and `b`.
+    ///
+    /// **Panics** if `a` or `b` is not in the tree.
+    pub fn lca(&self, mut a: NodeIndex<Ix>, mut b: NodeIndex<Ix>) -> NodeIndex<Ix> {
+        while self.head(a) != self.head(b) {
+            if self.depth(self.head(a)) < self.depth(self.head(b)) {
+                mem::swap(&mut a, &mut b);
+            }
+            a = self.parent[self.head(a).index()].unwrap();
+        }
+        if self.depth(a) < self.depth(b) { a } else { b }
+    }
+
+    /// Return ranges of positions that together cover exactly the nodes on
+    /// the path from `a` to `b`, both included.
+    ///
+    /// There is at most one range per chain on the path, and the ranges are
+    /// in no particular order.
+    ///
+    /// **Panics** if `a` or `b` is not in the tree.
+    pub fn path_segments(&self, mut a: NodeIndex<Ix>, mut b: NodeIndex<Ix>) -> Vec<Range<usize>> {
+        let mut segments = Vec::new();
+        while self.head(a) != self.head(b) {
+            if self.depth(self.head(a)) < self.depth(self.head(b)) {
+                mem::swap(&mut a, &mut b);
+            }
+            let h = self.head(a);
+            segments.push(self.position[h.index()].unwrap()..self.position[a.index()].unwrap() + 1);
+            a = self.parent[h.index()].unwrap();
+        }
+        let (pa, pb) = (self.position[a.index()].unwrap(), self.position[b.index()].unwrap());
+        segments.push(min(pa, pb)..max(pa, pb) + 1);
+        segments
+    }
+}
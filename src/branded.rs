@@ -0,0 +1,235 @@
+//! ***Opt-in.*** Node and edge indices branded with the graph they belong to.
+//!
+//! A plain `NodeIndex` is just a number: an index from one graph can index
+//! another graph, silently reaching the wrong node. Inside
+//! [`brand`](fn.brand.html), the graph hands out indices that carry a unique
+//! lifetime, the *brand*, and only accepts indices with its own brand, so
+//! mixing up graphs is a compile time error:
+//!
+//! ```compile_fail
+//! use petgraph::Graph;
+//! use petgraph::graph::branded::brand;
+//!
+//! let mut g = Graph::<&str, ()>::new();
+//! let mut h = Graph::<&str, ()>::new();
+//! brand(&mut g, |mut g| {
+//!     brand(&mut h, |mut h| {
+//!         let a = g.add_node("a");
+//!         let b = h.add_node("b");
+//!         h.add_edge(a, b, ()); // error: `a` is an index of `g`
+//!     })
+//! });
+//! ```
+//!
+//! Branded indices are always valid: a branded graph can grow, but it can't
+//! remove nodes or edges, which would shift the indices.
+
+use core::iter;
+use core::marker::PhantomData;
+use core::ops::{
+    Index,
+    IndexMut,
+};
+
+use EdgeType;
+use super::{
+    EdgeIndex,
+    Graph,
+    IndexType,
+    Neighbors,
+    NodeIndex,
+    NodeIndices,
+};
+
+/// An invariant lifetime, so that two brands never unify.
+type Brand<'id> = PhantomData<fn(&'id ()) -> &'id ()>;
+
+/// The function that brands node indices, in iterators.
+type BrandNode<'id, Ix> = fn(NodeIndex<Ix>) -> BrandedNodeIndex<'id, Ix>;
+
+/// A node index of the branded graph `'id`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BrandedNodeIndex<'id, Ix: IndexType> {
+    index: NodeIndex<Ix>,
+    brand: Brand<'id>,
+}
+
+impl<'id, Ix: IndexType> BrandedNodeIndex<'id, Ix> {
+    /// Return the plain node index.
+    pub fn index(self) -> NodeIndex<Ix> {
+        self.index
+    }
+}
+
+/// An edge index of the branded graph `'id`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BrandedEdgeIndex<'id, Ix: IndexType> {
+    index: EdgeIndex<Ix>,
+    brand: Brand<'id>,
+}
+
+impl<'id, Ix: IndexType> BrandedEdgeIndex<'id, Ix> {
+    /// Return the plain edge index.
+    pub fn index(self) -> EdgeIndex<Ix> {
+        self.index
+    }
+}
+
+/// A graph that only accepts indices with its brand `'id`, see
+/// [`brand`](fn.brand.html).
+pub struct BrandedGraph<'id, 'a, N: 'a, E: 'a, Ty: 'a, Ix: 'a + IndexType> {
+    graph: &'a mut Graph<N, E, Ty, Ix>,
+    brand: Brand<'id>,
+}
+
+/// Call `f` with a branded view of the graph `g`.
+///
+/// The brand is only valid inside `f`; use
+/// [`.index()`](struct.BrandedNodeIndex.html#method.index) to take plain
+/// indices out.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::graph::branded::brand;
+///
+/// let mut g = Graph::<&str, u32>::new();
+/// let a = g.add_node("a");
+/// let ab = brand(&mut g, |mut g| {
+///     // plain indices are checked once, when they enter the brand
+///     let a = g.node(a).unwrap();
+///     let b = g.add_node("b");
+///     let ab = g.add_edge(a, b, 7);
+///     assert_eq!(g[b], "b");
+///     assert_eq!(g.edge_endpoints(ab), (a, b));
+///     ab.index()
+/// });
+/// assert_eq!(g[ab], 7);
+/// ```
+pub fn brand<'a, N, E, Ty, Ix, F, R>(g: &'a mut Graph<N, E, Ty, Ix>, f: F) -> R
+    where Ty: EdgeType,
+          Ix: IndexType,
+          F: for<'id> FnOnce(BrandedGraph<'id, 'a, N, E, Ty, Ix>) -> R,
+{
+    f(BrandedGraph {
+        graph: g,
+        brand: PhantomData,
+    })
+}
+
+impl<'id, 'a, N, E, Ty, Ix> BrandedGraph<'id, 'a, N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    /// Return the underlying graph.
+    pub fn graph(&self) -> &Graph<N, E, Ty, Ix> {
+        self.graph
+    }
+
+    /// Brand the node index `a`, or return `None` if it is not a node of
+    /// the graph.
+    pub fn node(&self, a: NodeIndex<Ix>) -> Option<BrandedNodeIndex<'id, Ix>> {
+        if self.graph.contains_node_index(a) {
+            Some(node(a))
+        } else {
+            None
+        }
+    }
+
+    /// Brand the edge index `e`, or return `None` if it is not an edge of
+    /// the graph.
+    pub fn edge(&self, e: EdgeIndex<Ix>) -> Option<BrandedEdgeIndex<'id, Ix>> {
+        if self.graph.contains_edge_index(e) {
+            Some(edge(e))
+        } else {
+            None
+        }
+    }
+
+    /// Add a node with weight `weight`, and return its index.
+    pub fn add_node(&mut self, weight: N) -> BrandedNodeIndex<'id, Ix> {
+        node(self.graph.add_node(weight))
+    }
+
+    /// Add an edge from `a` to `b` with weight `weight`, and return its
+    /// index.
+    pub fn add_edge(&mut self,
+                    a: BrandedNodeIndex<'id, Ix>,
+                    b: BrandedNodeIndex<'id, Ix>,
+                    weight: E) -> BrandedEdgeIndex<'id, Ix>
+    {
+        edge(self.graph.add_edge(a.index, b.index, weight))
+    }
+
+    /// Return the source and target of the edge `e`.
+    pub fn edge_endpoints(&self, e: BrandedEdgeIndex<'id, Ix>)
+        -> (BrandedNodeIndex<'id, Ix>, BrandedNodeIndex<'id, Ix>)
+    {
+        let (a, b) = self.graph.edge_endpoints(e.index).unwrap();
+        (node(a), node(b))
+    }
+
+    /// Return an iterator of the nodes of the graph.
+    pub fn node_indices(&self) -> iter::Map<NodeIndices<Ix>, BrandNode<'id, Ix>> {
+        self.graph.node_indices().map(node)
+    }
+
+    /// Return an iterator of the neighbors of `a`, as
+    /// [`Graph::neighbors`](../struct.Graph.html#method.neighbors).
+    pub fn neighbors(&self, a: BrandedNodeIndex<'id, Ix>)
+        -> iter::Map<Neighbors<E, Ix>, BrandNode<'id, Ix>>
+    {
+        self.graph.neighbors(a.index).map(node)
+    }
+}
+
+fn node<'id, Ix: IndexType>(a: NodeIndex<Ix>) -> BrandedNodeIndex<'id, Ix> {
+    BrandedNodeIndex {
+        index: a,
+        brand: PhantomData,
+    }
+}
+
+fn edge<'id, Ix: IndexType>(e: EdgeIndex<Ix>) -> BrandedEdgeIndex<'id, Ix> {
+    BrandedEdgeIndex {
+        index: e,
+        brand: PhantomData,
+    }
+}
+
+impl<'id, 'a, N, E, Ty, Ix> Index<BrandedNodeIndex<'id, Ix>> for BrandedGraph<'id, 'a, N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    type Output = N;
+    fn index(&self, a: BrandedNodeIndex<'id, Ix>) -> &N {
+        &self.graph[a.index]
+    }
+}
+
+impl<'id, 'a, N, E, Ty, Ix> IndexMut<BrandedNodeIndex<'id, Ix>> for BrandedGraph<'id, 'a, N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn index_mut(&mut self, a: BrandedNodeIndex<'id, Ix>) -> &mut N {
+        &mut self.graph[a.index]
+    }
+}
+
+impl<'id, 'a, N, E, Ty, Ix> Index<BrandedEdgeIndex<'id, Ix>> for BrandedGraph<'id, 'a, N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    type Output = E;
+    fn index(&self, e: BrandedEdgeIndex<'id, Ix>) -> &E {
+        &self.graph[e.index]
+    }
+}
+
+impl<'id, 'a, N, E, Ty, Ix> IndexMut<BrandedEdgeIndex<'id, Ix>> for BrandedGraph<'id, 'a, N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn index_mut(&mut self, e: BrandedEdgeIndex<'id, Ix>) -> &mut E {
+        &mut self.graph[e.index]
+    }
+}
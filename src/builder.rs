@@ -0,0 +1,163 @@
+//! `GraphBuilder` builds a `Graph` from edge records between node keys.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use {
+    Directed,
+    EdgeType,
+    Undirected,
+};
+use super::{
+    DefIndex,
+    EdgeIndex,
+    Graph,
+    IndexType,
+    NodeIndex,
+};
+
+/// Build a `Graph` from `(key_a, key_b, weight)` records, creating one node
+/// per distinct key.
+///
+/// The keys become the node weights. By default each record adds an edge;
+/// with [`merge_edges`](#method.merge_edges), a record between two nodes
+/// that already have an edge merges its weight into that edge instead.
+///
+/// ```
+/// use petgraph::graph::builder::GraphBuilder;
+///
+/// // count the calls between functions
+/// let calls = vec![("main", "parse", 1), ("parse", "lex", 1), ("main", "parse", 1)];
+///
+/// let mut builder = GraphBuilder::new().merge_edges(|count: &mut u32, more| *count += more);
+/// builder.extend(calls);
+/// let (g, index) = builder.build();
+///
+/// assert_eq!(g.node_count(), 3);
+/// assert_eq!(g.edge_count(), 2);
+/// let e = g.find_edge(index["main"], index["parse"]).unwrap();
+/// assert_eq!(g[e], 2);
+/// ```
+pub struct GraphBuilder<K, E, Ty = Directed, Ix: IndexType = DefIndex> {
+    graph: Graph<K, E, Ty, Ix>,
+    nodes: HashMap<K, NodeIndex<Ix>>,
+    edges: HashMap<(usize, usize), EdgeIndex<Ix>>,
+    merge: Option<Merge<E>>,
+}
+
+/// The function that merges the weight of a record into an edge.
+type Merge<E> = Box<FnMut(&mut E, E)>;
+
+impl<K, E> GraphBuilder<K, E, Directed>
+    where K: Clone + Eq + Hash,
+{
+    /// Create a builder of a directed graph.
+    pub fn new() -> Self {
+        GraphBuilder::from_graph(Graph::new())
+    }
+}
+
+impl<K, E> GraphBuilder<K, E, Undirected>
+    where K: Clone + Eq + Hash,
+{
+    /// Create a builder of an undirected graph.
+    pub fn new_undirected() -> Self {
+        GraphBuilder::from_graph(Graph::new_undirected())
+    }
+}
+
+impl<K, E, Ty, Ix> GraphBuilder<K, E, Ty, Ix>
+    where K: Clone + Eq + Hash,
+          Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn from_graph(graph: Graph<K, E, Ty, Ix>) -> Self {
+        GraphBuilder {
+            graph: graph,
+            nodes: HashMap::new(),
+            edges: HashMap::new(),
+            merge: None,
+        }
+    }
+
+    /// Merge the records between two nodes that already have an edge into
+    /// that edge, with `merge(&mut old_weight, new_weight)`.
+    ///
+    /// In an undirected graph, the records `(a, b)` and `(b, a)` are between
+    /// the same nodes.
+    pub fn merge_edges<F>(mut self, merge: F) -> Self
+        where F: FnMut(&mut E, E) + 'static,
+    {
+        self.merge = Some(Box::new(merge));
+        self
+    }
+
+    /// Reserve room for at least `nodes` more nodes and `edges` more edges.
+    pub fn reserve(&mut self, nodes: usize, edges: usize) {
+        self.nodes.reserve(nodes);
+        self.graph.reserve_nodes(nodes);
+        self.graph.reserve_edges(edges);
+    }
+
+    /// Return the index of the node with key `key`, adding it first if it
+    /// is new.
+    pub fn add_node(&mut self, key: K) -> NodeIndex<Ix> {
+        if let Some(&a) = self.nodes.get(&key) {
+            return a
+        }
+        let a = self.graph.add_node(key.clone());
+        self.nodes.insert(key, a);
+        a
+    }
+
+    /// Add an edge from the node with key `a` to the node with key `b`,
+    /// adding the nodes if they are new, and return the index of the edge.
+    ///
+    /// If edges are merged and the nodes already have an edge, `weight` is
+    /// merged into it and its index is returned.
+    pub fn add_edge(&mut self, a: K, b: K, weight: E) -> EdgeIndex<Ix> {
+        let a = self.add_node(a);
+        let b = self.add_node(b);
+        let merge = match self.merge {
+            None => return self.graph.add_edge(a, b, weight),
+            Some(ref mut merge) => merge,
+        };
+        let (x, y) = (a.index(), b.index());
+        let pair = if self.graph.is_directed() || x <= y { (x, y) } else { (y, x) };
+        match self.edges.get(&pair) {
+            Some(&e) => {
+                merge(&mut self.graph[e], weight);
+                e
+            }
+            None => {
+                let e = self.graph.add_edge(a, b, weight);
+                self.edges.insert(pair, e);
+                e
+            }
+        }
+    }
+
+    /// Finish the graph, and return it with the index of the node of each
+    /// key.
+    pub fn build(self) -> (Graph<K, E, Ty, Ix>, HashMap<K, NodeIndex<Ix>>) {
+        (self.graph, self.nodes)
+    }
+}
+
+/// Add an edge for each record.
+impl<K, E, Ty, Ix> Extend<(K, K, E)> for GraphBuilder<K, E, Ty, Ix>
+    where K: Clone + Eq + Hash,
+          Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn extend<I>(&mut self, records: I)
+        where I: IntoIterator<Item=(K, K, E)>,
+    {
+        let records = records.into_iter();
+        let (low, _) = records.size_hint();
+        self.graph.reserve_edges(low);
+        for (a, b, weight) in records {
+            self.add_edge(a, b, weight);
+        }
+    }
+}
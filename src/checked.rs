@@ -0,0 +1,297 @@
+//! `CheckedGraph` is a `Graph` whose mutations return errors instead of
+//! panicking.
+
+use core::fmt;
+use core::ops::{
+    Deref,
+    Index,
+    IndexMut,
+};
+
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use {
+    Directed,
+    EdgeType,
+    Undirected,
+};
+use super::{
+    DefIndex,
+    EdgeIndex,
+    Graph,
+    IndexType,
+    NodeIndex,
+};
+
+/// Which edges a [`CheckedGraph`](struct.CheckedGraph.html) accepts.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Policy {
+    /// Accept edges from a node to itself.
+    pub self_loops: bool,
+    /// Accept several edges between the same two nodes (in the same
+    /// direction, for a directed graph).
+    pub parallel_edges: bool,
+}
+
+impl Policy {
+    /// Accept any edge, like `Graph`.
+    pub fn any() -> Self {
+        Policy {
+            self_loops: true,
+            parallel_edges: true,
+        }
+    }
+
+    /// Accept the edges of a *simple graph*: no self loops and no parallel
+    /// edges.
+    pub fn simple() -> Self {
+        Policy {
+            self_loops: false,
+            parallel_edges: false,
+        }
+    }
+}
+
+/// The default policy accepts any edge.
+impl Default for Policy {
+    fn default() -> Self {
+        Policy::any()
+    }
+}
+
+/// An invalid mutation of a [`CheckedGraph`](struct.CheckedGraph.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GraphError<Ix: IndexType = DefIndex> {
+    /// The node is not in the graph.
+    NodeNotFound(NodeIndex<Ix>),
+    /// The edge is not in the graph.
+    EdgeNotFound(EdgeIndex<Ix>),
+    /// The edge would be a self loop, which the policy forbids.
+    SelfLoop(NodeIndex<Ix>),
+    /// The edge would be parallel to an existing edge, which the policy
+    /// forbids.
+    ParallelEdge(NodeIndex<Ix>, NodeIndex<Ix>),
+    /// The index type has no room for another node.
+    TooManyNodes,
+    /// The index type has no room for another edge.
+    TooManyEdges,
+}
+
+impl<Ix: IndexType> fmt::Display for GraphError<Ix> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            GraphError::NodeNotFound(a) => write!(f, "node {} is not in the graph", a.index()),
+            GraphError::EdgeNotFound(e) => write!(f, "edge {} is not in the graph", e.index()),
+            GraphError::SelfLoop(a) => write!(f, "self loop at node {}", a.index()),
+            GraphError::ParallelEdge(a, b) => {
+                write!(f, "parallel edge between nodes {} and {}", a.index(), b.index())
+            }
+            GraphError::TooManyNodes => write!(f, "too many nodes for the index type"),
+            GraphError::TooManyEdges => write!(f, "too many edges for the index type"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Ix: IndexType> Error for GraphError<Ix> {
+    fn description(&self) -> &str {
+        match *self {
+            GraphError::NodeNotFound(_) => "node not found",
+            GraphError::EdgeNotFound(_) => "edge not found",
+            GraphError::SelfLoop(_) => "self loop",
+            GraphError::ParallelEdge(..) => "parallel edge",
+            GraphError::TooManyNodes => "too many nodes",
+            GraphError::TooManyEdges => "too many edges",
+        }
+    }
+}
+
+/// A `Graph` that checks every mutation, and returns a
+/// [`GraphError`](enum.GraphError.html) instead of panicking or breaking its
+/// [`Policy`](struct.Policy.html).
+///
+/// Use it to build graphs from untrusted input. Everything that reads the
+/// graph is available through `Deref`.
+///
+/// ```
+/// use petgraph::graph::checked::{CheckedGraph, GraphError, Policy};
+/// use petgraph::graph::node_index as n;
+///
+/// let mut g = CheckedGraph::<&str, ()>::new(Policy::simple());
+/// let a = g.try_add_node("a").unwrap();
+/// let b = g.try_add_node("b").unwrap();
+/// assert!(g.try_add_edge(a, b, ()).is_ok());
+/// assert_eq!(g.try_add_edge(a, b, ()), Err(GraphError::ParallelEdge(a, b)));
+/// assert_eq!(g.try_add_edge(a, a, ()), Err(GraphError::SelfLoop(a)));
+/// assert_eq!(g.try_add_edge(a, n(5), ()), Err(GraphError::NodeNotFound(n(5))));
+/// assert_eq!(g.edge_count(), 1);
+/// ```
+#[derive(Clone)]
+pub struct CheckedGraph<N, E, Ty = Directed, Ix: IndexType = DefIndex> {
+    graph: Graph<N, E, Ty, Ix>,
+    policy: Policy,
+}
+
+impl<N, E> CheckedGraph<N, E, Directed> {
+    /// Create a new directed `CheckedGraph` with the policy `policy`.
+    pub fn new(policy: Policy) -> Self {
+        CheckedGraph {
+            graph: Graph::new(),
+            policy: policy,
+        }
+    }
+}
+
+impl<N, E> CheckedGraph<N, E, Undirected> {
+    /// Create a new undirected `CheckedGraph` with the policy `policy`.
+    pub fn new_undirected(policy: Policy) -> Self {
+        CheckedGraph {
+            graph: Graph::new_undirected(),
+            policy: policy,
+        }
+    }
+}
+
+impl<N, E, Ty, Ix> CheckedGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    /// Check that the edges of `graph` follow `policy`, and wrap it.
+    ///
+    /// Return the error of the first edge that breaks the policy, otherwise.
+    pub fn from_graph(graph: Graph<N, E, Ty, Ix>, policy: Policy)
+        -> Result<Self, GraphError<Ix>>
+    {
+        for (i, edge) in graph.raw_edges().iter().enumerate() {
+            let (a, b) = (edge.source(), edge.target());
+            if !policy.self_loops && a == b {
+                return Err(GraphError::SelfLoop(a))
+            }
+            if !policy.parallel_edges && graph.find_edge(a, b).map_or(false, |e| e.index() != i) {
+                return Err(GraphError::ParallelEdge(a, b))
+            }
+        }
+        Ok(CheckedGraph {
+            graph: graph,
+            policy: policy,
+        })
+    }
+
+    /// Return the policy of the graph.
+    pub fn policy(&self) -> Policy {
+        self.policy
+    }
+
+    /// Return the underlying graph.
+    pub fn into_graph(self) -> Graph<N, E, Ty, Ix> {
+        self.graph
+    }
+
+    /// Add a node with weight `weight`, and return its index.
+    ///
+    /// Return `TooManyNodes` if the index type is full.
+    pub fn try_add_node(&mut self, weight: N) -> Result<NodeIndex<Ix>, GraphError<Ix>> {
+        if self.graph.node_count() >= <Ix as IndexType>::max().index() {
+            return Err(GraphError::TooManyNodes)
+        }
+        Ok(self.graph.add_node(weight))
+    }
+
+    /// Add an edge from `a` to `b` with weight `weight`, and return its
+    /// index.
+    ///
+    /// Return an error if a node is missing, if the edge breaks the policy,
+    /// or if the index type is full.
+    pub fn try_add_edge(&mut self, a: NodeIndex<Ix>, b: NodeIndex<Ix>, weight: E)
+        -> Result<EdgeIndex<Ix>, GraphError<Ix>>
+    {
+        for &x in &[a, b] {
+            if !self.graph.contains_node_index(x) {
+                return Err(GraphError::NodeNotFound(x))
+            }
+        }
+        if !self.policy.self_loops && a == b {
+            return Err(GraphError::SelfLoop(a))
+        }
+        if !self.policy.parallel_edges && self.graph.find_edge(a, b).is_some() {
+            return Err(GraphError::ParallelEdge(a, b))
+        }
+        if self.graph.edge_count() >= <Ix as IndexType>::max().index() {
+            return Err(GraphError::TooManyEdges)
+        }
+        Ok(self.graph.add_edge(a, b, weight))
+    }
+
+    /// Remove the node `a` and its edges, as
+    /// [`Graph::remove_node`](../struct.Graph.html#method.remove_node), and
+    /// return its weight.
+    pub fn try_remove_node(&mut self, a: NodeIndex<Ix>) -> Result<N, GraphError<Ix>> {
+        self.graph.remove_node(a).ok_or(GraphError::NodeNotFound(a))
+    }
+
+    /// Remove the edge `e`, as
+    /// [`Graph::remove_edge`](../struct.Graph.html#method.remove_edge), and
+    /// return its weight.
+    pub fn try_remove_edge(&mut self, e: EdgeIndex<Ix>) -> Result<E, GraphError<Ix>> {
+        self.graph.remove_edge(e).ok_or(GraphError::EdgeNotFound(e))
+    }
+}
+
+impl<N, E, Ty, Ix> fmt::Debug for CheckedGraph<N, E, Ty, Ix>
+    where N: fmt::Debug,
+          E: fmt::Debug,
+          Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.graph.fmt(f)
+    }
+}
+
+impl<N, E, Ty, Ix> Deref for CheckedGraph<N, E, Ty, Ix>
+    where Ix: IndexType,
+{
+    type Target = Graph<N, E, Ty, Ix>;
+    fn deref(&self) -> &Graph<N, E, Ty, Ix> {
+        &self.graph
+    }
+}
+
+impl<N, E, Ty, Ix> Index<NodeIndex<Ix>> for CheckedGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    type Output = N;
+    fn index(&self, a: NodeIndex<Ix>) -> &N {
+        &self.graph[a]
+    }
+}
+
+impl<N, E, Ty, Ix> IndexMut<NodeIndex<Ix>> for CheckedGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn index_mut(&mut self, a: NodeIndex<Ix>) -> &mut N {
+        &mut self.graph[a]
+    }
+}
+
+impl<N, E, Ty, Ix> Index<EdgeIndex<Ix>> for CheckedGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    type Output = E;
+    fn index(&self, e: EdgeIndex<Ix>) -> &E {
+        &self.graph[e]
+    }
+}
+
+impl<N, E, Ty, Ix> IndexMut<EdgeIndex<Ix>> for CheckedGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn index_mut(&mut self, e: EdgeIndex<Ix>) -> &mut E {
+        &mut self.graph[e]
+    }
+}
@@ -0,0 +1,269 @@
+//! `Csr<Ix>` is a compressed sparse row view of the structure of a `Graph`,
+//! with sorted neighbor lists.
+
+use core::cmp::Ordering;
+use core::slice;
+use alloc::vec::Vec;
+
+use {
+    EdgeType,
+    Graph,
+};
+use graph::{
+    DefIndex,
+    EdgeIndex,
+    IndexType,
+    NodeIndex,
+};
+
+/// `Csr<Ix>` is the *compressed sparse row* form of a graph's structure:
+/// the neighbors of all nodes in one array, each node's list sorted by
+/// neighbor index.
+///
+/// Sorted lists make [`contains_edge`](#method.contains_edge) a binary
+/// search, in **O(log d)** time where **d** is the degree, and let
+/// neighbor sets be intersected by merging, see
+/// [`common_neighbors`](#method.common_neighbors).
+///
+/// A `Csr` is created from a `Graph` with
+/// [`from_graph`](#method.from_graph) and keeps its node and edge indices:
+/// look up weights in the graph with them. It does not follow later changes
+/// of the graph.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::csr::Csr;
+/// use petgraph::graph::node_index as n;
+///
+/// let g = Graph::<(), u32, Undirected>::from_edges(&[(0, 3, 7), (0, 1, 8), (1, 3, 9), (2, 3, 10)]);
+/// let csr = Csr::from_graph(&g);
+///
+/// assert_eq!(csr.neighbors(n(3)), &[n(0), n(1), n(2)]);
+/// assert!(csr.contains_edge(n(3), n(1)));
+/// assert_eq!(g[csr.find_edge(n(3), n(2)).unwrap()], 10);
+/// assert_eq!(csr.common_neighbors(n(0), n(1)).collect::<Vec<_>>(), vec![n(3)]);
+/// assert_eq!(csr.triangle_count(), 1);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Csr<Ix: IndexType = DefIndex> {
+    /// The entries of node `a` are `row[a]..row[a + 1]`.
+    row: Vec<usize>,
+    /// The neighbor of each entry.
+    column: Vec<NodeIndex<Ix>>,
+    /// The graph edge of each entry.
+    edges: Vec<EdgeIndex<Ix>>,
+    edge_count: usize,
+    directed: bool,
+}
+
+impl<Ix: IndexType> Csr<Ix> {
+    /// Create the `Csr` of `g`.
+    ///
+    /// For a directed graph, the neighbors of a node are the targets of its
+    /// outgoing edges. For an undirected graph, each edge is in the lists of
+    /// both endpoints (a self loop only once). Parallel edges give repeated
+    /// neighbors, in edge index order.
+    ///
+    /// Computes in **O(|V| + |E| log |E|)** time.
+    pub fn from_graph<N, E, Ty>(g: &Graph<N, E, Ty, Ix>) -> Self
+        where Ty: EdgeType,
+    {
+        let directed = g.is_directed();
+        let mut row = vec![0; g.node_count() + 1];
+        for edge in g.raw_edges() {
+            let (a, b) = (edge.source().index(), edge.target().index());
+            row[a + 1] += 1;
+            if !directed && a != b {
+                row[b + 1] += 1;
+            }
+        }
+        for i in 0..g.node_count() {
+            row[i + 1] += row[i];
+        }
+
+        // fill each row in edge index order, then sort the rows stably
+        let mut next = row.clone();
+        let mut entries = vec![(NodeIndex::end(), EdgeIndex::end()); row[g.node_count()]];
+        for (i, edge) in g.raw_edges().iter().enumerate() {
+            let (a, b) = (edge.source(), edge.target());
+            entries[next[a.index()]] = (b, EdgeIndex::new(i));
+            next[a.index()] += 1;
+            if !directed && a != b {
+                entries[next[b.index()]] = (a, EdgeIndex::new(i));
+                next[b.index()] += 1;
+            }
+        }
+        for a in 0..g.node_count() {
+            entries[row[a]..row[a + 1]].sort_by_key(|&(b, _)| b);
+        }
+
+        Csr {
+            row: row,
+            column: entries.iter().map(|&(b, _)| b).collect(),
+            edges: entries.iter().map(|&(_, e)| e).collect(),
+            edge_count: g.edge_count(),
+            directed: directed,
+        }
+    }
+
+    /// Return the number of nodes.
+    pub fn node_count(&self) -> usize {
+        self.row.len() - 1
+    }
+
+    /// Return the number of edges of the graph.
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    /// Return whether the graph has directed edges.
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    /// Return the number of neighbors of `a`, counting repeats.
+    ///
+    /// **Panics** if `a` is out of bounds.
+    pub fn degree(&self, a: NodeIndex<Ix>) -> usize {
+        self.row[a.index() + 1] - self.row[a.index()]
+    }
+
+    /// Return the neighbors of `a`, sorted by index.
+    ///
+    /// **Panics** if `a` is out of bounds.
+    pub fn neighbors(&self, a: NodeIndex<Ix>) -> &[NodeIndex<Ix>] {
+        &self.column[self.row[a.index()]..self.row[a.index() + 1]]
+    }
+
+    /// Return the edges of `a`, in the order of
+    /// [`neighbors`](#method.neighbors): the `i`th edge leads to the `i`th
+    /// neighbor.
+    ///
+    /// **Panics** if `a` is out of bounds.
+    pub fn neighbor_edges(&self, a: NodeIndex<Ix>) -> &[EdgeIndex<Ix>] {
+        &self.edges[self.row[a.index()]..self.row[a.index() + 1]]
+    }
+
+    /// Return `true` if there is an edge from `a` to `b`.
+    ///
+    /// Computes in **O(log d)** time, where **d** is the degree of `a`.
+    ///
+    /// **Panics** if `a` is out of bounds.
+    pub fn contains_edge(&self, a: NodeIndex<Ix>, b: NodeIndex<Ix>) -> bool {
+        self.neighbors(a).binary_search(&b).is_ok()
+    }
+
+    /// Return the index of the edge from `a` to `b`, the lowest one if
+    /// there are parallel edges.
+    ///
+    /// Computes in **O(log d)** time, where **d** is the degree of `a`.
+    ///
+    /// **Panics** if `a` is out of bounds.
+    pub fn find_edge(&self, a: NodeIndex<Ix>, b: NodeIndex<Ix>) -> Option<EdgeIndex<Ix>> {
+        let adj = self.neighbors(a);
+        // the first entry not less than b
+        let (mut low, mut high) = (0, adj.len());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if adj[mid] < b {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        if low < adj.len() && adj[low] == b {
+            Some(self.neighbor_edges(a)[low])
+        } else {
+            None
+        }
+    }
+
+    /// Return an iterator of the nodes that are neighbors of both `a` and
+    /// `b`, each once, in index order.
+    ///
+    /// The lists are merged, in **O(d(a) + d(b))** time.
+    ///
+    /// **Panics** if `a` or `b` is out of bounds.
+    pub fn common_neighbors(&self, a: NodeIndex<Ix>, b: NodeIndex<Ix>) -> CommonNeighbors<Ix> {
+        CommonNeighbors {
+            xs: self.neighbors(a).iter(),
+            ys: self.neighbors(b).iter(),
+        }
+    }
+
+    /// Return the number of triangles.
+    ///
+    /// For an undirected graph, a triangle is a set of three nodes that are
+    /// all adjacent. For a directed graph, it is a triple of nodes with
+    /// edges `a → b`, `a → c` and `b → c`, so a directed three-cycle is not
+    /// counted. Self loops and parallel edges don't make more triangles.
+    ///
+    /// Computes in **O(Σ d(a) + d(b))** time, over the edges `(a, b)`.
+    pub fn triangle_count(&self) -> usize {
+        let mut count = 0;
+        for a in 0..self.node_count() {
+            let a = NodeIndex::new(a);
+            let mut last = None;
+            for &b in self.neighbors(a) {
+                if b == a || last == Some(b) || (!self.directed && b < a) {
+                    continue;
+                }
+                last = Some(b);
+                // undirected: count each triangle a < b < c once
+                count += self.common_neighbors(a, b)
+                             .filter(|&c| c != a && c != b && (self.directed || c > b))
+                             .count();
+            }
+        }
+        count
+    }
+}
+
+/// An iterator of the common neighbors of two nodes, see
+/// [`Csr::common_neighbors`](struct.Csr.html#method.common_neighbors).
+#[derive(Clone)]
+pub struct CommonNeighbors<'a, Ix: 'a> {
+    xs: slice::Iter<'a, NodeIndex<Ix>>,
+    ys: slice::Iter<'a, NodeIndex<Ix>>,
+}
+
+impl<'a, Ix: IndexType> Iterator for CommonNeighbors<'a, Ix> {
+    type Item = NodeIndex<Ix>;
+
+    fn next(&mut self) -> Option<NodeIndex<Ix>> {
+        let mut x = match self.xs.next() {
+            None => return None,
+            Some(&x) => x,
+        };
+        let mut y = match self.ys.next() {
+            None => return None,
+            Some(&y) => y,
+        };
+        loop {
+            match x.cmp(&y) {
+                Ordering::Less => {
+                    x = match self.xs.next() {
+                        None => return None,
+                        Some(&x) => x,
+                    };
+                }
+                Ordering::Greater => {
+                    y = match self.ys.next() {
+                        None => return None,
+                        Some(&y) => y,
+                    };
+                }
+                Ordering::Equal => {
+                    // skip the repeats of parallel edges
+                    while self.xs.as_slice().first() == Some(&x) {
+                        self.xs.next();
+                    }
+                    while self.ys.as_slice().first() == Some(&y) {
+                        self.ys.next();
+                    }
+                    return Some(x)
+                }
+            }
+        }
+    }
+}
@@ -1,12 +1,14 @@
 use std::collections::{
     HashMap,
     BinaryHeap,
+    VecDeque,
 };
 use std::collections::hash_map::Entry::{
     Occupied,
     Vacant,
 };
 
+use std::cmp::Reverse;
 use std::default::Default;
 use std::hash::Hash;
 use std::ops::{
@@ -14,11 +16,65 @@ use std::ops::{
 };
 
 use scored::MinScored;
+use super::{
+    EdgeType,
+    Graph,
+};
+use super::graph::{
+    EdgeIndex,
+    IndexType,
+    NodeIndex,
+};
+use super::path::{
+    Path,
+    Predecessors,
+};
 use super::visit::{
+    NeighborIter,
     Visitable,
     VisitMap,
 };
 
+/// A map from nodes to path costs, that
+/// [`dijkstra_into`](fn.dijkstra_into.html) writes its output into.
+///
+/// It is implemented by `HashMap`, for any graph, and by `Vec<Option<K>>`
+/// for graphs with `NodeIndex` nodes, which is indexed by node index and
+/// grows as needed.
+pub trait ScoreMap<N, K> {
+    /// Return the cost recorded for `n`, if any.
+    fn score(&self, n: &N) -> Option<K>;
+    /// Record the cost `score` for `n`, replacing any previous cost.
+    fn set_score(&mut self, n: N, score: K);
+}
+
+impl<N, K> ScoreMap<N, K> for HashMap<N, K>
+    where N: Eq + Hash,
+          K: Copy,
+{
+    fn score(&self, n: &N) -> Option<K> {
+        self.get(n).cloned()
+    }
+    fn set_score(&mut self, n: N, score: K) {
+        self.insert(n, score);
+    }
+}
+
+impl<Ix, K> ScoreMap<NodeIndex<Ix>, K> for Vec<Option<K>>
+    where Ix: IndexType,
+          K: Copy,
+{
+    fn score(&self, n: &NodeIndex<Ix>) -> Option<K> {
+        self.get(n.index()).and_then(|&k| k)
+    }
+    fn set_score(&mut self, n: NodeIndex<Ix>, score: K) {
+        if self.len() <= n.index() {
+            self.resize(n.index() + 1, None);
+        }
+        self[n.index()] = Some(score);
+    }
+}
+
 /// Dijkstra's shortest path algorithm.
 ///
 /// Compute the length of the shortest path from `start` to every reachable
@@ -31,11 +87,217 @@ use super::visit::{
 /// If `goal` is not `None`, then the algorithm terminates once the `goal` node's
 /// cost is calculated.
 ///
-/// Returns a `HashMap` that maps `NodeId` to path cost.
+/// Returns a `HashMap` that maps `NodeId` to path cost. See
+/// [`dijkstra_into`](fn.dijkstra_into.html) to write the costs into another
+/// map.
 pub fn dijkstra<'a, G: Visitable, K, F, Edges>(graph: &'a G,
                                                start: G::NodeId,
                                                goal: Option<G::NodeId>,
-                                               mut edges: F) -> HashMap<G::NodeId, K> where
+                                               edges: F) -> HashMap<G::NodeId, K> where
+    G::NodeId: Eq + Hash,
+    K: Default + Add<Output=K> + Copy + PartialOrd,
+    F: FnMut(&'a G, G::NodeId) -> Edges,
+    Edges: Iterator<Item=(G::NodeId, K)>,
+{
+    let mut scores = HashMap::new();
+    dijkstra_into(graph, start, goal, edges, &mut scores);
+    scores
+}
+
+/// Dijkstra's shortest path algorithm, writing the path costs into `scores`.
+///
+/// This is [`dijkstra`](fn.dijkstra.html) with a caller-provided
+/// [`ScoreMap`](trait.ScoreMap.html), which should be empty. For a graph
+/// with compact node indices, like `Graph`, a `Vec<Option<K>>` is much
+/// faster than a `HashMap`; after the search, it holds the cost of node `i`
+/// at index `i`, if the node was reached.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::algo::dijkstra_into;
+///
+/// let g = Graph::<(), u32>::from_edges(&[(0, 1, 2), (1, 2, 2), (0, 2, 5), (3, 0, 1)]);
+/// let a = g.node_indices().next().unwrap();
+///
+/// let mut scores = vec![None; g.node_count()];
+/// dijkstra_into(&g, a, None, |gr, n| gr.edges(n).map(|(n, &e)| (n, e)), &mut scores);
+/// assert_eq!(scores, [Some(0), Some(2), Some(4), None]);
+/// ```
+pub fn dijkstra_into<'a, G: Visitable, K, F, Edges, M>(graph: &'a G,
+                                                       start: G::NodeId,
+                                                       goal: Option<G::NodeId>,
+                                                       mut edges: F,
+                                                       scores: &mut M) where
+    G::NodeId: Eq,
+    K: Default + Add<Output=K> + Copy + PartialOrd,
+    F: FnMut(&'a G, G::NodeId) -> Edges,
+    Edges: Iterator<Item=(G::NodeId, K)>,
+    M: ScoreMap<G::NodeId, K>,
+{
+    let mut visited = graph.visit_map();
+    let mut visit_next = BinaryHeap::new();
+    let zero_score: K = Default::default();
+    scores.set_score(start.clone(), zero_score);
+    visit_next.push(MinScored(zero_score, start));
+    while let Some(MinScored(node_score, node)) = visit_next.pop() {
+        if visited.is_visited(&node) {
+            continue
+        }
+        if goal.as_ref() == Some(&node) {
+            break
+        }
+        for (next, edge) in edges(graph, node.clone()) {
+            if visited.is_visited(&next) {
+                continue
+            }
+            let mut next_score = node_score + edge;
+            match scores.score(&next) {
+                Some(old_score) if !(next_score < old_score) => next_score = old_score,
+                _ => scores.set_score(next.clone(), next_score),
+            }
+            visit_next.push(MinScored(next_score, next));
+        }
+        visited.visit(node);
+    }
+}
+
+/// Dijkstra's shortest path algorithm, stopping at a goal node.
+///
+/// Compute the length of the shortest path from `start` to `goal`, and the
+/// path itself. The search stops as soon as `goal` is settled, so only the
+/// nodes closer to `start` than `goal` are visited.
+///
+/// The graph should be `Visitable`, and `edges` a closure that maps
+/// a node identifier to an iterator of `(n, k)` pairs where `n` is an adjacent
+/// node and `k` the edge weight.
+///
+/// Returns the path cost together with the nodes of the path, from `start` to
+/// `goal` inclusive, or `None` if `goal` is not reachable.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::algo::dijkstra_path;
+///
+/// let mut g = Graph::new();
+/// let a = g.add_node("a");
+/// let b = g.add_node("b");
+/// let c = g.add_node("c");
+/// g.add_edge(a, b, 1);
+/// g.add_edge(b, c, 2);
+/// g.add_edge(a, c, 4);
+///
+/// let path = dijkstra_path(&g, a, c, |gr, n| gr.edges(n).map(|(n, &e)| (n, e)));
+/// assert_eq!(path, Some((3, vec![a, b, c])));
+/// assert_eq!(dijkstra_path(&g, c, a, |gr, n| gr.edges(n).map(|(n, &e)| (n, e))), None);
+/// ```
+pub fn dijkstra_path<'a, G: Visitable, K, F, Edges>(graph: &'a G,
+                                                    start: G::NodeId,
+                                                    goal: G::NodeId,
+                                                    edges: F) -> Option<(K, Vec<G::NodeId>)> where
+    G::NodeId: Eq + Hash,
+    K: Default + Add<Output=K> + Copy + PartialOrd,
+    F: FnMut(&'a G, G::NodeId) -> Edges,
+    Edges: Iterator<Item=(G::NodeId, K)>,
+{
+    // the search ends when goal is settled, or unreachable
+    let (scores, predecessors) = dijkstra_predecessors(graph, start, Some(goal.clone()), edges);
+    predecessors.path_to(&goal).map(|path| (scores[&goal], path))
+}
+
+/// Find a shortest path in `g` from `start` to `goal` with Dijkstra's
+/// algorithm, as a [`Path`](../path/struct.Path.html) of both its nodes and
+/// its edges.
+///
+/// `edge_cost` maps an edge weight to its cost, which must not be negative.
+/// Of parallel edges, the path takes one of the least cost.
+///
+/// Returns the path cost together with the path, or `None` if `goal` is not
+/// reachable.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::algo::shortest_path;
+///
+/// let mut g = Graph::new();
+/// let a = g.add_node("a");
+/// let b = g.add_node("b");
+/// let c = g.add_node("c");
+/// let ab = g.add_edge(a, b, 1);
+/// let bc = g.add_edge(b, c, 2);
+/// g.add_edge(a, c, 4);
+///
+/// let (cost, path) = shortest_path(&g, a, c, |&w| w).unwrap();
+/// assert_eq!(cost, 3);
+/// assert_eq!(path.nodes(), &[a, b, c]);
+/// assert_eq!(path.edges(), &[ab, bc]);
+/// assert_eq!(path.total_weight(|e| g[e]), cost);
+/// ```
+pub fn shortest_path<N, E, Ty, Ix, F, K>(g: &Graph<N, E, Ty, Ix>,
+                                         start: NodeIndex<Ix>,
+                                         goal: NodeIndex<Ix>,
+                                         mut edge_cost: F)
+    -> Option<(K, Path<Graph<N, E, Ty, Ix>>)>
+    where Ty: EdgeType,
+          Ix: IndexType + Hash,
+          F: FnMut(&E) -> K,
+          K: Default + Add<Output=K> + Copy + PartialOrd,
+{
+    let found = dijkstra_path(g, start, goal, |gr, a| {
+        gr.edges(a).map(|(b, w)| (b, edge_cost(w))).collect::<Vec<_>>().into_iter()
+    });
+    let (cost, nodes) = match found {
+        None => return None,
+        Some(found) => found,
+    };
+    let mut path = Path::new(start);
+    for step in nodes.windows(2) {
+        // the search took the cheapest of the edges from step[0] to step[1]
+        let mut best: Option<(K, EdgeIndex<Ix>)> = None;
+        let mut walk = g.neighbors(step[0]).detach();
+        while let Some((e, next)) = walk.next(g) {
+            if next == step[1] {
+                let cost = edge_cost(&g[e]);
+                if best.map_or(true, |(best_cost, _)| cost < best_cost) {
+                    best = Some((cost, e));
+                }
+            }
+        }
+        path.push(best.unwrap().1, step[1]);
+    }
+    Some((cost, path))
+}
+
+/// Dijkstra's shortest path algorithm, recording the shortest path tree.
+///
+/// Compute the length of the shortest path from `start` to every reachable
+/// node, and the predecessor of each node on its shortest path, from which
+/// the paths are rebuilt with
+/// [`Predecessors::path_to`](../path/struct.Predecessors.html#method.path_to).
+///
+/// The graph should be `Visitable`, and `edges` a closure that maps
+/// a node identifier to an iterator of `(n, k)` pairs where `n` is an adjacent
+/// node and `k` the edge weight.
+///
+/// If `goal` is not `None`, then the algorithm terminates once the `goal` node's
+/// cost is calculated.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::algo::dijkstra_predecessors;
+///
+/// let g = Graph::<(), u32>::from_edges(&[(0, 1, 1), (1, 2, 2), (0, 2, 4), (2, 3, 1)]);
+/// let nodes = g.node_indices().collect::<Vec<_>>();
+///
+/// let (scores, pred) = dijkstra_predecessors(&g, nodes[0], None,
+///                                            |gr, n| gr.edges(n).map(|(n, &e)| (n, e)));
+/// assert_eq!(scores[&nodes[3]], 4);
+/// assert_eq!(pred.path_to(&nodes[3]), Some(vec![nodes[0], nodes[1], nodes[2], nodes[3]]));
+/// ```
+pub fn dijkstra_predecessors<'a, G: Visitable, K, F, Edges>(graph: &'a G,
+                                                            start: G::NodeId,
+                                                            goal: Option<G::NodeId>,
+                                                            mut edges: F)
+    -> (HashMap<G::NodeId, K>, Predecessors<G::NodeId>) where
     G::NodeId: Eq + Hash,
     K: Default + Add<Output=K> + Copy + PartialOrd,
     F: FnMut(&'a G, G::NodeId) -> Edges,
@@ -43,10 +305,11 @@ pub fn dijkstra<'a, G: Visitable, K, F, Edges>(graph: &'a G,
 {
     let mut visited = graph.visit_map();
     let mut scores = HashMap::new();
-    //let mut predecessor = HashMap::new();
+    let mut predecessors = Predecessors::new();
     let mut visit_next = BinaryHeap::new();
     let zero_score: K = Default::default();
     scores.insert(start.clone(), zero_score);
+    predecessors.add_source(start.clone());
     visit_next.push(MinScored(zero_score, start));
     while let Some(MinScored(node_score, node)) = visit_next.pop() {
         if visited.is_visited(&node) {
@@ -59,17 +322,123 @@ pub fn dijkstra<'a, G: Visitable, K, F, Edges>(graph: &'a G,
             if visited.is_visited(&next) {
                 continue
             }
-            let mut next_score = node_score + edge;
+            let next_score = node_score + edge;
+            match scores.entry(next.clone()) {
+                Occupied(ent) => if next_score < *ent.get() {
+                    *ent.into_mut() = next_score;
+                } else {
+                    continue
+                },
+                Vacant(ent) => {
+                    ent.insert(next_score);
+                }
+            }
+            predecessors.set_predecessor(next.clone(), node.clone());
+            visit_next.push(MinScored(next_score, next));
+        }
+        visited.visit(node);
+    }
+    (scores, predecessors)
+}
+
+/// Breadth-first search from `start`, recording the search tree.
+///
+/// The predecessor of each reachable node is the node it was first reached
+/// from, so [`Predecessors::path_to`](../path/struct.Predecessors.html#method.path_to)
+/// rebuilds a path with the fewest edges.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::algo::bfs_predecessors;
+/// use petgraph::graph::node_index as n;
+///
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (0, 2), (4, 0)]);
+/// let pred = bfs_predecessors(&g, n(0));
+/// assert_eq!(pred.path_to(&n(3)), Some(vec![n(0), n(2), n(3)]));
+/// assert_eq!(pred.path_to(&n(4)), None);
+/// ```
+pub fn bfs_predecessors<'a, G>(graph: &'a G, start: G::NodeId) -> Predecessors<G::NodeId>
+    where G: Visitable + NeighborIter<'a>,
+          G::NodeId: Eq + Hash,
+{
+    let mut discovered = graph.visit_map();
+    let mut predecessors = Predecessors::new();
+    let mut queue = VecDeque::new();
+    discovered.visit(start.clone());
+    predecessors.add_source(start.clone());
+    queue.push_back(start);
+    while let Some(node) = queue.pop_front() {
+        for next in graph.neighbors(node.clone()) {
+            if discovered.visit(next.clone()) {
+                predecessors.set_predecessor(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+    predecessors
+}
+
+/// Dijkstra's shortest path algorithm, bounded by a maximum path cost.
+///
+/// Compute the length of the shortest path from `start` to every node whose
+/// path cost is at most `max_cost`. Nodes further away are never expanded,
+/// so this finds all nodes within a radius of `start` without visiting the
+/// rest of the graph.
+///
+/// The graph should be `Visitable`, and `edges` a closure that maps
+/// a node identifier to an iterator of `(n, k)` pairs where `n` is an adjacent
+/// node and `k` the edge weight.
+///
+/// Returns a `HashMap` that maps `NodeId` to path cost, for the nodes within
+/// `max_cost` only.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::algo::dijkstra_bounded;
+///
+/// let g = Graph::<(), u32>::from_edges(&[(0, 1, 2), (1, 2, 2), (2, 3, 2)]);
+/// let a = g.node_indices().next().unwrap();
+/// let scores = dijkstra_bounded(&g, a, 4, |gr, n| gr.edges(n).map(|(n, &e)| (n, e)));
+/// assert_eq!(scores.len(), 3);
+/// ```
+pub fn dijkstra_bounded<'a, G: Visitable, K, F, Edges>(graph: &'a G,
+                                                       start: G::NodeId,
+                                                       max_cost: K,
+                                                       mut edges: F) -> HashMap<G::NodeId, K> where
+    G::NodeId: Eq + Hash,
+    K: Default + Add<Output=K> + Copy + PartialOrd,
+    F: FnMut(&'a G, G::NodeId) -> Edges,
+    Edges: Iterator<Item=(G::NodeId, K)>,
+{
+    let mut visited = graph.visit_map();
+    let mut scores = HashMap::new();
+    let mut visit_next = BinaryHeap::new();
+    let zero_score: K = Default::default();
+    if zero_score > max_cost {
+        return scores
+    }
+    scores.insert(start.clone(), zero_score);
+    visit_next.push(MinScored(zero_score, start));
+    while let Some(MinScored(node_score, node)) = visit_next.pop() {
+        if visited.is_visited(&node) {
+            continue
+        }
+        for (next, edge) in edges(graph, node.clone()) {
+            if visited.is_visited(&next) {
+                continue
+            }
+            let next_score = node_score + edge;
+            if next_score > max_cost {
+                continue
+            }
             match scores.entry(next.clone()) {
                 Occupied(ent) => if next_score < *ent.get() {
                     *ent.into_mut() = next_score;
-                    //predecessor.insert(next.clone(), node.clone());
                 } else {
-                    next_score = *ent.get();
+                    continue
                 },
                 Vacant(ent) => {
                     ent.insert(next_score);
-                    //predecessor.insert(next.clone(), node.clone());
                 }
             }
             visit_next.push(MinScored(next_score, next));
@@ -78,3 +447,191 @@ pub fn dijkstra<'a, G: Visitable, K, F, Edges>(graph: &'a G,
     }
     scores
 }
+
+/// Dijkstra's shortest path algorithm from several sources at once.
+///
+/// Compute, for every node reachable from any of the `sources`, the nearest
+/// source and the length of the path from it. `sources` are `(s, k)` pairs of
+/// a source node `s` and an initial cost `k` to start from; use the default
+/// (zero) cost to treat all sources alike. This partitions the graph into the
+/// Voronoi cells of the sources.
+///
+/// The graph should be `Visitable`, and `edges` a closure that maps
+/// a node identifier to an iterator of `(n, k)` pairs where `n` is an adjacent
+/// node and `k` the edge weight.
+///
+/// Returns a `HashMap` that maps `NodeId` to the path cost and the nearest
+/// source. Ties between sources are broken arbitrarily.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::algo::multi_source_dijkstra;
+///
+/// // a path 0 - 1 - 2 - 3 - 4
+/// let g = Graph::<(), u32>::from_edges(&[(0, 1, 1), (1, 2, 1), (2, 3, 1), (3, 4, 1)])
+///              .into_edge_type::<petgraph::Undirected>();
+/// let mut nodes = g.node_indices();
+/// let a = nodes.next().unwrap();
+/// let e = nodes.last().unwrap();
+///
+/// let scores = multi_source_dijkstra(&g, vec![(a, 0), (e, 0)],
+///                                    |gr, n| gr.edges(n).map(|(n, &e)| (n, e)));
+/// let b = g.neighbors(a).next().unwrap();
+/// assert_eq!(scores[&b], (1, a));
+/// ```
+pub fn multi_source_dijkstra<'a, G: Visitable, K, I, F, Edges>(graph: &'a G,
+                                                               sources: I,
+                                                               mut edges: F)
+    -> HashMap<G::NodeId, (K, G::NodeId)> where
+    G::NodeId: Eq + Hash,
+    K: Default + Add<Output=K> + Copy + PartialOrd,
+    I: IntoIterator<Item=(G::NodeId, K)>,
+    F: FnMut(&'a G, G::NodeId) -> Edges,
+    Edges: Iterator<Item=(G::NodeId, K)>,
+{
+    let mut visited = graph.visit_map();
+    let mut scores = HashMap::<G::NodeId, (K, G::NodeId)>::new();
+    let mut visit_next = BinaryHeap::new();
+    for (source, score) in sources {
+        match scores.entry(source.clone()) {
+            Occupied(ent) => if score < ent.get().0 {
+                *ent.into_mut() = (score, source.clone());
+            } else {
+                continue
+            },
+            Vacant(ent) => {
+                ent.insert((score, source.clone()));
+            }
+        }
+        visit_next.push(MinScored(score, source));
+    }
+    while let Some(MinScored(node_score, node)) = visit_next.pop() {
+        if visited.is_visited(&node) {
+            continue
+        }
+        let source = scores[&node].1.clone();
+        for (next, edge) in edges(graph, node.clone()) {
+            if visited.is_visited(&next) {
+                continue
+            }
+            let next_score = node_score + edge;
+            match scores.entry(next.clone()) {
+                Occupied(ent) => if next_score < ent.get().0 {
+                    *ent.into_mut() = (next_score, source.clone());
+                } else {
+                    continue
+                },
+                Vacant(ent) => {
+                    ent.insert((next_score, source.clone()));
+                }
+            }
+            visit_next.push(MinScored(next_score, next));
+        }
+        visited.visit(node);
+    }
+    scores
+}
+
+/// Find a widest path from `start` to `goal`: the path that maximizes the
+/// smallest edge capacity along it, also known as the bottleneck or maximin
+/// path.
+///
+/// This is Dijkstra's algorithm, extending the widest path found so far
+/// instead of the shortest one. The path has at least one edge, so if
+/// `start` is `goal` it is the widest closed walk through `start`; in an
+/// undirected graph that may be a single edge traversed both ways.
+///
+/// The graph should be `Visitable`, and `edges` a closure that maps
+/// a node identifier to an iterator of `(n, k)` pairs where `n` is an adjacent
+/// node and `k` the edge capacity.
+///
+/// Returns the bottleneck capacity together with the nodes of the path, from
+/// `start` to `goal` inclusive, or `None` if `goal` is not reachable.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::algo::widest_path;
+///
+/// let mut g = Graph::new();
+/// let a = g.add_node("a");
+/// let b = g.add_node("b");
+/// let c = g.add_node("c");
+/// g.add_edge(a, c, 2);
+/// g.add_edge(a, b, 5);
+/// g.add_edge(b, c, 4);
+///
+/// let path = widest_path(&g, a, c, |gr, n| gr.edges(n).map(|(n, &e)| (n, e)));
+/// assert_eq!(path, Some((4, vec![a, b, c])));
+/// ```
+pub fn widest_path<'a, G: Visitable, K, F, Edges>(graph: &'a G,
+                                                  start: G::NodeId,
+                                                  goal: G::NodeId,
+                                                  mut edges: F) -> Option<(K, Vec<G::NodeId>)> where
+    G::NodeId: Eq + Hash,
+    K: Copy + PartialOrd,
+    F: FnMut(&'a G, G::NodeId) -> Edges,
+    Edges: Iterator<Item=(G::NodeId, K)>,
+{
+    let mut visited = graph.visit_map();
+    let mut widths = HashMap::new();
+    let mut predecessor = HashMap::new();
+    let mut visit_next = BinaryHeap::new();
+    if start != goal {
+        visited.visit(start.clone());
+    }
+    for (next, capacity) in edges(graph, start.clone()) {
+        if !visited.is_visited(&next) {
+            widen(&mut widths, &mut predecessor, &mut visit_next, next, capacity, &start);
+        }
+    }
+    while let Some(Reverse(MinScored(node_width, node))) = visit_next.pop() {
+        if visited.is_visited(&node) {
+            continue
+        }
+        if node == goal {
+            let mut path = vec![node.clone()];
+            let mut node = predecessor[&node].clone();
+            while node != start {
+                path.push(node.clone());
+                node = predecessor[&node].clone();
+            }
+            path.push(start);
+            path.reverse();
+            return Some((node_width, path))
+        }
+        for (next, capacity) in edges(graph, node.clone()) {
+            if visited.is_visited(&next) {
+                continue
+            }
+            let next_width = if capacity < node_width { capacity } else { node_width };
+            widen(&mut widths, &mut predecessor, &mut visit_next, next, next_width, &node);
+        }
+        visited.visit(node);
+    }
+    None
+}
+
+/// Record a path of width `width` to `node` through `from`, if it is wider
+/// than the widest known path to `node`.
+fn widen<N, K>(widths: &mut HashMap<N, K>,
+               predecessor: &mut HashMap<N, N>,
+               visit_next: &mut BinaryHeap<Reverse<MinScored<K, N>>>,
+               node: N,
+               width: K,
+               from: &N)
+    where N: Eq + Hash + Clone,
+          K: Copy + PartialOrd,
+{
+    match widths.entry(node.clone()) {
+        Occupied(ent) => if width > *ent.get() {
+            *ent.into_mut() = width;
+        } else {
+            return
+        },
+        Vacant(ent) => {
+            ent.insert(width);
+        }
+    }
+    predecessor.insert(node.clone(), from.clone());
+    visit_next.push(Reverse(MinScored(width, node)));
+}
@@ -1,4 +1,7 @@
 //! Simple graphviz dot file format output.
+//!
+//! With `feature = "dot_parser"`, also simple dot file format input, see
+//! [`io::dot`](../io/dot/index.html).
 
 use std::fmt::{self, Display, Write};
 use {
@@ -6,24 +9,66 @@ use {
     EdgeType,
     GraphMap,
 };
-use super::graph::IndexType;
+use super::graph::{EdgeIndex, IndexType};
+use super::visit::GraphEdgeId;
 use graphmap::NodeTrait;
 use std::collections::HashMap;
 
+#[cfg(feature = "dot_parser")]
+pub use io::dot::{parse, ParseError};
+
 /// `Dot` implements output to graphviz .dot format for a graph.
 ///
 /// Formatting and options are rather simple, this is mostly intended
 /// for debugging. Exact output may change.
-pub struct Dot<'a, G: 'a> {
+///
+/// Use [`Dot::with_attr_getters`](#method.with_attr_getters) to emit
+/// custom node and edge attributes, for example:
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::dot::{Dot, Config};
+///
+/// let mut graph = Graph::<&str, u32>::new();
+/// let a = graph.add_node("a");
+/// let b = graph.add_node("b");
+/// graph.add_edge(a, b, 7);
+///
+/// let node_attrs = |g: &Graph<&str, u32>, n| {
+///     vec![("shape".to_string(), "box".to_string()),
+///          ("label".to_string(), format!("node {}", g[n]))]
+/// };
+/// let edge_attrs = |g: &Graph<&str, u32>, e| {
+///     let color = if g[e] > 5 { "red" } else { "black" };
+///     vec![("color".to_string(), color.to_string())]
+/// };
+/// let dot = Dot::with_attr_getters(&graph, &[], &edge_attrs, &node_attrs)
+///               .graph_attributes(&[("rankdir", "LR")]);
+/// assert_eq!(format!("{:?}", dot),
+/// r#"digraph {
+///     rankdir="LR"
+///     0 [shape="box", label="node a"]
+///     1 [shape="box", label="node b"]
+///     0 -> 1 [label="7", color="red"]
+/// }
+/// "#);
+/// ```
+pub struct Dot<'a, G: 'a + GraphEdgeId> {
     graph: &'a G,
     config: &'a [Config],
+    directed: Option<bool>,
+    graph_attributes: &'a [(&'a str, &'a str)],
+    get_edge_attributes: Option<&'a Fn(&G, G::EdgeId) -> Vec<(String, String)>>,
+    get_node_attributes: Option<&'a Fn(&G, G::NodeId) -> Vec<(String, String)>>,
 }
 
 static TYPE: [&'static str; 2] = ["graph", "digraph"];
 static EDGE: [&'static str; 2] = ["--", "->"];
 static INDENT: &'static str = "    ";
 
-impl<'a, G> Dot<'a, G> {
+impl<'a, G> Dot<'a, G>
+    where G: GraphEdgeId,
+{
     /// Create a `Dot` formatting wrapper with default configuration.
     pub fn new(graph: &'a G) -> Self {
         Self::with_config(graph, &[])
@@ -34,6 +79,72 @@ impl<'a, G> Dot<'a, G> {
         Dot {
             graph: graph,
             config: config,
+            directed: None,
+            graph_attributes: &[],
+            get_edge_attributes: None,
+            get_node_attributes: None,
+        }
+    }
+
+    /// Create a `Dot` formatting wrapper with custom configuration and
+    /// functions that produce extra attributes for each edge and node.
+    ///
+    /// The attribute functions return a list of *(name, value)* pairs. The
+    /// values are quoted and escaped in the output. A `"label"` attribute
+    /// replaces the default label that is produced from the weight.
+    pub fn with_attr_getters(graph: &'a G,
+                             config: &'a [Config],
+                             get_edge_attributes: &'a Fn(&G, G::EdgeId) -> Vec<(String, String)>,
+                             get_node_attributes: &'a Fn(&G, G::NodeId) -> Vec<(String, String)>)
+        -> Self
+    {
+        Dot {
+            get_edge_attributes: Some(get_edge_attributes),
+            get_node_attributes: Some(get_node_attributes),
+            .. Self::with_config(graph, config)
+        }
+    }
+
+    /// Set graph-level attributes, which are emitted as *name="value"*
+    /// statements at the start of the graph.
+    pub fn graph_attributes(mut self, attributes: &'a [(&'a str, &'a str)]) -> Self {
+        self.graph_attributes = attributes;
+        self
+    }
+
+    /// Output a `digraph` with `->` edges if `directed` is `true`, or
+    /// a `graph` with `--` edges if it is `false`.
+    ///
+    /// By default this follows the edge type of the graph.
+    pub fn directed(mut self, directed: bool) -> Self {
+        self.directed = Some(directed);
+        self
+    }
+
+    /// Write the graph header and the graph attributes.
+    fn header_fmt(&self, f: &mut fmt::Formatter, directed: bool) -> fmt::Result {
+        try!(writeln!(f, "{} {{", TYPE[directed as usize]));
+        for &(name, value) in self.graph_attributes {
+            try!(write!(f, "{}", INDENT));
+            try!(write_id(f, name));
+            try!(write!(f, "=\""));
+            try!(write!(&mut Escaper(&mut *f), "{}", value));
+            try!(writeln!(f, "\""));
+        }
+        Ok(())
+    }
+
+    fn node_attributes(&self, node: G::NodeId) -> Vec<(String, String)> {
+        match self.get_node_attributes {
+            Some(getter) => getter(self.graph, node),
+            None => Vec::new(),
+        }
+    }
+
+    fn edge_attributes(&self, edge: G::EdgeId) -> Vec<(String, String)> {
+        match self.get_edge_attributes {
+            Some(getter) => getter(self.graph, edge),
+            None => Vec::new(),
         }
     }
 }
@@ -63,17 +174,20 @@ impl<'a, N, E, Ty, Ix> Dot<'a, Graph<N, E, Ty, Ix>>
               G: FnMut(&E, &mut FnMut(&Display) -> fmt::Result) -> fmt::Result,
 {
         let g = self.graph;
-        try!(writeln!(f, "{} {{", TYPE[g.is_directed() as usize]));
+        let directed = self.directed.unwrap_or(g.is_directed());
+        try!(self.header_fmt(f, directed));
 
         // output all labels
         for index in g.node_indices() {
             try!(write!(f, "{}{}", INDENT, index.index()));
-            if self.config.contains(&Config::NodeIndexLabel) {
-                try!(writeln!(f, ""));
+            let attrs = self.node_attributes(index);
+            if self.config.contains(&Config::NodeIndexLabel) || has_label(&attrs) {
+                try!(attrs_fmt(f, false, &attrs));
             } else {
                 try!(write!(f, " [label=\""));
                 try!(node_fmt(&g[index], &mut |d| Escaped(d).fmt(f)));
-                try!(writeln!(f, "\"]"));
+                try!(write!(f, "\""));
+                try!(attrs_fmt(f, true, &attrs));
             }
 
         }
@@ -82,16 +196,19 @@ impl<'a, N, E, Ty, Ix> Dot<'a, Graph<N, E, Ty, Ix>>
             try!(write!(f, "{}{} {} {}",
                         INDENT,
                         edge.source().index(),
-                        EDGE[g.is_directed() as usize],
+                        EDGE[directed as usize],
                         edge.target().index()));
-            if self.config.contains(&Config::EdgeNoLabel) {
-                try!(writeln!(f, ""));
+            let attrs = self.edge_attributes(EdgeIndex::new(i));
+            if self.config.contains(&Config::EdgeNoLabel) || has_label(&attrs) {
+                try!(attrs_fmt(f, false, &attrs));
             } else if self.config.contains(&Config::EdgeIndexLabel) {
-                try!(writeln!(f, " [label=\"{}\"]", i));
+                try!(write!(f, " [label=\"{}\"", i));
+                try!(attrs_fmt(f, true, &attrs));
             } else {
                 try!(write!(f, " [label=\""));
                 try!(edge_fmt(&edge.weight, &mut |d| Escaped(d).fmt(f)));
-                try!(writeln!(f, "\"]"));
+                try!(write!(f, "\""));
+                try!(attrs_fmt(f, true, &attrs));
             }
         }
 
@@ -133,7 +250,8 @@ impl<'a, N, E> Dot<'a, GraphMap<N, E>>
               G: FnMut(&E, &mut FnMut(&Display) -> fmt::Result) -> fmt::Result,
     {
         let g = self.graph;
-        try!(writeln!(f, "{} {{", TYPE[0]));
+        let directed = self.directed.unwrap_or(false);
+        try!(self.header_fmt(f, directed));
 
         let mut labels = HashMap::new();
 
@@ -141,12 +259,14 @@ impl<'a, N, E> Dot<'a, GraphMap<N, E>>
         for (i, node) in g.nodes().enumerate() {
             labels.insert(node, i);
             try!(write!(f, "{}{}", INDENT, i));
-            if self.config.contains(&Config::NodeIndexLabel) {
-                try!(writeln!(f, ""));
+            let attrs = self.node_attributes(node);
+            if self.config.contains(&Config::NodeIndexLabel) || has_label(&attrs) {
+                try!(attrs_fmt(f, false, &attrs));
             } else {
                 try!(write!(f, " [label=\""));
                 try!(node_fmt(&node, &mut |d| Escaped(d).fmt(f)));
-                try!(writeln!(f, "\"]"));
+                try!(write!(f, "\""));
+                try!(attrs_fmt(f, true, &attrs));
             }
         }
         // output all edges
@@ -154,16 +274,19 @@ impl<'a, N, E> Dot<'a, GraphMap<N, E>>
             try!(write!(f, "{}{} {} {}",
                         INDENT,
                         labels[&a],
-                        EDGE[0],
+                        EDGE[directed as usize],
                         labels[&b]));
-            if self.config.contains(&Config::EdgeNoLabel) {
-                try!(writeln!(f, ""));
+            let attrs = self.edge_attributes((a, b));
+            if self.config.contains(&Config::EdgeNoLabel) || has_label(&attrs) {
+                try!(attrs_fmt(f, false, &attrs));
             } else if self.config.contains(&Config::EdgeIndexLabel) {
-                try!(writeln!(f, " [label=\"{}\"]", i));
+                try!(write!(f, " [label=\"{}\"", i));
+                try!(attrs_fmt(f, true, &attrs));
             } else {
                 try!(write!(f, " [label=\""));
                 try!(edge_fmt(&edge_weight, &mut |d| Escaped(d).fmt(f)));
-                try!(writeln!(f, "\"]"));
+                try!(write!(f, "\""));
+                try!(attrs_fmt(f, true, &attrs));
             }
         }
 
@@ -191,6 +314,46 @@ impl<'a, N, E> fmt::Debug for Dot<'a, GraphMap<N, E>>
     }
 }
 
+fn has_label(attrs: &[(String, String)]) -> bool {
+    attrs.iter().any(|&(ref name, _)| name == "label")
+}
+
+/// Write the rest of an attribute list and end the statement.
+///
+/// `open` is true if an attribute list was already started with `[`.
+fn attrs_fmt(f: &mut fmt::Formatter, open: bool, attrs: &[(String, String)]) -> fmt::Result {
+    for (i, &(ref name, ref value)) in attrs.iter().enumerate() {
+        if open || i > 0 {
+            try!(write!(f, ", "));
+        } else {
+            try!(write!(f, " ["));
+        }
+        try!(write_id(f, name));
+        try!(write!(f, "=\""));
+        try!(write!(&mut Escaper(&mut *f), "{}", value));
+        try!(write!(f, "\""));
+    }
+    if open || !attrs.is_empty() {
+        try!(write!(f, "]"));
+    }
+    writeln!(f, "")
+}
+
+/// Write an identifier (attribute name) as is if it is a plain
+/// alphanumeric identifier, otherwise quoted.
+fn write_id(f: &mut fmt::Formatter, id: &str) -> fmt::Result {
+    let plain = !id.is_empty() &&
+        !id.starts_with(|c: char| c.is_digit(10)) &&
+        id.chars().all(|c| c.is_alphanumeric() || c == '_');
+    if plain {
+        write!(f, "{}", id)
+    } else {
+        try!(write!(f, "\""));
+        try!(write!(&mut Escaper(&mut *f), "{}", id));
+        write!(f, "\"")
+    }
+}
+
 /// Escape for Graphviz
 struct Escaper<W>(W);
 
@@ -206,7 +369,7 @@ impl<W> fmt::Write for Escaper<W>
 
     fn write_char(&mut self, c: char) -> fmt::Result {
         match c {
-            '"' => try!(self.0.write_char('\\')),
+            '"' | '\\' => try!(self.0.write_char('\\')),
             // \l is for left justified linebreak
             '\n' => return self.0.write_str(r#"\l"#),
             _   => { }
@@ -223,7 +386,8 @@ impl<T> fmt::Display for Escaped<T>
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         if f.alternate() {
-            write!(&mut Escaper(f), "{:#}\\l", &self.0)
+            try!(write!(&mut Escaper(&mut *f), "{:#}", &self.0));
+            f.write_str("\\l")
         } else {
             write!(&mut Escaper(f), "{}", &self.0)
         }
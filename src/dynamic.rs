@@ -0,0 +1,694 @@
+//! Structures that maintain a graph property while the graph changes.
+
+use std::collections::{
+    HashMap,
+    HashSet,
+    VecDeque,
+};
+use std::error::Error;
+use std::fmt;
+use std::marker::PhantomData;
+
+use super::{
+    Directed,
+    EdgeType,
+    Graph,
+};
+use super::graph::{
+    DefIndex,
+    IndexType,
+    NodeIndex,
+};
+use super::unionfind::UnionFind;
+
+/// `DynamicConnectivity` tracks the connected components of an undirected
+/// graph under edge insertions and removals, so that `connected(a, b)` can be
+/// answered in constant time without recomputing the components.
+///
+/// Each node stores the label of its component. Inserting an edge between two
+/// components relabels the smaller one. Removing an edge searches from both
+/// of its endpoints in lockstep, until one search reaches the other endpoint,
+/// or runs out of nodes and has found the smaller of the two parts split
+/// apart. Either way, the work is proportional to the smaller part.
+///
+/// Parallel edges and self loops are allowed.
+///
+/// ```
+/// use petgraph::dynamic::DynamicConnectivity;
+/// use petgraph::graph::node_index as n;
+///
+/// let mut conn = DynamicConnectivity::<u32>::new(4);
+/// conn.add_edge(n(0), n(1));
+/// conn.add_edge(n(1), n(2));
+/// conn.add_edge(n(2), n(0));
+/// assert!(conn.connected(n(0), n(2)));
+/// assert_eq!(conn.component_count(), 2);
+///
+/// // the cycle keeps 0 and 2 connected
+/// conn.remove_edge(n(0), n(2));
+/// assert!(conn.connected(n(0), n(2)));
+/// conn.remove_edge(n(1), n(2));
+/// assert!(!conn.connected(n(0), n(2)));
+/// assert_eq!(conn.component_count(), 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DynamicConnectivity<Ix = DefIndex>
+{
+    // For each node, the number of edges to each of its neighbors.
+    adjacency: Vec<HashMap<usize, usize>>,
+    // For each node, its component label.
+    component: Vec<usize>,
+    // For each component label, the number of nodes in the component.
+    size: Vec<usize>,
+    // Labels not in use by any component.
+    free_labels: Vec<usize>,
+    component_count: usize,
+    ty: PhantomData<Ix>,
+}
+
+impl<Ix> DynamicConnectivity<Ix>
+    where Ix: IndexType
+{
+    /// Create a new `DynamicConnectivity` of `n` isolated nodes.
+    pub fn new(n: usize) -> Self
+    {
+        DynamicConnectivity {
+            adjacency: vec![HashMap::new(); n],
+            component: (0..n).collect(),
+            size: vec![1; n],
+            free_labels: Vec::new(),
+            component_count: n,
+            ty: PhantomData,
+        }
+    }
+
+    /// Create a new `DynamicConnectivity` with the nodes and edges of `g`.
+    ///
+    /// The graph is treated as undirected.
+    pub fn from_graph<N, E, Ty>(g: &Graph<N, E, Ty, Ix>) -> Self
+        where Ty: EdgeType,
+    {
+        let mut conn = Self::new(g.node_count());
+        for edge in g.raw_edges() {
+            conn.add_edge(edge.source(), edge.target());
+        }
+        conn
+    }
+
+    /// Return the number of nodes.
+    pub fn node_count(&self) -> usize
+    {
+        self.component.len()
+    }
+
+    /// Return the number of connected components.
+    pub fn component_count(&self) -> usize
+    {
+        self.component_count
+    }
+
+    /// Add an isolated node and return its index.
+    pub fn add_node(&mut self) -> NodeIndex<Ix>
+    {
+        let a = self.node_count();
+        let label = self.new_label(1);
+        self.adjacency.push(HashMap::new());
+        self.component.push(label);
+        self.component_count += 1;
+        NodeIndex::new(a)
+    }
+
+    /// Return `true` if there is a path between `a` and `b`.
+    ///
+    /// **Panics** if `a` or `b` is out of bounds.
+    pub fn connected(&self, a: NodeIndex<Ix>, b: NodeIndex<Ix>) -> bool
+    {
+        self.component[a.index()] == self.component[b.index()]
+    }
+
+    /// Add an edge between `a` and `b`.
+    ///
+    /// Return `true` if this connected two components, `false` if `a` and
+    /// `b` were already connected.
+    ///
+    /// **Panics** if `a` or `b` is out of bounds.
+    pub fn add_edge(&mut self, a: NodeIndex<Ix>, b: NodeIndex<Ix>) -> bool
+    {
+        let (a, b) = (a.index(), b.index());
+        assert!(a < self.node_count() && b < self.node_count());
+        let (alabel, blabel) = (self.component[a], self.component[b]);
+        let merge = alabel != blabel;
+        if merge {
+            // relabel the smaller component before the edge joins them
+            let (from, to) = if self.size[alabel] < self.size[blabel] {
+                (a, blabel)
+            } else {
+                (b, alabel)
+            };
+            let old = self.component[from];
+            let nodes = self.search(from);
+            for &x in &nodes {
+                self.component[x] = to;
+            }
+            self.size[to] += nodes.len();
+            self.size[old] = 0;
+            self.free_labels.push(old);
+            self.component_count -= 1;
+        }
+        *self.adjacency[a].entry(b).or_insert(0) += 1;
+        if a != b {
+            *self.adjacency[b].entry(a).or_insert(0) += 1;
+        }
+        merge
+    }
+
+    /// Remove one edge between `a` and `b`.
+    ///
+    /// Return `false` if there was no such edge.
+    ///
+    /// **Panics** if `a` or `b` is out of bounds.
+    pub fn remove_edge(&mut self, a: NodeIndex<Ix>, b: NodeIndex<Ix>) -> bool
+    {
+        let (a, b) = (a.index(), b.index());
+        if !remove_one(&mut self.adjacency[a], b) {
+            return false
+        }
+        if a == b {
+            return true
+        }
+        remove_one(&mut self.adjacency[b], a);
+        if self.adjacency[a].contains_key(&b) {
+            // a parallel edge remains
+            return true
+        }
+
+        // Search from both endpoints, one node at a time each, until either
+        // finds the other endpoint or runs out of nodes.
+        let mut searches = [Search::new(a), Search::new(b)];
+        let split = 'search: loop {
+            for (s, target) in (0..2).zip(&[b, a]) {
+                match searches[s].step(&self.adjacency) {
+                    Some(found) => if found.iter().any(|x| x == target) {
+                        break 'search None
+                    },
+                    None => break 'search Some(s),
+                }
+            }
+        };
+        if let Some(s) = split {
+            let nodes = &searches[s].visited;
+            let old = self.component[a];
+            let label = self.new_label(nodes.len());
+            for &x in nodes {
+                self.component[x] = label;
+            }
+            self.size[old] -= nodes.len();
+            self.component_count += 1;
+        }
+        true
+    }
+
+    fn new_label(&mut self, size: usize) -> usize
+    {
+        match self.free_labels.pop() {
+            Some(label) => {
+                self.size[label] = size;
+                label
+            }
+            None => {
+                self.size.push(size);
+                self.size.len() - 1
+            }
+        }
+    }
+
+    /// Return all nodes reachable from `start`.
+    fn search(&self, start: usize) -> Vec<usize>
+    {
+        let mut search = Search::new(start);
+        while search.step(&self.adjacency).is_some() { }
+        search.visited.into_iter().collect()
+    }
+}
+
+/// An edge insertion was rejected because the edge would create a cycle.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CycleError<Ix = DefIndex> {
+    source: NodeIndex<Ix>,
+    target: NodeIndex<Ix>,
+}
+
+impl<Ix: IndexType> CycleError<Ix> {
+    /// Return the endpoints of the rejected edge.
+    pub fn edge(&self) -> (NodeIndex<Ix>, NodeIndex<Ix>) {
+        (self.source, self.target)
+    }
+}
+
+impl<Ix: IndexType> fmt::Display for CycleError<Ix> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "edge from {} to {} would create a cycle",
+               self.source.index(), self.target.index())
+    }
+}
+
+impl<Ix: IndexType> Error for CycleError<Ix> {
+    fn description(&self) -> &str {
+        "edge would create a cycle"
+    }
+}
+
+/// `IncrementalTopo` maintains a topological order of a directed acyclic
+/// graph under edge insertions, rejecting the edges that would create a cycle.
+///
+/// It uses the Pearce-Kelly algorithm: an inserted edge that goes backwards
+/// in the current order only reorders the nodes whose positions lie between
+/// its endpoints and that are reachable from its target or reach its source.
+/// Edges that already agree with the order cost nothing extra.
+///
+/// Removing edges always keeps the order valid.
+///
+/// ```
+/// use petgraph::dynamic::IncrementalTopo;
+/// use petgraph::graph::node_index as n;
+///
+/// let mut topo = IncrementalTopo::<u32>::new(3);
+/// topo.add_edge(n(2), n(1)).unwrap();
+/// topo.add_edge(n(1), n(0)).unwrap();
+/// assert_eq!(topo.order(), [n(2), n(1), n(0)]);
+///
+/// let err = topo.add_edge(n(0), n(2)).unwrap_err();
+/// assert_eq!(err.edge(), (n(0), n(2)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct IncrementalTopo<Ix = DefIndex>
+{
+    // For each node, the number of edges to each successor and predecessor.
+    successors: Vec<HashMap<usize, usize>>,
+    predecessors: Vec<HashMap<usize, usize>>,
+    // For each node, its position in the order.
+    position: Vec<usize>,
+    // For each position, the node at that position.
+    node_at: Vec<usize>,
+    ty: PhantomData<Ix>,
+}
+
+impl<Ix> IncrementalTopo<Ix>
+    where Ix: IndexType
+{
+    /// Create a new `IncrementalTopo` of `n` nodes without edges, ordered by
+    /// node index.
+    pub fn new(n: usize) -> Self
+    {
+        IncrementalTopo {
+            successors: vec![HashMap::new(); n],
+            predecessors: vec![HashMap::new(); n],
+            position: (0..n).collect(),
+            node_at: (0..n).collect(),
+            ty: PhantomData,
+        }
+    }
+
+    /// Create a new `IncrementalTopo` with the nodes and edges of the
+    /// directed graph `g`.
+    ///
+    /// Return an error for the first edge that closes a cycle.
+    pub fn from_graph<N, E>(g: &Graph<N, E, Directed, Ix>) -> Result<Self, CycleError<Ix>>
+    {
+        let mut topo = Self::new(g.node_count());
+        for edge in g.raw_edges() {
+            try!(topo.add_edge(edge.source(), edge.target()));
+        }
+        Ok(topo)
+    }
+
+    /// Return the number of nodes.
+    pub fn node_count(&self) -> usize
+    {
+        self.position.len()
+    }
+
+    /// Add a node, placed last in the order, and return its index.
+    pub fn add_node(&mut self) -> NodeIndex<Ix>
+    {
+        let a = self.node_count();
+        self.successors.push(HashMap::new());
+        self.predecessors.push(HashMap::new());
+        self.position.push(a);
+        self.node_at.push(a);
+        NodeIndex::new(a)
+    }
+
+    /// Return the position of `a` in the topological order.
+    ///
+    /// **Panics** if `a` is out of bounds.
+    pub fn position(&self, a: NodeIndex<Ix>) -> usize
+    {
+        self.position[a.index()]
+    }
+
+    /// Return the nodes in topological order.
+    pub fn order(&self) -> Vec<NodeIndex<Ix>>
+    {
+        self.node_at.iter().map(|&a| NodeIndex::new(a)).collect()
+    }
+
+    /// Add an edge from `a` to `b`, updating the order.
+    ///
+    /// If the edge would create a cycle, it is not added and an error is
+    /// returned instead.
+    ///
+    /// **Panics** if `a` or `b` is out of bounds.
+    pub fn add_edge(&mut self, a: NodeIndex<Ix>, b: NodeIndex<Ix>) -> Result<(), CycleError<Ix>>
+    {
+        let (x, y) = (a.index(), b.index());
+        assert!(x < self.node_count() && y < self.node_count());
+        let (lower, upper) = (self.position[y], self.position[x]);
+        if lower <= upper {
+            // The edge goes backwards: find the nodes in the affected range
+            // reachable from y, and the nodes in it that reach x.
+            let forward = match self.search(&self.successors, y, |p| p <= upper, Some(x)) {
+                Some(nodes) => nodes,
+                None => return Err(CycleError { source: a, target: b }),
+            };
+            let backward = self.search(&self.predecessors, x, |p| p >= lower, None)
+                               .unwrap_or_default();
+            self.reorder(backward, forward);
+        }
+        *self.successors[x].entry(y).or_insert(0) += 1;
+        *self.predecessors[y].entry(x).or_insert(0) += 1;
+        Ok(())
+    }
+
+    /// Remove one edge from `a` to `b`.
+    ///
+    /// Return `false` if there was no such edge.
+    ///
+    /// **Panics** if `a` or `b` is out of bounds.
+    pub fn remove_edge(&mut self, a: NodeIndex<Ix>, b: NodeIndex<Ix>) -> bool
+    {
+        let (x, y) = (a.index(), b.index());
+        if !remove_one(&mut self.successors[x], y) {
+            return false
+        }
+        remove_one(&mut self.predecessors[y], x);
+        true
+    }
+
+    /// Return the nodes reachable from `start` through `edges` whose
+    /// position is accepted by `in_range`, or `None` if `goal` is reachable.
+    fn search<F>(&self,
+                 edges: &[HashMap<usize, usize>],
+                 start: usize,
+                 in_range: F,
+                 goal: Option<usize>) -> Option<Vec<usize>>
+        where F: Fn(usize) -> bool,
+    {
+        let mut visited = HashSet::new();
+        let mut stack = vec![start];
+        visited.insert(start);
+        while let Some(a) = stack.pop() {
+            if Some(a) == goal {
+                return None
+            }
+            for &b in edges[a].keys() {
+                if in_range(self.position[b]) && visited.insert(b) {
+                    stack.push(b);
+                }
+            }
+        }
+        Some(visited.into_iter().collect())
+    }
+
+    /// Move the `backward` nodes before the `forward` nodes, reusing the
+    /// positions they occupy together.
+    fn reorder(&mut self, mut backward: Vec<usize>, mut forward: Vec<usize>)
+    {
+        backward.sort_by_key(|&a| self.position[a]);
+        forward.sort_by_key(|&a| self.position[a]);
+        let mut positions = backward.iter().chain(&forward)
+                                    .map(|&a| self.position[a])
+                                    .collect::<Vec<_>>();
+        positions.sort();
+        for (a, p) in backward.into_iter().chain(forward).zip(positions) {
+            self.position[a] = p;
+            self.node_at[p] = a;
+        }
+    }
+}
+
+/// `IncrementalScc` maintains the strongly connected components of a
+/// directed graph under edge insertions, together with a topological order
+/// of the components.
+///
+/// The order is maintained like in [`IncrementalTopo`](struct.IncrementalTopo.html),
+/// but an edge that closes a cycle merges all the components on the cycles
+/// it creates into one, instead of being rejected.
+///
+/// ```
+/// use petgraph::dynamic::IncrementalScc;
+/// use petgraph::graph::node_index as n;
+///
+/// let mut scc = IncrementalScc::<u32>::new(4);
+/// assert!(!scc.add_edge(n(0), n(1)));
+/// assert!(!scc.add_edge(n(1), n(2)));
+/// assert!(!scc.add_edge(n(2), n(3)));
+/// assert_eq!(scc.component_count(), 4);
+///
+/// // closing the cycle 1 -> 2 -> 1 merges two components
+/// assert!(scc.add_edge(n(2), n(1)));
+/// assert!(scc.same_component(n(1), n(2)));
+/// assert_eq!(scc.component_count(), 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct IncrementalScc<Ix = DefIndex>
+{
+    successors: Vec<Vec<usize>>,
+    predecessors: Vec<Vec<usize>>,
+    sets: UnionFind<usize>,
+    // For each component representative, the nodes in the component.
+    members: Vec<Vec<usize>>,
+    // For each component representative, its position in the order.
+    position: Vec<usize>,
+    // For each position, the component at that position, if any.
+    component_at: Vec<Option<usize>>,
+    component_count: usize,
+    ty: PhantomData<Ix>,
+}
+
+impl<Ix> IncrementalScc<Ix>
+    where Ix: IndexType
+{
+    /// Create a new `IncrementalScc` of `n` nodes without edges.
+    pub fn new(n: usize) -> Self
+    {
+        IncrementalScc {
+            successors: vec![Vec::new(); n],
+            predecessors: vec![Vec::new(); n],
+            sets: UnionFind::new(n),
+            members: (0..n).map(|a| vec![a]).collect(),
+            position: (0..n).collect(),
+            component_at: (0..n).map(Some).collect(),
+            component_count: n,
+            ty: PhantomData,
+        }
+    }
+
+    /// Create a new `IncrementalScc` with the nodes and edges of the directed
+    /// graph `g`.
+    pub fn from_graph<N, E>(g: &Graph<N, E, Directed, Ix>) -> Self
+    {
+        let mut scc = Self::new(g.node_count());
+        for edge in g.raw_edges() {
+            scc.add_edge(edge.source(), edge.target());
+        }
+        scc
+    }
+
+    /// Return the number of nodes.
+    pub fn node_count(&self) -> usize
+    {
+        self.successors.len()
+    }
+
+    /// Return the number of strongly connected components.
+    pub fn component_count(&self) -> usize
+    {
+        self.component_count
+    }
+
+    /// Return the representative node of the component of `a`.
+    ///
+    /// **Panics** if `a` is out of bounds.
+    pub fn component(&self, a: NodeIndex<Ix>) -> NodeIndex<Ix>
+    {
+        NodeIndex::new(self.sets.find(a.index()))
+    }
+
+    /// Return `true` if `a` and `b` are in the same strongly connected
+    /// component.
+    ///
+    /// **Panics** if `a` or `b` is out of bounds.
+    pub fn same_component(&self, a: NodeIndex<Ix>, b: NodeIndex<Ix>) -> bool
+    {
+        self.component(a) == self.component(b)
+    }
+
+    /// Return the strongly connected components, each as a vector of its
+    /// nodes, in topological order.
+    pub fn components(&self) -> Vec<Vec<NodeIndex<Ix>>>
+    {
+        self.component_at.iter().filter_map(|&c| c).map(|c| {
+            self.members[c].iter().map(|&a| NodeIndex::new(a)).collect()
+        }).collect()
+    }
+
+    /// Add an edge from `a` to `b`.
+    ///
+    /// Return `true` if the edge closed a cycle and merged components.
+    ///
+    /// **Panics** if `a` or `b` is out of bounds.
+    pub fn add_edge(&mut self, a: NodeIndex<Ix>, b: NodeIndex<Ix>) -> bool
+    {
+        let (a, b) = (a.index(), b.index());
+        assert!(a < self.node_count() && b < self.node_count());
+        self.successors[a].push(b);
+        self.predecessors[b].push(a);
+        let (x, y) = (self.sets.find(a), self.sets.find(b));
+        let (lower, upper) = (self.position[y], self.position[x]);
+        if x == y || lower > upper {
+            return false
+        }
+
+        // The edge goes backwards: find the components in the affected range
+        // reachable from y, and the components in it that reach x.
+        let forward = self.search(&self.successors, y, |p| p <= upper);
+        let backward = self.search(&self.predecessors, x, |p| p >= lower);
+        let mut positions = forward.iter().chain(&backward)
+                                   .map(|&c| self.position[c])
+                                   .collect::<Vec<_>>();
+        positions.sort();
+        positions.dedup();
+
+        // Components both reachable from y and reaching x are on a cycle
+        // through the new edge.
+        let cycle = forward.intersection(&backward).cloned().collect::<Vec<_>>();
+        let mut forward = forward.into_iter().filter(|c| !backward.contains(c))
+                                 .collect::<Vec<_>>();
+        let mut backward = backward.into_iter().filter(|c| !cycle.contains(c))
+                                   .collect::<Vec<_>>();
+        forward.sort_by_key(|&c| self.position[c]);
+        backward.sort_by_key(|&c| self.position[c]);
+
+        // The backward components take the first positions, followed by the
+        // merged component, and the forward components take the last
+        // positions. That way no component moves past a neighbor outside the
+        // affected range; the positions left over are unused from now on.
+        let merged = self.merge(&cycle);
+        for &p in &positions {
+            self.component_at[p] = None;
+        }
+        let forward_positions = &positions[positions.len() - forward.len()..];
+        let order = backward.into_iter().chain(merged).zip(&positions)
+                            .chain(forward.into_iter().zip(forward_positions));
+        for (c, &p) in order {
+            self.position[c] = p;
+            self.component_at[p] = Some(c);
+        }
+        !cycle.is_empty()
+    }
+
+    /// Merge the components `cycle` into one and return its representative,
+    /// or `None` if `cycle` is empty.
+    fn merge(&mut self, cycle: &[usize]) -> Option<usize>
+    {
+        let first = match cycle.first() {
+            None => return None,
+            Some(&c) => c,
+        };
+        for &c in &cycle[1..] {
+            self.sets.union(first, c);
+        }
+        let rep = self.sets.find_mut(first);
+        let mut members = Vec::new();
+        for &c in cycle {
+            members.append(&mut self.members[c]);
+        }
+        self.members[rep] = members;
+        self.component_count -= cycle.len() - 1;
+        Some(rep)
+    }
+
+    /// Return the components reachable from the component `start` through
+    /// `edges` whose position is accepted by `in_range`.
+    fn search<F>(&self, edges: &[Vec<usize>], start: usize, in_range: F) -> HashSet<usize>
+        where F: Fn(usize) -> bool,
+    {
+        let mut visited = HashSet::new();
+        let mut stack = vec![start];
+        visited.insert(start);
+        while let Some(c) = stack.pop() {
+            for &a in &self.members[c] {
+                for &b in &edges[a] {
+                    let d = self.sets.find(b);
+                    if in_range(self.position[d]) && visited.insert(d) {
+                        stack.push(d);
+                    }
+                }
+            }
+        }
+        visited
+    }
+}
+
+/// Remove one edge to `b` from an adjacency map, return `false` if there was none.
+fn remove_one(adjacency: &mut HashMap<usize, usize>, b: usize) -> bool
+{
+    let count = match adjacency.get_mut(&b) {
+        None => return false,
+        Some(count) => {
+            *count -= 1;
+            *count
+        }
+    };
+    if count == 0 {
+        adjacency.remove(&b);
+    }
+    true
+}
+
+/// A breadth first search that can be advanced one node at a time.
+struct Search {
+    queue: VecDeque<usize>,
+    visited: HashSet<usize>,
+}
+
+impl Search {
+    fn new(start: usize) -> Self
+    {
+        let mut visited = HashSet::new();
+        visited.insert(start);
+        Search {
+            queue: Some(start).into_iter().collect(),
+            visited: visited,
+        }
+    }
+
+    /// Expand the next node and return its newly discovered neighbors, or
+    /// `None` if the search is complete.
+    fn step(&mut self, adjacency: &[HashMap<usize, usize>]) -> Option<Vec<usize>>
+    {
+        let queue = &mut self.queue;
+        let visited = &mut self.visited;
+        queue.pop_front().map(|a| {
+            let mut found = Vec::new();
+            for &b in adjacency[a].keys() {
+                if visited.insert(b) {
+                    queue.push_back(b);
+                    found.push(b);
+                }
+            }
+            found
+        })
+    }
+}
@@ -3,8 +3,15 @@
 //! ***Unstable: API may change at any time.*** Depends on `feature = "generate"`.
 //!
 
-use {Graph, Directed, EdgeType};
-use graph::NodeIndex;
+use std::cmp;
+use std::collections::{HashMap, HashSet};
+use std::mem;
+
+use rand::Rng;
+
+use {Graph, Directed, EdgeType, Undirected};
+use graph::{EdgeIndex, IndexType, NodeIndex};
+use unionfind::UnionFind;
 
 // A DAG has the property that the adjacency matrix is lower triangular,
 // diagonal zero.
@@ -127,3 +134,661 @@ impl<Ty: EdgeType> Iterator for Generator<Ty> {
         self.next_ref().cloned()
     }
 }
+
+/// Create a graph with `n` nodes and no edges.
+fn empty_graph<Ty: EdgeType>(n: usize, edges: usize) -> Graph<(), (), Ty> {
+    let mut g = Graph::with_capacity(n, edges);
+    for _ in 0..n {
+        g.add_node(());
+    }
+    g
+}
+
+/// Create the complete graph with `n` nodes: there is an edge between each
+/// pair of distinct nodes.
+///
+/// ***Unstable: API may change at any time.*** Depends on `feature = "generate"`.
+///
+/// An undirected graph has an edge *i → j* for each *i < j*, and a directed
+/// graph has an edge *i → j* for each *i ≠ j*, in order of *i* and then *j*.
+///
+/// ```
+/// use petgraph::Undirected;
+/// use petgraph::generate::complete_graph;
+///
+/// let g = complete_graph::<Undirected>(5);
+/// assert_eq!(g.edge_count(), 10);
+/// ```
+pub fn complete_graph<Ty: EdgeType>(n: usize) -> Graph<(), (), Ty> {
+    let scale = if Ty::is_directed() { 1 } else { 2 };
+    let mut g = empty_graph(n, n * n.saturating_sub(1) / scale);
+    for i in 0..n {
+        let start = if Ty::is_directed() { 0 } else { i + 1 };
+        for j in start..n {
+            if i != j {
+                g.add_edge(NodeIndex::new(i), NodeIndex::new(j), ());
+            }
+        }
+    }
+    g
+}
+
+/// Create the cycle graph with `n` nodes, with the edges *i → i + 1* and
+/// *n - 1 → 0*, in order.
+///
+/// ***Unstable: API may change at any time.*** Depends on `feature = "generate"`.
+///
+/// With one node the cycle is a self loop, and with two nodes it is two
+/// edges between them.
+///
+/// ```
+/// use petgraph::Directed;
+/// use petgraph::generate::cycle_graph;
+///
+/// let g = cycle_graph::<Directed>(4);
+/// assert_eq!(g.edge_count(), 4);
+/// assert!(petgraph::algo::is_cyclic_directed(&g));
+/// ```
+pub fn cycle_graph<Ty: EdgeType>(n: usize) -> Graph<(), (), Ty> {
+    let mut g = empty_graph(n, n);
+    for i in 0..n {
+        g.add_edge(NodeIndex::new(i), NodeIndex::new((i + 1) % n), ());
+    }
+    g
+}
+
+/// Create the path graph with `n` nodes, with the edges *i → i + 1*, in order.
+///
+/// ***Unstable: API may change at any time.*** Depends on `feature = "generate"`.
+///
+/// ```
+/// use petgraph::Directed;
+/// use petgraph::generate::path_graph;
+///
+/// let g = path_graph::<Directed>(4);
+/// assert_eq!(g.edge_count(), 3);
+/// ```
+pub fn path_graph<Ty: EdgeType>(n: usize) -> Graph<(), (), Ty> {
+    let mut g = empty_graph(n, n.saturating_sub(1));
+    for i in 1..n {
+        g.add_edge(NodeIndex::new(i - 1), NodeIndex::new(i), ());
+    }
+    g
+}
+
+/// Create the star graph with `n` nodes: node 0 is the center, with the
+/// edges *0 → i* to each other node, in order.
+///
+/// ***Unstable: API may change at any time.*** Depends on `feature = "generate"`.
+///
+/// ```
+/// use petgraph::Undirected;
+/// use petgraph::generate::star_graph;
+///
+/// let g = star_graph::<Undirected>(5);
+/// assert_eq!(g.neighbors(0.into()).count(), 4);
+/// ```
+pub fn star_graph<Ty: EdgeType>(n: usize) -> Graph<(), (), Ty> {
+    let mut g = empty_graph(n, n.saturating_sub(1));
+    for i in 1..n {
+        g.add_edge(NodeIndex::new(0), NodeIndex::new(i), ());
+    }
+    g
+}
+
+/// Create a two-dimensional grid graph with `rows` rows and `cols` columns.
+///
+/// ***Unstable: API may change at any time.*** Depends on `feature = "generate"`.
+///
+/// The node in row `r` and column `c` has index `r * cols + c`. Going
+/// through the nodes in order, each node has an edge to its right
+/// neighbor, then an edge to the neighbor below it, if they exist.
+///
+/// ```
+/// use petgraph::Undirected;
+/// use petgraph::generate::grid_2d;
+///
+/// let g = grid_2d::<Undirected>(3, 4);
+/// assert_eq!(g.node_count(), 12);
+/// assert_eq!(g.edge_count(), 3 * 3 + 2 * 4);
+/// ```
+pub fn grid_2d<Ty: EdgeType>(rows: usize, cols: usize) -> Graph<(), (), Ty> {
+    let edges = rows * cols.saturating_sub(1) + rows.saturating_sub(1) * cols;
+    let mut g = empty_graph(rows * cols, edges);
+    for r in 0..rows {
+        for c in 0..cols {
+            let i = r * cols + c;
+            if c + 1 < cols {
+                g.add_edge(NodeIndex::new(i), NodeIndex::new(i + 1), ());
+            }
+            if r + 1 < rows {
+                g.add_edge(NodeIndex::new(i), NodeIndex::new(i + cols), ());
+            }
+        }
+    }
+    g
+}
+
+/// Generate a random graph in the Erdős–Rényi *G(n, p)* model: a graph with
+/// `n` nodes, where each possible edge is added with probability `p`,
+/// independently of the others.
+///
+/// ***Unstable: API may change at any time.*** Depends on `feature = "generate"`.
+///
+/// The graph is directed or undirected after `Ty`, and has no self loops
+/// or parallel edges.
+///
+/// **Panics** if `p` is not in the range `0..1` (inclusive).
+///
+/// ```
+/// extern crate rand;
+/// extern crate petgraph;
+///
+/// use petgraph::Undirected;
+/// use petgraph::generate::gnp_random_graph;
+///
+/// # fn main() {
+/// let g = gnp_random_graph::<Undirected, _>(10, 1., &mut rand::thread_rng());
+/// assert_eq!(g.edge_count(), 45);
+/// # }
+/// ```
+pub fn gnp_random_graph<Ty, R>(n: usize, p: f64, rng: &mut R) -> Graph<(), (), Ty>
+    where Ty: EdgeType,
+          R: Rng,
+{
+    assert!(p >= 0. && p <= 1., "gnp_random_graph: p must be a probability");
+    let mut g = empty_graph(n, 0);
+    for i in 0..n {
+        let start = if Ty::is_directed() { 0 } else { i + 1 };
+        for j in start..n {
+            if i != j && rng.gen::<f64>() < p {
+                g.add_edge(NodeIndex::new(i), NodeIndex::new(j), ());
+            }
+        }
+    }
+    g
+}
+
+/// Generate a random graph in the Erdős–Rényi *G(n, m)* model: a graph
+/// chosen uniformly at random among the graphs with `n` nodes and `m` edges.
+///
+/// ***Unstable: API may change at any time.*** Depends on `feature = "generate"`.
+///
+/// The graph is directed or undirected after `Ty`, and has no self loops
+/// or parallel edges.
+///
+/// **Panics** if `m` is larger than the number of possible edges.
+///
+/// ```
+/// extern crate rand;
+/// extern crate petgraph;
+///
+/// use petgraph::Directed;
+/// use petgraph::generate::gnm_random_graph;
+///
+/// # fn main() {
+/// let g = gnm_random_graph::<Directed, _>(10, 20, &mut rand::thread_rng());
+/// assert_eq!(g.node_count(), 10);
+/// assert_eq!(g.edge_count(), 20);
+/// # }
+/// ```
+pub fn gnm_random_graph<Ty, R>(n: usize, m: usize, rng: &mut R) -> Graph<(), (), Ty>
+    where Ty: EdgeType,
+          R: Rng,
+{
+    let scale = if Ty::is_directed() { 1 } else { 2 };
+    let max_edges = n * n.saturating_sub(1) / scale;
+    assert!(m <= max_edges, "gnm_random_graph: too many edges for {} nodes", n);
+    let mut g = empty_graph(n, m);
+    if m * 2 <= max_edges {
+        // sparse: pick random pairs until there are enough distinct ones
+        let mut chosen = HashSet::with_capacity(m);
+        while chosen.len() < m {
+            let i = rng.gen_range(0, n);
+            let j = rng.gen_range(0, n);
+            if i == j {
+                continue;
+            }
+            let pair = if Ty::is_directed() { (i, j) } else { (cmp::min(i, j), cmp::max(i, j)) };
+            if chosen.insert(pair) {
+                g.add_edge(NodeIndex::new(pair.0), NodeIndex::new(pair.1), ());
+            }
+        }
+    } else {
+        // dense: shuffle the first m of all possible pairs into place
+        let mut pairs = Vec::with_capacity(max_edges);
+        for i in 0..n {
+            let start = if Ty::is_directed() { 0 } else { i + 1 };
+            for j in start..n {
+                if i != j {
+                    pairs.push((i, j));
+                }
+            }
+        }
+        for k in 0..m {
+            let r = rng.gen_range(k, pairs.len());
+            pairs.swap(k, r);
+            g.add_edge(NodeIndex::new(pairs[k].0), NodeIndex::new(pairs[k].1), ());
+        }
+    }
+    g
+}
+
+/// Generate a random scale-free graph with the Barabási–Albert preferential
+/// attachment model.
+///
+/// ***Unstable: API may change at any time.*** Depends on `feature = "generate"`.
+///
+/// The graph starts with `m` nodes and no edges. Each of the other
+/// `n - m` nodes is added with `m` edges to distinct existing nodes, which
+/// are chosen with probability proportional to their degree (the first
+/// added node connects to all the initial nodes). The result has
+/// `(n - m) * m` edges.
+///
+/// **Panics** if `m` is zero or not less than `n`.
+///
+/// ```
+/// extern crate rand;
+/// extern crate petgraph;
+///
+/// use petgraph::generate::barabasi_albert;
+///
+/// # fn main() {
+/// let g = barabasi_albert(100, 3, &mut rand::thread_rng());
+/// assert_eq!(g.node_count(), 100);
+/// assert_eq!(g.edge_count(), 97 * 3);
+/// # }
+/// ```
+pub fn barabasi_albert<R>(n: usize, m: usize, rng: &mut R) -> Graph<(), (), Undirected>
+    where R: Rng,
+{
+    assert!(m >= 1 && m < n, "barabasi_albert: m must be in the range 1..n");
+    let mut g = Graph::with_capacity(n, (n - m) * m);
+    for _ in 0..m {
+        g.add_node(());
+    }
+    // every node appears here once for each of its edges, so picking a
+    // uniform element picks a node proportionally to its degree
+    let mut repeated_nodes = Vec::with_capacity(2 * (n - m) * m);
+    let mut targets = (0..m).collect::<Vec<_>>();
+    for source in m..n {
+        let a = g.add_node(());
+        for &target in &targets {
+            g.add_edge(a, NodeIndex::new(target), ());
+            repeated_nodes.push(target);
+            repeated_nodes.push(source);
+        }
+        let mut chosen = HashSet::with_capacity(m);
+        while chosen.len() < m {
+            chosen.insert(repeated_nodes[rng.gen_range(0, repeated_nodes.len())]);
+        }
+        targets.clear();
+        targets.extend(chosen);
+        // keep the result deterministic for a given rng
+        targets.sort();
+    }
+    g
+}
+
+/// Generate a random small-world graph with the Watts–Strogatz model.
+///
+/// ***Unstable: API may change at any time.*** Depends on `feature = "generate"`.
+///
+/// The graph starts as a ring lattice of `n` nodes, where each node is
+/// connected to its `k / 2` nearest neighbors on each side (`k` is rounded
+/// down to an even number). Then the far end of each lattice edge is
+/// rewired with probability `beta` to a node chosen uniformly at random,
+/// avoiding self loops and parallel edges. The result has `n * (k / 2)`
+/// edges.
+///
+/// **Panics** if `k` is not less than `n`, or if `beta` is not in the range
+/// `0..1` (inclusive).
+///
+/// ```
+/// extern crate rand;
+/// extern crate petgraph;
+///
+/// use petgraph::generate::watts_strogatz;
+///
+/// # fn main() {
+/// let g = watts_strogatz(20, 4, 0.1, &mut rand::thread_rng());
+/// assert_eq!(g.node_count(), 20);
+/// assert_eq!(g.edge_count(), 40);
+/// # }
+/// ```
+pub fn watts_strogatz<R>(n: usize, k: usize, beta: f64, rng: &mut R)
+    -> Graph<(), (), Undirected>
+    where R: Rng,
+{
+    assert!(k < n, "watts_strogatz: k must be less than n");
+    assert!(beta >= 0. && beta <= 1., "watts_strogatz: beta must be a probability");
+    let mut adjacent = vec![HashSet::new(); n];
+    let mut edges = Vec::with_capacity(n * (k / 2));
+    for j in 1..k / 2 + 1 {
+        for u in 0..n {
+            let v = (u + j) % n;
+            adjacent[u].insert(v);
+            adjacent[v].insert(u);
+            edges.push((u, v));
+        }
+    }
+    for edge in &mut edges {
+        let (u, v) = *edge;
+        // a node that is adjacent to all others can't be rewired
+        if adjacent[u].len() + 1 >= n || rng.gen::<f64>() >= beta {
+            continue;
+        }
+        let mut w = rng.gen_range(0, n);
+        while w == u || adjacent[u].contains(&w) {
+            w = rng.gen_range(0, n);
+        }
+        adjacent[u].remove(&v);
+        adjacent[v].remove(&u);
+        adjacent[u].insert(w);
+        adjacent[w].insert(u);
+        *edge = (u, w);
+    }
+
+    let mut g = empty_graph(n, edges.len());
+    for (u, v) in edges {
+        g.add_edge(NodeIndex::new(u), NodeIndex::new(v), ());
+    }
+    g
+}
+
+/// Generate a random directed acyclic graph with `n` nodes, where each
+/// possible edge *i → j* with *i < j* is added with probability
+/// `edge_prob`, independently of the others.
+///
+/// ***Unstable: API may change at any time.*** Depends on `feature = "generate"`.
+///
+/// The node indices are a topological order of the result.
+///
+/// **Panics** if `edge_prob` is not in the range `0..1` (inclusive).
+///
+/// ```
+/// extern crate rand;
+/// extern crate petgraph;
+///
+/// use petgraph::algo::is_cyclic_directed;
+/// use petgraph::generate::random_dag;
+///
+/// # fn main() {
+/// let g = random_dag(20, 0.3, &mut rand::thread_rng());
+/// assert!(!is_cyclic_directed(&g));
+/// # }
+/// ```
+pub fn random_dag<R>(n: usize, edge_prob: f64, rng: &mut R) -> Graph<(), (), Directed>
+    where R: Rng,
+{
+    assert!(edge_prob >= 0. && edge_prob <= 1., "random_dag: edge_prob must be a probability");
+    let mut g = empty_graph(n, 0);
+    for i in 0..n {
+        for j in i + 1..n {
+            if rng.gen::<f64>() < edge_prob {
+                g.add_edge(NodeIndex::new(i), NodeIndex::new(j), ());
+            }
+        }
+    }
+    g
+}
+
+/// Generate a tree with `n` nodes, chosen uniformly at random among the
+/// labeled trees, by decoding a random Prüfer sequence.
+///
+/// ***Unstable: API may change at any time.*** Depends on `feature = "generate"`.
+///
+/// ```
+/// extern crate rand;
+/// extern crate petgraph;
+///
+/// use petgraph::algo::{connected_components, is_cyclic_undirected};
+/// use petgraph::generate::random_tree;
+///
+/// # fn main() {
+/// let g = random_tree(20, &mut rand::thread_rng());
+/// assert_eq!(g.edge_count(), 19);
+/// assert_eq!(connected_components(&g), 1);
+/// assert!(!is_cyclic_undirected(&g));
+/// # }
+/// ```
+pub fn random_tree<R>(n: usize, rng: &mut R) -> Graph<(), (), Undirected>
+    where R: Rng,
+{
+    let mut g = empty_graph(n, n.saturating_sub(1));
+    if n < 2 {
+        return g;
+    }
+    let sequence = (0..n - 2).map(|_| rng.gen_range(0, n)).collect::<Vec<_>>();
+
+    // Decode the sequence in linear time: `leaf` is the smallest leaf, and
+    // `next` the smallest leaf candidate that has not been passed yet.
+    let mut degree = vec![1; n];
+    for &x in &sequence {
+        degree[x] += 1;
+    }
+    let mut next = degree.iter().position(|&d| d == 1).unwrap();
+    let mut leaf = next;
+    for &x in &sequence {
+        g.add_edge(NodeIndex::new(leaf), NodeIndex::new(x), ());
+        degree[x] -= 1;
+        if x < next && degree[x] == 1 {
+            leaf = x;
+        } else {
+            next += 1;
+            while degree[next] != 1 {
+                next += 1;
+            }
+            leaf = next;
+        }
+    }
+    g.add_edge(NodeIndex::new(leaf), NodeIndex::new(n - 1), ());
+    g
+}
+
+/// Build a simple undirected graph whose node *i* has degree `seq[i]`, with
+/// the Havel–Hakimi algorithm.
+///
+/// ***Unstable: API may change at any time.*** Depends on `feature = "generate"`.
+///
+/// Repeatedly, the node with the largest remaining degree is connected to
+/// the nodes with the next largest remaining degrees. Return `None` if
+/// `seq` is not graphical, see [`is_graphical`](../algo/fn.is_graphical.html).
+///
+/// ```
+/// use petgraph::algo::degree_sequence;
+/// use petgraph::generate::from_degree_sequence;
+///
+/// let g = from_degree_sequence(&[3, 3, 2, 2, 2]).unwrap();
+/// assert_eq!(degree_sequence(&g), vec![3, 3, 2, 2, 2]);
+/// assert!(from_degree_sequence(&[3, 3, 1, 1]).is_none());
+/// ```
+pub fn from_degree_sequence(seq: &[usize]) -> Option<Graph<(), (), Undirected>> {
+    let mut g = empty_graph(seq.len(), seq.iter().sum::<usize>() / 2);
+    let mut remaining = seq.iter().cloned().enumerate().map(|(i, d)| (d, i)).collect::<Vec<_>>();
+    loop {
+        remaining.retain(|&(d, _)| d > 0);
+        if remaining.is_empty() {
+            return Some(g);
+        }
+        remaining.sort_by(|a, b| b.cmp(a));
+        let (d, a) = remaining[0];
+        if d >= remaining.len() {
+            return None;
+        }
+        remaining[0].0 = 0;
+        for &mut (ref mut d, b) in &mut remaining[1..d + 1] {
+            *d -= 1;
+            g.add_edge(NodeIndex::new(a), NodeIndex::new(b), ());
+        }
+    }
+}
+
+/// Randomize the graph with `n_swaps` attempted *double-edge swaps*, which
+/// keep the degree of every node.
+///
+/// ***Unstable: API may change at any time.*** Depends on `feature = "generate"`.
+///
+/// A swap picks two edges *a → b* and *c → d* at random and replaces them
+/// with *a → d* and *c → b*, each keeping the weight of the edge it
+/// replaces. In a directed graph this keeps every in and out degree; in an
+/// undirected graph the second edge is also taken as *d → c* half of the
+/// time. A swap is skipped if it would create a self loop or an edge
+/// between two nodes that are already adjacent. Rewiring a graph with many
+/// swaps gives a random null model with the same degree sequence.
+///
+/// Edge indices are not kept: a swap removes the two edges and adds the new
+/// ones at the end. Return the number of swaps done.
+///
+/// ```
+/// extern crate rand;
+/// extern crate petgraph;
+///
+/// use petgraph::algo::degree_sequence;
+/// use petgraph::generate::{barabasi_albert, rewire};
+///
+/// # fn main() {
+/// let mut rng = rand::thread_rng();
+/// let mut g = barabasi_albert(50, 2, &mut rng);
+/// let degrees = degree_sequence(&g);
+/// rewire(&mut g, 500, &mut rng);
+/// assert_eq!(degree_sequence(&g), degrees);
+/// # }
+/// ```
+pub fn rewire<N, E, Ty, Ix, R>(g: &mut Graph<N, E, Ty, Ix>, n_swaps: usize, rng: &mut R) -> usize
+    where Ty: EdgeType,
+          Ix: IndexType,
+          R: Rng,
+{
+    if g.edge_count() < 2 {
+        return 0;
+    }
+    let pair = |a: NodeIndex<Ix>, b: NodeIndex<Ix>| {
+        let (a, b) = (a.index(), b.index());
+        if Ty::is_directed() || a <= b { (a, b) } else { (b, a) }
+    };
+    // the number of edges between each pair of nodes
+    let mut edges = HashMap::new();
+    for edge in g.raw_edges() {
+        *edges.entry(pair(edge.source(), edge.target())).or_insert(0usize) += 1;
+    }
+    let mut done = 0;
+    for _ in 0..n_swaps {
+        let e = EdgeIndex::new(rng.gen_range(0, g.edge_count()));
+        let f = EdgeIndex::new(rng.gen_range(0, g.edge_count()));
+        if e == f {
+            continue;
+        }
+        let (a, b) = g.edge_endpoints(e).unwrap();
+        let (mut c, mut d) = g.edge_endpoints(f).unwrap();
+        if !Ty::is_directed() && rng.gen() {
+            mem::swap(&mut c, &mut d);
+        }
+        if a == d || c == b || edges.contains_key(&pair(a, d)) || edges.contains_key(&pair(c, b)) {
+            continue;
+        }
+        for old in &[pair(a, b), pair(c, d)] {
+            let count = edges.get_mut(old).unwrap();
+            *count -= 1;
+            if *count == 0 {
+                edges.remove(old);
+            }
+        }
+        edges.insert(pair(a, d), 1);
+        edges.insert(pair(c, b), 1);
+        // remove the later edge first, so the earlier one keeps its index
+        let (first, second) = if e < f { (e, f) } else { (f, e) };
+        let second_weight = g.remove_edge(second).unwrap();
+        let first_weight = g.remove_edge(first).unwrap();
+        let (e_weight, f_weight) = if e < f {
+            (first_weight, second_weight)
+        } else {
+            (second_weight, first_weight)
+        };
+        g.add_edge(a, d, e_weight);
+        g.add_edge(c, b, f_weight);
+        done += 1;
+    }
+    done
+}
+
+/// Sample a spanning tree of `g` uniformly at random, with Wilson's
+/// algorithm, and return its edges in index order.
+///
+/// ***Unstable: API may change at any time.*** Depends on `feature = "generate"`.
+///
+/// Edge directions are ignored, and parallel edges are distinct, so a pair
+/// of nodes joined by two edges is twice as likely to be joined in the
+/// tree. If `g` is not connected, this is a uniformly random spanning
+/// forest, with a tree for each connected component.
+///
+/// Each node not yet in the tree starts a random walk, which stops when it
+/// hits the tree; the walk with its loops erased joins the tree. Computes in
+/// expected time of the order of the mean hitting time of the graph,
+/// **O(|V|³)** at worst.
+///
+/// ```
+/// extern crate rand;
+/// extern crate petgraph;
+///
+/// use petgraph::algo::is_cyclic_undirected;
+/// use petgraph::generate::{grid_2d, random_spanning_tree};
+/// use petgraph::Undirected;
+///
+/// # fn main() {
+/// let g = grid_2d::<Undirected>(5, 5);
+/// let tree = random_spanning_tree(&g, &mut rand::thread_rng());
+/// assert_eq!(tree.len(), 24);
+///
+/// let t = g.filter_map(|_, &w| Some(w), |e, &w| if tree.contains(&e) { Some(w) } else { None });
+/// assert!(!is_cyclic_undirected(&t));
+/// # }
+/// ```
+pub fn random_spanning_tree<N, E, Ty, Ix, R>(g: &Graph<N, E, Ty, Ix>, rng: &mut R)
+    -> Vec<EdgeIndex<Ix>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+          R: Rng,
+{
+    let n = g.node_count();
+    let mut adjacency = vec![Vec::new(); n];
+    let mut components = UnionFind::new(n);
+    for (i, edge) in g.raw_edges().iter().enumerate() {
+        let (a, b) = (edge.source().index(), edge.target().index());
+        if a != b {
+            adjacency[a].push((EdgeIndex::new(i), b));
+            adjacency[b].push((EdgeIndex::new(i), a));
+            components.union(a, b);
+        }
+    }
+
+    // root each component at one of its nodes, chosen at random
+    let mut members = HashMap::new();
+    for a in 0..n {
+        members.entry(components.find(a)).or_insert_with(Vec::new).push(a);
+    }
+    let mut in_tree = vec![false; n];
+    for nodes in members.values() {
+        in_tree[nodes[rng.gen_range(0, nodes.len())]] = true;
+    }
+
+    // the last step of the walk out of each node
+    let mut next = vec![(EdgeIndex::end(), 0); n];
+    let mut tree = Vec::with_capacity(n.saturating_sub(members.len()));
+    for start in 0..n {
+        let mut a = start;
+        while !in_tree[a] {
+            next[a] = adjacency[a][rng.gen_range(0, adjacency[a].len())];
+            a = next[a].1;
+        }
+        // follow the loop erased walk into the tree
+        let mut a = start;
+        while !in_tree[a] {
+            in_tree[a] = true;
+            tree.push(next[a].0);
+            a = next[a].1;
+        }
+    }
+    tree.sort();
+    tree
+}
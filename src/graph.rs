@@ -1,11 +1,14 @@
 //! `Graph<N, E, Ty, Ix>` is a graph datastructure using an adjacency list representation.
 
-use std::cmp;
-use std::fmt;
-use std::iter;
-use std::marker::PhantomData;
-use std::ops::{Index, IndexMut, Range};
-use std::slice;
+use core::cmp;
+use core::fmt;
+use core::iter;
+use core::marker::PhantomData;
+use core::mem;
+use core::ops::{Index, IndexMut, Range};
+use core::slice;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
 
 use {
     EdgeDirection, Outgoing, Incoming,
@@ -24,6 +27,13 @@ pub type DefIndex = u32;
 ///
 /// Marked `unsafe` because: the trait must faithfully preseve
 /// and convert index values.
+///
+/// It is implemented for `u8`, `u16`, `u32`, `u64` and `usize`. The maximum
+/// value of the type is reserved as the “end” sentinel (see
+/// `NodeIndex::end`), so every node and edge index of a graph is below it.
+/// With `u64` or `usize` indices, a graph can have more than 2<sup>32</sup>
+/// nodes and edges; for those types the sentinel is out of reach of any
+/// index that can exist in memory.
 pub unsafe trait IndexType : Copy + Ord + fmt::Debug + 'static
 {
     fn new(x: usize) -> Self;
@@ -37,7 +47,16 @@ unsafe impl IndexType for usize {
     #[inline(always)]
     fn index(&self) -> Self { *self }
     #[inline(always)]
-    fn max() -> Self { ::std::usize::MAX }
+    fn max() -> Self { ::core::usize::MAX }
+}
+
+unsafe impl IndexType for u64 {
+    #[inline(always)]
+    fn new(x: usize) -> Self { x as u64 }
+    #[inline(always)]
+    fn index(&self) -> usize { *self as usize }
+    #[inline(always)]
+    fn max() -> Self { ::core::u64::MAX }
 }
 
 unsafe impl IndexType for u32 {
@@ -46,7 +65,7 @@ unsafe impl IndexType for u32 {
     #[inline(always)]
     fn index(&self) -> usize { *self as usize }
     #[inline(always)]
-    fn max() -> Self { ::std::u32::MAX }
+    fn max() -> Self { ::core::u32::MAX }
 }
 
 unsafe impl IndexType for u16 {
@@ -55,7 +74,7 @@ unsafe impl IndexType for u16 {
     #[inline(always)]
     fn index(&self) -> usize { *self as usize }
     #[inline(always)]
-    fn max() -> Self { ::std::u16::MAX }
+    fn max() -> Self { ::core::u16::MAX }
 }
 
 unsafe impl IndexType for u8 {
@@ -64,7 +83,7 @@ unsafe impl IndexType for u8 {
     #[inline(always)]
     fn index(&self) -> usize { *self as usize }
     #[inline(always)]
-    fn max() -> Self { ::std::u8::MAX }
+    fn max() -> Self { ::core::u8::MAX }
 }
 
 /// Node identifier.
@@ -418,11 +437,28 @@ impl<N, E, Ty, Ix> Graph<N, E, Ty, Ix>
         let node = Node{weight: weight, next: [EdgeIndex::end(), EdgeIndex::end()]};
         let node_idx = NodeIndex::new(self.nodes.len());
         // check for max capacity, except if we use usize
-        assert!(Ix::max().index() == !0 || NodeIndex::end() != node_idx);
+        assert!(<Ix as IndexType>::max().index() == !0 || NodeIndex::end() != node_idx);
         self.nodes.push(node);
         node_idx
     }
 
+    /// Return `true` if `a` is the index of a node of the graph.
+    ///
+    /// An index is valid if it is below the node count; that doesn't tell
+    /// whether it was returned by this graph. See the
+    /// [`branded`](branded/index.html) module to catch indices from other
+    /// graphs at compile time.
+    pub fn contains_node_index(&self, a: NodeIndex<Ix>) -> bool
+    {
+        a.index() < self.nodes.len()
+    }
+
+    /// Return `true` if `e` is the index of an edge of the graph.
+    pub fn contains_edge_index(&self, e: EdgeIndex<Ix>) -> bool
+    {
+        e.index() < self.edges.len()
+    }
+
     /// Access the weight for node `a`.
     ///
     /// Also available with indexing syntax: `&graph[a]`.
@@ -439,6 +475,17 @@ impl<N, E, Ty, Ix> Graph<N, E, Ty, Ix>
         self.nodes.get_mut(a.index()).map(|n| &mut n.weight)
     }
 
+    /// Return the index of the first node whose weight equals `weight`.
+    ///
+    /// Computes in **O(|V|)** time; see
+    /// [`IndexedGraph`](indexed/struct.IndexedGraph.html) for constant time
+    /// lookups.
+    pub fn node_index_by_weight(&self, weight: &N) -> Option<NodeIndex<Ix>>
+        where N: PartialEq,
+    {
+        self.nodes.iter().position(|n| n.weight == *weight).map(NodeIndex::new)
+    }
+
     /// Add an edge from `a` to `b` to the graph, with its associated
     /// data `weight`.
     ///
@@ -455,7 +502,7 @@ impl<N, E, Ty, Ix> Graph<N, E, Ty, Ix>
     pub fn add_edge(&mut self, a: NodeIndex<Ix>, b: NodeIndex<Ix>, weight: E) -> EdgeIndex<Ix>
     {
         let edge_idx = EdgeIndex::new(self.edges.len());
-        assert!(Ix::max().index() == !0 || EdgeIndex::end() != edge_idx);
+        assert!(<Ix as IndexType>::max().index() == !0 || EdgeIndex::end() != edge_idx);
         let mut edge = Edge {
             weight: weight,
             node: [a, b],
@@ -479,6 +526,70 @@ impl<N, E, Ty, Ix> Graph<N, E, Ty, Ix>
         edge_idx
     }
 
+    /// Add the edges of `iterable`, and return the indices of the new edges.
+    ///
+    /// Edge weights `E` may either be specified in the list, or they are
+    /// filled with default values.
+    ///
+    /// The result is the same as calling [`.add_edge()`](#method.add_edge)
+    /// for each edge in order, but all edges are appended first and then
+    /// linked into the adjacency lists in one pass, which is faster for
+    /// large batches.
+    ///
+    /// **Panics** if any of the nodes don't exist, or if the Graph would
+    /// exceed the maximum number of edges for its index type. No edge is
+    /// added then.
+    ///
+    /// ```
+    /// use petgraph::Graph;
+    ///
+    /// let mut g = Graph::<(), u32>::new();
+    /// let a = g.add_node(());
+    /// let b = g.add_node(());
+    /// let new = g.add_edges_batch(vec![(a, b, 1), (b, a, 2), (b, b, 3)]);
+    /// assert_eq!(new.map(|e| e.index()).collect::<Vec<_>>(), vec![0, 1, 2]);
+    /// assert_eq!(g.neighbors(b).collect::<Vec<_>>(), vec![b, a]);
+    /// ```
+    pub fn add_edges_batch<I>(&mut self, iterable: I) -> EdgeIndices<Ix>
+        where I: IntoIterator,
+              I::Item: IntoWeightedEdge<E>,
+              <I::Item as IntoWeightedEdge<E>>::NodeId: Into<NodeIndex<Ix>>,
+    {
+        let start = self.edges.len();
+        let iter = iterable.into_iter();
+        let (low, _) = iter.size_hint();
+        self.edges.reserve(low);
+        for elt in iter {
+            let (a, b, weight) = elt.into_weighted_edge();
+            let (a, b) = (a.into(), b.into());
+            if cmp::max(a, b).index() >= self.nodes.len() {
+                self.edges.truncate(start);
+                panic!("Graph::add_edges_batch: node indices out of bounds");
+            }
+            self.edges.push(Edge {
+                weight: weight,
+                node: [a, b],
+                next: [EdgeIndex::end(); 2],
+            });
+        }
+        let max = <Ix as IndexType>::max().index();
+        if max != !0 && self.edges.len() > max {
+            self.edges.truncate(start);
+            panic!("Graph::add_edges_batch: too many edges for the index type");
+        }
+        // prepend each new edge to its nodes' lists, in index order, like
+        // add_edge does
+        for i in start..self.edges.len() {
+            let edge = &mut self.edges[i];
+            for k in 0..2 {
+                let node = &mut self.nodes[edge.node[k].index()];
+                edge.next[k] = node.next[k];
+                node.next[k] = EdgeIndex::new(i);
+            }
+        }
+        EdgeIndices { r: start..self.edges.len(), ty: PhantomData }
+    }
+
     /// Add or update an edge from `a` to `b`.
     /// If the edge already exists, its weight is updated.
     ///
@@ -1006,6 +1117,18 @@ impl<N, E, Ty, Ix> Graph<N, E, Ty, Ix>
         (self.nodes.capacity(), self.edges.capacity())
     }
 
+    /// Return an estimate of the heap memory used by the graph, in bytes.
+    ///
+    /// It is the allocated capacity of the node and edge lists, which hold
+    /// the weights and the adjacency links. Memory owned by the weights
+    /// themselves is not counted.
+    ///
+    /// Computes in **O(1)** time.
+    pub fn memory_usage(&self) -> usize {
+        self.nodes.capacity() * mem::size_of::<Node<N, Ix>>() +
+            self.edges.capacity() * mem::size_of::<Edge<E, Ix>>()
+    }
+
     /// Reserves capacity for at least `additional` more nodes to be inserted in
     /// the graph. Graph may reserve more space to avoid frequent reallocations.
     ///
@@ -1100,6 +1223,87 @@ impl<N, E, Ty, Ix> Graph<N, E, Ty, Ix>
         }
     }
 
+    /// Return `true` if the graph has an edge from a node to itself.
+    pub fn has_self_loops(&self) -> bool
+    {
+        self.edges.iter().any(|e| e.source() == e.target())
+    }
+
+    /// Return the number of edges that repeat an earlier edge between the
+    /// same nodes: in the same direction for a directed graph, in either
+    /// direction for an undirected graph.
+    ///
+    /// A graph without self loops and with a parallel edge count of zero is
+    /// a simple graph.
+    pub fn parallel_edge_count(&self) -> usize
+    {
+        let mut seen = BTreeSet::new();
+        self.edges.iter().filter(|e| !seen.insert(self.edge_key(e))).count()
+    }
+
+    /// Remove all edges from a node to itself, and return how many were
+    /// removed.
+    ///
+    /// Like [`.remove_edge()`](#method.remove_edge), this changes the
+    /// indices of other edges.
+    pub fn remove_self_loops(&mut self) -> usize
+    {
+        let before = self.edge_count();
+        self.retain_edges(|g, e| {
+            let (a, b) = (g.edges[e.index()].source(), g.edges[e.index()].target());
+            a != b
+        });
+        before - self.edge_count()
+    }
+
+    /// Remove parallel edges, merging their weights.
+    ///
+    /// Of the edges between the same nodes (in the same direction for a
+    /// directed graph), the one with the lowest index is kept, and the
+    /// weight of each other edge is passed to `merge` along with the weight
+    /// of the kept edge, from the highest index down. Return how many edges
+    /// were removed.
+    ///
+    /// Like [`.remove_edge()`](#method.remove_edge), this changes the
+    /// indices of other edges.
+    ///
+    /// ```
+    /// use petgraph::Graph;
+    ///
+    /// let mut g = Graph::<(), u32>::from_edges(&[(0, 1, 2), (1, 0, 3), (0, 1, 4)]);
+    /// assert_eq!(g.deduplicate_edges(|total, w| *total += w), 1);
+    /// assert_eq!(g.edge_count(), 2);
+    /// assert_eq!(g[g.find_edge(0.into(), 1.into()).unwrap()], 6);
+    /// ```
+    pub fn deduplicate_edges<F>(&mut self, mut merge: F) -> usize
+        where F: FnMut(&mut E, E)
+    {
+        let mut first = BTreeMap::new();
+        let mut keep = Vec::with_capacity(self.edges.len());
+        for (i, edge) in self.edges.iter().enumerate() {
+            keep.push(*first.entry(self.edge_key(edge)).or_insert(i));
+        }
+        // Remove from the highest index down: the edge moved into a removed
+        // edge's place comes later, so it is not the kept edge of any edge
+        // still to be removed.
+        let before = self.edge_count();
+        for i in (0..keep.len()).rev() {
+            if keep[i] != i {
+                let weight = self.remove_edge(EdgeIndex::new(i)).unwrap();
+                merge(&mut self.edges[keep[i]].weight, weight);
+            }
+        }
+        before - self.edge_count()
+    }
+
+    /// Return the endpoints of `edge` as a key that is equal for parallel
+    /// edges.
+    fn edge_key(&self, edge: &Edge<E, Ix>) -> (usize, usize)
+    {
+        let (a, b) = (edge.source().index(), edge.target().index());
+        if self.is_directed() || a <= b { (a, b) } else { (b, a) }
+    }
+
 
     /// Create a new `Graph` from an iterable of edges.
     ///
@@ -1157,6 +1361,59 @@ impl<N, E, Ty, Ix> Graph<N, E, Ty, Ix>
         }
     }
 
+    /// Return the structure of the graph as flat arrays: the node count,
+    /// and the source and target of each edge, in edge index order.
+    ///
+    /// The arrays can be passed across an FFI or wasm boundary as they are;
+    /// [`from_flat`](#method.from_flat) rebuilds the graph with the same
+    /// indices.
+    ///
+    /// ```
+    /// use petgraph::Graph;
+    /// use petgraph::graph::FlatGraph;
+    ///
+    /// let mut g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0)]);
+    /// g.add_node(());
+    ///
+    /// let flat = g.as_flat();
+    /// assert_eq!(flat, FlatGraph { node_count: 4, sources: vec![0, 1, 2], targets: vec![1, 2, 0] });
+    ///
+    /// let h = Graph::<(), ()>::from_flat(&flat);
+    /// assert_eq!(h.as_flat(), flat);
+    /// ```
+    pub fn as_flat(&self) -> FlatGraph<Ix>
+    {
+        FlatGraph {
+            node_count: self.node_count(),
+            sources: self.edges.iter().map(|e| e.source().0).collect(),
+            targets: self.edges.iter().map(|e| e.target().0).collect(),
+        }
+    }
+
+    /// Create a new `Graph` from flat arrays, as returned by
+    /// [`as_flat`](#method.as_flat).
+    ///
+    /// Node and edge weights are set to default values. Node `i` and edge
+    /// `i` of the new graph have index `i`.
+    ///
+    /// **Panics** if `sources` and `targets` have different lengths, or if
+    /// an edge endpoint is not less than `node_count`.
+    pub fn from_flat(flat: &FlatGraph<Ix>) -> Self
+        where N: Default,
+              E: Default,
+    {
+        assert_eq!(flat.sources.len(), flat.targets.len(),
+                   "Graph::from_flat: sources and targets have different lengths");
+        let mut g = Self::with_capacity(flat.node_count, flat.sources.len());
+        for _ in 0..flat.node_count {
+            g.add_node(N::default());
+        }
+        for (&a, &b) in flat.sources.iter().zip(&flat.targets) {
+            g.add_edge(NodeIndex(a), NodeIndex(b), E::default());
+        }
+        g
+    }
+
 
     /// Create a new `Graph` by mapping node and
     /// edge weights to new values.
@@ -1235,6 +1492,21 @@ impl<N, E, Ty, Ix> Graph<N, E, Ty, Ix>
     }
 }
 
+/// The structure of a graph as flat arrays, see
+/// [`Graph::as_flat`](struct.Graph.html#method.as_flat).
+///
+/// Edge `i` goes from `sources[i]` to `targets[i]`; the arrays hold raw index
+/// values.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FlatGraph<Ix = DefIndex> {
+    /// The number of nodes.
+    pub node_count: usize,
+    /// The source of each edge.
+    pub sources: Vec<Ix>,
+    /// The target of each edge.
+    pub targets: Vec<Ix>,
+}
+
 /// An iterator over either the nodes without edges to them or from them.
 pub struct Externals<'a, N: 'a, Ty, Ix: IndexType = DefIndex> {
     iter: iter::Enumerate<slice::Iter<'a, Node<N, Ix>>>,
@@ -1414,7 +1686,7 @@ impl<'a, E, Ix> Clone for Edges<'a, E, Ix>
 
 /// Iterator yielding mutable access to all node weights.
 pub struct NodeWeightsMut<'a, N: 'a, Ix: IndexType = DefIndex> {
-    nodes: ::std::slice::IterMut<'a, Node<N, Ix>>,
+    nodes: ::core::slice::IterMut<'a, Node<N, Ix>>,
 }
 
 impl<'a, N, Ix> Iterator for NodeWeightsMut<'a, N, Ix> where
@@ -1433,7 +1705,7 @@ impl<'a, N, Ix> Iterator for NodeWeightsMut<'a, N, Ix> where
 
 /// Iterator yielding mutable access to all edge weights.
 pub struct EdgeWeightsMut<'a, E: 'a, Ix: IndexType = DefIndex> {
-    edges: ::std::slice::IterMut<'a, Edge<E, Ix>>,
+    edges: ::core::slice::IterMut<'a, Edge<E, Ix>>,
 }
 
 impl<'a, E, Ix> Iterator for EdgeWeightsMut<'a, E, Ix> where
@@ -1664,7 +1936,7 @@ impl<Ix: IndexType> WalkEdges<Ix> {
 }
 
 
-fn enumerate<I>(iterable: I) -> ::std::iter::Enumerate<I::IntoIter>
+fn enumerate<I>(iterable: I) -> ::core::iter::Enumerate<I::IntoIter>
     where I: IntoIterator,
 {
     iterable.into_iter().enumerate()
@@ -1724,3 +1996,20 @@ impl<Ix: IndexType> DoubleEndedIterator for EdgeIndices<Ix> {
 #[path = "stable.rs"]
 pub mod stable;
 
+#[path = "branded.rs"]
+pub mod branded;
+
+#[cfg(feature = "std")]
+#[path = "builder.rs"]
+pub mod builder;
+
+#[path = "checked.rs"]
+pub mod checked;
+
+#[cfg(feature = "std")]
+#[path = "indexed.rs"]
+pub mod indexed;
+
+#[path = "inline.rs"]
+pub mod inline;
+
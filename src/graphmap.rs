@@ -2,20 +2,30 @@
 
 use std::cmp::Ordering;
 use std::collections::HashMap;
-use std::collections::hash_map::{
-    Keys,
-};
-use std::collections::hash_map::Iter as HashmapIter;
 use std::hash::{self, Hash};
 use std::iter::Cloned;
 use std::iter::FromIterator;
+use std::mem;
 use std::slice::{
     Iter,
 };
 use std::fmt;
 use std::ops::{Index, IndexMut, Deref};
 
-use IntoWeightedEdge;
+use {
+    EdgeType,
+    IntoWeightedEdge,
+    Undirected,
+};
+use graph::{
+    Graph,
+    IndexType,
+    NodeIndex,
+};
+use ordermap::{
+    self,
+    OrderMap,
+};
 
 /// `GraphMap<N, E>` is an undirected graph, with generic node values `N` and edge weights `E`.
 ///
@@ -29,10 +39,15 @@ use IntoWeightedEdge;
 /// order the pair (`a`, `b`) for an edge connecting any two nodes `a` and `b`.
 ///
 /// `GraphMap` does not allow parallel edges, but self loops are allowed.
+///
+/// Iteration order is deterministic: nodes, neighbors and edges are visited
+/// in the order they were added. Removing a node or an edge moves the most
+/// recently added one into its place, so the order only ever depends on the
+/// sequence of insertions and removals, not on hashing.
 #[derive(Clone)]
 pub struct GraphMap<N, E> {
-    nodes: HashMap<N, Vec<N>>,
-    edges: HashMap<(N, N), E>,
+    nodes: OrderMap<N, Vec<N>>,
+    edges: OrderMap<(N, N), E>,
 }
 
 impl<N: Eq + Hash + fmt::Debug, E: fmt::Debug> fmt::Debug for GraphMap<N, E> {
@@ -57,16 +72,16 @@ impl<N, E> GraphMap<N, E>
     /// Create a new `GraphMap`.
     pub fn new() -> Self {
         GraphMap {
-            nodes: HashMap::new(),
-            edges: HashMap::new(),
+            nodes: OrderMap::new(),
+            edges: OrderMap::new(),
         }
     }
 
     /// Create a new `GraphMap` with estimated capacity.
     pub fn with_capacity(nodes: usize, edges: usize) -> Self {
         GraphMap {
-            nodes: HashMap::with_capacity(nodes),
-            edges: HashMap::with_capacity(edges),
+            nodes: OrderMap::with_capacity(nodes),
+            edges: OrderMap::with_capacity(edges),
         }
     }
 
@@ -75,6 +90,19 @@ impl<N, E> GraphMap<N, E>
         (self.nodes.capacity(), self.edges.capacity())
     }
 
+    /// Return an estimate of the heap memory used by the graph, in bytes.
+    ///
+    /// It counts the allocated capacity of the node and edge maps and of
+    /// the neighbor lists. The size of a hash table is estimated from its
+    /// capacity, and memory owned by the edge weights is not counted.
+    ///
+    /// Computes in **O(|V|)** time.
+    pub fn memory_usage(&self) -> usize {
+        let neighbors = self.nodes.iter().map(|(_, adj)| adj.capacity()).sum::<usize>();
+        self.nodes.memory_usage() + self.edges.memory_usage() +
+            neighbors * mem::size_of::<N>()
+    }
+
     /// Create a new `GraphMap` from an iterable of edges.
     ///
     /// Node values are taken directly from the list.
@@ -117,7 +145,7 @@ impl<N, E> GraphMap<N, E>
 
     /// Add node `n` to the graph.
     pub fn add_node(&mut self, n: N) -> N {
-        self.nodes.entry(n).or_insert(Vec::new());
+        self.nodes.get_or_insert_with(n, Vec::new);
         n
     }
 
@@ -163,13 +191,9 @@ impl<N, E> GraphMap<N, E>
             old
         } else {
             // insert in the adjacency list if it's a new edge
-            self.nodes.entry(a)
-                      .or_insert_with(|| Vec::with_capacity(1))
-                      .push(b);
+            self.nodes.get_or_insert_with(a, || Vec::with_capacity(1)).push(b);
             if a != b {
-                self.nodes.entry(b)
-                          .or_insert_with(|| Vec::with_capacity(1))
-                          .push(a);
+                self.nodes.get_or_insert_with(b, || Vec::with_capacity(1)).push(a);
             }
             None
         }
@@ -217,14 +241,26 @@ impl<N, E> GraphMap<N, E>
         self.edges.contains_key(&edge_key(a, b))
     }
 
-    /// Return an iterator over the nodes of the graph.
+    /// Return an iterator over the nodes of the graph, in the order they
+    /// were added.
     ///
     /// Iterator element type is `N`.
     pub fn nodes(&self) -> Nodes<N> {
-        Nodes{iter: self.nodes.keys().cloned()}
+        Nodes{iter: self.nodes.keys()}
+    }
+
+    /// Return an iterator over the nodes without edges.
+    ///
+    /// Since the graph is undirected, these nodes are both the sources
+    /// and the sinks of the graph.
+    ///
+    /// Iterator element type is `N`.
+    pub fn externals(&self) -> Externals<N> {
+        Externals { iter: self.nodes.iter() }
     }
 
-    /// Return an iterator over the nodes that are connected with `from` by edges.
+    /// Return an iterator over the nodes that are connected with `from` by edges,
+    /// in the order the edges were added.
     ///
     /// If the node `from` does not exist in the graph, return an empty iterator.
     ///
@@ -264,7 +300,8 @@ impl<N, E> GraphMap<N, E>
         self.edges.get_mut(&edge_key(a, b))
     }
 
-    /// Return an iterator over all edges of the graph with their weight in arbitrary order.
+    /// Return an iterator over all edges of the graph with their weight, in the
+    /// order they were added.
     ///
     /// Iterator element type is `(N, N, &E)`
     pub fn all_edges(&self) -> AllEdges<N, E> {
@@ -272,6 +309,128 @@ impl<N, E> GraphMap<N, E>
             inner: self.edges.iter()
         }
     }
+
+    /// Return an iterator over all edges of the graph with their weight, in the
+    /// order they were added, with mutable access to the weights.
+    ///
+    /// Iterator element type is `(N, N, &mut E)`
+    ///
+    /// ```
+    /// use petgraph::GraphMap;
+    ///
+    /// let mut g = GraphMap::from_edges(&[(1, 2, 1.), (2, 3, 2.)]);
+    /// for (_, _, weight) in g.all_edges_mut() {
+    ///     *weight *= 10.;
+    /// }
+    /// assert_eq!(g.all_edges().collect::<Vec<_>>(), vec![(1, 2, &10.), (2, 3, &20.)]);
+    /// ```
+    pub fn all_edges_mut(&mut self) -> AllEdgesMut<N, E> {
+        AllEdgesMut {
+            inner: self.edges.iter_mut()
+        }
+    }
+
+    /// Return a `Graph` that corresponds to this `GraphMap`.
+    ///
+    /// 1. Note that node and edge indices in the `Graph` have nothing in common
+    ///    with the `GraphMap`s node weights `N`. The node weights `N` are used as
+    ///    node weights in the resulting `Graph`, too.
+    /// 2. Node indices are assigned in the order the nodes are visited by
+    ///    `.nodes()`, and edge indices in the order of `.all_edges()`.
+    ///
+    /// Computes in **O(|V| + |E|)** time (average).
+    ///
+    /// **Panics** if the number of nodes or edges does not fit with
+    /// the resulting graph's index type.
+    pub fn into_graph<Ix>(self) -> Graph<N, E, Undirected, Ix>
+        where Ix: IndexType,
+    {
+        self.into_graph_with_map().0
+    }
+
+    /// Return a `Graph` that corresponds to this `GraphMap`, as
+    /// [`into_graph`](#method.into_graph), and the node index of each node
+    /// of the `GraphMap` in it.
+    ///
+    /// ```
+    /// use petgraph::{Graph, GraphMap, Undirected};
+    ///
+    /// let gr = GraphMap::<_, ()>::from_edges(&[("a", "b"), ("b", "c")]);
+    /// let (g, index): (Graph<_, _, Undirected>, _) = gr.into_graph_with_map();
+    /// assert_eq!(g[index["c"]], "c");
+    /// assert!(g.find_edge(index["a"], index["b"]).is_some());
+    /// ```
+    ///
+    /// **Panics** if the number of nodes or edges does not fit with
+    /// the resulting graph's index type.
+    pub fn into_graph_with_map<Ix>(self) -> (Graph<N, E, Undirected, Ix>, HashMap<N, NodeIndex<Ix>>)
+        where Ix: IndexType,
+    {
+        let mut gr = Graph::with_capacity(self.node_count(), self.edge_count());
+        let mut node_index = HashMap::with_capacity(self.node_count());
+        for node in self.nodes() {
+            node_index.insert(node, gr.add_node(node));
+        }
+        for ((a, b), edge_weight) in self.edges {
+            gr.add_edge(node_index[&a], node_index[&b], edge_weight);
+        }
+        (gr, node_index)
+    }
+
+    /// Create a new `GraphMap` from a `Graph`, using the node weights as
+    /// node identifiers.
+    ///
+    /// Since `GraphMap` is undirected, a directed graph's edges are
+    /// inserted without their direction.
+    ///
+    /// 1. Nodes with equal weights are merged into one node.
+    /// 2. `GraphMap` does not allow parallel edges: if two edges connect
+    ///    the same pair of nodes, the edge weight with the highest edge index
+    ///    is kept.
+    ///
+    /// Computes in **O(|V| + |E|)** time (average).
+    pub fn from_graph<Ty, Ix>(graph: Graph<N, E, Ty, Ix>) -> Self
+        where Ty: EdgeType,
+              Ix: IndexType,
+    {
+        let mut new_graph = GraphMap::with_capacity(graph.node_count(),
+                                                    graph.edge_count());
+        let (nodes, edges) = graph.into_nodes_edges();
+        let nodes = nodes.into_iter().map(|node| node.weight).collect::<Vec<N>>();
+        for &node in &nodes {
+            new_graph.add_node(node);
+        }
+        for edge in edges {
+            let (a, b) = (edge.source(), edge.target());
+            new_graph.add_edge(nodes[a.index()], nodes[b.index()], edge.weight);
+        }
+        new_graph
+    }
+}
+
+/// Convert a `GraphMap` into a `Graph`.
+///
+/// See [`GraphMap::into_graph`](struct.GraphMap.html#method.into_graph).
+impl<N, E, Ix> From<GraphMap<N, E>> for Graph<N, E, Undirected, Ix>
+    where N: NodeTrait,
+          Ix: IndexType,
+{
+    fn from(graph: GraphMap<N, E>) -> Self {
+        graph.into_graph()
+    }
+}
+
+/// Convert a `Graph` into a `GraphMap`.
+///
+/// See [`GraphMap::from_graph`](struct.GraphMap.html#method.from_graph).
+impl<N, E, Ty, Ix> From<Graph<N, E, Ty, Ix>> for GraphMap<N, E>
+    where N: NodeTrait,
+          Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn from(graph: Graph<N, E, Ty, Ix>) -> Self {
+        GraphMap::from_graph(graph)
+    }
 }
 
 /// Create a new `GraphMap` from an iterable of edges.
@@ -346,7 +505,7 @@ macro_rules! iterator_wrap {
 iterator_wrap! {
     Nodes <'a, N> where { N: 'a + NodeTrait }
     item: N,
-    iter: Cloned<Keys<'a, N, Vec<N>>>,
+    iter: ordermap::Keys<'a, N, Vec<N>>,
 }
 
 impl<'a, N: 'a + NodeTrait> ExactSizeIterator for Nodes<'a, N> { }
@@ -373,9 +532,29 @@ impl<'a, N: 'a + NodeTrait> Clone for Neighbors<'a, N> {
     }
 }
 
+/// An iterator over the nodes without edges.
+pub struct Externals<'a, N: 'a> where N: NodeTrait {
+    iter: ordermap::Iter<'a, N, Vec<N>>,
+}
+
+impl<'a, N> Iterator for Externals<'a, N>
+    where N: 'a + NodeTrait
+{
+    type Item = N;
+    fn next(&mut self) -> Option<N>
+    {
+        while let Some((&node, neighbors)) = self.iter.next() {
+            if neighbors.is_empty() {
+                return Some(node);
+            }
+        }
+        None
+    }
+}
+
 pub struct Edges<'a, N, E: 'a> where N: 'a + NodeTrait {
     from: N,
-    edges: &'a HashMap<(N, N), E>,
+    edges: &'a OrderMap<(N, N), E>,
     iter: Neighbors<'a, N>,
 }
 
@@ -401,7 +580,7 @@ impl<'a, N, E> Iterator for Edges<'a, N, E>
 }
 
 pub struct AllEdges<'a, N, E: 'a> where N: 'a + NodeTrait {
-    inner: HashmapIter<'a, (N, N), E>
+    inner: ordermap::Iter<'a, (N, N), E>
 }
 
 impl<'a, N, E> Iterator for AllEdges<'a, N, E>
@@ -417,6 +596,20 @@ impl<'a, N, E> Iterator for AllEdges<'a, N, E>
     }
 }
 
+pub struct AllEdgesMut<'a, N, E: 'a> where N: 'a + NodeTrait {
+    inner: ordermap::IterMut<'a, (N, N), E>
+}
+
+impl<'a, N, E> Iterator for AllEdgesMut<'a, N, E>
+    where N: 'a + NodeTrait, E: 'a
+{
+    type Item = (N, N, &'a mut E);
+    fn next(&mut self) -> Option<Self::Item>
+    {
+        self.inner.next().map(|(&(a, b), v)| (a, b, v))
+    }
+}
+
 /// Index `GraphMap` by node pairs to access edge weights.
 impl<N, E> Index<(N, N)> for GraphMap<N, E>
     where N: NodeTrait
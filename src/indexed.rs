@@ -0,0 +1,201 @@
+//! `IndexedGraph` is a `Graph` that finds nodes by weight in constant time.
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::ops::{
+    Deref,
+    Index,
+    IndexMut,
+};
+
+use {
+    Directed,
+    EdgeType,
+};
+use super::{
+    DefIndex,
+    EdgeIndex,
+    Graph,
+    IndexType,
+    NodeIndex,
+};
+
+/// A `Graph` with a map from node weights to node indices.
+///
+/// Node weights are keys: they are unique in the graph, and can't be mutated
+/// in place. Everything that reads the graph is available through `Deref`,
+/// and the mutations that keep the map up to date are methods of
+/// `IndexedGraph`.
+///
+/// ```
+/// use petgraph::graph::indexed::IndexedGraph;
+///
+/// let mut g = IndexedGraph::<String, ()>::new();
+/// let a = g.add_node("a".to_string());
+/// let b = g.add_node("b".to_string());
+/// g.add_edge(a, b, ());
+///
+/// assert_eq!(g.node_index_by_weight("b"), Some(b));
+/// assert_eq!(g.add_node("a".to_string()), a);
+/// assert_eq!(g.node_count(), 2);
+///
+/// g.remove_node(a);
+/// assert_eq!(g.node_index_by_weight("a"), None);
+/// assert_eq!(g.node_index_by_weight("b"), Some(a));
+/// ```
+#[derive(Clone)]
+pub struct IndexedGraph<N, E, Ty = Directed, Ix: IndexType = DefIndex>
+    where N: Eq + Hash,
+{
+    graph: Graph<N, E, Ty, Ix>,
+    index: HashMap<N, NodeIndex<Ix>>,
+}
+
+impl<N, E> IndexedGraph<N, E, Directed>
+    where N: Clone + Eq + Hash,
+{
+    /// Create a new directed `IndexedGraph`.
+    pub fn new() -> Self {
+        IndexedGraph::from_graph(Graph::new())
+    }
+}
+
+impl<N, E, Ty, Ix> IndexedGraph<N, E, Ty, Ix>
+    where N: Clone + Eq + Hash,
+          Ty: EdgeType,
+          Ix: IndexType,
+{
+    /// Create an `IndexedGraph` from a graph.
+    ///
+    /// **Panics** if two nodes of `graph` have equal weights.
+    pub fn from_graph(graph: Graph<N, E, Ty, Ix>) -> Self {
+        let mut index = HashMap::with_capacity(graph.node_count());
+        for a in graph.node_indices() {
+            let dup = index.insert(graph[a].clone(), a);
+            assert!(dup.is_none(), "IndexedGraph::from_graph: two nodes have equal weights");
+        }
+        IndexedGraph {
+            graph: graph,
+            index: index,
+        }
+    }
+
+    /// Return the underlying graph, dropping the map.
+    pub fn into_graph(self) -> Graph<N, E, Ty, Ix> {
+        self.graph
+    }
+
+    /// Return the index of the node with weight `weight`.
+    ///
+    /// Computes in **O(1)** time (average).
+    pub fn node_index_by_weight<Q: ?Sized>(&self, weight: &Q) -> Option<NodeIndex<Ix>>
+        where N: Borrow<Q>,
+              Q: Eq + Hash,
+    {
+        self.index.get(weight).cloned()
+    }
+
+    /// Add a node with weight `weight`, and return its index. If a node
+    /// with an equal weight exists, return its index instead.
+    pub fn add_node(&mut self, weight: N) -> NodeIndex<Ix> {
+        if let Some(&a) = self.index.get(&weight) {
+            return a
+        }
+        let a = self.graph.add_node(weight.clone());
+        self.index.insert(weight, a);
+        a
+    }
+
+    /// Add an edge from `a` to `b`, as
+    /// [`Graph::add_edge`](../struct.Graph.html#method.add_edge).
+    pub fn add_edge(&mut self, a: NodeIndex<Ix>, b: NodeIndex<Ix>, weight: E) -> EdgeIndex<Ix> {
+        self.graph.add_edge(a, b, weight)
+    }
+
+    /// Access the weight of edge `e`, mutably.
+    pub fn edge_weight_mut(&mut self, e: EdgeIndex<Ix>) -> Option<&mut E> {
+        self.graph.edge_weight_mut(e)
+    }
+
+    /// Remove the node `a` and its edges, as
+    /// [`Graph::remove_node`](../struct.Graph.html#method.remove_node): the
+    /// last node takes its index.
+    pub fn remove_node(&mut self, a: NodeIndex<Ix>) -> Option<N> {
+        let weight = match self.graph.remove_node(a) {
+            None => return None,
+            Some(weight) => weight,
+        };
+        self.index.remove(&weight);
+        if let Some(moved) = self.graph.node_weight(a) {
+            self.index.insert(moved.clone(), a);
+        }
+        Some(weight)
+    }
+
+    /// Remove the edge `e`, as
+    /// [`Graph::remove_edge`](../struct.Graph.html#method.remove_edge).
+    pub fn remove_edge(&mut self, e: EdgeIndex<Ix>) -> Option<E> {
+        self.graph.remove_edge(e)
+    }
+
+    /// Remove all nodes and edges.
+    pub fn clear(&mut self) {
+        self.graph.clear();
+        self.index.clear();
+    }
+}
+
+impl<N, E, Ty, Ix> fmt::Debug for IndexedGraph<N, E, Ty, Ix>
+    where N: Eq + Hash + fmt::Debug,
+          E: fmt::Debug,
+          Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.graph.fmt(f)
+    }
+}
+
+impl<N, E, Ty, Ix> Deref for IndexedGraph<N, E, Ty, Ix>
+    where N: Eq + Hash,
+          Ix: IndexType,
+{
+    type Target = Graph<N, E, Ty, Ix>;
+    fn deref(&self) -> &Graph<N, E, Ty, Ix> {
+        &self.graph
+    }
+}
+
+impl<N, E, Ty, Ix> Index<EdgeIndex<Ix>> for IndexedGraph<N, E, Ty, Ix>
+    where N: Eq + Hash,
+          Ty: EdgeType,
+          Ix: IndexType,
+{
+    type Output = E;
+    fn index(&self, e: EdgeIndex<Ix>) -> &E {
+        &self.graph[e]
+    }
+}
+
+impl<N, E, Ty, Ix> IndexMut<EdgeIndex<Ix>> for IndexedGraph<N, E, Ty, Ix>
+    where N: Eq + Hash,
+          Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn index_mut(&mut self, e: EdgeIndex<Ix>) -> &mut E {
+        &mut self.graph[e]
+    }
+}
+
+impl<N, E, Ty, Ix> Index<NodeIndex<Ix>> for IndexedGraph<N, E, Ty, Ix>
+    where N: Eq + Hash,
+          Ty: EdgeType,
+          Ix: IndexType,
+{
+    type Output = N;
+    fn index(&self, a: NodeIndex<Ix>) -> &N {
+        &self.graph[a]
+    }
+}
@@ -0,0 +1,430 @@
+//! `InlineGraph` is a graph for low degree nodes, with their edges stored
+//! inline.
+
+use core::fmt;
+use core::iter;
+use core::marker::PhantomData;
+use core::ops::{
+    Index,
+    IndexMut,
+};
+use core::slice;
+use alloc::vec::Vec;
+
+use {
+    Directed,
+    EdgeDirection,
+    EdgeType,
+    Outgoing,
+    Undirected,
+};
+use super::{
+    DefIndex,
+    EdgeIndex,
+    EdgeIndices,
+    Graph,
+    IndexType,
+    NodeIndex,
+    NodeIndices,
+};
+
+/// The number of edges of a node, in each direction, that are stored inline.
+const INLINE: usize = 4;
+
+/// A neighbor and the edge that leads to it.
+type Entry<Ix> = (NodeIndex<Ix>, EdgeIndex<Ix>);
+
+/// The edges of a node in one direction: inline while there are at most
+/// `INLINE`, on the heap after that.
+#[derive(Clone)]
+enum Adjacency<Ix> {
+    Inline(usize, [Entry<Ix>; INLINE]),
+    Heap(Vec<Entry<Ix>>),
+}
+
+impl<Ix: IndexType> Adjacency<Ix> {
+    fn new() -> Self {
+        Adjacency::Inline(0, [(NodeIndex::end(), EdgeIndex::end()); INLINE])
+    }
+
+    fn as_slice(&self) -> &[Entry<Ix>] {
+        match *self {
+            Adjacency::Inline(len, ref entries) => &entries[..len],
+            Adjacency::Heap(ref entries) => entries,
+        }
+    }
+
+    fn push(&mut self, entry: Entry<Ix>) {
+        let spilled = match *self {
+            Adjacency::Inline(ref mut len, ref mut entries) => {
+                if *len < INLINE {
+                    entries[*len] = entry;
+                    *len += 1;
+                    return
+                }
+                let mut heap = Vec::with_capacity(2 * INLINE);
+                heap.extend_from_slice(entries);
+                heap.push(entry);
+                heap
+            }
+            Adjacency::Heap(ref mut entries) => {
+                entries.push(entry);
+                return
+            }
+        };
+        *self = Adjacency::Heap(spilled);
+    }
+}
+
+#[derive(Clone)]
+struct InlineNode<N, Ix> {
+    weight: N,
+    /// Outgoing and incoming edges.
+    adj: [Adjacency<Ix>; 2],
+}
+
+#[derive(Clone)]
+struct InlineEdge<E, Ix> {
+    weight: E,
+    node: [NodeIndex<Ix>; 2],
+}
+
+/// `InlineGraph<N, E, Ty, Ix>` is a graph that stores the first few edges of
+/// each node inline, next to the node, instead of in linked lists.
+///
+/// Walking the neighbors of a node reads one contiguous list, without
+/// visiting the edges, so traversals of graphs where most nodes have at
+/// most four edges in each direction follow fewer pointers than in a
+/// `Graph`. Nodes with more edges keep them in a vector.
+///
+/// Nodes and edges can be added, but not removed. Node and edge indices are
+/// the same as in a `Graph` built in the same order, and the graph converts
+/// to and from `Graph` keeping them. Unlike in a `Graph`, the neighbors of a
+/// node are listed in the order their edges were added.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::graph::inline::InlineGraph;
+/// use petgraph::visit::Bfs;
+///
+/// let g = Graph::<u32, ()>::from_edges(&[(0, 1), (1, 2), (1, 3), (3, 0)]);
+/// let g = InlineGraph::from(g);
+/// assert_eq!(g.neighbors(1.into()).collect::<Vec<_>>(), vec![2.into(), 3.into()]);
+///
+/// let mut bfs = Bfs::new(&g, 0.into());
+/// let mut order = Vec::new();
+/// while let Some(a) = bfs.next(&g) {
+///     order.push(a.index());
+/// }
+/// assert_eq!(order, vec![0, 1, 2, 3]);
+/// ```
+#[derive(Clone)]
+pub struct InlineGraph<N, E, Ty = Directed, Ix = DefIndex> {
+    nodes: Vec<InlineNode<N, Ix>>,
+    edges: Vec<InlineEdge<E, Ix>>,
+    ty: PhantomData<Ty>,
+}
+
+impl<N, E> InlineGraph<N, E, Directed> {
+    /// Create a new directed `InlineGraph`.
+    pub fn new() -> Self {
+        InlineGraph::with_capacity(0, 0)
+    }
+}
+
+impl<N, E> InlineGraph<N, E, Undirected> {
+    /// Create a new undirected `InlineGraph`.
+    pub fn new_undirected() -> Self {
+        InlineGraph::with_capacity(0, 0)
+    }
+}
+
+impl<N, E, Ty, Ix> InlineGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    /// Create a new `InlineGraph` with estimated capacity.
+    pub fn with_capacity(nodes: usize, edges: usize) -> Self {
+        InlineGraph {
+            nodes: Vec::with_capacity(nodes),
+            edges: Vec::with_capacity(edges),
+            ty: PhantomData,
+        }
+    }
+
+    /// Return the number of nodes in the graph.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Return the number of edges in the graph.
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Whether the graph has directed edges or not.
+    pub fn is_directed(&self) -> bool {
+        Ty::is_directed()
+    }
+
+    /// Add a node with weight `weight`, and return its index.
+    ///
+    /// **Panics** if the graph is at the maximum number of nodes for its
+    /// index type.
+    pub fn add_node(&mut self, weight: N) -> NodeIndex<Ix> {
+        let a = NodeIndex::new(self.nodes.len());
+        assert!(<Ix as IndexType>::max().index() == !0 || a != NodeIndex::end());
+        self.nodes.push(InlineNode {
+            weight: weight,
+            adj: [Adjacency::new(), Adjacency::new()],
+        });
+        a
+    }
+
+    /// Add an edge from `a` to `b` with weight `weight`, and return its
+    /// index.
+    ///
+    /// **Panics** if any of the nodes don't exist.<br>
+    /// **Panics** if the graph is at the maximum number of edges for its
+    /// index type.
+    pub fn add_edge(&mut self, a: NodeIndex<Ix>, b: NodeIndex<Ix>, weight: E) -> EdgeIndex<Ix> {
+        let e = EdgeIndex::new(self.edges.len());
+        assert!(<Ix as IndexType>::max().index() == !0 || e != EdgeIndex::end());
+        assert!(a.index() < self.nodes.len() && b.index() < self.nodes.len(),
+                "InlineGraph::add_edge: node indices out of bounds");
+        self.nodes[a.index()].adj[0].push((b, e));
+        // an undirected self loop is listed once
+        if self.is_directed() || a != b {
+            self.nodes[b.index()].adj[1].push((a, e));
+        }
+        self.edges.push(InlineEdge {
+            weight: weight,
+            node: [a, b],
+        });
+        e
+    }
+
+    /// Access the weight of node `a`.
+    pub fn node_weight(&self, a: NodeIndex<Ix>) -> Option<&N> {
+        self.nodes.get(a.index()).map(|node| &node.weight)
+    }
+
+    /// Access the weight of node `a`, mutably.
+    pub fn node_weight_mut(&mut self, a: NodeIndex<Ix>) -> Option<&mut N> {
+        self.nodes.get_mut(a.index()).map(|node| &mut node.weight)
+    }
+
+    /// Access the weight of edge `e`.
+    pub fn edge_weight(&self, e: EdgeIndex<Ix>) -> Option<&E> {
+        self.edges.get(e.index()).map(|edge| &edge.weight)
+    }
+
+    /// Access the weight of edge `e`, mutably.
+    pub fn edge_weight_mut(&mut self, e: EdgeIndex<Ix>) -> Option<&mut E> {
+        self.edges.get_mut(e.index()).map(|edge| &mut edge.weight)
+    }
+
+    /// Return the source and target of edge `e`.
+    pub fn edge_endpoints(&self, e: EdgeIndex<Ix>) -> Option<(NodeIndex<Ix>, NodeIndex<Ix>)> {
+        self.edges.get(e.index()).map(|edge| (edge.node[0], edge.node[1]))
+    }
+
+    /// Return an iterator of the neighbors of `a`, as
+    /// [`Graph::neighbors`](../struct.Graph.html#method.neighbors).
+    ///
+    /// Produces an empty iterator if the node doesn't exist.
+    pub fn neighbors(&self, a: NodeIndex<Ix>) -> Neighbors<Ix> {
+        self.neighbors_directed(a, Outgoing)
+    }
+
+    /// Return an iterator of the neighbors of `a` in direction `dir`, as
+    /// [`Graph::neighbors_directed`](../struct.Graph.html#method.neighbors_directed).
+    ///
+    /// Produces an empty iterator if the node doesn't exist.
+    pub fn neighbors_directed(&self, a: NodeIndex<Ix>, dir: EdgeDirection) -> Neighbors<Ix> {
+        Neighbors {
+            iter: self.entries(a, dir),
+        }
+    }
+
+    /// Return an iterator of the neighbors of `a` and the weights of the
+    /// edges to them, as [`Graph::edges`](../struct.Graph.html#method.edges).
+    ///
+    /// Produces an empty iterator if the node doesn't exist.
+    pub fn edges(&self, a: NodeIndex<Ix>) -> Edges<E, Ix> {
+        Edges {
+            iter: self.entries(a, Outgoing),
+            edges: &self.edges,
+        }
+    }
+
+    /// Return an index of an edge from `a` to `b`, or between them for an
+    /// undirected graph.
+    ///
+    /// Computes in **O(e')** time, where **e'** is the number of edges of
+    /// `a`.
+    pub fn find_edge(&self, a: NodeIndex<Ix>, b: NodeIndex<Ix>) -> Option<EdgeIndex<Ix>> {
+        self.entries(a, Outgoing).find(|&&(x, _)| x == b).map(|&(_, e)| e)
+    }
+
+    /// Return an iterator of the node indices of the graph.
+    pub fn node_indices(&self) -> NodeIndices<Ix> {
+        NodeIndices { r: 0..self.node_count(), ty: PhantomData }
+    }
+
+    /// Return an iterator of the edge indices of the graph.
+    pub fn edge_indices(&self) -> EdgeIndices<Ix> {
+        EdgeIndices { r: 0..self.edge_count(), ty: PhantomData }
+    }
+
+    /// Convert the graph into a `Graph` with the same node and edge
+    /// indices.
+    pub fn into_graph(self) -> Graph<N, E, Ty, Ix> {
+        let mut g = Graph::with_capacity(self.node_count(), self.edge_count());
+        for node in self.nodes {
+            g.add_node(node.weight);
+        }
+        for edge in self.edges {
+            g.add_edge(edge.node[0], edge.node[1], edge.weight);
+        }
+        g
+    }
+
+    fn entries(&self, a: NodeIndex<Ix>, dir: EdgeDirection) -> Entries<Ix> {
+        let (first, second): (&[_], &[_]) = match self.nodes.get(a.index()) {
+            None => (&[], &[]),
+            Some(node) => {
+                let k = dir as usize;
+                if self.is_directed() {
+                    (node.adj[k].as_slice(), &[])
+                } else {
+                    (node.adj[0].as_slice(), node.adj[1].as_slice())
+                }
+            }
+        };
+        first.iter().chain(second)
+    }
+}
+
+/// Create an `InlineGraph` with the same node and edge indices as `g`.
+impl<N, E, Ty, Ix> From<Graph<N, E, Ty, Ix>> for InlineGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn from(g: Graph<N, E, Ty, Ix>) -> Self {
+        let mut inline = InlineGraph::with_capacity(g.node_count(), g.edge_count());
+        let (nodes, edges) = g.into_nodes_edges();
+        for node in nodes {
+            inline.add_node(node.weight);
+        }
+        for edge in edges {
+            inline.add_edge(edge.source(), edge.target(), edge.weight);
+        }
+        inline
+    }
+}
+
+impl<N, E, Ty, Ix> Default for InlineGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn default() -> Self { Self::with_capacity(0, 0) }
+}
+
+impl<N, E, Ty, Ix> fmt::Debug for InlineGraph<N, E, Ty, Ix>
+    where N: fmt::Debug,
+          E: fmt::Debug,
+          Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("InlineGraph")
+         .field("nodes", &self.nodes.iter().map(|node| &node.weight).collect::<Vec<_>>())
+         .field("edges", &self.edges.iter().map(|edge| {
+             (edge.node[0].index(), edge.node[1].index(), &edge.weight)
+         }).collect::<Vec<_>>())
+         .finish()
+    }
+}
+
+impl<N, E, Ty, Ix> Index<NodeIndex<Ix>> for InlineGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    type Output = N;
+    fn index(&self, a: NodeIndex<Ix>) -> &N {
+        &self.nodes[a.index()].weight
+    }
+}
+
+impl<N, E, Ty, Ix> IndexMut<NodeIndex<Ix>> for InlineGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn index_mut(&mut self, a: NodeIndex<Ix>) -> &mut N {
+        &mut self.nodes[a.index()].weight
+    }
+}
+
+impl<N, E, Ty, Ix> Index<EdgeIndex<Ix>> for InlineGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    type Output = E;
+    fn index(&self, e: EdgeIndex<Ix>) -> &E {
+        &self.edges[e.index()].weight
+    }
+}
+
+impl<N, E, Ty, Ix> IndexMut<EdgeIndex<Ix>> for InlineGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn index_mut(&mut self, e: EdgeIndex<Ix>) -> &mut E {
+        &mut self.edges[e.index()].weight
+    }
+}
+
+type Entries<'a, Ix> = iter::Chain<slice::Iter<'a, Entry<Ix>>, slice::Iter<'a, Entry<Ix>>>;
+
+/// An iterator of the neighbors of a node, see
+/// [`InlineGraph::neighbors`](struct.InlineGraph.html#method.neighbors).
+#[derive(Clone)]
+pub struct Neighbors<'a, Ix: 'a> {
+    iter: Entries<'a, Ix>,
+}
+
+impl<'a, Ix: IndexType> Iterator for Neighbors<'a, Ix> {
+    type Item = NodeIndex<Ix>;
+
+    fn next(&mut self) -> Option<NodeIndex<Ix>> {
+        self.iter.next().map(|&(b, _)| b)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// An iterator of the neighbors of a node and the weights of the edges to
+/// them, see [`InlineGraph::edges`](struct.InlineGraph.html#method.edges).
+#[derive(Clone)]
+pub struct Edges<'a, E: 'a, Ix: 'a> {
+    iter: Entries<'a, Ix>,
+    edges: &'a [InlineEdge<E, Ix>],
+}
+
+impl<'a, E, Ix: IndexType> Iterator for Edges<'a, E, Ix> {
+    type Item = (NodeIndex<Ix>, &'a E);
+
+    fn next(&mut self) -> Option<(NodeIndex<Ix>, &'a E)> {
+        match self.iter.next() {
+            None => None,
+            Some(&(b, e)) => Some((b, &self.edges[e.index()].weight)),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
@@ -0,0 +1,534 @@
+//! ***Unstable.*** Graphviz dot format input.
+//!
+//! ***Unstable: API may change at any time.*** Depends on `feature = "dot_parser"`.
+
+use std::collections::HashMap;
+use std::iter::Peekable;
+use std::str::Chars;
+
+use Graph;
+use graph::NodeIndex;
+pub use super::ParseError;
+use super::MAX_DEPTH;
+
+/// Parse a graph in graphviz dot format.
+///
+/// ***Unstable: API may change at any time.*** Depends on `feature = "dot_parser"`.
+///
+/// The parser understands a useful subset of the dot language: node, edge
+/// and attribute statements, subgraphs (including subgraphs as edge
+/// endpoints), quoted, numeral and html identifiers, and comments.
+/// Ports are accepted but ignored, as are graph attributes.
+///
+/// Each distinct node identifier becomes a node. Its weight is the node's
+/// `label` attribute if it has one, otherwise the node identifier.
+/// Each edge's weight is its `label` attribute, or an empty string.
+/// Nodes are added in the order of their first appearance, and edges in
+/// the order of appearance.
+///
+/// The result is always a directed graph. For an undirected input (`graph`),
+/// each edge is added in the direction it was written; use
+/// `.into_edge_type::<Undirected>()` to convert the result.
+///
+/// Escape sequences in quoted strings are kept as is, except for `\"`, which
+/// is unescaped to `"`.
+///
+/// ```
+/// use petgraph::io::dot::parse;
+///
+/// let g = parse(r#"digraph {
+///     a -> b [label="ab"];
+///     b -> { c d }
+///     c [label="C"]
+/// }"#).unwrap();
+///
+/// assert_eq!(g.node_count(), 4);
+/// assert_eq!(g.edge_count(), 3);
+/// let names = g.node_indices().map(|i| &g[i][..]).collect::<Vec<_>>();
+/// assert_eq!(names, ["a", "b", "C", "d"]);
+/// assert_eq!(g.raw_edges()[0].weight, "ab");
+/// ```
+pub fn parse(input: &str) -> Result<Graph<String, String>, ParseError> {
+    let tokens = try!(tokenize(input));
+    let mut parser = Parser {
+        tokens: tokens,
+        pos: 0,
+        graph: Graph::new(),
+        nodes: HashMap::new(),
+        subgraphs: Vec::new(),
+        directed: true,
+    };
+    try!(parser.parse_graph());
+    Ok(parser.graph)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    /// An identifier; `true` if it was quoted (and thus not a keyword).
+    Id(String, bool),
+    LBrace,
+    RBrace,
+    LBracket,
+    RBracket,
+    Semi,
+    Comma,
+    Equal,
+    Colon,
+    /// Edge operator, `true` for `->` and `false` for `--`.
+    EdgeOp(bool),
+}
+
+fn is_id_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c as u32 >= 0x80
+}
+
+/// Skip the rest of the line, including the newline.
+fn skip_line(chars: &mut Peekable<Chars>) {
+    while let Some(c) = chars.next() {
+        if c == '\n' {
+            break;
+        }
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<(Token, usize)>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    let mut line = 1;
+    let mut line_start = true;
+    // set after a `+` that joins two quoted strings
+    let mut concat = false;
+    while let Some(c) = chars.next() {
+        let start_line = line;
+        if concat && c != '"' && !c.is_whitespace() {
+            return Err(parse_error(line, "expected a quoted string after '+'"));
+        }
+        match c {
+            '\n' => {
+                line += 1;
+                line_start = true;
+                continue;
+            }
+            c if c.is_whitespace() => continue,
+            // preprocessor output lines
+            '#' if line_start => {
+                skip_line(&mut chars);
+                line += 1;
+                continue;
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                skip_line(&mut chars);
+                line += 1;
+                line_start = true;
+                continue;
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = ' ';
+                loop {
+                    match chars.next() {
+                        None => return Err(parse_error(start_line, "unterminated comment")),
+                        Some('/') if prev == '*' => break,
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            prev = c;
+                        }
+                    }
+                }
+                continue;
+            }
+            '{' => tokens.push((Token::LBrace, line)),
+            '}' => tokens.push((Token::RBrace, line)),
+            '[' => tokens.push((Token::LBracket, line)),
+            ']' => tokens.push((Token::RBracket, line)),
+            ';' => tokens.push((Token::Semi, line)),
+            ',' => tokens.push((Token::Comma, line)),
+            '=' => tokens.push((Token::Equal, line)),
+            ':' => tokens.push((Token::Colon, line)),
+            '-' if chars.peek() == Some(&'>') => {
+                chars.next();
+                tokens.push((Token::EdgeOp(true), line));
+            }
+            '-' if chars.peek() == Some(&'-') => {
+                chars.next();
+                tokens.push((Token::EdgeOp(false), line));
+            }
+            '+' => {
+                match tokens.last() {
+                    Some(&(Token::Id(_, true), _)) => concat = true,
+                    _ => return Err(parse_error(line, "unexpected '+'")),
+                }
+            }
+            '"' => {
+                let mut s = String::new();
+                loop {
+                    match chars.next() {
+                        None => return Err(parse_error(start_line, "unterminated string")),
+                        Some('"') => break,
+                        Some('\\') => {
+                            match chars.next() {
+                                Some('"') => s.push('"'),
+                                // line continuation
+                                Some('\n') => line += 1,
+                                Some(c) => {
+                                    s.push('\\');
+                                    s.push(c);
+                                }
+                                None => return Err(parse_error(start_line, "unterminated string")),
+                            }
+                        }
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            s.push(c);
+                        }
+                    }
+                }
+                if concat {
+                    concat = false;
+                    if let Some(&mut (Token::Id(ref mut prefix, true), _)) = tokens.last_mut() {
+                        prefix.push_str(&s);
+                    }
+                } else {
+                    tokens.push((Token::Id(s, true), start_line));
+                }
+            }
+            '<' => {
+                // html string: keep the markup between the outer brackets verbatim
+                let mut s = String::new();
+                let mut depth = 1;
+                loop {
+                    let c = match chars.next() {
+                        None => return Err(parse_error(start_line, "unterminated html string")),
+                        Some(c) => c,
+                    };
+                    match c {
+                        '<' => depth += 1,
+                        '>' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        '\n' => line += 1,
+                        _ => {}
+                    }
+                    s.push(c);
+                }
+                tokens.push((Token::Id(s, true), start_line));
+            }
+            c if c == '-' || c == '.' || c.is_digit(10) => {
+                let mut s = String::new();
+                s.push(c);
+                while let Some(&c) = chars.peek() {
+                    if c == '.' || c.is_digit(10) {
+                        s.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                if !s.chars().any(|c| c.is_digit(10)) {
+                    return Err(parse_error(line, format!("unexpected '{}'", s)));
+                }
+                tokens.push((Token::Id(s, false), line));
+            }
+            c if is_id_char(c) => {
+                let mut s = String::new();
+                s.push(c);
+                while let Some(&c) = chars.peek() {
+                    if is_id_char(c) {
+                        s.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push((Token::Id(s, false), line));
+            }
+            c => return Err(parse_error(line, format!("unexpected character '{}'", c))),
+        }
+        line_start = false;
+    }
+    if concat {
+        return Err(parse_error(line, "expected a quoted string after '+'"));
+    }
+    Ok(tokens)
+}
+
+/// Default attributes of a graph or subgraph scope.
+#[derive(Clone, Default)]
+struct Defaults {
+    node_label: Option<String>,
+    edge_label: Option<String>,
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+    graph: Graph<String, String>,
+    nodes: HashMap<String, NodeIndex>,
+    /// The nodes of each subgraph that is being parsed, innermost last.
+    subgraphs: Vec<Vec<NodeIndex>>,
+    directed: bool,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|t| &t.0)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&Token> {
+        self.tokens.get(self.pos + offset).map(|t| &t.0)
+    }
+
+    fn line(&self) -> usize {
+        match self.tokens.get(self.pos) {
+            Some(t) => t.1,
+            None => self.tokens.last().map_or(1, |t| t.1),
+        }
+    }
+
+    fn error<T, S: Into<String>>(&self, message: S) -> Result<T, ParseError> {
+        Err(parse_error(self.line(), message))
+    }
+
+    fn bump(&mut self) -> Option<Token> {
+        let tok = self.peek().cloned();
+        if tok.is_some() {
+            self.pos += 1;
+        }
+        tok
+    }
+
+    /// Consume `tok` if it is next.
+    fn eat(&mut self, tok: &Token) -> bool {
+        if self.peek() == Some(tok) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, tok: &Token, what: &str) -> Result<(), ParseError> {
+        if self.eat(tok) {
+            Ok(())
+        } else {
+            self.error(format!("expected {}", what))
+        }
+    }
+
+    /// Return `true` if the next token is the unquoted keyword `kw`.
+    fn at_keyword(&self, kw: &str) -> bool {
+        self.at_keyword_at(0, kw)
+    }
+
+    fn at_keyword_at(&self, offset: usize, kw: &str) -> bool {
+        match self.peek_at(offset) {
+            Some(&Token::Id(ref s, false)) => s.eq_ignore_ascii_case(kw),
+            _ => false,
+        }
+    }
+
+    fn at_id(&self) -> bool {
+        match self.peek() {
+            Some(&Token::Id(..)) => true,
+            _ => false,
+        }
+    }
+
+    fn id(&mut self) -> Result<String, ParseError> {
+        match self.bump() {
+            Some(Token::Id(s, _)) => Ok(s),
+            _ => {
+                self.pos -= 1;
+                self.error("expected an identifier")
+            }
+        }
+    }
+
+    fn parse_graph(&mut self) -> Result<(), ParseError> {
+        if self.at_keyword("strict") {
+            self.pos += 1;
+        }
+        if self.at_keyword("digraph") {
+            self.directed = true;
+        } else if self.at_keyword("graph") {
+            self.directed = false;
+        } else {
+            return self.error("expected 'graph' or 'digraph'");
+        }
+        self.pos += 1;
+        if self.at_id() {
+            try!(self.id());
+        }
+        try!(self.expect(&Token::LBrace, "'{'"));
+        let mut defaults = Defaults::default();
+        try!(self.parse_stmt_list(&mut defaults));
+        try!(self.expect(&Token::RBrace, "'}'"));
+        if self.peek().is_some() {
+            return self.error("unexpected input after the graph");
+        }
+        Ok(())
+    }
+
+    /// Parse statements until a closing brace (not consumed).
+    fn parse_stmt_list(&mut self, defaults: &mut Defaults) -> Result<(), ParseError> {
+        loop {
+            match self.peek() {
+                None | Some(&Token::RBrace) => return Ok(()),
+                _ => {}
+            }
+            try!(self.parse_stmt(defaults));
+            self.eat(&Token::Semi);
+        }
+    }
+
+    fn parse_stmt(&mut self, defaults: &mut Defaults) -> Result<(), ParseError> {
+        let is_attr_stmt = (self.at_keyword("graph") ||
+                            self.at_keyword("node") ||
+                            self.at_keyword("edge")) &&
+            self.peek_at(1) == Some(&Token::LBracket);
+        if is_attr_stmt {
+            let kind = try!(self.id()).to_lowercase();
+            let attrs = try!(self.parse_attr_lists());
+            let label = label_of(&attrs);
+            if kind == "node" && label.is_some() {
+                defaults.node_label = label;
+            } else if kind == "edge" && label.is_some() {
+                defaults.edge_label = label;
+            }
+            return Ok(());
+        }
+        // graph attribute: ID '=' ID
+        if self.at_id() && self.peek_at(1) == Some(&Token::Equal) {
+            try!(self.id());
+            self.pos += 1;
+            try!(self.id());
+            return Ok(());
+        }
+        let first = try!(self.parse_operand(defaults));
+        if let Some(&Token::EdgeOp(_)) = self.peek() {
+            let mut operands = vec![first];
+            while let Some(&Token::EdgeOp(directed)) = self.peek() {
+                if directed != self.directed {
+                    return self.error(if self.directed {
+                        "'--' used in a digraph"
+                    } else {
+                        "'->' used in an undirected graph"
+                    });
+                }
+                self.pos += 1;
+                operands.push(try!(self.parse_operand(defaults)));
+            }
+            let attrs = if self.peek() == Some(&Token::LBracket) {
+                try!(self.parse_attr_lists())
+            } else {
+                Vec::new()
+            };
+            let label = label_of(&attrs)
+                .or_else(|| defaults.edge_label.clone())
+                .unwrap_or_default();
+            for pair in operands.windows(2) {
+                for &a in &pair[0] {
+                    for &b in &pair[1] {
+                        self.graph.add_edge(a, b, label.clone());
+                    }
+                }
+            }
+        } else if first.len() == 1 && self.peek() == Some(&Token::LBracket) {
+            // node statement with attributes
+            let attrs = try!(self.parse_attr_lists());
+            if let Some(label) = label_of(&attrs) {
+                self.graph[first[0]] = label;
+            }
+        }
+        Ok(())
+    }
+
+    /// Parse a node id or a subgraph, and return the nodes it stands for.
+    fn parse_operand(&mut self, defaults: &Defaults) -> Result<Vec<NodeIndex>, ParseError> {
+        let is_subgraph = self.at_keyword("subgraph") || self.peek() == Some(&Token::LBrace);
+        if is_subgraph {
+            if self.at_keyword("subgraph") {
+                self.pos += 1;
+                if self.at_id() {
+                    try!(self.id());
+                }
+            }
+            try!(self.expect(&Token::LBrace, "'{'"));
+            if self.subgraphs.len() == MAX_DEPTH {
+                return self.error("subgraphs too deeply nested");
+            }
+            self.subgraphs.push(Vec::new());
+            let mut scope = defaults.clone();
+            try!(self.parse_stmt_list(&mut scope));
+            try!(self.expect(&Token::RBrace, "'}'"));
+            return Ok(self.subgraphs.pop().unwrap_or_default());
+        }
+        let name = try!(self.id());
+        // optional port and compass point, ignored
+        if self.eat(&Token::Colon) {
+            try!(self.id());
+            if self.eat(&Token::Colon) {
+                try!(self.id());
+            }
+        }
+        Ok(vec![self.node(name, defaults)])
+    }
+
+    /// Return the node for `name`, adding it if it is new.
+    ///
+    /// The node is recorded as a member of all enclosing subgraphs.
+    fn node(&mut self, name: String, defaults: &Defaults) -> NodeIndex {
+        let ix = match self.nodes.get(&name) {
+            Some(&ix) => ix,
+            None => {
+                let weight = defaults.node_label.clone().unwrap_or_else(|| name.clone());
+                let ix = self.graph.add_node(weight);
+                self.nodes.insert(name, ix);
+                ix
+            }
+        };
+        for members in &mut self.subgraphs {
+            if !members.contains(&ix) {
+                members.push(ix);
+            }
+        }
+        ix
+    }
+
+    /// Parse one or more bracketed attribute lists.
+    fn parse_attr_lists(&mut self) -> Result<Vec<(String, String)>, ParseError> {
+        let mut attrs = Vec::new();
+        while self.eat(&Token::LBracket) {
+            while !self.eat(&Token::RBracket) {
+                let name = try!(self.id());
+                let value = if self.eat(&Token::Equal) {
+                    try!(self.id())
+                } else {
+                    "true".to_string()
+                };
+                attrs.push((name, value));
+                if !self.eat(&Token::Comma) {
+                    self.eat(&Token::Semi);
+                }
+            }
+        }
+        Ok(attrs)
+    }
+}
+
+fn label_of(attrs: &[(String, String)]) -> Option<String> {
+    attrs.iter().rev()
+         .find(|&&(ref name, _)| name == "label")
+         .map(|&(_, ref value)| value.clone())
+}
+
+fn parse_error<S: Into<String>>(line: usize, message: S) -> ParseError {
+    ParseError::new("dot", line, message)
+}
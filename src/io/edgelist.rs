@@ -0,0 +1,158 @@
+//! ***Unstable.*** Edge list input and output.
+//!
+//! ***Unstable: API may change at any time.*** Depends on `feature = "edgelist"`.
+//!
+//! An edge list has one edge per line: the source node label, the target
+//! node label, and optionally weight columns, separated by a delimiter, for
+//! example `,` for CSV or `\t` for TSV files.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+use {
+    EdgeType,
+    Graph,
+};
+use graph::IndexType;
+pub use super::{
+    ParseError,
+    ReadError,
+};
+
+/// Read a graph from an edge list, with columns separated by `delimiter`.
+///
+/// ***Unstable: API may change at any time.*** Depends on `feature = "edgelist"`.
+///
+/// The node labels are parsed as `N`, for example `u32` for integer labels
+/// or `String` for any labels, and a node is created for each distinct
+/// label, in the order of first appearance. The rest of the line after
+/// the target label, if any, is parsed as the edge weight `E`; without it
+/// the weight is `E::default()`. Use `String` as `E` to keep several
+/// weight columns as they are.
+///
+/// Fields are trimmed of whitespace, and empty lines and lines that start
+/// with `#` or `%` are skipped.
+///
+/// The result is always a directed graph; use
+/// `.into_edge_type::<Undirected>()` to convert it.
+///
+/// ```
+/// use petgraph::io::edgelist::read_edge_list;
+///
+/// let input = "# source,target,weight
+/// 1,2,0.5
+/// 2,3
+/// 10,1,2.0
+/// ";
+/// let g = read_edge_list::<u32, f64, _>(input.as_bytes(), ',').unwrap();
+///
+/// assert_eq!(g.node_count(), 4);
+/// let edges = g.raw_edges().iter()
+///              .map(|e| (g[e.source()], g[e.target()], e.weight))
+///              .collect::<Vec<_>>();
+/// assert_eq!(edges, [(1, 2, 0.5), (2, 3, 0.), (10, 1, 2.)]);
+/// ```
+pub fn read_edge_list<N, E, R>(reader: R, delimiter: char) -> Result<Graph<N, E>, ReadError>
+    where N: FromStr + Hash + Eq + Clone,
+          E: FromStr + Default,
+          R: BufRead,
+{
+    let mut graph = Graph::new();
+    let mut nodes = HashMap::new();
+    for (i, line) in reader.lines().enumerate() {
+        let line = try!(line);
+        let line_number = i + 1;
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with('%') {
+            continue;
+        }
+        let mut fields = trimmed.splitn(3, delimiter);
+        let mut endpoints = Vec::with_capacity(2);
+        for _ in 0..2 {
+            let field = match fields.next() {
+                Some(field) => field.trim(),
+                None => {
+                    return Err(parse_error(line_number, "expected a source and a target").into());
+                }
+            };
+            let label = match field.parse::<N>() {
+                Ok(label) => label,
+                Err(_) => {
+                    return Err(parse_error(line_number,
+                                               format!("invalid node label '{}'", field)).into());
+                }
+            };
+            let index = match nodes.get(&label) {
+                Some(&index) => index,
+                None => {
+                    let index = graph.add_node(label.clone());
+                    nodes.insert(label, index);
+                    index
+                }
+            };
+            endpoints.push(index);
+        }
+        let weight = match fields.next().map(str::trim) {
+            None | Some("") => E::default(),
+            Some(field) => match field.parse() {
+                Ok(weight) => weight,
+                Err(_) => {
+                    return Err(parse_error(line_number,
+                                               format!("invalid edge weight '{}'", field)).into());
+                }
+            },
+        };
+        graph.add_edge(endpoints[0], endpoints[1], weight);
+    }
+    Ok(graph)
+}
+
+/// Write `graph` as an edge list, with columns separated by `delimiter`.
+///
+/// ***Unstable: API may change at any time.*** Depends on `feature = "edgelist"`.
+///
+/// Each edge is written as a line with the source and target node weights,
+/// and the edge weight, if it does not format as an empty string. Nodes
+/// without edges are not written.
+///
+/// The output is only read back as the same graph if the node weights
+/// format uniquely, and no weights contain the delimiter or a newline.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::io::edgelist::write_edge_list;
+///
+/// let mut graph = Graph::<&str, f64>::new();
+/// let a = graph.add_node("a");
+/// let b = graph.add_node("b");
+/// graph.add_edge(a, b, 0.5);
+///
+/// let mut output = Vec::new();
+/// write_edge_list(&mut output, &graph, '\t').unwrap();
+/// assert_eq!(output, b"a\tb\t0.5\n");
+/// ```
+pub fn write_edge_list<W, N, E, Ty, Ix>(mut writer: W, graph: &Graph<N, E, Ty, Ix>,
+                                        delimiter: char) -> io::Result<()>
+    where W: Write,
+          N: fmt::Display,
+          E: fmt::Display,
+          Ty: EdgeType,
+          Ix: IndexType,
+{
+    for edge in graph.raw_edges() {
+        try!(write!(writer, "{}{}{}", graph[edge.source()], delimiter, graph[edge.target()]));
+        let weight = edge.weight.to_string();
+        if !weight.is_empty() {
+            try!(write!(writer, "{}{}", delimiter, weight));
+        }
+        try!(writeln!(writer, ""));
+    }
+    Ok(())
+}
+
+fn parse_error<S: Into<String>>(line: usize, message: S) -> ParseError {
+    ParseError::new("edge list", line, message)
+}
@@ -0,0 +1,425 @@
+//! ***Unstable.*** GML file format input and output.
+//!
+//! ***Unstable: API may change at any time.*** Depends on `feature = "gml"`.
+//!
+//! [GML](https://en.wikipedia.org/wiki/Graph_Modelling_Language) is a
+//! simple text format that is read and written by, for example, networkx
+//! and Cytoscape.
+
+use std::collections::HashMap;
+use std::fmt::{self, Display, Write};
+use std::iter::Peekable;
+use std::str::Chars;
+
+use {
+    EdgeType,
+    Graph,
+};
+use graph::{
+    IndexType,
+    NodeIndex,
+};
+use super::DebugFmt;
+pub use super::ParseError;
+use super::MAX_DEPTH;
+
+/// `Gml` implements output to GML format for a graph.
+///
+/// ***Unstable: API may change at any time.*** Depends on `feature = "gml"`.
+///
+/// Each node is written with its index as `id` and its weight as `label`,
+/// and each edge with its weight as `label`. Use `{}` to format the
+/// weights with `Display` and `{:?}` to format them with `Debug`.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::io::gml::Gml;
+///
+/// let mut graph = Graph::<&str, u32>::new();
+/// let a = graph.add_node("a");
+/// let b = graph.add_node("b");
+/// graph.add_edge(a, b, 7);
+///
+/// assert_eq!(Gml::new(&graph).to_string(),
+/// r#"graph [
+///   directed 1
+///   node [
+///     id 0
+///     label "a"
+///   ]
+///   node [
+///     id 1
+///     label "b"
+///   ]
+///   edge [
+///     source 0
+///     target 1
+///     label "7"
+///   ]
+/// ]
+/// "#);
+/// ```
+pub struct Gml<'a, N: 'a, E: 'a, Ty: 'a, Ix: 'a + IndexType> {
+    graph: &'a Graph<N, E, Ty, Ix>,
+}
+
+impl<'a, N, E, Ty, Ix> Gml<'a, N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    /// Create a `Gml` formatting wrapper.
+    pub fn new(graph: &'a Graph<N, E, Ty, Ix>) -> Self {
+        Gml {
+            graph: graph,
+        }
+    }
+
+    fn graph_fmt<F, G>(&self, f: &mut fmt::Formatter,
+                       mut node_fmt: F, mut edge_fmt: G) -> fmt::Result
+        where F: FnMut(&N, &mut FnMut(&Display) -> fmt::Result) -> fmt::Result,
+              G: FnMut(&E, &mut FnMut(&Display) -> fmt::Result) -> fmt::Result,
+    {
+        let g = self.graph;
+        try!(writeln!(f, "graph ["));
+        try!(writeln!(f, "  directed {}", g.is_directed() as u8));
+        for index in g.node_indices() {
+            try!(writeln!(f, "  node ["));
+            try!(writeln!(f, "    id {}", index.index()));
+            try!(write!(f, "    label \""));
+            try!(node_fmt(&g[index], &mut |d| write!(&mut Escaper(&mut *f), "{}", d)));
+            try!(writeln!(f, "\""));
+            try!(writeln!(f, "  ]"));
+        }
+        for edge in g.raw_edges() {
+            try!(writeln!(f, "  edge ["));
+            try!(writeln!(f, "    source {}", edge.source().index()));
+            try!(writeln!(f, "    target {}", edge.target().index()));
+            try!(write!(f, "    label \""));
+            try!(edge_fmt(&edge.weight, &mut |d| write!(&mut Escaper(&mut *f), "{}", d)));
+            try!(writeln!(f, "\""));
+            try!(writeln!(f, "  ]"));
+        }
+        writeln!(f, "]")
+    }
+}
+
+impl<'a, N, E, Ty, Ix> fmt::Display for Gml<'a, N, E, Ty, Ix>
+    where N: fmt::Display,
+          E: fmt::Display,
+          Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.graph_fmt(f, |n, cb| cb(n), |e, cb| cb(e))
+    }
+}
+
+impl<'a, N, E, Ty, Ix> fmt::Debug for Gml<'a, N, E, Ty, Ix>
+    where N: fmt::Debug,
+          E: fmt::Debug,
+          Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.graph_fmt(f,
+                       |n, cb| cb(&DebugFmt(n)),
+                       |e, cb| cb(&DebugFmt(e)))
+    }
+}
+
+/// Escape for GML strings, which use character entities
+struct Escaper<W>(W);
+
+impl<W> fmt::Write for Escaper<W>
+    where W: fmt::Write
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            try!(self.write_char(c));
+        }
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        match c {
+            '&' => self.0.write_str("&amp;"),
+            '"' => self.0.write_str("&quot;"),
+            _ => self.0.write_char(c),
+        }
+    }
+}
+
+/// Parse a graph in GML format.
+///
+/// ***Unstable: API may change at any time.*** Depends on `feature = "gml"`.
+///
+/// The first `graph` list of the input is read; other top level keys are
+/// ignored. Each `node` becomes a node, with its `label` as weight, or its
+/// `id` if it has no label. Each `edge` becomes an edge from `source` to
+/// `target`, with its `label` as weight, or an empty string. Nodes are
+/// added in the order of appearance and edges in the order of appearance;
+/// other keys are ignored.
+///
+/// The result is always a directed graph. For an undirected input
+/// (`directed 0`, the default), each edge is added from its source to its
+/// target; use `.into_edge_type::<Undirected>()` to convert the result.
+///
+/// ```
+/// use petgraph::io::gml::parse;
+///
+/// let g = parse(r#"
+/// graph [
+///   node [ id 1 label "a" ]
+///   node [ id 2 ]
+///   edge [ source 1 target 2 weight 0.5 ]
+/// ]"#).unwrap();
+///
+/// assert_eq!(g.node_count(), 2);
+/// assert_eq!(g.edge_count(), 1);
+/// let names = g.node_indices().map(|i| &g[i][..]).collect::<Vec<_>>();
+/// assert_eq!(names, ["a", "2"]);
+/// ```
+pub fn parse(input: &str) -> Result<Graph<String, String>, ParseError> {
+    let mut reader = Reader {
+        chars: input.chars().peekable(),
+        line: 1,
+        depth: 0,
+    };
+    let items = try!(reader.list(false));
+    let graph_items = match items.into_iter().find(|item| item.key == "graph") {
+        Some(Item { value: Value::List(items), .. }) => items,
+        Some(item) => return Err(parse_error(item.line, "expected a list for 'graph'")),
+        None => return Err(parse_error(reader.line, "no graph found")),
+    };
+
+    let mut graph = Graph::new();
+    let mut nodes = HashMap::new();
+    let mut edges = Vec::new();
+    for item in graph_items {
+        let line = item.line;
+        match &item.key[..] {
+            "node" => {
+                let items = try!(item.into_list());
+                let id = try!(required(&items, "id", line));
+                let label = find(&items, "label").unwrap_or(id).to_string();
+                if nodes.contains_key(id) {
+                    return Err(parse_error(line, format!("duplicate node id {}", id)));
+                }
+                nodes.insert(id.to_string(), graph.add_node(label));
+            }
+            // edges may refer to nodes that come later
+            "edge" => edges.push((line, try!(item.into_list()))),
+            _ => {}
+        }
+    }
+    for (line, items) in edges {
+        let a = try!(node_of(&nodes, try!(required(&items, "source", line)), line));
+        let b = try!(node_of(&nodes, try!(required(&items, "target", line)), line));
+        let label = find(&items, "label").unwrap_or("").to_string();
+        graph.add_edge(a, b, label);
+    }
+    Ok(graph)
+}
+
+struct Item {
+    key: String,
+    value: Value,
+    line: usize,
+}
+
+enum Value {
+    /// A number or a string, with entities decoded.
+    Scalar(String),
+    List(Vec<Item>),
+}
+
+impl Item {
+    fn into_list(self) -> Result<Vec<Item>, ParseError> {
+        match self.value {
+            Value::List(items) => Ok(items),
+            Value::Scalar(_) => {
+                Err(parse_error(self.line, format!("expected a list for '{}'", self.key)))
+            }
+        }
+    }
+}
+
+fn find<'a>(items: &'a [Item], key: &str) -> Option<&'a str> {
+    items.iter().filter(|item| item.key == key).filter_map(|item| {
+        match item.value {
+            Value::Scalar(ref s) => Some(&s[..]),
+            Value::List(_) => None,
+        }
+    }).next()
+}
+
+fn required<'a>(items: &'a [Item], key: &str, line: usize) -> Result<&'a str, ParseError> {
+    find(items, key).ok_or_else(|| parse_error(line, format!("missing '{}'", key)))
+}
+
+fn node_of(nodes: &HashMap<String, NodeIndex>, id: &str, line: usize)
+    -> Result<NodeIndex, ParseError>
+{
+    nodes.get(id).cloned().ok_or_else(|| {
+        parse_error(line, format!("no node with id {}", id))
+    })
+}
+
+struct Reader<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    /// The number of enclosing lists.
+    depth: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next();
+        if c == Some('\n') {
+            self.line += 1;
+        }
+        c
+    }
+
+    fn error<T, S: Into<String>>(&self, message: S) -> Result<T, ParseError> {
+        Err(parse_error(self.line, message))
+    }
+
+    /// Skip whitespace and comment lines.
+    fn skip_whitespace(&mut self) {
+        while let Some(&c) = self.chars.peek() {
+            if c == '#' {
+                while self.chars.peek().map_or(false, |&c| c != '\n') {
+                    self.bump();
+                }
+            } else if c.is_whitespace() {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Read key value pairs until the end of a list (if `nested`) or the end
+    /// of the input.
+    fn list(&mut self, nested: bool) -> Result<Vec<Item>, ParseError> {
+        let mut items = Vec::new();
+        loop {
+            self.skip_whitespace();
+            match self.chars.peek() {
+                None if nested => return self.error("unterminated list"),
+                None => return Ok(items),
+                Some(&']') if nested => {
+                    self.bump();
+                    return Ok(items);
+                }
+                _ => {}
+            }
+            let line = self.line;
+            let key = try!(self.key());
+            self.skip_whitespace();
+            let value = try!(self.value());
+            items.push(Item {
+                key: key,
+                value: value,
+                line: line,
+            });
+        }
+    }
+
+    fn key(&mut self) -> Result<String, ParseError> {
+        let mut s = String::new();
+        while let Some(&c) = self.chars.peek() {
+            if !(c.is_alphanumeric() || c == '_') {
+                break;
+            }
+            s.push(c);
+            self.bump();
+        }
+        if s.is_empty() || s.starts_with(|c: char| c.is_digit(10)) {
+            return self.error("expected a key");
+        }
+        Ok(s)
+    }
+
+    fn value(&mut self) -> Result<Value, ParseError> {
+        match self.chars.peek() {
+            Some(&'[') => {
+                if self.depth == MAX_DEPTH {
+                    return self.error("too deeply nested");
+                }
+                self.bump();
+                self.depth += 1;
+                let list = try!(self.list(true));
+                self.depth -= 1;
+                Ok(Value::List(list))
+            }
+            Some(&'"') => {
+                self.bump();
+                let mut s = String::new();
+                loop {
+                    match self.bump() {
+                        None => return self.error("unterminated string"),
+                        Some('"') => return Ok(Value::Scalar(s)),
+                        Some('&') => s.push_str(&try!(self.entity())),
+                        Some(c) => s.push(c),
+                    }
+                }
+            }
+            _ => {
+                let mut s = String::new();
+                while let Some(&c) = self.chars.peek() {
+                    if !(c.is_digit(10) || c == '-' || c == '+' || c == '.' ||
+                         c == 'e' || c == 'E') {
+                        break;
+                    }
+                    s.push(c);
+                    self.bump();
+                }
+                if s.is_empty() {
+                    return self.error("expected a value");
+                }
+                Ok(Value::Scalar(s))
+            }
+        }
+    }
+
+    /// Decode a character entity; the `&` is already read. Unknown entities
+    /// are kept as is.
+    fn entity(&mut self) -> Result<String, ParseError> {
+        let mut name = String::new();
+        while let Some(&c) = self.chars.peek() {
+            if !(c.is_alphanumeric() || c == '#') {
+                break;
+            }
+            name.push(c);
+            self.bump();
+        }
+        if self.chars.peek() != Some(&';') {
+            return Ok(format!("&{}", name));
+        }
+        self.bump();
+        let c = match &name[..] {
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "apos" => Some('\''),
+            _ if name.starts_with("#x") => {
+                u32::from_str_radix(&name[2..], 16).ok().and_then(::std::char::from_u32)
+            }
+            _ if name.starts_with('#') => {
+                name[1..].parse().ok().and_then(::std::char::from_u32)
+            }
+            _ => None,
+        };
+        Ok(match c {
+            Some(c) => c.to_string(),
+            None => format!("&{};", name),
+        })
+    }
+}
+
+fn parse_error<S: Into<String>>(line: usize, message: S) -> ParseError {
+    ParseError::new("gml", line, message)
+}
@@ -0,0 +1,325 @@
+//! ***Unstable.*** graph6 and sparse6 format input and output.
+//!
+//! ***Unstable: API may change at any time.*** Depends on `feature = "graph6"`.
+//!
+//! [graph6 and sparse6](http://users.cecs.anu.edu.au/~bdm/data/formats.txt)
+//! are compact printable encodings of undirected graphs, one graph per line,
+//! used by nauty and by collections of small graphs. graph6 is best for
+//! dense graphs; sparse6 is best for sparse graphs and also allows loops
+//! and parallel edges.
+
+use {
+    Graph,
+    Undirected,
+};
+use graph::{
+    DefIndex,
+    IndexType,
+};
+pub use super::ParseError;
+use super::MAX_UNLISTED_NODES;
+
+/// The largest number of nodes that the formats can encode.
+const MAX_NODES: u64 = (1 << 36) - 1;
+
+/// Encode `graph` in graph6 format, without a header or a newline.
+///
+/// ***Unstable: API may change at any time.*** Depends on `feature = "graph6"`.
+///
+/// graph6 encodes simple graphs only: loops are left out and parallel edges
+/// are written once.
+///
+/// **Panics** if the graph has more than 2<sup>36</sup> - 1 nodes.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::io::graph6::to_graph6;
+///
+/// let mut graph = Graph::new_undirected();
+/// let a = graph.add_node(());
+/// let b = graph.add_node(());
+/// let c = graph.add_node(());
+/// graph.add_edge(a, b, ());
+/// graph.add_edge(b, c, ());
+///
+/// assert_eq!(to_graph6(&graph), "Bg");
+/// ```
+pub fn to_graph6<N, E, Ix>(graph: &Graph<N, E, Undirected, Ix>) -> String
+    where Ix: IndexType,
+{
+    let n = graph.node_count();
+    let mut s = String::new();
+    encode_size(&mut s, n);
+    // the upper triangle of the adjacency matrix, column by column
+    let mut bits = vec![false; n * n.saturating_sub(1) / 2];
+    for edge in graph.raw_edges() {
+        let (i, j) = (edge.source().index(), edge.target().index());
+        let (i, j) = if i < j { (i, j) } else { (j, i) };
+        if i != j {
+            bits[j * (j - 1) / 2 + i] = true;
+        }
+    }
+    encode_bits(&mut s, &bits);
+    s
+}
+
+/// Encode `graph` in sparse6 format, without a header or a newline.
+///
+/// ***Unstable: API may change at any time.*** Depends on `feature = "graph6"`.
+///
+/// **Panics** if the graph has more than 2<sup>36</sup> - 1 nodes.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::io::graph6::to_sparse6;
+///
+/// let mut graph = Graph::new_undirected();
+/// let a = graph.add_node(());
+/// let b = graph.add_node(());
+/// let c = graph.add_node(());
+/// graph.add_edge(a, b, ());
+/// graph.add_edge(b, c, ());
+///
+/// assert_eq!(to_sparse6(&graph), ":Bd");
+/// ```
+pub fn to_sparse6<N, E, Ix>(graph: &Graph<N, E, Undirected, Ix>) -> String
+    where Ix: IndexType,
+{
+    let n = graph.node_count();
+    let mut s = ":".to_string();
+    encode_size(&mut s, n);
+    let k = sparse6_width(n);
+
+    let mut edges = graph.raw_edges().iter().map(|edge| {
+        let (u, v) = (edge.source().index(), edge.target().index());
+        if u < v { (v, u) } else { (u, v) }
+    }).collect::<Vec<_>>();
+    edges.sort();
+
+    let mut bits = Vec::new();
+    let push = |bits: &mut Vec<bool>, x: usize| {
+        for i in (0..k).rev() {
+            bits.push(x >> i & 1 == 1);
+        }
+    };
+    let mut current = 0;
+    for (v, u) in edges {
+        if v == current {
+            bits.push(false);
+        } else if v == current + 1 {
+            current = v;
+            bits.push(true);
+        } else {
+            current = v;
+            bits.push(true);
+            push(&mut bits, v);
+            bits.push(false);
+        }
+        push(&mut bits, u);
+    }
+    // Pad with ones, unless that would read as an edge to the last node.
+    let padding = (6 - bits.len() % 6) % 6;
+    if k < 6 && n == 1 << k && padding >= k && current < n - 1 {
+        bits.push(false);
+    }
+    while bits.len() % 6 != 0 {
+        bits.push(true);
+    }
+    encode_bits(&mut s, &bits);
+    s
+}
+
+/// Decode a graph in graph6 format.
+///
+/// ***Unstable: API may change at any time.*** Depends on `feature = "graph6"`.
+///
+/// An optional `>>graph6<<` header and surrounding whitespace are ignored.
+/// Edges are added in column order of the adjacency matrix.
+///
+/// ```
+/// use petgraph::io::graph6::from_graph6;
+///
+/// let g = from_graph6("Bg").unwrap();
+/// assert_eq!(g.node_count(), 3);
+/// assert_eq!(g.edge_count(), 2);
+/// ```
+pub fn from_graph6(input: &str) -> Result<Graph<(), (), Undirected>, ParseError> {
+    let input = input.trim();
+    let input = if input.starts_with(">>graph6<<") { &input[10..] } else { input };
+    let data = try!(decode_bytes(input));
+    let (n, rest) = try!(decode_size(&data));
+    try!(check_node_count(n));
+    // n nodes need n (n - 1) / 2 bits, which is checked before allocating
+    let expected = match n.checked_mul(n.saturating_sub(1)) {
+        None => return Err(parse_error("too many nodes for the edge data")),
+        Some(bits) => (bits / 2 + 5) / 6,
+    };
+    if rest.len() != expected {
+        return Err(parse_error(format!("expected {} bytes of edge data, found {}",
+                                           expected, rest.len())));
+    }
+    let mut graph = Graph::with_capacity(n, 0);
+    for _ in 0..n {
+        graph.add_node(());
+    }
+    let nodes = graph.node_indices().collect::<Vec<_>>();
+    let mut bit = 0;
+    for j in 1..n {
+        for i in 0..j {
+            if rest[bit / 6] >> (5 - bit % 6) & 1 == 1 {
+                graph.add_edge(nodes[i], nodes[j], ());
+            }
+            bit += 1;
+        }
+    }
+    Ok(graph)
+}
+
+/// Decode a graph in sparse6 format.
+///
+/// ***Unstable: API may change at any time.*** Depends on `feature = "graph6"`.
+///
+/// An optional `>>sparse6<<` header and surrounding whitespace are ignored.
+/// Edges are added in the order they are encoded.
+///
+/// ```
+/// use petgraph::io::graph6::from_sparse6;
+///
+/// let g = from_sparse6(":Bd").unwrap();
+/// assert_eq!(g.node_count(), 3);
+/// assert_eq!(g.edge_count(), 2);
+/// ```
+pub fn from_sparse6(input: &str) -> Result<Graph<(), (), Undirected>, ParseError> {
+    let input = input.trim();
+    let input = if input.starts_with(">>sparse6<<") { &input[11..] } else { input };
+    if !input.starts_with(':') {
+        return Err(parse_error("sparse6 data must start with ':'"));
+    }
+    let data = try!(decode_bytes(&input[1..]));
+    let (n, rest) = try!(decode_size(&data));
+    try!(check_node_count(n));
+    // the header is not trusted to size the allocation; each bit of edge
+    // data reaches at most one more node
+    if n > rest.len().saturating_mul(6).saturating_add(MAX_UNLISTED_NODES) {
+        return Err(parse_error(format!("{} nodes are too many for {} bytes of edge data",
+                                           n, rest.len())));
+    }
+    let k = sparse6_width(n);
+
+    let mut graph = Graph::with_capacity(n, 0);
+    for _ in 0..n {
+        graph.add_node(());
+    }
+    let nodes = graph.node_indices().collect::<Vec<_>>();
+    let mut bits = rest.iter().flat_map(|&byte| (0..6).rev().map(move |i| byte >> i & 1 == 1));
+    let mut v = 0;
+    loop {
+        let b = match bits.next() {
+            Some(b) => b,
+            None => break,
+        };
+        let mut x = 0;
+        for _ in 0..k {
+            match bits.next() {
+                Some(bit) => x = x << 1 | bit as usize,
+                // the rest is padding
+                None => return Ok(graph),
+            }
+        }
+        if b {
+            v += 1;
+        }
+        if x >= n || v >= n {
+            break;
+        } else if x > v {
+            v = x;
+        } else {
+            graph.add_edge(nodes[x], nodes[v], ());
+        }
+    }
+    Ok(graph)
+}
+
+/// Check that `n` nodes fit in the node indices of the decoded graph.
+fn check_node_count(n: usize) -> Result<(), ParseError> {
+    let max = <DefIndex as IndexType>::max().index();
+    if max != !0 && n > max {
+        return Err(parse_error(format!("{} nodes do not fit in the node index type", n)));
+    }
+    Ok(())
+}
+
+/// Return the number of bits that sparse6 uses for a node number.
+fn sparse6_width(n: usize) -> usize {
+    let mut k = 1;
+    while 1 << k < n {
+        k += 1;
+    }
+    k
+}
+
+fn encode_size(s: &mut String, n: usize) {
+    let n = n as u64;
+    assert!(n <= MAX_NODES, "graph6: too many nodes to encode");
+    let width = if n <= 62 {
+        s.push((n as u8 + 63) as char);
+        return;
+    } else if n <= 258047 {
+        s.push('~');
+        3
+    } else {
+        s.push_str("~~");
+        6
+    };
+    for i in (0..width).rev() {
+        s.push(((n >> (6 * i) & 0x3f) as u8 + 63) as char);
+    }
+}
+
+fn encode_bits(s: &mut String, bits: &[bool]) {
+    for chunk in bits.chunks(6) {
+        let mut byte = 0;
+        for i in 0..6 {
+            byte = byte << 1 | chunk.get(i).map_or(0, |&b| b as u8);
+        }
+        s.push((byte + 63) as char);
+    }
+}
+
+/// Check that the input is printable data and subtract the offset 63.
+fn decode_bytes(input: &str) -> Result<Vec<u8>, ParseError> {
+    input.bytes().map(|byte| {
+        if byte >= 63 && byte <= 126 {
+            Ok(byte - 63)
+        } else {
+            Err(parse_error(format!("invalid character {:?}", byte as char)))
+        }
+    }).collect()
+}
+
+/// Decode the number of nodes, and return it and the rest of the data.
+fn decode_size(data: &[u8]) -> Result<(usize, &[u8]), ParseError> {
+    let width = match data.first() {
+        None => return Err(parse_error("missing number of nodes")),
+        Some(&n) if n != 63 => return Ok((n as usize, &data[1..])),
+        Some(_) if data.get(1) == Some(&63) => 6,
+        Some(_) => 3,
+    };
+    let start = if width == 3 { 1 } else { 2 };
+    if data.len() < start + width {
+        return Err(parse_error("truncated number of nodes"));
+    }
+    let mut n = 0u64;
+    for &byte in &data[start..start + width] {
+        n = n << 6 | byte as u64;
+    }
+    if n > ::std::usize::MAX as u64 {
+        return Err(parse_error("too many nodes"));
+    }
+    Ok((n as usize, &data[start + width..]))
+}
+
+/// Return a parse error; a graph6 or sparse6 string is a single line.
+fn parse_error<S: Into<String>>(message: S) -> ParseError {
+    ParseError::new("graph6", 1, message)
+}
@@ -0,0 +1,638 @@
+//! ***Unstable.*** GraphML file format input and output.
+//!
+//! ***Unstable: API may change at any time.*** Depends on `feature = "graphml"`.
+//!
+//! [GraphML](http://graphml.graphdrawing.org/) is an XML based format for
+//! graphs that is understood by many graph tools, for example Gephi and yEd.
+
+use std::collections::HashMap;
+use std::fmt::{self, Write};
+use std::iter::Peekable;
+use std::str::Chars;
+
+use {
+    Directed,
+    EdgeType,
+    Graph,
+};
+use graph::{
+    EdgeIndex,
+    IndexType,
+    NodeIndex,
+};
+pub use super::ParseError;
+
+/// A typed attribute value for GraphML output.
+///
+/// The variant decides the `attr.type` of the GraphML key that the value
+/// is written with.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AttrValue {
+    Boolean(bool),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    String(String),
+}
+
+impl AttrValue {
+    fn type_name(&self) -> &'static str {
+        match *self {
+            AttrValue::Boolean(_) => "boolean",
+            AttrValue::Int(_) => "int",
+            AttrValue::Long(_) => "long",
+            AttrValue::Float(_) => "float",
+            AttrValue::Double(_) => "double",
+            AttrValue::String(_) => "string",
+        }
+    }
+}
+
+impl fmt::Display for AttrValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            AttrValue::Boolean(x) => x.fmt(f),
+            AttrValue::Int(x) => x.fmt(f),
+            AttrValue::Long(x) => x.fmt(f),
+            AttrValue::Float(x) => x.fmt(f),
+            AttrValue::Double(x) => x.fmt(f),
+            AttrValue::String(ref x) => x.fmt(f),
+        }
+    }
+}
+
+/// The attributes of a node or an edge, as *(name, value)* pairs.
+pub type Attributes = Vec<(String, AttrValue)>;
+
+/// `GraphMl` implements output to GraphML format for a graph.
+///
+/// ***Unstable: API may change at any time.*** Depends on `feature = "graphml"`.
+///
+/// By default only the graph structure is written. Use
+/// `.export_node_weights()` and `.export_edge_weights()` to write data for
+/// nodes and edges; every attribute name becomes a GraphML key, typed by
+/// the first value that is seen for it.
+///
+/// Nodes are given the ids `n0`, `n1`, ... and edges `e0`, `e1`, ...
+/// after their indices.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::io::graphml::{GraphMl, AttrValue};
+///
+/// let mut graph = Graph::<&str, f64>::new();
+/// let a = graph.add_node("a");
+/// let b = graph.add_node("b");
+/// graph.add_edge(a, b, 1.5);
+///
+/// let graphml = GraphMl::new(&graph)
+///     .export_node_weights(|n| vec![("name".to_string(), AttrValue::String(n.to_string()))])
+///     .export_edge_weights(|&e| vec![("weight".to_string(), AttrValue::Double(e))]);
+/// let output = graphml.to_string();
+/// assert!(output.contains(r#"<key id="d0" for="node" attr.name="name" attr.type="string"/>"#));
+/// assert!(output.contains(r#"<data key="d1">1.5</data>"#));
+/// ```
+pub struct GraphMl<'a, N: 'a, E: 'a, Ty: 'a, Ix: 'a + IndexType> {
+    graph: &'a Graph<N, E, Ty, Ix>,
+    export_nodes: Option<Box<Fn(&N) -> Attributes + 'a>>,
+    export_edges: Option<Box<Fn(&E) -> Attributes + 'a>>,
+}
+
+impl<'a, N, E, Ty, Ix> GraphMl<'a, N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    /// Create a `GraphMl` formatting wrapper that writes the graph structure.
+    pub fn new(graph: &'a Graph<N, E, Ty, Ix>) -> Self {
+        GraphMl {
+            graph: graph,
+            export_nodes: None,
+            export_edges: None,
+        }
+    }
+
+    /// Write the attributes that `f` produces for each node weight.
+    pub fn export_node_weights<F>(mut self, f: F) -> Self
+        where F: Fn(&N) -> Attributes + 'a
+    {
+        self.export_nodes = Some(Box::new(f));
+        self
+    }
+
+    /// Write the attributes that `f` produces for each edge weight.
+    pub fn export_edge_weights<F>(mut self, f: F) -> Self
+        where F: Fn(&E) -> Attributes + 'a
+    {
+        self.export_edges = Some(Box::new(f));
+        self
+    }
+}
+
+/// A GraphML key declaration: the id, and which elements it is for.
+struct Key {
+    id: String,
+    for_: &'static str,
+    name: String,
+    type_name: &'static str,
+}
+
+impl<'a, N, E, Ty, Ix> fmt::Display for GraphMl<'a, N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let g = self.graph;
+        let node_data = g.raw_nodes().iter().map(|node| {
+            self.export_nodes.as_ref().map_or(Vec::new(), |get| get(&node.weight))
+        }).collect::<Vec<_>>();
+        let edge_data = g.raw_edges().iter().map(|edge| {
+            self.export_edges.as_ref().map_or(Vec::new(), |get| get(&edge.weight))
+        }).collect::<Vec<_>>();
+
+        // Keys have to be declared before the graph, so collect them first.
+        let mut keys = Vec::new();
+        let mut node_keys = HashMap::new();
+        let mut edge_keys = HashMap::new();
+        let mut sides = [("node", &node_data, &mut node_keys),
+                         ("edge", &edge_data, &mut edge_keys)];
+        for &mut (for_, data, ref mut key_ids) in sides.iter_mut() {
+            for attrs in data {
+                for &(ref name, ref value) in attrs {
+                    if !key_ids.contains_key(name) {
+                        let id = format!("d{}", keys.len());
+                        key_ids.insert(name.clone(), id.clone());
+                        keys.push(Key {
+                            id: id,
+                            for_: for_,
+                            name: name.clone(),
+                            type_name: value.type_name(),
+                        });
+                    }
+                }
+            }
+        }
+
+        try!(writeln!(f, r#"<?xml version="1.0" encoding="UTF-8"?>"#));
+        try!(writeln!(f, r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#));
+        for key in &keys {
+            try!(write!(f, r#"  <key id="{}" for="{}" attr.name=""#, key.id, key.for_));
+            try!(write!(&mut Escaper(&mut *f), "{}", key.name));
+            try!(writeln!(f, r#"" attr.type="{}"/>"#, key.type_name));
+        }
+        let edgedefault = if g.is_directed() { "directed" } else { "undirected" };
+        try!(writeln!(f, r#"  <graph id="G" edgedefault="{}">"#, edgedefault));
+        for (i, attrs) in node_data.iter().enumerate() {
+            try!(write!(f, r#"    <node id="n{}""#, i));
+            try!(finish_element(f, "node", attrs, &node_keys));
+        }
+        for (i, (edge, attrs)) in g.raw_edges().iter().zip(&edge_data).enumerate() {
+            try!(write!(f, r#"    <edge id="e{}" source="n{}" target="n{}""#,
+                        i, edge.source().index(), edge.target().index()));
+            try!(finish_element(f, "edge", attrs, &edge_keys));
+        }
+        try!(writeln!(f, "  </graph>"));
+        try!(writeln!(f, "</graphml>"));
+        Ok(())
+    }
+}
+
+/// Finish an element's start tag, then write its data elements and its end
+/// tag, if it has any data.
+fn finish_element(f: &mut fmt::Formatter, name: &str, attrs: &Attributes,
+                  key_ids: &HashMap<String, String>) -> fmt::Result
+{
+    if attrs.is_empty() {
+        return writeln!(f, "/>");
+    }
+    try!(writeln!(f, ">"));
+    for &(ref attr_name, ref value) in attrs {
+        try!(write!(f, r#"      <data key="{}">"#, key_ids[attr_name]));
+        try!(write!(&mut Escaper(&mut *f), "{}", value));
+        try!(writeln!(f, "</data>"));
+    }
+    writeln!(f, "    </{}>", name)
+}
+
+/// Escape for XML
+struct Escaper<W>(W);
+
+impl<W> fmt::Write for Escaper<W>
+    where W: fmt::Write
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            try!(self.write_char(c));
+        }
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        match c {
+            '&' => self.0.write_str("&amp;"),
+            '<' => self.0.write_str("&lt;"),
+            '>' => self.0.write_str("&gt;"),
+            '"' => self.0.write_str("&quot;"),
+            '\'' => self.0.write_str("&apos;"),
+            _ => self.0.write_char(c),
+        }
+    }
+}
+
+/// Parse a graph in GraphML format.
+///
+/// ***Unstable: API may change at any time.*** Depends on `feature = "graphml"`.
+///
+/// Each node and edge weight maps the `attr.name` of a key to the text of
+/// its data element (or to the key's default value, if it has no data
+/// element). Data for undeclared keys is stored under the key id.
+/// Nodes are added in the order of their appearance and edges in the order
+/// of appearance; an edge that refers to a node not yet declared adds it.
+/// Nested graphs are flattened, and other elements, such as ports and
+/// hyperedges, are ignored.
+///
+/// The result is always a directed graph. For an undirected input, each
+/// edge is added from its source to its target; use
+/// `.into_edge_type::<Undirected>()` to convert the result.
+///
+/// ```
+/// use petgraph::io::graphml::parse;
+///
+/// let g = parse(r#"<?xml version="1.0" encoding="UTF-8"?>
+/// <graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+///   <key id="d0" for="node" attr.name="color" attr.type="string">
+///     <default>gray</default>
+///   </key>
+///   <graph id="G" edgedefault="directed">
+///     <node id="a"><data key="d0">red</data></node>
+///     <node id="b"/>
+///     <edge source="a" target="b"/>
+///   </graph>
+/// </graphml>"#).unwrap();
+///
+/// assert_eq!(g.node_count(), 2);
+/// assert_eq!(g.edge_count(), 1);
+/// let colors = g.node_indices().map(|i| &g[i]["color"][..]).collect::<Vec<_>>();
+/// assert_eq!(colors, ["red", "gray"]);
+/// ```
+pub fn parse(input: &str)
+    -> Result<Graph<HashMap<String, String>, HashMap<String, String>>, ParseError>
+{
+    let mut reader = Reader {
+        chars: input.chars().peekable(),
+        line: 1,
+    };
+    let mut graph = Graph::<_, _, Directed>::new();
+    let mut nodes = HashMap::new();
+    let mut keys = HashMap::new();
+    let mut key_defaults = Vec::new();
+    // open elements, with what they are
+    let mut stack: Vec<(String, Element)> = Vec::new();
+    let mut seen_graphml = false;
+
+    while let Some(event) = try!(reader.next_event()) {
+        let line = reader.line;
+        match event {
+            Event::Start(name, attrs, empty) => {
+                let elt = match (stack.last().map(|s| &s.1), &name[..]) {
+                    (None, "graphml") => {
+                        seen_graphml = true;
+                        Element::Other
+                    }
+                    (None, _) => {
+                        return Err(parse_error(line, format!("expected <graphml>, found <{}>", name)));
+                    }
+                    (_, "key") => {
+                        let id = try!(required_attr(&attrs, "id", line));
+                        let for_ = attr(&attrs, "for").unwrap_or("all").to_string();
+                        let attr_name = attr(&attrs, "attr.name").unwrap_or(id).to_string();
+                        keys.insert(id.to_string(), attr_name.clone());
+                        Element::Key(attr_name, for_)
+                    }
+                    (Some(&Element::Key(ref attr_name, ref for_)), "default") => {
+                        Element::Default(attr_name.clone(), for_.clone(), String::new())
+                    }
+                    (_, "node") => {
+                        let id = try!(required_attr(&attrs, "id", line));
+                        Element::Node(node_index(&mut graph, &mut nodes, id))
+                    }
+                    (_, "edge") => {
+                        let source = try!(required_attr(&attrs, "source", line));
+                        let target = try!(required_attr(&attrs, "target", line));
+                        let a = node_index(&mut graph, &mut nodes, source);
+                        let b = node_index(&mut graph, &mut nodes, target);
+                        Element::Edge(graph.add_edge(a, b, HashMap::new()))
+                    }
+                    (Some(&Element::Node(n)), "data") => {
+                        let key = try!(required_attr(&attrs, "key", line));
+                        Element::Data(Owner::Node(n), key.to_string(), String::new())
+                    }
+                    (Some(&Element::Edge(e)), "data") => {
+                        let key = try!(required_attr(&attrs, "key", line));
+                        Element::Data(Owner::Edge(e), key.to_string(), String::new())
+                    }
+                    _ => Element::Other,
+                };
+                if empty {
+                    close_element(elt, &mut graph, &keys, &mut key_defaults);
+                } else {
+                    stack.push((name, elt));
+                }
+            }
+            Event::End(name) => {
+                match stack.pop() {
+                    Some((ref open, _)) if *open != name => {
+                        return Err(parse_error(line, format!("expected </{}>, found </{}>", open, name)));
+                    }
+                    Some((_, elt)) => close_element(elt, &mut graph, &keys, &mut key_defaults),
+                    None => return Err(parse_error(line, format!("unexpected </{}>", name))),
+                }
+            }
+            Event::Text(text) => {
+                // text of nested elements is collected too
+                for &mut (_, ref mut elt) in stack.iter_mut().rev() {
+                    match *elt {
+                        Element::Data(_, _, ref mut s) |
+                        Element::Default(_, _, ref mut s) => {
+                            s.push_str(&text);
+                            break;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+    if let Some(&(ref name, _)) = stack.last() {
+        return Err(parse_error(reader.line, format!("unclosed <{}>", name)));
+    }
+    if !seen_graphml {
+        return Err(parse_error(reader.line, "no graphml element"));
+    }
+
+    for (name, for_, value) in key_defaults {
+        if for_ == "node" || for_ == "all" {
+            for node in graph.node_indices() {
+                graph[node].entry(name.clone()).or_insert_with(|| value.clone());
+            }
+        }
+        if for_ == "edge" || for_ == "all" {
+            for edge in graph.edge_indices() {
+                graph[edge].entry(name.clone()).or_insert_with(|| value.clone());
+            }
+        }
+    }
+    Ok(graph)
+}
+
+type AttrMap = HashMap<String, String>;
+
+/// What an open element is, and the state collected for it.
+enum Element {
+    Key(String, String),
+    /// The default of a key: attribute name, key domain and value.
+    Default(String, String, String),
+    Node(NodeIndex),
+    Edge(EdgeIndex),
+    /// A data element: its owner, key id and text.
+    Data(Owner, String, String),
+    Other,
+}
+
+enum Owner {
+    Node(NodeIndex),
+    Edge(EdgeIndex),
+}
+
+fn close_element(elt: Element,
+                 graph: &mut Graph<AttrMap, AttrMap>,
+                 keys: &HashMap<String, String>,
+                 key_defaults: &mut Vec<(String, String, String)>)
+{
+    match elt {
+        Element::Default(name, for_, value) => key_defaults.push((name, for_, value)),
+        Element::Data(owner, key, value) => {
+            let name = keys.get(&key).cloned().unwrap_or(key);
+            match owner {
+                Owner::Node(n) => graph[n].insert(name, value),
+                Owner::Edge(e) => graph[e].insert(name, value),
+            };
+        }
+        _ => {}
+    }
+}
+
+fn node_index(graph: &mut Graph<AttrMap, AttrMap>,
+              nodes: &mut HashMap<String, NodeIndex>,
+              id: &str) -> NodeIndex
+{
+    if let Some(&n) = nodes.get(id) {
+        return n;
+    }
+    let n = graph.add_node(HashMap::new());
+    nodes.insert(id.to_string(), n);
+    n
+}
+
+fn attr<'a>(attrs: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    attrs.iter().find(|a| a.0 == name).map(|a| &a.1[..])
+}
+
+fn required_attr<'a>(attrs: &'a [(String, String)], name: &str, line: usize)
+    -> Result<&'a str, ParseError>
+{
+    attr(attrs, name).ok_or_else(|| {
+        parse_error(line, format!("missing attribute '{}'", name))
+    })
+}
+
+enum Event {
+    /// A start tag: element name, attributes, and `true` if the element is empty.
+    Start(String, Vec<(String, String)>, bool),
+    End(String),
+    Text(String),
+}
+
+/// A minimal XML reader; declarations, processing instructions and comments
+/// are skipped and namespace prefixes are kept as part of names.
+struct Reader<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next();
+        if c == Some('\n') {
+            self.line += 1;
+        }
+        c
+    }
+
+    fn error<T, S: Into<String>>(&self, message: S) -> Result<T, ParseError> {
+        Err(parse_error(self.line, message))
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.peek().map_or(false, |c| c.is_whitespace()) {
+            self.bump();
+        }
+    }
+
+    /// Skip input until after `end`.
+    fn skip_past(&mut self, end: &str) -> Result<String, ParseError> {
+        let mut s = String::new();
+        while !s.ends_with(end) {
+            match self.bump() {
+                None => return self.error(format!("expected '{}'", end)),
+                Some(c) => s.push(c),
+            }
+        }
+        let len = s.len() - end.len();
+        s.truncate(len);
+        Ok(s)
+    }
+
+    fn name(&mut self) -> Result<String, ParseError> {
+        let mut s = String::new();
+        while let Some(&c) = self.chars.peek() {
+            if c.is_whitespace() || c == '>' || c == '/' || c == '=' {
+                break;
+            }
+            s.push(c);
+            self.bump();
+        }
+        if s.is_empty() {
+            return self.error("expected a name");
+        }
+        Ok(s)
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), ParseError> {
+        match self.bump() {
+            Some(x) if x == c => Ok(()),
+            _ => self.error(format!("expected '{}'", c)),
+        }
+    }
+
+    fn next_event(&mut self) -> Result<Option<Event>, ParseError> {
+        loop {
+            match self.chars.peek() {
+                None => return Ok(None),
+                Some(&'<') => {}
+                Some(_) => {
+                    let mut text = String::new();
+                    while let Some(&c) = self.chars.peek() {
+                        if c == '<' {
+                            break;
+                        }
+                        self.bump();
+                        if c == '&' {
+                            text.push(try!(self.entity()));
+                        } else {
+                            text.push(c);
+                        }
+                    }
+                    return Ok(Some(Event::Text(text)));
+                }
+            }
+            self.bump();
+            match self.chars.peek() {
+                Some(&'?') => {
+                    try!(self.skip_past("?>"));
+                }
+                Some(&'!') => {
+                    self.bump();
+                    if self.chars.peek() == Some(&'-') {
+                        try!(self.skip_past("-->"));
+                    } else if self.chars.peek() == Some(&'[') {
+                        try!(self.expect('['));
+                        for c in "CDATA[".chars() {
+                            try!(self.expect(c));
+                        }
+                        let text = try!(self.skip_past("]]>"));
+                        return Ok(Some(Event::Text(text)));
+                    } else {
+                        try!(self.skip_past(">"));
+                    }
+                }
+                Some(&'/') => {
+                    self.bump();
+                    let name = try!(self.name());
+                    self.skip_whitespace();
+                    try!(self.expect('>'));
+                    return Ok(Some(Event::End(name)));
+                }
+                _ => {
+                    let name = try!(self.name());
+                    let mut attrs = Vec::new();
+                    loop {
+                        self.skip_whitespace();
+                        match self.chars.peek() {
+                            Some(&'>') => {
+                                self.bump();
+                                return Ok(Some(Event::Start(name, attrs, false)));
+                            }
+                            Some(&'/') => {
+                                self.bump();
+                                try!(self.expect('>'));
+                                return Ok(Some(Event::Start(name, attrs, true)));
+                            }
+                            None => return self.error("unexpected end of input"),
+                            _ => {}
+                        }
+                        let attr_name = try!(self.name());
+                        self.skip_whitespace();
+                        try!(self.expect('='));
+                        self.skip_whitespace();
+                        let value = try!(self.attr_value());
+                        attrs.push((attr_name, value));
+                    }
+                }
+            }
+        }
+    }
+
+    fn attr_value(&mut self) -> Result<String, ParseError> {
+        let quote = match self.bump() {
+            Some(c @ '"') | Some(c @ '\'') => c,
+            _ => return self.error("expected a quoted attribute value"),
+        };
+        let mut s = String::new();
+        loop {
+            match self.bump() {
+                None => return self.error("unterminated attribute value"),
+                Some(c) if c == quote => return Ok(s),
+                Some('&') => s.push(try!(self.entity())),
+                Some(c) => s.push(c),
+            }
+        }
+    }
+
+    /// Decode an entity reference; the `&` is already read.
+    fn entity(&mut self) -> Result<char, ParseError> {
+        let name = try!(self.skip_past(";"));
+        let c = match &name[..] {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ if name.starts_with("#x") => {
+                u32::from_str_radix(&name[2..], 16).ok().and_then(char::from_u32)
+            }
+            _ if name.starts_with('#') => {
+                name[1..].parse().ok().and_then(char::from_u32)
+            }
+            _ => None,
+        };
+        match c {
+            Some(c) => Ok(c),
+            None => self.error(format!("unknown entity '&{};'", name)),
+        }
+    }
+}
+
+fn parse_error<S: Into<String>>(line: usize, message: S) -> ParseError {
+    ParseError::new("graphml", line, message)
+}
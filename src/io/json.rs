@@ -0,0 +1,523 @@
+//! ***Unstable.*** JSON node-link format input and output.
+//!
+//! ***Unstable: API may change at any time.*** Depends on `feature = "json"`.
+//!
+//! The node-link format is the JSON layout that d3 force layouts and
+//! networkx's `node_link_data` use: an object with a `nodes` array and a
+//! `links` array, where each link has a `source` and a `target`.
+
+use std::collections::HashMap;
+use std::fmt::{self, Write};
+use std::iter::Peekable;
+use std::str::Chars;
+
+use {
+    EdgeType,
+    Graph,
+};
+use graph::IndexType;
+pub use super::ParseError;
+use super::MAX_DEPTH;
+
+/// A JSON value.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    /// An object, with its members in order.
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// Return the value of the member `key`, if `self` is an object that has it.
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match *self {
+            Json::Object(ref members) => {
+                members.iter().find(|m| m.0 == key).map(|m| &m.1)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Json::Null => f.write_str("null"),
+            Json::Bool(x) => x.fmt(f),
+            Json::Number(x) if x.is_finite() => x.fmt(f),
+            // JSON has no infinities or NaN
+            Json::Number(_) => f.write_str("null"),
+            Json::String(ref s) => write_string(f, s),
+            Json::Array(ref values) => {
+                try!(f.write_str("["));
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        try!(f.write_str(","));
+                    }
+                    try!(value.fmt(f));
+                }
+                f.write_str("]")
+            }
+            Json::Object(ref members) => {
+                try!(f.write_str("{"));
+                for (i, &(ref key, ref value)) in members.iter().enumerate() {
+                    if i > 0 {
+                        try!(f.write_str(","));
+                    }
+                    try!(write_string(f, key));
+                    try!(f.write_str(":"));
+                    try!(value.fmt(f));
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+    try!(f.write_char('"'));
+    for c in s.chars() {
+        match c {
+            '"' => try!(f.write_str("\\\"")),
+            '\\' => try!(f.write_str("\\\\")),
+            '\n' => try!(f.write_str("\\n")),
+            '\r' => try!(f.write_str("\\r")),
+            '\t' => try!(f.write_str("\\t")),
+            c if (c as u32) < 0x20 => try!(write!(f, "\\u{:04x}", c as u32)),
+            c => try!(f.write_char(c)),
+        }
+    }
+    f.write_char('"')
+}
+
+/// Conversion of node and edge weights to JSON.
+pub trait ToJson {
+    fn to_json(&self) -> Json;
+}
+
+impl ToJson for Json {
+    fn to_json(&self) -> Json {
+        self.clone()
+    }
+}
+
+impl ToJson for () {
+    fn to_json(&self) -> Json {
+        Json::Null
+    }
+}
+
+impl ToJson for bool {
+    fn to_json(&self) -> Json {
+        Json::Bool(*self)
+    }
+}
+
+macro_rules! number_to_json {
+    ($($t:ty)*) => {
+        $(
+        impl ToJson for $t {
+            fn to_json(&self) -> Json {
+                Json::Number(*self as f64)
+            }
+        }
+        )*
+    }
+}
+
+number_to_json!{i8 i16 i32 i64 isize u8 u16 u32 u64 usize f32 f64}
+
+impl ToJson for str {
+    fn to_json(&self) -> Json {
+        Json::String(self.to_string())
+    }
+}
+
+impl ToJson for String {
+    fn to_json(&self) -> Json {
+        Json::String(self.clone())
+    }
+}
+
+impl<'a, T: ?Sized + ToJson> ToJson for &'a T {
+    fn to_json(&self) -> Json {
+        (**self).to_json()
+    }
+}
+
+impl<T: ToJson> ToJson for Option<T> {
+    fn to_json(&self) -> Json {
+        match *self {
+            Some(ref x) => x.to_json(),
+            None => Json::Null,
+        }
+    }
+}
+
+impl<T: ToJson> ToJson for Vec<T> {
+    fn to_json(&self) -> Json {
+        Json::Array(self.iter().map(ToJson::to_json).collect())
+    }
+}
+
+/// Add the members of `weight` to `members` if it is an object, otherwise
+/// add it as the member `"weight"`.
+fn add_weight(members: &mut Vec<(String, Json)>, weight: Json) {
+    match weight {
+        Json::Object(fields) => members.extend(fields),
+        Json::Null => {}
+        weight => members.push(("weight".to_string(), weight)),
+    }
+}
+
+/// Write `graph` in node-link format.
+///
+/// ***Unstable: API may change at any time.*** Depends on `feature = "json"`.
+///
+/// Each node is written as an object with its index as `"id"`, and each
+/// link with the indices of its endpoints as `"source"` and `"target"`.
+/// A weight that converts to a JSON object contributes its members to the
+/// node or link object, a `null` weight is left out, and any other weight
+/// is written as the member `"weight"`.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::io::json::to_node_link;
+///
+/// let mut graph = Graph::<&str, f64>::new();
+/// let a = graph.add_node("a");
+/// let b = graph.add_node("b");
+/// graph.add_edge(a, b, 0.5);
+///
+/// assert_eq!(to_node_link(&graph),
+///            concat!(r#"{"directed":true,"multigraph":true,"#,
+///                    r#""nodes":[{"id":0,"weight":"a"},{"id":1,"weight":"b"}],"#,
+///                    r#""links":[{"source":0,"target":1,"weight":0.5}]}"#));
+/// ```
+pub fn to_node_link<N, E, Ty, Ix>(graph: &Graph<N, E, Ty, Ix>) -> String
+    where N: ToJson,
+          E: ToJson,
+          Ty: EdgeType,
+          Ix: IndexType,
+{
+    let nodes = graph.node_indices().map(|index| {
+        let mut members = vec![("id".to_string(), Json::Number(index.index() as f64))];
+        add_weight(&mut members, graph[index].to_json());
+        Json::Object(members)
+    }).collect();
+    let links = graph.raw_edges().iter().map(|edge| {
+        let mut members = vec![
+            ("source".to_string(), Json::Number(edge.source().index() as f64)),
+            ("target".to_string(), Json::Number(edge.target().index() as f64)),
+        ];
+        add_weight(&mut members, edge.weight.to_json());
+        Json::Object(members)
+    }).collect();
+    Json::Object(vec![
+        ("directed".to_string(), Json::Bool(graph.is_directed())),
+        ("multigraph".to_string(), Json::Bool(true)),
+        ("nodes".to_string(), Json::Array(nodes)),
+        ("links".to_string(), Json::Array(links)),
+    ]).to_string()
+}
+
+/// Parse a graph in node-link format.
+///
+/// ***Unstable: API may change at any time.*** Depends on `feature = "json"`.
+///
+/// Each element of `nodes` becomes a node, and each element of `links` (or
+/// `edges`) becomes an edge. The weights are the node and link objects,
+/// without their `"id"`, `"source"` and `"target"` members.
+///
+/// A link's `source` and `target` refer to the node with that `"id"`; if
+/// no node has it and it is an integer, it refers to the node at that
+/// position in `nodes`, as in d3.
+///
+/// The result is always a directed graph. For an undirected input, each
+/// edge is added from its source to its target; use
+/// `.into_edge_type::<Undirected>()` to convert the result.
+///
+/// ```
+/// use petgraph::io::json::{from_node_link, Json};
+///
+/// let g = from_node_link(r#"{
+///     "nodes": [{"id": "a", "group": 1}, {"id": "b", "group": 2}],
+///     "links": [{"source": "a", "target": "b", "value": 3}]
+/// }"#).unwrap();
+///
+/// assert_eq!(g.node_count(), 2);
+/// assert_eq!(g.edge_count(), 1);
+/// assert_eq!(g.raw_edges()[0].weight.get("value"), Some(&Json::Number(3.)));
+/// ```
+pub fn from_node_link(input: &str) -> Result<Graph<Json, Json>, ParseError> {
+    let mut reader = Reader {
+        chars: input.chars().peekable(),
+        line: 1,
+        depth: 0,
+    };
+    let json = try!(reader.value());
+    reader.skip_whitespace();
+    if reader.chars.peek().is_some() {
+        return reader.error("expected the end of input");
+    }
+    let line = reader.line;
+
+    let mut graph = Graph::new();
+    let mut ids = HashMap::new();
+    let nodes = match json.get("nodes") {
+        Some(&Json::Array(ref nodes)) => &nodes[..],
+        _ => return Err(parse_error(line, "expected a 'nodes' array")),
+    };
+    for node in nodes {
+        let (weight, id) = try!(split_members(node, &["id"], line));
+        let index = graph.add_node(weight);
+        if let Some(id) = id.into_iter().next() {
+            ids.insert(id.to_string(), index);
+        }
+    }
+    let links = match json.get("links").or(json.get("edges")) {
+        Some(&Json::Array(ref links)) => &links[..],
+        None => &[][..],
+        _ => return Err(parse_error(line, "expected a 'links' array")),
+    };
+    for link in links {
+        let (weight, ends) = try!(split_members(link, &["source", "target"], line));
+        if ends.len() != 2 {
+            return Err(parse_error(line, "a link needs a 'source' and a 'target'"));
+        }
+        let mut indices = Vec::new();
+        for end in ends {
+            let index = match ids.get(&end.to_string()) {
+                Some(&index) => index,
+                None => {
+                    let position = match end {
+                        Json::Number(x) if x >= 0. && x.fract() == 0. => x as usize,
+                        _ => graph.node_count(),
+                    };
+                    if position >= graph.node_count() {
+                        return Err(parse_error(line, format!("no node with id {}", end)));
+                    }
+                    graph.node_indices().nth(position).unwrap()
+                }
+            };
+            indices.push(index);
+        }
+        graph.add_edge(indices[0], indices[1], weight);
+    }
+    Ok(graph)
+}
+
+/// Split the members with the given keys from an object; return the rest of
+/// the object and the values of the keys, in the order of `keys`.
+fn split_members(json: &Json, keys: &[&str], line: usize)
+    -> Result<(Json, Vec<Json>), ParseError>
+{
+    let members = match *json {
+        Json::Object(ref members) => members,
+        _ => return Err(parse_error(line, format!("expected an object, found {}", json))),
+    };
+    let rest = members.iter().filter(|m| !keys.contains(&&m.0[..])).cloned().collect();
+    let values = keys.iter().filter_map(|&key| json.get(key).cloned()).collect();
+    Ok((Json::Object(rest), values))
+}
+
+struct Reader<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    /// The number of enclosing objects and arrays.
+    depth: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next();
+        if c == Some('\n') {
+            self.line += 1;
+        }
+        c
+    }
+
+    fn error<T, S: Into<String>>(&self, message: S) -> Result<T, ParseError> {
+        Err(parse_error(self.line, message))
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.peek().map_or(false, |c| c.is_whitespace()) {
+            self.bump();
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), ParseError> {
+        self.skip_whitespace();
+        match self.bump() {
+            Some(x) if x == c => Ok(()),
+            _ => self.error(format!("expected '{}'", c)),
+        }
+    }
+
+    /// Return `true` and skip `c` if it is next.
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        if self.chars.peek() == Some(&c) {
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn value(&mut self) -> Result<Json, ParseError> {
+        self.skip_whitespace();
+        match self.chars.peek().cloned() {
+            Some('{') | Some('[') => {
+                if self.depth == MAX_DEPTH {
+                    return self.error("too deeply nested");
+                }
+                self.depth += 1;
+                let value = self.compound();
+                self.depth -= 1;
+                value
+            }
+            Some('"') => self.string().map(Json::String),
+            Some(c) if c == '-' || c.is_digit(10) => {
+                let mut s = String::new();
+                while let Some(&c) = self.chars.peek() {
+                    if !(c.is_digit(10) || c == '-' || c == '+' || c == '.' ||
+                         c == 'e' || c == 'E') {
+                        break;
+                    }
+                    s.push(c);
+                    self.bump();
+                }
+                match s.parse() {
+                    Ok(x) => Ok(Json::Number(x)),
+                    Err(_) => self.error(format!("invalid number '{}'", s)),
+                }
+            }
+            Some(c) if c.is_alphabetic() => {
+                let mut s = String::new();
+                while self.chars.peek().map_or(false, |c| c.is_alphabetic()) {
+                    s.push(self.bump().unwrap());
+                }
+                match &s[..] {
+                    "null" => Ok(Json::Null),
+                    "true" => Ok(Json::Bool(true)),
+                    "false" => Ok(Json::Bool(false)),
+                    _ => self.error(format!("unexpected '{}'", s)),
+                }
+            }
+            Some(c) => self.error(format!("unexpected '{}'", c)),
+            None => self.error("unexpected end of input"),
+        }
+    }
+
+    /// Read an object or an array.
+    fn compound(&mut self) -> Result<Json, ParseError> {
+        match self.chars.peek().cloned() {
+            Some('{') => {
+                self.bump();
+                let mut members = Vec::new();
+                if self.eat('}') {
+                    return Ok(Json::Object(members));
+                }
+                loop {
+                    self.skip_whitespace();
+                    let key = try!(self.string());
+                    try!(self.expect(':'));
+                    let value = try!(self.value());
+                    members.push((key, value));
+                    if !self.eat(',') {
+                        try!(self.expect('}'));
+                        return Ok(Json::Object(members));
+                    }
+                }
+            }
+            Some('[') => {
+                self.bump();
+                let mut values = Vec::new();
+                if self.eat(']') {
+                    return Ok(Json::Array(values));
+                }
+                loop {
+                    values.push(try!(self.value()));
+                    if !self.eat(',') {
+                        try!(self.expect(']'));
+                        return Ok(Json::Array(values));
+                    }
+                }
+            }
+            _ => self.error("expected an object or an array"),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, ParseError> {
+        if self.bump() != Some('"') {
+            return self.error("expected a string");
+        }
+        let mut s = String::new();
+        loop {
+            match self.bump() {
+                None => return self.error("unterminated string"),
+                Some('"') => return Ok(s),
+                Some('\\') => {
+                    let c = match self.bump() {
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('u') => try!(self.unicode_escape()),
+                        Some(c @ '"') | Some(c @ '\\') | Some(c @ '/') => c,
+                        _ => return self.error("invalid escape sequence"),
+                    };
+                    s.push(c);
+                }
+                Some(c) => s.push(c),
+            }
+        }
+    }
+
+    /// Decode a `\u` escape, including a surrogate pair; the `\u` is
+    /// already read.
+    fn unicode_escape(&mut self) -> Result<char, ParseError> {
+        let high = try!(self.hex4());
+        let code = if high >= 0xD800 && high < 0xDC00 {
+            if self.bump() != Some('\\') || self.bump() != Some('u') {
+                return self.error("expected a low surrogate");
+            }
+            let low = try!(self.hex4());
+            if low < 0xDC00 || low > 0xDFFF {
+                return self.error("expected a low surrogate");
+            }
+            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+        } else {
+            high
+        };
+        match ::std::char::from_u32(code) {
+            Some(c) => Ok(c),
+            None => self.error("invalid unicode escape"),
+        }
+    }
+
+    fn hex4(&mut self) -> Result<u32, ParseError> {
+        let mut s = String::new();
+        for _ in 0..4 {
+            match self.bump() {
+                Some(c) => s.push(c),
+                None => break,
+            }
+        }
+        match u32::from_str_radix(&s, 16) {
+            Ok(x) => Ok(x),
+            Err(_) => self.error("invalid unicode escape"),
+        }
+    }
+}
+
+fn parse_error<S: Into<String>>(line: usize, message: S) -> ParseError {
+    ParseError::new("json", line, message)
+}
@@ -0,0 +1,250 @@
+//! ***Unstable.*** Matrix Market sparse matrix input and output.
+//!
+//! ***Unstable: API may change at any time.*** Depends on `feature = "matrix_market"`.
+//!
+//! The [Matrix Market](http://math.nist.gov/MatrixMarket/formats.html)
+//! coordinate format stores a sparse matrix as a list of its nonzero
+//! entries. It is read and written by most numerical software, and sparse
+//! matrix collections use it for adjacency matrices of graphs.
+
+use std::cmp;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+use {
+    EdgeType,
+    Graph,
+};
+use graph::{
+    DefIndex,
+    IndexType,
+};
+pub use super::{
+    ParseError,
+    ReadError,
+};
+use super::MAX_UNLISTED_NODES;
+
+#[derive(Copy, Clone, PartialEq)]
+enum Symmetry {
+    General,
+    Symmetric,
+    SkewSymmetric,
+}
+
+/// Read a sparse matrix in Matrix Market coordinate format as a graph.
+///
+/// ***Unstable: API may change at any time.*** Depends on `feature = "matrix_market"`.
+///
+/// The graph has a node for each row and column (the larger of the two
+/// counts), and an edge from node *i - 1* to node *j - 1* for each entry
+/// *(i, j)*, with the entry's value as weight, or `1.0` for a `pattern`
+/// matrix. The `real`, `integer` and `pattern` fields and the `general`,
+/// `symmetric` and `skew-symmetric` symmetries are supported. The node count
+/// may exceed the length of the input in bytes by at most 65536.
+///
+/// For a symmetric matrix, each entry off the diagonal also adds the
+/// reverse edge (with the negated value, if skew-symmetric), so that the
+/// adjacency matrix of the result is the full matrix.
+///
+/// ```
+/// use petgraph::io::matrix_market::read_matrix_market;
+///
+/// let input = "%%MatrixMarket matrix coordinate real symmetric
+/// % a path with three nodes
+/// 3 3 2
+/// 2 1 0.5
+/// 3 2 1.5
+/// ";
+/// let g = read_matrix_market(input.as_bytes()).unwrap();
+///
+/// assert_eq!(g.node_count(), 3);
+/// assert_eq!(g.edge_count(), 4);
+/// ```
+pub fn read_matrix_market<R>(reader: R) -> Result<Graph<(), f64>, ReadError>
+    where R: BufRead,
+{
+    let mut lines = reader.lines().enumerate().map(|(i, line)| line.map(|l| (i + 1, l)));
+    let header = match lines.next() {
+        Some(line) => try!(line).1,
+        None => return Err(parse_error(1, "missing header").into()),
+    };
+    let words = header.split_whitespace().map(str::to_lowercase).collect::<Vec<_>>();
+    if words.len() != 5 || words[0] != "%%matrixmarket" || words[1] != "matrix" {
+        return Err(parse_error(1, "expected a %%MatrixMarket matrix header").into());
+    }
+    if words[2] != "coordinate" {
+        return Err(parse_error(1, format!("unsupported format '{}'", words[2])).into());
+    }
+    let pattern = match &words[3][..] {
+        "real" | "integer" => false,
+        "pattern" => true,
+        field => return Err(parse_error(1, format!("unsupported field '{}'", field)).into()),
+    };
+    let symmetry = match &words[4][..] {
+        "general" => Symmetry::General,
+        "symmetric" => Symmetry::Symmetric,
+        "skew-symmetric" => Symmetry::SkewSymmetric,
+        symmetry => {
+            return Err(parse_error(1, format!("unsupported symmetry '{}'", symmetry)).into());
+        }
+    };
+
+    // the nodes are added once the input is read, since the size line is not
+    // trusted to size the allocation
+    let mut edges = Vec::new();
+    let mut size = None;
+    let mut size_line = 0;
+    let mut entries = 0;
+    let mut last_line = 1;
+    let mut bytes = header.len() + 1;
+    for line in lines {
+        let (line_number, line) = try!(line);
+        last_line = line_number;
+        bytes += line.len() + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('%') {
+            continue;
+        }
+        let words = line.split_whitespace().collect::<Vec<_>>();
+        let (rows, cols) = match size {
+            None => {
+                let numbers = words.iter().filter_map(|w| w.parse::<usize>().ok())
+                                   .collect::<Vec<_>>();
+                if words.len() != 3 || numbers.len() != 3 {
+                    return Err(parse_error(line_number, "expected the matrix size").into());
+                }
+                let n = cmp::max(numbers[0], numbers[1]);
+                // check the header before allocating anything for it
+                let max = <DefIndex as IndexType>::max().index();
+                if max != !0 && n > max {
+                    return Err(parse_error(line_number,
+                                               format!("{} nodes do not fit in the node index type", n))
+                               .into());
+                }
+                if numbers[0].checked_mul(numbers[1]).map_or(false, |cells| numbers[2] > cells) {
+                    return Err(parse_error(line_number, "more entries than matrix cells").into());
+                }
+                // off-diagonal entries of symmetric matrices make two edges
+                let edges = match symmetry {
+                    Symmetry::General => Some(numbers[2]),
+                    _ => numbers[2].checked_mul(2),
+                };
+                if edges.map_or(true, |edges| max != !0 && edges > max) {
+                    return Err(parse_error(line_number,
+                                               "entries do not fit in the edge index type").into());
+                }
+                size = Some((numbers[0], numbers[1], numbers[2]));
+                size_line = line_number;
+                continue;
+            }
+            Some((rows, cols, _)) => (rows, cols),
+        };
+        let expected = if pattern { 2 } else { 3 };
+        if words.len() != expected {
+            return Err(parse_error(line_number,
+                                       format!("expected {} values in an entry", expected)).into());
+        }
+        let i = match words[0].parse::<usize>() {
+            Ok(i) if i >= 1 && i <= rows => i - 1,
+            _ => return Err(parse_error(line_number, format!("invalid row '{}'", words[0])).into()),
+        };
+        let j = match words[1].parse::<usize>() {
+            Ok(j) if j >= 1 && j <= cols => j - 1,
+            _ => {
+                return Err(parse_error(line_number,
+                                           format!("invalid column '{}'", words[1])).into());
+            }
+        };
+        let value = if pattern {
+            1.
+        } else {
+            match words[2].parse::<f64>() {
+                Ok(value) => value,
+                Err(_) => {
+                    return Err(parse_error(line_number,
+                                               format!("invalid value '{}'", words[2])).into());
+                }
+            }
+        };
+        edges.push((i, j, value));
+        if i != j {
+            match symmetry {
+                Symmetry::General => {}
+                Symmetry::Symmetric => edges.push((j, i, value)),
+                Symmetry::SkewSymmetric => edges.push((j, i, -value)),
+            }
+        }
+        entries += 1;
+    }
+    let n = match size {
+        None => return Err(parse_error(last_line, "missing matrix size").into()),
+        Some((_, _, count)) if count != entries => {
+            return Err(parse_error(last_line,
+                                       format!("expected {} entries, found {}", count, entries))
+                       .into());
+        }
+        Some((rows, cols, _)) => cmp::max(rows, cols),
+    };
+    if n > bytes.saturating_add(MAX_UNLISTED_NODES) {
+        return Err(parse_error(size_line,
+                                   format!("{} nodes are too many for the length of the input", n))
+                   .into());
+    }
+    let mut graph = Graph::with_capacity(n, edges.len());
+    let nodes = (0..n).map(|_| graph.add_node(())).collect::<Vec<_>>();
+    for (i, j, value) in edges {
+        graph.add_edge(nodes[i], nodes[j], value);
+    }
+    Ok(graph)
+}
+
+/// Write the adjacency matrix of `graph` in Matrix Market coordinate format.
+///
+/// ***Unstable: API may change at any time.*** Depends on `feature = "matrix_market"`.
+///
+/// Each edge is written as an entry, with its weight as the value. A
+/// directed graph is written as a `general` matrix, and an undirected graph
+/// as a `symmetric` matrix, with each edge in the lower triangle. The weights
+/// should format as numbers; parallel edges are written as separate entries,
+/// which most readers add together.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::io::matrix_market::write_matrix_market;
+///
+/// let mut graph = Graph::<(), f64>::new();
+/// let a = graph.add_node(());
+/// let b = graph.add_node(());
+/// graph.add_edge(a, b, 0.5);
+///
+/// let mut output = Vec::new();
+/// write_matrix_market(&mut output, &graph).unwrap();
+/// assert_eq!(String::from_utf8(output).unwrap(),
+/// "%%MatrixMarket matrix coordinate real general
+/// 2 2 1
+/// 1 2 0.5
+/// ");
+/// ```
+pub fn write_matrix_market<W, N, E, Ty, Ix>(mut writer: W, graph: &Graph<N, E, Ty, Ix>)
+    -> io::Result<()>
+    where W: Write,
+          E: fmt::Display,
+          Ty: EdgeType,
+          Ix: IndexType,
+{
+    let symmetry = if graph.is_directed() { "general" } else { "symmetric" };
+    try!(writeln!(writer, "%%MatrixMarket matrix coordinate real {}", symmetry));
+    let n = graph.node_count();
+    try!(writeln!(writer, "{} {} {}", n, n, graph.edge_count()));
+    for edge in graph.raw_edges() {
+        let (i, j) = (edge.source().index(), edge.target().index());
+        let (i, j) = if !graph.is_directed() && i < j { (j, i) } else { (i, j) };
+        try!(writeln!(writer, "{} {} {}", i + 1, j + 1, edge.weight));
+    }
+    Ok(())
+}
+
+fn parse_error<S: Into<String>>(line: usize, message: S) -> ParseError {
+    ParseError::new("matrix market", line, message)
+}
@@ -0,0 +1,135 @@
+//! ***Unstable.*** Graph file format input and output.
+//!
+//! ***Unstable: API may change at any time.*** Each format depends on its
+//! own feature:
+//!
+//! - `dot`: Graphviz dot input, `feature = "dot_parser"`; the output is
+//!   [`dot::Dot`](../dot/struct.Dot.html).
+//! - `graphml`: the GraphML format, `feature = "graphml"`.
+//! - `gml`: the GML format, `feature = "gml"`.
+//! - `json`: the JSON node-link format, `feature = "json"`.
+//! - `edgelist`: CSV, TSV and other delimited edge lists, `feature = "edgelist"`.
+//! - `graph6`: the graph6 and sparse6 formats of nauty, `feature = "graph6"`.
+//! - `pajek`: the Pajek .net format, `feature = "pajek"`.
+//! - `matrix_market`: Matrix Market sparse matrices, `feature = "matrix_market"`.
+
+#[cfg(feature = "dot_parser")]
+pub mod dot;
+#[cfg(feature = "graphml")]
+pub mod graphml;
+#[cfg(feature = "gml")]
+pub mod gml;
+#[cfg(feature = "json")]
+pub mod json;
+#[cfg(feature = "edgelist")]
+pub mod edgelist;
+#[cfg(feature = "graph6")]
+pub mod graph6;
+#[cfg(feature = "pajek")]
+pub mod pajek;
+#[cfg(feature = "matrix_market")]
+pub mod matrix_market;
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+/// The deepest nesting of lists, arrays or subgraphs that the parsers accept,
+/// so that deeply nested input is an error rather than a stack overflow.
+const MAX_DEPTH: usize = 128;
+
+/// The number of nodes that a header may declare beyond the ones the rest of
+/// the input can describe, so that a short input cannot ask for a huge graph.
+const MAX_UNLISTED_NODES: usize = 1 << 16;
+
+/// An error in the contents of a graph file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    format: &'static str,
+    line: usize,
+    message: String,
+}
+
+impl ParseError {
+    fn new<S: Into<String>>(format: &'static str, line: usize, message: S) -> Self {
+        ParseError {
+            format: format,
+            line: line,
+            message: message.into(),
+        }
+    }
+
+    /// Return the line (counted from 1) where the error was found.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} parse error on line {}: {}", self.format, self.line, self.message)
+    }
+}
+
+impl Error for ParseError {
+    fn description(&self) -> &str {
+        &self.message
+    }
+}
+
+/// An error from reading a graph file.
+#[derive(Debug)]
+pub enum ReadError {
+    /// The reader failed.
+    Io(io::Error),
+    /// The contents could not be parsed.
+    Parse(ParseError),
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ReadError::Io(ref e) => e.fmt(f),
+            ReadError::Parse(ref e) => e.fmt(f),
+        }
+    }
+}
+
+impl Error for ReadError {
+    fn description(&self) -> &str {
+        match *self {
+            ReadError::Io(ref e) => e.description(),
+            ReadError::Parse(ref e) => e.description(),
+        }
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        match *self {
+            ReadError::Io(ref e) => Some(e),
+            ReadError::Parse(ref e) => Some(e),
+        }
+    }
+}
+
+impl From<io::Error> for ReadError {
+    fn from(e: io::Error) -> Self {
+        ReadError::Io(e)
+    }
+}
+
+impl From<ParseError> for ReadError {
+    fn from(e: ParseError) -> Self {
+        ReadError::Parse(e)
+    }
+}
+
+/// Pass Debug formatting to Display
+struct DebugFmt<T>(T);
+
+impl<T> fmt::Display for DebugFmt<T>
+    where T: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
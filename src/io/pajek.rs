@@ -0,0 +1,302 @@
+//! ***Unstable.*** Pajek .net file format input and output.
+//!
+//! ***Unstable: API may change at any time.*** Depends on `feature = "pajek"`.
+//!
+//! The [Pajek](http://mrvar.fdv.uni-lj.si/pajek/) network format lists the
+//! vertices, numbered from 1, with their labels, followed by sections of
+//! directed arcs and undirected edges. Many social network datasets are
+//! distributed in it.
+
+use std::fmt::{self, Display, Write};
+
+use {
+    EdgeType,
+    Graph,
+};
+use graph::{
+    DefIndex,
+    IndexType,
+    NodeIndex,
+};
+use super::DebugFmt;
+pub use super::ParseError;
+use super::MAX_UNLISTED_NODES;
+
+/// `Pajek` implements output to Pajek .net format for a graph.
+///
+/// ***Unstable: API may change at any time.*** Depends on `feature = "pajek"`.
+///
+/// Each node is written with its index plus one as its number and its
+/// weight as its label, and each edge with its weight as the edge weight,
+/// in an `*Arcs` section for a directed graph or an `*Edges` section for an
+/// undirected graph. Use `{}` to format the weights with `Display` and
+/// `{:?}` to format them with `Debug`.
+///
+/// The format has no escapes, so double quotes in labels are written as
+/// single quotes.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::io::pajek::Pajek;
+///
+/// let mut graph = Graph::<&str, f64>::new();
+/// let a = graph.add_node("a");
+/// let b = graph.add_node("b");
+/// graph.add_edge(a, b, 0.5);
+///
+/// assert_eq!(Pajek::new(&graph).to_string(),
+/// r#"*Vertices 2
+/// 1 "a"
+/// 2 "b"
+/// *Arcs
+/// 1 2 0.5
+/// "#);
+/// ```
+pub struct Pajek<'a, N: 'a, E: 'a, Ty: 'a, Ix: 'a + IndexType> {
+    graph: &'a Graph<N, E, Ty, Ix>,
+}
+
+impl<'a, N, E, Ty, Ix> Pajek<'a, N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    /// Create a `Pajek` formatting wrapper.
+    pub fn new(graph: &'a Graph<N, E, Ty, Ix>) -> Self {
+        Pajek {
+            graph: graph,
+        }
+    }
+
+    fn graph_fmt<F, G>(&self, f: &mut fmt::Formatter,
+                       mut node_fmt: F, mut edge_fmt: G) -> fmt::Result
+        where F: FnMut(&N, &mut FnMut(&Display) -> fmt::Result) -> fmt::Result,
+              G: FnMut(&E, &mut FnMut(&Display) -> fmt::Result) -> fmt::Result,
+    {
+        let g = self.graph;
+        try!(writeln!(f, "*Vertices {}", g.node_count()));
+        for index in g.node_indices() {
+            try!(write!(f, "{} \"", index.index() + 1));
+            try!(node_fmt(&g[index], &mut |d| write!(&mut Escaper(&mut *f), "{}", d)));
+            try!(writeln!(f, "\""));
+        }
+        try!(writeln!(f, "{}", if g.is_directed() { "*Arcs" } else { "*Edges" }));
+        for edge in g.raw_edges() {
+            try!(write!(f, "{} {} ", edge.source().index() + 1, edge.target().index() + 1));
+            try!(edge_fmt(&edge.weight, &mut |d| d.fmt(f)));
+            try!(writeln!(f, ""));
+        }
+        Ok(())
+    }
+}
+
+impl<'a, N, E, Ty, Ix> fmt::Display for Pajek<'a, N, E, Ty, Ix>
+    where N: fmt::Display,
+          E: fmt::Display,
+          Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.graph_fmt(f, |n, cb| cb(n), |e, cb| cb(e))
+    }
+}
+
+impl<'a, N, E, Ty, Ix> fmt::Debug for Pajek<'a, N, E, Ty, Ix>
+    where N: fmt::Debug,
+          E: fmt::Debug,
+          Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.graph_fmt(f,
+                       |n, cb| cb(&DebugFmt(n)),
+                       |e, cb| cb(&DebugFmt(e)))
+    }
+}
+
+/// Keep labels on one line and inside their quotes
+struct Escaper<W>(W);
+
+impl<W> fmt::Write for Escaper<W>
+    where W: fmt::Write
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            try!(self.write_char(c));
+        }
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        match c {
+            '"' => self.0.write_char('\''),
+            '\n' | '\r' => self.0.write_char(' '),
+            _ => self.0.write_char(c),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq)]
+enum Section {
+    Start,
+    Vertices,
+    Arcs,
+    ArcsList,
+    Other,
+}
+
+/// Parse a graph in Pajek .net format.
+///
+/// ***Unstable: API may change at any time.*** Depends on `feature = "pajek"`.
+///
+/// The `*Vertices` section gives the number of nodes, which may exceed the
+/// number of vertex lines by at most 65536; each node's weight is its label,
+/// or its number if it has no label. Vertex coordinates and
+/// drawing attributes are ignored. Each line of an `*Arcs` or `*Edges`
+/// section becomes an edge, with its weight, or `1.0` if it has none, and
+/// each line of an `*Arcslist` or `*Edgeslist` section becomes an edge from
+/// its first vertex to each of the others, with weight `1.0`. Comment lines
+/// that start with `%` and other sections are ignored.
+///
+/// The result is always a directed graph. Undirected edges are added from
+/// their first vertex to their second; use `.into_edge_type::<Undirected>()`
+/// to convert the result.
+///
+/// ```
+/// use petgraph::io::pajek::parse;
+///
+/// let g = parse("*Network friends
+/// *Vertices 3
+/// 1 \"Ann\" 0.1 0.5 0.5
+/// 2 \"Bob\"
+/// *Arcs
+/// 1 2 2.0
+/// *Edges
+/// 2 3
+/// ").unwrap();
+///
+/// let names = g.node_indices().map(|i| &g[i][..]).collect::<Vec<_>>();
+/// assert_eq!(names, ["Ann", "Bob", "3"]);
+/// let edges = g.raw_edges().iter().map(|e| e.weight).collect::<Vec<_>>();
+/// assert_eq!(edges, [2.0, 1.0]);
+/// ```
+pub fn parse(input: &str) -> Result<Graph<String, f64>, ParseError> {
+    let mut graph = Graph::new();
+    let mut nodes = Vec::new();
+    let mut section = Section::Start;
+    for (i, line) in input.lines().enumerate() {
+        let line_number = i + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('%') {
+            continue;
+        }
+        if line.starts_with('*') {
+            let mut words = line.split_whitespace();
+            let keyword = words.next().unwrap().to_lowercase();
+            section = match &keyword[..] {
+                "*vertices" => {
+                    if !nodes.is_empty() {
+                        return Err(parse_error(line_number, "more than one *Vertices section"));
+                    }
+                    let count = match words.next().and_then(|n| n.parse::<usize>().ok()) {
+                        Some(count) => count,
+                        None => return Err(parse_error(line_number, "expected a vertex count")),
+                    };
+                    let max = <DefIndex as IndexType>::max().index();
+                    if max != !0 && count > max {
+                        return Err(parse_error(line_number,
+                                                   format!("{} vertices do not fit in the node index type",
+                                                           count)));
+                    }
+                    // the count is not trusted to size the allocation
+                    if count > input.len().saturating_add(MAX_UNLISTED_NODES) {
+                        return Err(parse_error(line_number,
+                                                   format!("{} vertices are too many for the length of the input",
+                                                           count)));
+                    }
+                    for n in 0..count {
+                        nodes.push(graph.add_node((n + 1).to_string()));
+                    }
+                    Section::Vertices
+                }
+                "*arcs" | "*edges" => Section::Arcs,
+                "*arcslist" | "*edgeslist" => Section::ArcsList,
+                _ => Section::Other,
+            };
+            continue;
+        }
+        match section {
+            Section::Start => {
+                return Err(parse_error(line_number, "expected *Vertices"));
+            }
+            Section::Vertices => {
+                let (number, rest) = split_first(line);
+                let node = try!(vertex(&nodes, number, line_number));
+                if let Some(label) = try!(label(rest, line_number)) {
+                    graph[node] = label;
+                }
+            }
+            Section::Arcs => {
+                let mut words = line.split_whitespace();
+                let a = try!(vertex(&nodes, words.next().unwrap(), line_number));
+                let b = match words.next() {
+                    Some(word) => try!(vertex(&nodes, word, line_number)),
+                    None => return Err(parse_error(line_number, "expected two vertices")),
+                };
+                let weight = match words.next() {
+                    Some(word) => match word.parse() {
+                        Ok(weight) => weight,
+                        Err(_) => {
+                            return Err(parse_error(line_number,
+                                                       format!("invalid weight '{}'", word)));
+                        }
+                    },
+                    None => 1.,
+                };
+                graph.add_edge(a, b, weight);
+            }
+            Section::ArcsList => {
+                let mut words = line.split_whitespace();
+                let a = try!(vertex(&nodes, words.next().unwrap(), line_number));
+                for word in words {
+                    let b = try!(vertex(&nodes, word, line_number));
+                    graph.add_edge(a, b, 1.);
+                }
+            }
+            Section::Other => {}
+        }
+    }
+    Ok(graph)
+}
+
+/// Split off the first word of a line.
+fn split_first(line: &str) -> (&str, &str) {
+    match line.find(char::is_whitespace) {
+        Some(i) => (&line[..i], line[i..].trim_left()),
+        None => (line, ""),
+    }
+}
+
+fn vertex(nodes: &[NodeIndex], word: &str, line: usize) -> Result<NodeIndex, ParseError> {
+    match word.parse::<usize>() {
+        Ok(n) if n >= 1 && n <= nodes.len() => Ok(nodes[n - 1]),
+        _ => Err(parse_error(line, format!("invalid vertex '{}'", word))),
+    }
+}
+
+/// Read the label at the start of `rest`, quoted or a single word.
+fn label(rest: &str, line: usize) -> Result<Option<String>, ParseError> {
+    if rest.starts_with('"') {
+        match rest[1..].find('"') {
+            Some(end) => Ok(Some(rest[1..end + 1].to_string())),
+            None => Err(parse_error(line, "unterminated label")),
+        }
+    } else if rest.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(split_first(rest).0.to_string()))
+    }
+}
+
+fn parse_error<S: Into<String>>(line: usize, message: S) -> ParseError {
+    ParseError::new("pajek", line, message)
+}
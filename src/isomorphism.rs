@@ -1,4 +1,5 @@
-use std::marker;
+use core::marker;
+use alloc::vec::Vec;
 use fixedbitset::FixedBitSet;
 
 use super::{
@@ -0,0 +1,535 @@
+//! Force-directed graph layout.
+//!
+//! A force-directed layout places the nodes in the plane as if they were
+//! charged particles joined by springs: all nodes repel each other, the
+//! edges pull their endpoints together, and the simulation runs for a
+//! limited number of iterations while it cools down. The result is a
+//! position per node, ready to draw.
+//!
+//! Directed acyclic graphs are drawn in layers instead by
+//! [`layered_layout`](fn.layered_layout.html).
+
+use std::cmp::max;
+use std::f32;
+
+use {
+    Directed,
+    EdgeType,
+    Graph,
+};
+use graph::{
+    DefIndex,
+    IndexType,
+    NodeIndex,
+};
+
+/// The forces of a [`ForceLayout`](struct.ForceLayout.html).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ForceModel {
+    /// Fruchterman–Reingold: nodes at distance *d* repel with force
+    /// *k² / d* and adjacent nodes attract with force *d² / k*, where *k* is
+    /// the ideal edge length for the area.
+    FruchtermanReingold,
+    /// In the style of ForceAtlas2: the repulsion grows with the degrees of
+    /// the nodes, attraction is linear in the distance, and a gravity pulls
+    /// each node to the center. Hubs spread out, and leaves gather close to
+    /// them.
+    ForceAtlas2,
+}
+
+/// `ForceLayout` computes a force-directed layout of a graph, see the
+/// [module documentation](index.html).
+///
+/// The nodes start at random positions from a seeded generator, so the
+/// same settings always give the same layout. Positions stay within a
+/// `width × height` frame with its corner at the origin. Each iteration
+/// takes **O(|V|² + |E|)** time.
+///
+/// Edge directions are ignored, self loops have no effect, and parallel
+/// edges pull their endpoints together once each.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::layout::{ForceLayout, ForceModel};
+///
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (2, 3)]);
+/// let positions = ForceLayout::new()
+///     .model(ForceModel::FruchtermanReingold)
+///     .size(100., 100.)
+///     .iterations(200)
+///     .seed(7)
+///     .layout(&g);
+/// assert_eq!(positions.len(), 4);
+/// for &(x, y) in &positions {
+///     assert!(0. <= x && x <= 100. && 0. <= y && y <= 100.);
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ForceLayout {
+    model: ForceModel,
+    iterations: usize,
+    seed: u64,
+    width: f32,
+    height: f32,
+}
+
+impl ForceLayout {
+    /// Create a Fruchterman–Reingold layout of 100 iterations in the unit
+    /// square, with seed `0`.
+    pub fn new() -> Self {
+        ForceLayout {
+            model: ForceModel::FruchtermanReingold,
+            iterations: 100,
+            seed: 0,
+            width: 1.,
+            height: 1.,
+        }
+    }
+
+    /// Use the forces of `model`.
+    pub fn model(mut self, model: ForceModel) -> Self {
+        self.model = model;
+        self
+    }
+
+    /// Run the simulation for `iterations` steps; with zero steps, the
+    /// nodes keep their random start positions.
+    pub fn iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Seed the generator of the start positions.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Lay out the nodes in a `width × height` frame.
+    ///
+    /// **Panics** if `width` or `height` is not positive.
+    pub fn size(mut self, width: f32, height: f32) -> Self {
+        assert!(width > 0. && height > 0., "ForceLayout::size: the frame must not be empty");
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Return the position `(x, y)` of each node of `g`, indexed by node
+    /// index.
+    pub fn layout<N, E, Ty, Ix>(&self, g: &Graph<N, E, Ty, Ix>) -> Vec<(f32, f32)>
+        where Ty: EdgeType,
+              Ix: IndexType,
+    {
+        let n = g.node_count();
+        let mut rng = XorShift::new(self.seed);
+        let mut pos = (0..n).map(|_| (rng.next_f32() * self.width, rng.next_f32() * self.height))
+                            .collect::<Vec<_>>();
+        if n == 0 {
+            return pos
+        }
+        let edges = g.raw_edges().iter()
+                     .map(|edge| (edge.source().index(), edge.target().index()))
+                     .filter(|&(a, b)| a != b)
+                     .collect::<Vec<_>>();
+        let k = (self.width * self.height / n as f32).sqrt();
+        // the mass of a node scales its repulsion; for ForceAtlas2 it is
+        // one more than its degree, relative to the average
+        let mut mass = vec![1.; n];
+        if self.model == ForceModel::ForceAtlas2 {
+            for &(a, b) in &edges {
+                mass[a] += 1.;
+                mass[b] += 1.;
+            }
+            let average = mass.iter().sum::<f32>() / n as f32;
+            for m in &mut mass {
+                *m /= average;
+            }
+        }
+        let center = (self.width / 2., self.height / 2.);
+        let start_temperature = self.width.min(self.height) / 10.;
+        let mut disp = vec![(0f32, 0f32); n];
+        for iteration in 0..self.iterations {
+            for d in &mut disp {
+                *d = (0., 0.);
+            }
+            for a in 0..n {
+                for b in a + 1..n {
+                    let (dx, dy, dist) = offset(pos[a], pos[b]);
+                    let force = k * k * mass[a] * mass[b] / dist;
+                    disp[a].0 += dx / dist * force;
+                    disp[a].1 += dy / dist * force;
+                    disp[b].0 -= dx / dist * force;
+                    disp[b].1 -= dy / dist * force;
+                }
+            }
+            for &(a, b) in &edges {
+                let (dx, dy, dist) = offset(pos[a], pos[b]);
+                let force = match self.model {
+                    ForceModel::FruchtermanReingold => dist * dist / k,
+                    ForceModel::ForceAtlas2 => dist,
+                };
+                disp[a].0 -= dx / dist * force;
+                disp[a].1 -= dy / dist * force;
+                disp[b].0 += dx / dist * force;
+                disp[b].1 += dy / dist * force;
+            }
+            if self.model == ForceModel::ForceAtlas2 {
+                for a in 0..n {
+                    let (dx, dy, dist) = offset(pos[a], center);
+                    let force = GRAVITY * k * mass[a];
+                    disp[a].0 -= dx / dist * force;
+                    disp[a].1 -= dy / dist * force;
+                }
+            }
+            // cool down linearly, limiting how far a node moves per step
+            let temperature = start_temperature * (1. - iteration as f32 / self.iterations as f32);
+            for a in 0..n {
+                let (dx, dy) = disp[a];
+                let length = (dx * dx + dy * dy).sqrt();
+                if length > 0. {
+                    let step = length.min(temperature) / length;
+                    pos[a].0 = (pos[a].0 + dx * step).max(0.).min(self.width);
+                    pos[a].1 = (pos[a].1 + dy * step).max(0.).min(self.height);
+                }
+            }
+        }
+        pos
+    }
+}
+
+impl Default for ForceLayout {
+    fn default() -> Self {
+        ForceLayout::new()
+    }
+}
+
+/// The strength of the gravity of `ForceAtlas2`, relative to the ideal edge
+/// length.
+const GRAVITY: f32 = 0.1;
+
+/// Return the offset from `b` to `a` and its length, pushing apart nodes
+/// that are at the same position.
+fn offset(a: (f32, f32), b: (f32, f32)) -> (f32, f32, f32) {
+    let (dx, dy) = (a.0 - b.0, a.1 - b.1);
+    let dist = (dx * dx + dy * dy).sqrt();
+    if dist < 1e-6 {
+        (1e-3, 0., 1e-3)
+    } else {
+        (dx, dy, dist)
+    }
+}
+
+/// A xorshift* generator for the start positions, so that layouts do not
+/// need the `rand` crate.
+struct XorShift(u64);
+
+impl XorShift {
+    fn new(seed: u64) -> Self {
+        // the state must not be zero
+        let state = seed ^ 0x9e37_79b9_7f4a_7c15;
+        XorShift(if state == 0 { 1 } else { state })
+    }
+
+    /// Return a number in `[0, 1)`.
+    fn next_f32(&mut self) -> f32 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        let x = self.0.wrapping_mul(0x2545_f491_4f6c_dd1d);
+        (x >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+/// A layered drawing of a directed acyclic graph, see
+/// [`layered_layout`](fn.layered_layout.html).
+///
+/// Layer `0` is at the top, and every edge points down to a later layer.
+/// Positions are `(x, y)` with `y` the layer and `x` at least one apart
+/// within a layer, starting from `0`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayeredLayout<Ix: IndexType = DefIndex> {
+    /// The layer of each node, indexed by node index.
+    pub layer: Vec<usize>,
+    /// The nodes of each layer, from left to right.
+    pub layers: Vec<Vec<NodeIndex<Ix>>>,
+    /// The position of each node, indexed by node index.
+    pub position: Vec<(f32, f32)>,
+    /// The bend points of each edge, indexed by edge index: where it passes
+    /// each layer between its endpoints, from source to target.
+    pub bends: Vec<Vec<(f32, f32)>>,
+}
+
+/// Compute a layered (Sugiyama) layout of a directed acyclic graph, as for
+/// a dependency graph.
+///
+/// The layout is found in four steps:
+///
+/// 1. Each node is assigned the layer of its longest path from a source, so
+///    sources are at the top.
+/// 2. Each edge that spans more than one layer is split by a dummy node on
+///    each layer in between, which becomes a bend point.
+/// 3. Edge crossings are reduced by sweeping the layers down and up,
+///    ordering each layer by the median position of its neighbors in the
+///    previous layer, and keeping the order with the fewest crossings.
+/// 4. Each node is placed near the median of its neighbors, as the order
+///    allows, so that long edges run straight.
+///
+/// Self loops are ignored. Return `None` if the graph has a cycle.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::layout::layered_layout;
+/// use petgraph::graph::node_index as n;
+///
+/// // 0 depends on 1 and 2, which depend on 3; and 0 also on 3
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 3), (2, 3), (0, 3)]);
+/// let layout = layered_layout(&g).unwrap();
+/// assert_eq!(layout.layer, vec![0, 1, 1, 2]);
+/// assert_eq!(layout.layers[1].len(), 2);
+/// assert_eq!(layout.position[3].1, 2.);
+/// // the edge 0 → 3 bends once, on layer 1
+/// assert_eq!(layout.bends[4].len(), 1);
+/// assert_eq!(layout.bends[4][0].1, 1.);
+/// ```
+pub fn layered_layout<N, E, Ix>(g: &Graph<N, E, Directed, Ix>) -> Option<LayeredLayout<Ix>>
+    where Ix: IndexType,
+{
+    let n = g.node_count();
+    let layer = match longest_path_layers(g) {
+        None => return None,
+        Some(layer) => layer,
+    };
+
+    // the nodes and dummy nodes, with their neighbors on the layers above
+    // and below, and the chain of dummies of each edge
+    let mut slot_layer = layer.clone();
+    let mut up = vec![Vec::new(); n];
+    let mut down = vec![Vec::new(); n];
+    let mut chains = Vec::with_capacity(g.edge_count());
+    for edge in g.raw_edges() {
+        let (a, b) = (edge.source().index(), edge.target().index());
+        let mut chain = Vec::new();
+        if a != b {
+            let mut prev = a;
+            for l in layer[a] + 1..layer[b] {
+                let dummy = slot_layer.len();
+                slot_layer.push(l);
+                up.push(vec![prev]);
+                down.push(Vec::new());
+                down[prev].push(dummy);
+                chain.push(dummy);
+                prev = dummy;
+            }
+            down[prev].push(b);
+            up[b].push(prev);
+        }
+        chains.push(chain);
+    }
+
+    let layer_count = layer.iter().map(|&l| l + 1).max().unwrap_or(0);
+    let mut order = vec![Vec::new(); layer_count];
+    for (s, &l) in slot_layer.iter().enumerate() {
+        order[l].push(s);
+    }
+    reduce_crossings(&mut order, &up, &down);
+    let x = assign_x(&order, &up, &down, slot_layer.len());
+
+    let point = |s: usize| (x[s], slot_layer[s] as f32);
+    Some(LayeredLayout {
+        layer: layer,
+        layers: order.iter()
+                     .map(|l| l.iter().filter(|&&s| s < n).map(|&s| NodeIndex::new(s)).collect())
+                     .collect(),
+        position: (0..n).map(&point).collect(),
+        bends: chains.iter().map(|chain| chain.iter().map(|&s| point(s)).collect()).collect(),
+    })
+}
+
+/// Return the layer of each node, the length of its longest path from a
+/// source, or `None` if the graph has a cycle other than a self loop.
+fn longest_path_layers<N, E, Ix>(g: &Graph<N, E, Directed, Ix>) -> Option<Vec<usize>>
+    where Ix: IndexType,
+{
+    let n = g.node_count();
+    let mut in_degree = vec![0; n];
+    for edge in g.raw_edges() {
+        if edge.source() != edge.target() {
+            in_degree[edge.target().index()] += 1;
+        }
+    }
+    let mut layer = vec![0; n];
+    let mut ready = (0..n).filter(|&a| in_degree[a] == 0).collect::<Vec<_>>();
+    let mut done = 0;
+    while let Some(a) = ready.pop() {
+        done += 1;
+        for b in g.neighbors(NodeIndex::new(a)) {
+            let b = b.index();
+            if b == a {
+                continue
+            }
+            layer[b] = max(layer[b], layer[a] + 1);
+            in_degree[b] -= 1;
+            if in_degree[b] == 0 {
+                ready.push(b);
+            }
+        }
+    }
+    if done == n { Some(layer) } else { None }
+}
+
+/// The number of down and up sweeps of the crossing reduction.
+const SWEEPS: usize = 12;
+
+/// Order the slots of each layer by the median heuristic, keeping the best
+/// order found.
+fn reduce_crossings(order: &mut Vec<Vec<usize>>, up: &[Vec<usize>], down: &[Vec<usize>]) {
+    let mut pos = vec![0; up.len()];
+    let mut best = order.clone();
+    let mut best_crossings = crossings(order, down, &mut pos);
+    for sweep in 0..SWEEPS {
+        let layers = order.len();
+        if sweep % 2 == 0 {
+            for l in 1..layers {
+                sort_by_median(order, l - 1, l, up, &mut pos);
+            }
+        } else {
+            for l in (0..layers.saturating_sub(1)).rev() {
+                sort_by_median(order, l + 1, l, down, &mut pos);
+            }
+        }
+        let count = crossings(order, down, &mut pos);
+        if count < best_crossings {
+            best_crossings = count;
+            best = order.clone();
+        }
+    }
+    *order = best;
+}
+
+/// Sort layer `l` by the median position of the neighbors of each slot in
+/// the fixed layer `fixed`; slots without neighbors there keep their place.
+fn sort_by_median(order: &mut [Vec<usize>], fixed: usize, l: usize,
+                  neighbors: &[Vec<usize>], pos: &mut [usize])
+{
+    for (i, &s) in order[fixed].iter().enumerate() {
+        pos[s] = i;
+    }
+    let mut keyed = order[l].iter().enumerate().map(|(i, &s)| {
+        let mut p = neighbors[s].iter().map(|&t| pos[t] as f32).collect::<Vec<_>>();
+        p.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let median = median(&p).unwrap_or(i as f32);
+        (median, s)
+    }).collect::<Vec<_>>();
+    keyed.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    order[l] = keyed.into_iter().map(|(_, s)| s).collect();
+}
+
+/// Return the median of the sorted `values`, the mean of the middle two if
+/// there is an even number.
+fn median(values: &[f32]) -> Option<f32> {
+    let m = values.len() / 2;
+    if values.is_empty() {
+        None
+    } else if values.len() % 2 == 1 {
+        Some(values[m])
+    } else {
+        Some((values[m - 1] + values[m]) / 2.)
+    }
+}
+
+/// Return the number of crossings between consecutive layers.
+fn crossings(order: &[Vec<usize>], down: &[Vec<usize>], pos: &mut [usize]) -> usize {
+    for layer in order {
+        for (i, &s) in layer.iter().enumerate() {
+            pos[s] = i;
+        }
+    }
+    let mut count = 0;
+    for l in 1..order.len() {
+        // two edges cross if their ends are in opposite orders: count the
+        // inversions of the lower ends, sorted by upper end
+        let mut ends = Vec::new();
+        for &s in &order[l - 1] {
+            let mut lower = down[s].iter().map(|&t| pos[t]).collect::<Vec<_>>();
+            lower.sort();
+            ends.extend(lower);
+        }
+        let mut tree = vec![0; order[l].len() + 1];
+        for (seen, &p) in ends.iter().enumerate() {
+            // count the ends before this one that are further right
+            let mut i = p + 1;
+            let mut not_greater = 0;
+            while i > 0 {
+                not_greater += tree[i];
+                i -= i & i.wrapping_neg();
+            }
+            count += seen - not_greater;
+            let mut i = p + 1;
+            while i < tree.len() {
+                tree[i] += 1;
+                i += i & i.wrapping_neg();
+            }
+        }
+    }
+    count
+}
+
+/// The number of rounds of x-coordinate assignment.
+const PLACEMENT_ROUNDS: usize = 4;
+
+/// Return the x coordinate of each slot: in each round, sweeping down and
+/// then up, every slot goes to the median of its neighbors in the previous
+/// layer, as far as its layer's order and spacing allow.
+fn assign_x(order: &[Vec<usize>], up: &[Vec<usize>], down: &[Vec<usize>], slots: usize)
+    -> Vec<f32>
+{
+    let mut x = vec![0.; slots];
+    for layer in order {
+        for (i, &s) in layer.iter().enumerate() {
+            x[s] = i as f32;
+        }
+    }
+    for _ in 0..PLACEMENT_ROUNDS {
+        for layer in order.iter().skip(1) {
+            place(layer, up, &mut x);
+        }
+        for layer in order.iter().rev().skip(1) {
+            place(layer, down, &mut x);
+        }
+    }
+    let left = x.iter().cloned().fold(f32::INFINITY, f32::min);
+    for x in &mut x {
+        *x -= left;
+    }
+    // the averaging and the shift can round a spacing to just below one
+    for layer in order {
+        for w in layer.windows(2) {
+            x[w[1]] = x[w[1]].max(x[w[0]] + 1.);
+        }
+    }
+    x
+}
+
+/// Move the slots of `layer` to the medians of their neighbors, keeping
+/// them in order and at least one apart.
+fn place(layer: &[usize], neighbors: &[Vec<usize>], x: &mut [f32]) {
+    let wanted = layer.iter().map(|&s| {
+        let mut p = neighbors[s].iter().map(|&t| x[t]).collect::<Vec<_>>();
+        p.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        median(&p).unwrap_or(x[s])
+    }).collect::<Vec<_>>();
+    // the leftmost and the rightmost placement that respect the spacing,
+    // averaged, which respects it as well
+    let mut left = wanted.clone();
+    for i in 1..left.len() {
+        left[i] = left[i].max(left[i - 1] + 1.);
+    }
+    let mut right = wanted;
+    for i in (0..right.len().saturating_sub(1)).rev() {
+        right[i] = right[i].min(right[i + 1] - 1.);
+    }
+    for (i, &s) in layer.iter().enumerate() {
+        x[s] = (left[i] + right[i]) / 2.;
+    }
+}
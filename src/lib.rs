@@ -8,10 +8,29 @@
 //! Petgraph also provides [`GraphMap`](./graphmap/struct.GraphMap.html) which
 //! is an hashmap-backed graph with undirected edges and only allows simple node
 //! identifiers (such as integers or references).
-
+//!
+//! # `no_std`
+//!
+//! Petgraph uses the standard library through the default feature `std`.
+//! Without it, petgraph is `#![no_std]` and only needs the `alloc` crate:
+//! `Graph`, `StableGraph`, the graph traits and the algorithms that work
+//! with indices are available, while `GraphMap`, the file formats and the
+//! algorithms that need hash maps are not.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[macro_use]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate core;
 extern crate fixedbitset;
+#[cfg(feature = "generate")]
+extern crate rand;
+#[cfg(feature = "rayon")]
+extern crate rayon;
 
 pub use graph::Graph;
+#[cfg(feature = "std")]
 pub use graphmap::GraphMap;
 
 pub use visit::{
@@ -24,17 +43,38 @@ pub use EdgeDirection::{Outgoing, Incoming};
 
 mod scored;
 pub mod algo;
+pub mod csr;
 #[cfg(feature = "generate")]
 pub mod generate;
+#[cfg(feature = "std")]
 pub mod graphmap;
 pub mod graph;
+#[cfg(feature = "std")]
 pub mod dot;
+#[cfg(feature = "std")]
+pub mod dynamic;
+#[cfg(feature = "std")]
+pub mod io;
+#[cfg(feature = "std")]
+pub mod layout;
+#[cfg(feature = "std")]
+pub mod par_algo;
+pub mod path;
+#[cfg(all(feature = "rayon", feature = "std"))]
+pub mod par_iter;
+#[cfg(feature = "std")]
+pub mod property;
 pub mod visit;
 pub mod unionfind;
+#[cfg(feature = "std")]
 mod dijkstra;
 mod isomorphism;
+#[cfg(feature = "std")]
+mod ordermap;
+#[cfg(feature = "generate")]
+mod sample;
 mod traits_graph;
-#[cfg(feature = "quickcheck")]
+#[cfg(all(feature = "quickcheck", feature = "std"))]
 pub mod quickcheck;
 
 // Index into the NodeIndex and EdgeIndex arrays
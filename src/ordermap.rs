@@ -0,0 +1,153 @@
+//! A hash map that iterates in a deterministic order.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::iter::Map;
+use std::mem;
+use std::slice;
+use std::vec;
+
+/// A hash map whose entries are kept in a vector, in insertion order.
+///
+/// Removing an entry moves the last entry into its place, so the order stays
+/// the insertion order as long as nothing is removed, and only ever depends
+/// on the sequence of insertions and removals, never on hashing.
+#[derive(Clone)]
+pub struct OrderMap<K, V> {
+    entries: Vec<(K, V)>,
+    index: HashMap<K, usize>,
+}
+
+pub type Keys<'a, K, V> = Map<slice::Iter<'a, (K, V)>, fn(&'a (K, V)) -> K>;
+pub type Iter<'a, K, V> = Map<slice::Iter<'a, (K, V)>, fn(&'a (K, V)) -> (&'a K, &'a V)>;
+pub type IterMut<'a, K, V> = Map<slice::IterMut<'a, (K, V)>,
+                                 fn(&'a mut (K, V)) -> (&'a K, &'a mut V)>;
+
+impl<K, V> OrderMap<K, V>
+    where K: Copy + Eq + Hash,
+{
+    pub fn new() -> Self {
+        OrderMap::with_capacity(0)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        OrderMap {
+            entries: Vec::with_capacity(capacity),
+            index: HashMap::with_capacity(capacity),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.index.capacity().min(self.entries.capacity())
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Return an estimate of the heap memory used, in bytes: the entries,
+    /// and a key, a position and a control byte per slot of the index.
+    pub fn memory_usage(&self) -> usize {
+        self.entries.capacity() * mem::size_of::<(K, V)>() +
+            self.index.capacity() * (mem::size_of::<(K, usize)>() + 1)
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        self.entries.reserve(additional);
+        self.index.reserve(additional);
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.index.clear();
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.index.contains_key(key)
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        match self.index.get(key) {
+            None => None,
+            Some(&i) => Some(&self.entries[i].1),
+        }
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        match self.index.get(key) {
+            None => None,
+            Some(&i) => Some(&mut self.entries[i].1),
+        }
+    }
+
+    /// Insert `value` for `key`, and return the previous value. An existing
+    /// key keeps its position.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(&i) = self.index.get(&key) {
+            return Some(mem::replace(&mut self.entries[i].1, value))
+        }
+        self.index.insert(key, self.entries.len());
+        self.entries.push((key, value));
+        None
+    }
+
+    /// Return the value for `key`, inserting `default()` first if there is
+    /// none.
+    pub fn get_or_insert_with<F>(&mut self, key: K, default: F) -> &mut V
+        where F: FnOnce() -> V,
+    {
+        let i = match self.index.get(&key) {
+            Some(&i) => i,
+            None => {
+                let i = self.entries.len();
+                self.index.insert(key, i);
+                self.entries.push((key, default()));
+                i
+            }
+        };
+        &mut self.entries[i].1
+    }
+
+    /// Remove the entry for `key`, moving the last entry into its place.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let i = match self.index.remove(key) {
+            None => return None,
+            Some(i) => i,
+        };
+        let (_, value) = self.entries.swap_remove(i);
+        if let Some(&(moved, _)) = self.entries.get(i) {
+            self.index.insert(moved, i);
+        }
+        Some(value)
+    }
+
+    pub fn keys(&self) -> Keys<K, V> {
+        fn key<K: Copy, V>(entry: &(K, V)) -> K { entry.0 }
+        self.entries.iter().map(key)
+    }
+
+    pub fn iter(&self) -> Iter<K, V> {
+        fn pair<K, V>(entry: &(K, V)) -> (&K, &V) { (&entry.0, &entry.1) }
+        self.entries.iter().map(pair)
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<K, V> {
+        fn pair<K, V>(entry: &mut (K, V)) -> (&K, &mut V) { (&entry.0, &mut entry.1) }
+        self.entries.iter_mut().map(pair)
+    }
+}
+
+impl<K, V> IntoIterator for OrderMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = vec::IntoIter<(K, V)>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for OrderMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.entries.iter().map(|e| (&e.0, &e.1))).finish()
+    }
+}
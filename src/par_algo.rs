@@ -0,0 +1,395 @@
+//! Parallel graph algorithms.
+//!
+//! These are parallel versions of algorithms whose work splits naturally into
+//! independent parts, for example one part per source node. They use the
+//! global [rayon](https://docs.rs/rayon) thread pool.
+//!
+//! The `par_` functions depend on `feature = "rayon"`. The other algorithms
+//! are always available, and run serially when the feature is disabled.
+
+#[cfg(feature = "rayon")]
+use std::collections::{
+    HashMap,
+    VecDeque,
+};
+use std::collections::BTreeMap;
+use std::f64;
+#[cfg(feature = "rayon")]
+use std::hash::Hash;
+#[cfg(feature = "rayon")]
+use std::ops::Add;
+#[cfg(feature = "rayon")]
+use std::sync::atomic::{
+    AtomicUsize,
+    Ordering,
+};
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use super::{
+    Graph,
+    EdgeType,
+};
+use super::graph::{
+    NodeIndex,
+    IndexType,
+};
+#[cfg(feature = "rayon")]
+use super::dijkstra::dijkstra;
+
+/// Compute the betweenness centrality of every node, in parallel.
+///
+/// The betweenness centrality of a node *v* is the sum, over all pairs of
+/// other nodes *s* and *t*, of the fraction of shortest *s*-*t* paths that
+/// pass through *v*. Edges are unweighted, and for an undirected graph each
+/// unordered pair is counted once.
+///
+/// If `normalized` is `true`, the scores are scaled to lie between 0 and 1,
+/// by dividing by the number of pairs of other nodes: **(|V| - 1)(|V| - 2)**
+/// for a directed graph and half that for an undirected graph.
+///
+/// Uses Brandes' algorithm with one breadth first search per source node,
+/// running the searches in parallel. Runtime **O(|V||E|)** total work.
+///
+/// Returns a vector of scores indexed by node index.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::par_algo::par_betweenness_centrality;
+///
+/// // a path a - b - c
+/// let g = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2)]);
+/// assert_eq!(par_betweenness_centrality(&g, false), [0., 1., 0.]);
+/// ```
+#[cfg(feature = "rayon")]
+pub fn par_betweenness_centrality<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>, normalized: bool)
+    -> Vec<f64>
+    where N: Sync,
+          E: Sync,
+          Ty: EdgeType + Sync,
+          Ix: IndexType + Sync,
+{
+    let n = g.node_count();
+    let mut centrality = (0..n).into_par_iter()
+        .fold(|| vec![0.; n], |mut centrality, source| {
+            accumulate_betweenness(g, source, &mut centrality);
+            centrality
+        })
+        .reduce(|| vec![0.; n], |mut a, b| {
+            for (x, y) in a.iter_mut().zip(b) {
+                *x += y;
+            }
+            a
+        });
+
+    let scale = if normalized {
+        if n > 2 { 1. / ((n - 1) * (n - 2)) as f64 } else { 1. }
+    } else if !g.is_directed() {
+        // each unordered pair was counted from both of its ends
+        0.5
+    } else {
+        1.
+    };
+    if scale != 1. {
+        for c in &mut centrality {
+            *c *= scale;
+        }
+    }
+    centrality
+}
+
+/// Add the dependencies of `source` on every other node to `centrality`.
+#[cfg(feature = "rayon")]
+fn accumulate_betweenness<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>,
+                                        source: usize,
+                                        centrality: &mut [f64])
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    let n = g.node_count();
+    // nodes in order of non-decreasing distance from the source
+    let mut stack = Vec::with_capacity(n);
+    let mut predecessors = vec![Vec::new(); n];
+    // number of shortest paths from the source
+    let mut sigma = vec![0.; n];
+    let mut distance = vec![None; n];
+    let mut queue = VecDeque::new();
+
+    sigma[source] = 1.;
+    distance[source] = Some(0);
+    queue.push_back(source);
+    while let Some(v) = queue.pop_front() {
+        stack.push(v);
+        let next_distance = distance[v].map(|d| d + 1);
+        for w in g.neighbors(NodeIndex::new(v)) {
+            let w = w.index();
+            if distance[w].is_none() {
+                distance[w] = next_distance;
+                queue.push_back(w);
+            }
+            if distance[w] == next_distance {
+                sigma[w] += sigma[v];
+                predecessors[w].push(v);
+            }
+        }
+    }
+
+    let mut delta = vec![0.; n];
+    while let Some(w) = stack.pop() {
+        for &v in &predecessors[w] {
+            delta[v] += sigma[v] / sigma[w] * (1. + delta[w]);
+        }
+        if w != source {
+            centrality[w] += delta[w];
+        }
+    }
+}
+
+/// Compute the length of the shortest paths between all pairs of nodes,
+/// running one [`dijkstra`](../algo/fn.dijkstra.html) search per source node
+/// in parallel.
+///
+/// `edge_cost` maps an edge weight to its cost, which must be non-negative.
+///
+/// Returns a vector indexed by the source's node index; each entry maps every
+/// node reachable from that source to its path cost.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::graph::NodeIndex;
+/// use petgraph::par_algo::par_all_pairs_shortest_paths;
+///
+/// let g = Graph::<(), u32>::from_edges(&[(0, 1, 2), (1, 2, 3), (0, 2, 7)]);
+/// let paths = par_all_pairs_shortest_paths(&g, |w| *w);
+/// assert_eq!(paths[0][&NodeIndex::new(2)], 5);
+/// assert!(!paths[2].contains_key(&NodeIndex::new(0)));
+/// ```
+#[cfg(feature = "rayon")]
+pub fn par_all_pairs_shortest_paths<N, E, Ty, Ix, F, K>(g: &Graph<N, E, Ty, Ix>, edge_cost: F)
+    -> Vec<HashMap<NodeIndex<Ix>, K>>
+    where N: Sync,
+          E: Sync,
+          Ty: EdgeType + Sync,
+          Ix: IndexType + Hash + Send + Sync,
+          F: Fn(&E) -> K + Sync,
+          K: Default + Add<Output=K> + Copy + PartialOrd + Send,
+{
+    (0..g.node_count()).into_par_iter()
+        .map(|source| {
+            dijkstra(g, NodeIndex::new(source), None, |g, a| {
+                g.edges(a).map(|(b, weight)| (b, edge_cost(weight)))
+            })
+        })
+        .collect()
+}
+
+/// Return the number of connected components of the graph, merging the
+/// endpoints of the edges in parallel.
+///
+/// For a directed graph, this is the *weakly* connected components.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::par_algo::par_connected_components;
+///
+/// let mut g = Graph::<(), ()>::from_edges(&[(0, 1), (2, 3), (3, 4)]);
+/// g.add_node(());
+/// assert_eq!(par_connected_components(&g), 3);
+/// ```
+#[cfg(feature = "rayon")]
+pub fn par_connected_components<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>) -> usize
+    where N: Sync,
+          E: Sync,
+          Ty: EdgeType + Sync,
+          Ix: IndexType + Sync,
+{
+    let sets = AtomicUnionFind::new(g.node_count());
+    g.raw_edges().par_iter().for_each(|edge| {
+        sets.union(edge.source().index(), edge.target().index());
+    });
+    (0..g.node_count()).into_par_iter()
+        .filter(|&x| sets.find(x) == x)
+        .count()
+}
+
+/// Compute the length of the shortest path from `source` to every node,
+/// using the delta-stepping algorithm.
+///
+/// Delta-stepping is a variant of Dijkstra's algorithm that settles the nodes
+/// in buckets of tentative distance of width `delta` instead of one at a time.
+/// All nodes in a bucket can be relaxed at once, which is done in parallel
+/// when the `rayon` feature is enabled, and serially otherwise. It performs
+/// best on large graphs of small degree, like road networks, with `delta`
+/// around the average edge cost.
+///
+/// `edge_cost` maps an edge weight to its cost, which must be non-negative.
+///
+/// Returns a vector of path costs indexed by node index; unreachable nodes
+/// have cost `f64::INFINITY`.
+///
+/// **Panics** if `delta` is not positive.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::graph::NodeIndex;
+/// use petgraph::par_algo::delta_stepping;
+///
+/// let g = Graph::<(), f64>::from_edges(&[(0, 1, 2.), (1, 2, 3.), (0, 2, 7.)]);
+/// let costs = delta_stepping(&g, NodeIndex::new(0), 2., |w| *w);
+/// assert_eq!(costs, [0., 2., 5.]);
+/// ```
+pub fn delta_stepping<N, E, Ty, Ix, F>(g: &Graph<N, E, Ty, Ix>,
+                                       source: NodeIndex<Ix>,
+                                       delta: f64,
+                                       edge_cost: F) -> Vec<f64>
+    where N: Sync,
+          E: Sync,
+          Ty: EdgeType + Sync,
+          Ix: IndexType + Sync,
+          F: Fn(&E) -> f64 + Sync,
+{
+    assert!(delta > 0., "delta_stepping: delta must be positive");
+    let mut distance = vec![f64::INFINITY; g.node_count()];
+    // only the buckets in use, since costs can be far apart
+    let mut buckets = BTreeMap::new();
+    relax(&mut distance, &mut buckets, delta, source.index(), 0.);
+
+    while let Some(i) = buckets.keys().next().cloned() {
+        // nodes removed from bucket `i`, whose heavy edges are relaxed last
+        let mut settled = Vec::new();
+        while let Some(mut frontier) = buckets.remove(&i) {
+            // skip nodes that have moved to an earlier bucket since
+            frontier.retain(|&v| bucket_index(distance[v], delta) == i);
+            frontier.sort();
+            frontier.dedup();
+            let requests = relax_requests(g, &frontier, &distance, &edge_cost,
+                                          |cost| cost <= delta);
+            for (w, d) in requests {
+                relax(&mut distance, &mut buckets, delta, w, d);
+            }
+            settled.extend(frontier);
+        }
+        settled.sort();
+        settled.dedup();
+        let requests = relax_requests(g, &settled, &distance, &edge_cost,
+                                      |cost| cost > delta);
+        for (w, d) in requests {
+            relax(&mut distance, &mut buckets, delta, w, d);
+        }
+    }
+    distance
+}
+
+fn bucket_index(distance: f64, delta: f64) -> usize {
+    (distance / delta) as usize
+}
+
+fn relax(distance: &mut [f64], buckets: &mut BTreeMap<usize, Vec<usize>>, delta: f64, w: usize, d: f64) {
+    // distances past the range of bucket indices share the last bucket
+    if d < distance[w] && d.is_finite() {
+        distance[w] = d;
+        buckets.entry(bucket_index(d, delta)).or_insert_with(Vec::new).push(w);
+    }
+}
+
+/// Return the `(node, distance)` pairs reached through the edges from `nodes`
+/// whose cost is accepted by `select`.
+#[cfg(feature = "rayon")]
+fn relax_requests<N, E, Ty, Ix, F, S>(g: &Graph<N, E, Ty, Ix>,
+                                      nodes: &[usize],
+                                      distance: &[f64],
+                                      edge_cost: &F,
+                                      select: S) -> Vec<(usize, f64)>
+    where N: Sync,
+          E: Sync,
+          Ty: EdgeType + Sync,
+          Ix: IndexType + Sync,
+          F: Fn(&E) -> f64 + Sync,
+          S: Fn(f64) -> bool + Sync,
+{
+    nodes.par_iter()
+        .flat_map(|&v| edge_requests(g, v, distance[v], edge_cost, &select))
+        .collect()
+}
+
+#[cfg(not(feature = "rayon"))]
+fn relax_requests<N, E, Ty, Ix, F, S>(g: &Graph<N, E, Ty, Ix>,
+                                      nodes: &[usize],
+                                      distance: &[f64],
+                                      edge_cost: &F,
+                                      select: S) -> Vec<(usize, f64)>
+    where Ty: EdgeType,
+          Ix: IndexType,
+          F: Fn(&E) -> f64,
+          S: Fn(f64) -> bool,
+{
+    nodes.iter()
+        .flat_map(|&v| edge_requests(g, v, distance[v], edge_cost, &select))
+        .collect()
+}
+
+fn edge_requests<N, E, Ty, Ix, F, S>(g: &Graph<N, E, Ty, Ix>,
+                                     v: usize,
+                                     distance: f64,
+                                     edge_cost: &F,
+                                     select: &S) -> Vec<(usize, f64)>
+    where Ty: EdgeType,
+          Ix: IndexType,
+          F: Fn(&E) -> f64,
+          S: Fn(f64) -> bool,
+{
+    g.edges(NodeIndex::new(v))
+        .map(|(w, weight)| (w.index(), edge_cost(weight)))
+        .filter(|&(_, cost)| select(cost))
+        .map(|(w, cost)| (w, distance + cost))
+        .collect()
+}
+
+/// A disjoint-set structure that supports concurrent `union` and `find`.
+///
+/// Roots are always linked to the root with the smaller index, so the
+/// parent pointers can't form a cycle no matter how the threads interleave.
+#[cfg(feature = "rayon")]
+struct AtomicUnionFind {
+    parent: Vec<AtomicUsize>,
+}
+
+#[cfg(feature = "rayon")]
+impl AtomicUnionFind {
+    fn new(n: usize) -> Self {
+        AtomicUnionFind {
+            parent: (0..n).map(AtomicUsize::new).collect(),
+        }
+    }
+
+    fn find(&self, mut x: usize) -> usize {
+        loop {
+            let parent = self.parent[x].load(Ordering::SeqCst);
+            if parent == x {
+                return x;
+            }
+            // path halving: it doesn't matter if another thread got there first
+            let grandparent = self.parent[parent].load(Ordering::SeqCst);
+            let _ = self.parent[x].compare_exchange(parent, grandparent,
+                                                    Ordering::SeqCst, Ordering::SeqCst);
+            x = grandparent;
+        }
+    }
+
+    fn union(&self, x: usize, y: usize) {
+        loop {
+            let xrep = self.find(x);
+            let yrep = self.find(y);
+            if xrep == yrep {
+                return;
+            }
+            let (low, high) = if xrep < yrep { (xrep, yrep) } else { (yrep, xrep) };
+            // fails if `high` stopped being a root meanwhile; then retry
+            if self.parent[high].compare_exchange(high, low, Ordering::SeqCst,
+                                                  Ordering::SeqCst).is_ok() {
+                return;
+            }
+        }
+    }
+}
@@ -0,0 +1,291 @@
+//! Parallel iterators over the nodes and edges of a graph.
+//!
+//! The iterators are created by the `par_*` methods of `Graph` and `GraphMap`
+//! and implement rayon's `ParallelIterator`, so they support the usual
+//! adaptors:
+//!
+//! ```
+//! extern crate petgraph;
+//! extern crate rayon;
+//!
+//! use petgraph::Graph;
+//! use rayon::prelude::*;
+//!
+//! # fn main() {
+//! let g = Graph::<u32, ()>::from_edges(&[(0, 1), (1, 2), (2, 0)]);
+//! let max_degree = g.par_node_indices()
+//!                   .map(|a| g.neighbors(a).count())
+//!                   .max();
+//! assert_eq!(max_degree, Some(1));
+//! # }
+//! ```
+//!
+//! Depends on `feature = "rayon"`.
+
+use std::marker::PhantomData;
+
+use rayon::prelude::*;
+use rayon::iter::plumbing::{
+    Consumer,
+    ProducerCallback,
+    UnindexedConsumer,
+};
+use rayon::range;
+use rayon::slice;
+use rayon::vec;
+
+use super::{
+    EdgeType,
+    Graph,
+    GraphMap,
+};
+use super::graph::{
+    Edge,
+    EdgeIndex,
+    IndexType,
+    Node,
+    NodeIndex,
+};
+use super::graphmap::NodeTrait;
+
+impl<N, E, Ty, Ix> Graph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    /// Return a parallel iterator over the node indices of the graph.
+    pub fn par_node_indices(&self) -> ParNodeIndices<Ix>
+    {
+        ParNodeIndices {
+            range: (0..self.node_count()).into_par_iter(),
+            ty: PhantomData,
+        }
+    }
+
+    /// Return a parallel iterator over the node weights of the graph, in
+    /// node index order.
+    pub fn par_node_weights(&self) -> ParNodeWeights<N, Ix>
+        where N: Sync,
+              Ix: Sync,
+    {
+        ParNodeWeights {
+            nodes: self.raw_nodes().par_iter(),
+        }
+    }
+
+    /// Return a parallel iterator over the edges of the graph, as pairs of
+    /// the edge index and the edge.
+    pub fn par_edge_references(&self) -> ParEdgeReferences<E, Ix>
+        where E: Sync,
+              Ix: Sync,
+    {
+        ParEdgeReferences {
+            edges: self.raw_edges().par_iter(),
+        }
+    }
+}
+
+impl<N, E> GraphMap<N, E>
+    where N: NodeTrait + Send,
+{
+    /// Return a parallel iterator over the nodes of the graph.
+    ///
+    /// The nodes are collected up front, since the hash map that stores
+    /// them can't be split between threads.
+    pub fn par_nodes(&self) -> ParNodes<N>
+    {
+        ParNodes {
+            nodes: self.nodes().collect::<Vec<_>>().into_par_iter(),
+        }
+    }
+}
+
+/// Parallel iterator over the node indices of a graph.
+///
+/// Created with [`.par_node_indices()`][1].
+///
+/// [1]: ../graph/struct.Graph.html#method.par_node_indices
+pub struct ParNodeIndices<Ix> {
+    range: range::Iter<usize>,
+    ty: PhantomData<Ix>,
+}
+
+impl<Ix> ParallelIterator for ParNodeIndices<Ix>
+    where Ix: IndexType + Send + Sync,
+{
+    type Item = NodeIndex<Ix>;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+        where C: UnindexedConsumer<Self::Item>,
+    {
+        self.range.map(NodeIndex::new).drive_unindexed(consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.range.len())
+    }
+}
+
+impl<Ix> IndexedParallelIterator for ParNodeIndices<Ix>
+    where Ix: IndexType + Send + Sync,
+{
+    fn len(&self) -> usize {
+        self.range.len()
+    }
+
+    fn drive<C>(self, consumer: C) -> C::Result
+        where C: Consumer<Self::Item>,
+    {
+        self.range.map(NodeIndex::new).drive(consumer)
+    }
+
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+        where CB: ProducerCallback<Self::Item>,
+    {
+        self.range.map(NodeIndex::new).with_producer(callback)
+    }
+}
+
+/// Parallel iterator over the node weights of a graph.
+///
+/// Created with [`.par_node_weights()`][1].
+///
+/// [1]: ../graph/struct.Graph.html#method.par_node_weights
+pub struct ParNodeWeights<'a, N: 'a, Ix: 'a + IndexType> {
+    nodes: slice::Iter<'a, Node<N, Ix>>,
+}
+
+impl<'a, N, Ix> ParallelIterator for ParNodeWeights<'a, N, Ix>
+    where N: Sync,
+          Ix: IndexType + Sync,
+{
+    type Item = &'a N;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+        where C: UnindexedConsumer<Self::Item>,
+    {
+        self.nodes.map(|node| &node.weight).drive_unindexed(consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.nodes.len())
+    }
+}
+
+impl<'a, N, Ix> IndexedParallelIterator for ParNodeWeights<'a, N, Ix>
+    where N: Sync,
+          Ix: IndexType + Sync,
+{
+    fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn drive<C>(self, consumer: C) -> C::Result
+        where C: Consumer<Self::Item>,
+    {
+        self.nodes.map(|node| &node.weight).drive(consumer)
+    }
+
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+        where CB: ProducerCallback<Self::Item>,
+    {
+        self.nodes.map(|node| &node.weight).with_producer(callback)
+    }
+}
+
+/// Parallel iterator over the edges of a graph.
+///
+/// Created with [`.par_edge_references()`][1].
+///
+/// [1]: ../graph/struct.Graph.html#method.par_edge_references
+pub struct ParEdgeReferences<'a, E: 'a, Ix: 'a + IndexType> {
+    edges: slice::Iter<'a, Edge<E, Ix>>,
+}
+
+impl<'a, E, Ix> ParallelIterator for ParEdgeReferences<'a, E, Ix>
+    where E: Sync,
+          Ix: IndexType + Send + Sync,
+{
+    type Item = (EdgeIndex<Ix>, &'a Edge<E, Ix>);
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+        where C: UnindexedConsumer<Self::Item>,
+    {
+        self.edges.enumerate()
+            .map(|(i, edge)| (EdgeIndex::new(i), edge))
+            .drive_unindexed(consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.edges.len())
+    }
+}
+
+impl<'a, E, Ix> IndexedParallelIterator for ParEdgeReferences<'a, E, Ix>
+    where E: Sync,
+          Ix: IndexType + Send + Sync,
+{
+    fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    fn drive<C>(self, consumer: C) -> C::Result
+        where C: Consumer<Self::Item>,
+    {
+        self.edges.enumerate()
+            .map(|(i, edge)| (EdgeIndex::new(i), edge))
+            .drive(consumer)
+    }
+
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+        where CB: ProducerCallback<Self::Item>,
+    {
+        self.edges.enumerate()
+            .map(|(i, edge)| (EdgeIndex::new(i), edge))
+            .with_producer(callback)
+    }
+}
+
+/// Parallel iterator over the nodes of a `GraphMap`.
+///
+/// Created with [`.par_nodes()`][1].
+///
+/// [1]: ../graphmap/struct.GraphMap.html#method.par_nodes
+pub struct ParNodes<N> {
+    nodes: vec::IntoIter<N>,
+}
+
+impl<N> ParallelIterator for ParNodes<N>
+    where N: NodeTrait + Send,
+{
+    type Item = N;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+        where C: UnindexedConsumer<Self::Item>,
+    {
+        self.nodes.drive_unindexed(consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.nodes.len())
+    }
+}
+
+impl<N> IndexedParallelIterator for ParNodes<N>
+    where N: NodeTrait + Send,
+{
+    fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn drive<C>(self, consumer: C) -> C::Result
+        where C: Consumer<Self::Item>,
+    {
+        self.nodes.drive(consumer)
+    }
+
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+        where CB: ProducerCallback<Self::Item>,
+    {
+        self.nodes.with_producer(callback)
+    }
+}
@@ -0,0 +1,364 @@
+//! `Path<G>` is a walk through a graph, as a sequence of nodes and the edges
+//! between them, and `Predecessors<N>` rebuilds the paths found by a search.
+
+use core::fmt;
+use core::ops::Add;
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::collections::hash_map;
+#[cfg(feature = "std")]
+use std::hash::Hash;
+
+use {
+    EdgeType,
+    Graph,
+};
+use graph::{
+    IndexType,
+    NodeIndex,
+};
+use visit::GraphEdgeId;
+
+/// A path through a graph `G`: a start node, then an edge and the node it
+/// leads to for each step.
+///
+/// A path always has at least one node, and has exactly one edge fewer than
+/// nodes. Nodes and edges may repeat, see [`is_simple`](#method.is_simple).
+/// [`shortest_path`](../algo/fn.shortest_path.html) returns one.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::path::Path;
+///
+/// let mut g = Graph::<&str, u32>::new();
+/// let a = g.add_node("a");
+/// let b = g.add_node("b");
+/// let c = g.add_node("c");
+/// let ab = g.add_edge(a, b, 2);
+/// let bc = g.add_edge(b, c, 3);
+///
+/// let mut path = Path::<Graph<&str, u32>>::new(a);
+/// path.push(ab, b);
+/// path.push(bc, c);
+///
+/// assert_eq!(path.len(), 2);
+/// assert_eq!(path.total_weight(|e| g[e]), 5);
+/// assert!(path.is_simple());
+/// assert_eq!(path.end(), &c);
+/// ```
+pub struct Path<G: GraphEdgeId> {
+    nodes: Vec<G::NodeId>,
+    edges: Vec<G::EdgeId>,
+}
+
+impl<G: GraphEdgeId> Path<G> {
+    /// Create a path of no edges, at the node `start`.
+    pub fn new(start: G::NodeId) -> Self {
+        Path {
+            nodes: vec![start],
+            edges: Vec::new(),
+        }
+    }
+
+    /// Create a path from its nodes and the edges between them.
+    ///
+    /// **Panics** if there is not exactly one more node than edges.
+    pub fn from_parts(nodes: Vec<G::NodeId>, edges: Vec<G::EdgeId>) -> Self {
+        assert!(nodes.len() == edges.len() + 1,
+                "Path::from_parts: must have one more node than edges");
+        Path {
+            nodes: nodes,
+            edges: edges,
+        }
+    }
+
+    /// Extend the path by the edge `edge` to the node `node`.
+    pub fn push(&mut self, edge: G::EdgeId, node: G::NodeId) {
+        self.edges.push(edge);
+        self.nodes.push(node);
+    }
+
+    /// Return the number of edges of the path.
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Return `true` if the path has no edges.
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    /// Return the first node.
+    pub fn start(&self) -> &G::NodeId {
+        &self.nodes[0]
+    }
+
+    /// Return the last node.
+    pub fn end(&self) -> &G::NodeId {
+        &self.nodes[self.nodes.len() - 1]
+    }
+
+    /// Return the nodes of the path, in order.
+    pub fn nodes(&self) -> &[G::NodeId] {
+        &self.nodes
+    }
+
+    /// Return the edges of the path, in order: edge `i` leads from node `i`
+    /// to node `i + 1`.
+    pub fn edges(&self) -> &[G::EdgeId] {
+        &self.edges
+    }
+
+    /// Return the nodes and the edges of the path.
+    pub fn into_parts(self) -> (Vec<G::NodeId>, Vec<G::EdgeId>) {
+        (self.nodes, self.edges)
+    }
+
+    /// Return the sum of `edge_cost` over the edges of the path, or the
+    /// default (zero) cost if it has no edges.
+    pub fn total_weight<F, K>(&self, mut edge_cost: F) -> K
+        where F: FnMut(G::EdgeId) -> K,
+              K: Default + Add<Output=K>,
+    {
+        self.edges.iter().fold(K::default(), |sum, e| sum + edge_cost(e.clone()))
+    }
+
+    /// Return `true` if no node of the path repeats.
+    ///
+    /// A closed path, which ends where it starts, is a *simple cycle* if no
+    /// other node repeats; check it with
+    /// [`is_simple_cycle`](#method.is_simple_cycle).
+    pub fn is_simple(&self) -> bool
+        where G::NodeId: Ord,
+    {
+        let mut seen = BTreeSet::new();
+        self.nodes.iter().all(|a| seen.insert(a))
+    }
+
+    /// Return `true` if the path has edges, ends where it starts, and no
+    /// other node repeats.
+    pub fn is_simple_cycle(&self) -> bool
+        where G::NodeId: Ord,
+    {
+        let mut seen = BTreeSet::new();
+        !self.is_empty() && self.start() == self.end() &&
+            self.nodes[1..].iter().all(|a| seen.insert(a))
+    }
+
+    /// Append `other`, which must start where `self` ends.
+    ///
+    /// **Panics** if `other` does not start at the last node of `self`.
+    pub fn concat(mut self, other: Path<G>) -> Self
+        where G::NodeId: PartialEq,
+    {
+        assert!(self.end() == other.start(),
+                "Path::concat: the paths must meet at a node");
+        self.nodes.extend(other.nodes.into_iter().skip(1));
+        self.edges.extend(other.edges);
+        self
+    }
+}
+
+impl<N, E, Ty, Ix> Path<Graph<N, E, Ty, Ix>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    /// Return the subgraph of `g` of the nodes and edges of the path, with
+    /// each repeated node or edge once.
+    ///
+    /// Also returns the original node index of each node of the subgraph:
+    /// node `i` of the subgraph is node `nodes[i]` of `g`. The nodes and the
+    /// edges keep their relative order.
+    ///
+    /// **Panics** if a node or edge of the path is not in `g`.
+    pub fn subgraph(&self, g: &Graph<N, E, Ty, Ix>)
+        -> (Graph<N, E, Ty, Ix>, Vec<NodeIndex<Ix>>)
+        where N: Clone,
+              E: Clone,
+    {
+        let mut in_path = vec![false; g.node_count()];
+        for a in &self.nodes {
+            in_path[a.index()] = true;
+        }
+        let mut edge_in_path = vec![false; g.edge_count()];
+        for e in &self.edges {
+            edge_in_path[e.index()] = true;
+        }
+        let sub = g.filter_map(|a, w| if in_path[a.index()] { Some(w.clone()) } else { None },
+                               |e, w| if edge_in_path[e.index()] { Some(w.clone()) } else { None });
+        let nodes = g.node_indices().filter(|a| in_path[a.index()]).collect();
+        (sub, nodes)
+    }
+}
+
+impl<G> Clone for Path<G>
+    where G: GraphEdgeId,
+{
+    fn clone(&self) -> Self {
+        Path {
+            nodes: self.nodes.clone(),
+            edges: self.edges.clone(),
+        }
+    }
+}
+
+impl<G> PartialEq for Path<G>
+    where G: GraphEdgeId,
+          G::NodeId: PartialEq,
+          G::EdgeId: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.nodes == other.nodes && self.edges == other.edges
+    }
+}
+
+impl<G> Eq for Path<G>
+    where G: GraphEdgeId,
+          G::NodeId: Eq,
+          G::EdgeId: Eq,
+{
+}
+
+impl<G> fmt::Debug for Path<G>
+    where G: GraphEdgeId,
+          G::NodeId: fmt::Debug,
+          G::EdgeId: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Path")
+         .field("nodes", &self.nodes)
+         .field("edges", &self.edges)
+         .finish()
+    }
+}
+
+/// The predecessor of each node reached by a search, from which the paths
+/// of the search tree are rebuilt.
+///
+/// Each reached node is either a *source*, where the search started, or has
+/// the node it was reached from as its predecessor. Searches like
+/// [`dijkstra_predecessors`](../algo/fn.dijkstra_predecessors.html) and
+/// [`bfs_predecessors`](../algo/fn.bfs_predecessors.html) return it.
+///
+/// ```
+/// use petgraph::path::Predecessors;
+///
+/// let mut pred = Predecessors::new();
+/// pred.add_source('a');
+/// pred.set_predecessor('b', 'a');
+/// pred.set_predecessor('c', 'b');
+///
+/// assert_eq!(pred.path_to(&'c'), Some(vec!['a', 'b', 'c']));
+/// assert_eq!(pred.path_to(&'a'), Some(vec!['a']));
+/// assert_eq!(pred.path_to(&'d'), None);
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Predecessors<N: Eq + Hash> {
+    map: HashMap<N, Option<N>>,
+}
+
+#[cfg(feature = "std")]
+impl<N> Predecessors<N>
+    where N: Clone + Eq + Hash,
+{
+    /// Create an empty predecessor map.
+    pub fn new() -> Self {
+        Predecessors {
+            map: HashMap::new(),
+        }
+    }
+
+    /// Record `a` as a source, without a predecessor.
+    pub fn add_source(&mut self, a: N) {
+        self.map.insert(a, None);
+    }
+
+    /// Record `pred` as the predecessor of `a`, replacing any previous one.
+    pub fn set_predecessor(&mut self, a: N, pred: N) {
+        self.map.insert(a, Some(pred));
+    }
+
+    /// Return the predecessor of `a`, or `None` if `a` is a source or was
+    /// not reached.
+    pub fn predecessor(&self, a: &N) -> Option<&N> {
+        match self.map.get(a) {
+            Some(&Some(ref pred)) => Some(pred),
+            _ => None,
+        }
+    }
+
+    /// Return `true` if `a` was reached, or is a source.
+    pub fn contains(&self, a: &N) -> bool {
+        self.map.contains_key(a)
+    }
+
+    /// Return the number of reached nodes, sources included.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Return `true` if no node was reached.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Return an iterator of the reached nodes and their predecessors, `None`
+    /// for sources, in arbitrary order.
+    pub fn iter(&self) -> PredecessorsIter<N> {
+        PredecessorsIter {
+            iter: self.map.iter(),
+        }
+    }
+
+    /// Return the nodes of the path from a source to `goal`, both inclusive,
+    /// or `None` if `goal` was not reached.
+    ///
+    /// **Panics** if the predecessors form a cycle.
+    pub fn path_to(&self, goal: &N) -> Option<Vec<N>> {
+        if !self.contains(goal) {
+            return None
+        }
+        let mut path = vec![goal.clone()];
+        let mut node = goal;
+        while let Some(pred) = self.predecessor(node) {
+            assert!(path.len() <= self.map.len(), "Predecessors::path_to: cycle of predecessors");
+            path.push(pred.clone());
+            node = pred;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<N> Default for Predecessors<N>
+    where N: Clone + Eq + Hash,
+{
+    fn default() -> Self {
+        Predecessors::new()
+    }
+}
+
+/// An iterator of the reached nodes and their predecessors, see
+/// [`Predecessors::iter`](struct.Predecessors.html#method.iter).
+#[cfg(feature = "std")]
+pub struct PredecessorsIter<'a, N: 'a> {
+    iter: hash_map::Iter<'a, N, Option<N>>,
+}
+
+#[cfg(feature = "std")]
+impl<'a, N> Iterator for PredecessorsIter<'a, N> {
+    type Item = (&'a N, Option<&'a N>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(a, pred)| (a, pred.as_ref()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
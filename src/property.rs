@@ -0,0 +1,328 @@
+//! Secondary storage of node and edge data, outside of the graph.
+//!
+//! A [`NodeMap`](type.NodeMap.html) or [`EdgeMap`](type.EdgeMap.html) attaches
+//! values to node or edge indices, so that an algorithm can keep temporary
+//! data (a distance, a color, a visited flag) without changing the weights of
+//! the graph.
+
+use std::collections::hash_map;
+use std::collections::HashMap;
+use std::mem;
+use std::ops::{
+    Index,
+    IndexMut,
+};
+use std::slice;
+
+use graph::{
+    DefIndex,
+    EdgeIndex,
+    GraphIndex,
+    NodeIndex,
+};
+
+/// A map from node indices to values.
+pub type NodeMap<T, Ix = DefIndex> = PropertyMap<NodeIndex<Ix>, T>;
+
+/// A map from edge indices to values.
+pub type EdgeMap<T, Ix = DefIndex> = PropertyMap<EdgeIndex<Ix>, T>;
+
+/// A map from node or edge indices `K` to values `T`.
+///
+/// The values are stored either in a vector, by index, or in a hash map:
+///
+/// - [`PropertyMap::dense`](#method.dense) suits a `Graph`, whose indices are
+///   the compact range `0..n`, and maps that get a value for most indices.
+/// - [`PropertyMap::sparse`](#method.sparse) suits a `StableGraph`, whose
+///   indices can have holes, and maps that get a value for few indices.
+///
+/// Iteration is in index order for a dense map, and in arbitrary order for a
+/// sparse map.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::property::NodeMap;
+///
+/// let g = Graph::<&str, ()>::from_edges(&[(0, 1), (0, 2), (1, 2)]);
+///
+/// // count the incoming edges of each node
+/// let mut in_degree = NodeMap::dense(g.node_count());
+/// for edge in g.raw_edges() {
+///     *in_degree.entry(edge.target()).or_insert(0) += 1;
+/// }
+/// assert_eq!(in_degree.get(0.into()), None);
+/// assert_eq!(in_degree[1.into()], 1);
+/// assert_eq!(in_degree[2.into()], 2);
+/// ```
+#[derive(Clone, Debug)]
+pub struct PropertyMap<K, T> {
+    storage: Storage<K, T>,
+    len: usize,
+}
+
+/// The values are stored with their keys, so that iteration can return the
+/// keys without rebuilding them from their indices.
+#[derive(Clone, Debug)]
+enum Storage<K, T> {
+    Dense(Vec<Option<(K, T)>>),
+    Sparse(HashMap<usize, (K, T)>),
+}
+
+impl<K: GraphIndex, T> PropertyMap<K, T> {
+    /// Create an empty map stored in a vector, with room for the indices
+    /// below `capacity`.
+    pub fn dense(capacity: usize) -> Self {
+        let mut values = Vec::with_capacity(capacity);
+        grow(&mut values, capacity);
+        PropertyMap {
+            storage: Storage::Dense(values),
+            len: 0,
+        }
+    }
+
+    /// Create an empty map stored in a hash map.
+    pub fn sparse() -> Self {
+        PropertyMap {
+            storage: Storage::Sparse(HashMap::new()),
+            len: 0,
+        }
+    }
+
+    /// Return the number of values in the map.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return `true` if the map has no values.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Remove all the values.
+    pub fn clear(&mut self) {
+        match self.storage {
+            Storage::Dense(ref mut values) => {
+                for value in values {
+                    *value = None;
+                }
+            }
+            Storage::Sparse(ref mut values) => values.clear(),
+        }
+        self.len = 0;
+    }
+
+    /// Return `true` if the map has a value for `key`.
+    pub fn contains_key(&self, key: K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Return the value for `key`, if any.
+    pub fn get(&self, key: K) -> Option<&T> {
+        match self.storage {
+            Storage::Dense(ref values) => {
+                values.get(key.index()).and_then(|v| v.as_ref()).map(|v| &v.1)
+            }
+            Storage::Sparse(ref values) => values.get(&key.index()).map(|v| &v.1),
+        }
+    }
+
+    /// Return the value for `key` mutably, if any.
+    pub fn get_mut(&mut self, key: K) -> Option<&mut T> {
+        match self.storage {
+            Storage::Dense(ref mut values) => {
+                values.get_mut(key.index()).and_then(|v| v.as_mut()).map(|v| &mut v.1)
+            }
+            Storage::Sparse(ref mut values) => values.get_mut(&key.index()).map(|v| &mut v.1),
+        }
+    }
+
+    /// Set the value for `key`, and return the previous value, if any.
+    pub fn insert(&mut self, key: K, value: T) -> Option<T> {
+        let old = match self.storage {
+            Storage::Dense(ref mut values) => {
+                let i = key.index();
+                grow(values, i + 1);
+                mem::replace(&mut values[i], Some((key, value)))
+            }
+            Storage::Sparse(ref mut values) => values.insert(key.index(), (key, value)),
+        };
+        let old = old.map(|v| v.1);
+        if old.is_none() {
+            self.len += 1;
+        }
+        old
+    }
+
+    /// Remove the value for `key`, and return it, if any.
+    pub fn remove(&mut self, key: K) -> Option<T> {
+        let old = match self.storage {
+            Storage::Dense(ref mut values) => {
+                values.get_mut(key.index()).and_then(|v| v.take())
+            }
+            Storage::Sparse(ref mut values) => values.remove(&key.index()),
+        };
+        let old = old.map(|v| v.1);
+        if old.is_some() {
+            self.len -= 1;
+        }
+        old
+    }
+
+    /// Return the entry for `key`, to read, insert or update its value in
+    /// place.
+    pub fn entry(&mut self, key: K) -> Entry<K, T> {
+        Entry {
+            map: self,
+            key: key,
+        }
+    }
+
+    /// Return an iterator of the indices and values in the map.
+    pub fn iter(&self) -> Iter<K, T> {
+        let iter = match self.storage {
+            Storage::Dense(ref values) => IterInner::Dense(values.iter()),
+            Storage::Sparse(ref values) => IterInner::Sparse(values.values()),
+        };
+        Iter {
+            iter: iter,
+        }
+    }
+}
+
+impl<K: GraphIndex, T> Default for PropertyMap<K, T> {
+    /// Create an empty dense map.
+    fn default() -> Self {
+        PropertyMap::dense(0)
+    }
+}
+
+/// Index the map by `key`.
+///
+/// **Panics** if the map has no value for `key`.
+impl<K: GraphIndex, T> Index<K> for PropertyMap<K, T> {
+    type Output = T;
+    fn index(&self, key: K) -> &T {
+        self.get(key).expect("PropertyMap: no value for the index")
+    }
+}
+
+/// Index the map by `key`.
+///
+/// **Panics** if the map has no value for `key`.
+impl<K: GraphIndex, T> IndexMut<K> for PropertyMap<K, T> {
+    fn index_mut(&mut self, key: K) -> &mut T {
+        self.get_mut(key).expect("PropertyMap: no value for the index")
+    }
+}
+
+/// The entry of an index in a [`PropertyMap`](struct.PropertyMap.html).
+pub struct Entry<'a, K: 'a, T: 'a> {
+    map: &'a mut PropertyMap<K, T>,
+    key: K,
+}
+
+impl<'a, K: GraphIndex, T> Entry<'a, K, T> {
+    /// Return the index of the entry.
+    pub fn key(&self) -> K {
+        self.key
+    }
+
+    /// Insert `default` if the entry has no value, and return the value.
+    pub fn or_insert(self, default: T) -> &'a mut T {
+        self.or_insert_with(|| default)
+    }
+
+    /// Insert the result of `default` if the entry has no value, and return
+    /// the value.
+    pub fn or_insert_with<F>(self, default: F) -> &'a mut T
+        where F: FnOnce() -> T,
+    {
+        let key = self.key;
+        let i = key.index();
+        let PropertyMap { ref mut storage, ref mut len, .. } = *self.map;
+        match *storage {
+            Storage::Dense(ref mut values) => {
+                grow(values, i + 1);
+                if values[i].is_none() {
+                    values[i] = Some((key, default()));
+                    *len += 1;
+                }
+                &mut values[i].as_mut().unwrap().1
+            }
+            Storage::Sparse(ref mut values) => {
+                match values.entry(i) {
+                    hash_map::Entry::Occupied(entry) => &mut entry.into_mut().1,
+                    hash_map::Entry::Vacant(entry) => {
+                        *len += 1;
+                        &mut entry.insert((key, default())).1
+                    }
+                }
+            }
+        }
+    }
+
+    /// Call `f` on the value, if the entry has one.
+    pub fn and_modify<F>(self, f: F) -> Self
+        where F: FnOnce(&mut T),
+    {
+        if let Some(value) = self.map.get_mut(self.key) {
+            f(value);
+        }
+        self
+    }
+}
+
+impl<'a, K: GraphIndex, T: Default> Entry<'a, K, T> {
+    /// Insert the default value if the entry has no value, and return the
+    /// value.
+    pub fn or_default(self) -> &'a mut T {
+        self.or_insert_with(T::default)
+    }
+}
+
+/// Iterator of the indices and values of a
+/// [`PropertyMap`](struct.PropertyMap.html).
+pub struct Iter<'a, K: 'a, T: 'a> {
+    iter: IterInner<'a, K, T>,
+}
+
+enum IterInner<'a, K: 'a, T: 'a> {
+    Dense(slice::Iter<'a, Option<(K, T)>>),
+    Sparse(hash_map::Values<'a, usize, (K, T)>),
+}
+
+impl<'a, K: GraphIndex, T> Iterator for Iter<'a, K, T> {
+    type Item = (K, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.iter {
+            IterInner::Dense(ref mut iter) => {
+                for entry in iter {
+                    if let Some((key, ref value)) = *entry {
+                        return Some((key, value))
+                    }
+                }
+                None
+            }
+            IterInner::Sparse(ref mut iter) => {
+                iter.next().map(|&(key, ref value)| (key, value))
+            }
+        }
+    }
+}
+
+impl<'a, K: GraphIndex, T> IntoIterator for &'a PropertyMap<K, T> {
+    type Item = (K, &'a T);
+    type IntoIter = Iter<'a, K, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Extend `values` with `None` up to length `len`, without requiring
+/// `T: Clone`.
+fn grow<T>(values: &mut Vec<Option<T>>, len: usize) {
+    while values.len() < len {
+        values.push(None);
+    }
+}
@@ -8,6 +8,7 @@ use {
 };
 use graph::{
     IndexType,
+    edge_index,
     node_index,
 };
 #[cfg(feature = "stable_graph")]
@@ -56,10 +57,11 @@ impl<N, E, Ty, Ix> Arbitrary for Graph<N, E, Ty, Ix>
     }
 
     // shrink the graph by splitting it in two by a very
-    // simple algorithm, just even and odd node indices
+    // simple algorithm, just even and odd node indices,
+    // then by removing one node or one edge at a time
     fn shrink(&self) -> Box<Iterator<Item=Self>> {
         let self_ = self.clone();
-        Box::new((0..2).filter_map(move |x| {
+        let halves = (0..2).filter_map(move |x| {
             let gr = self_.filter_map(|i, w| {
                 if i.index() % 2 == x {
                     Some(w.clone())
@@ -75,7 +77,20 @@ impl<N, E, Ty, Ix> Arbitrary for Graph<N, E, Ty, Ix>
             } else {
                 None
             }
-        }))
+        });
+        let self_ = self.clone();
+        let without_node = (0..self.node_count()).map(move |i| {
+            let mut gr = self_.clone();
+            gr.remove_node(node_index(i));
+            gr
+        });
+        let self_ = self.clone();
+        let without_edge = (0..self.edge_count()).map(move |i| {
+            let mut gr = self_.clone();
+            gr.remove_edge(edge_index(i));
+            gr
+        });
+        Box::new(halves.chain(without_node).chain(without_edge))
     }
 }
 
@@ -118,6 +133,33 @@ impl<N, E, Ty, Ix> Arbitrary for StableGraph<N, E, Ty, Ix>
         }
         gr
     }
+
+    // shrink the graph by removing one node or one edge at a time
+    fn shrink(&self) -> Box<Iterator<Item=Self>> {
+        let nodes = self.node_indices().collect::<Vec<_>>();
+        // removed edges leave holes, so probe the indices until all are found
+        let mut edges = Vec::with_capacity(self.edge_count());
+        let mut i = 0;
+        while edges.len() < self.edge_count() {
+            if self.edge_endpoints(edge_index(i)).is_some() {
+                edges.push(edge_index(i));
+            }
+            i += 1;
+        }
+        let self_ = self.clone();
+        let without_node = nodes.into_iter().map(move |a| {
+            let mut gr = self_.clone();
+            gr.remove_node(a);
+            gr
+        });
+        let self_ = self.clone();
+        let without_edge = edges.into_iter().map(move |e| {
+            let mut gr = self_.clone();
+            gr.remove_edge(e);
+            gr
+        });
+        Box::new(without_node.chain(without_edge))
+    }
 }
 
 /// `Arbitrary` for `GraphMap` creates a graph by selecting a node count
@@ -154,4 +196,23 @@ impl<N, E> Arbitrary for GraphMap<N, E>
         }
         gr
     }
+
+    // shrink the graph by removing one node or one edge at a time
+    fn shrink(&self) -> Box<Iterator<Item=Self>> {
+        let nodes = self.nodes().collect::<Vec<_>>();
+        let edges = self.all_edges().map(|(a, b, _)| (a, b)).collect::<Vec<_>>();
+        let self_ = self.clone();
+        let without_node = nodes.into_iter().map(move |n| {
+            let mut gr = self_.clone();
+            gr.remove_node(n);
+            gr
+        });
+        let self_ = self.clone();
+        let without_edge = edges.into_iter().map(move |(a, b)| {
+            let mut gr = self_.clone();
+            gr.remove_edge(a, b);
+            gr
+        });
+        Box::new(without_node.chain(without_edge))
+    }
 }
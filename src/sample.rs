@@ -0,0 +1,137 @@
+use rand::Rng;
+
+use super::{
+    EdgeType,
+    Graph,
+};
+use super::graph::{
+    EdgeIndex,
+    IndexType,
+    NodeIndex,
+};
+
+/// Choose `k` distinct indices below `n`, or all of them if `k >= n`, with
+/// a partial Fisher–Yates shuffle.
+fn sample_indices<R: Rng>(n: usize, k: usize, rng: &mut R) -> Vec<usize> {
+    let mut indices = (0..n).collect::<Vec<_>>();
+    let k = if k < n { k } else { n };
+    for i in 0..k {
+        let j = rng.gen_range(i, n);
+        indices.swap(i, j);
+    }
+    indices.truncate(k);
+    indices
+}
+
+/// Random sampling.
+///
+/// ***Unstable: API may change at any time.*** Depends on `feature = "generate"`.
+impl<N, E, Ty, Ix> Graph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    /// Return `k` distinct nodes chosen uniformly at random, in random
+    /// order, or all nodes if there are no more than `k`.
+    pub fn sample_nodes<R: Rng>(&self, k: usize, rng: &mut R) -> Vec<NodeIndex<Ix>> {
+        sample_indices(self.node_count(), k, rng).into_iter().map(NodeIndex::new).collect()
+    }
+
+    /// Return `k` distinct edges chosen uniformly at random, in random
+    /// order, or all edges if there are no more than `k`.
+    pub fn sample_edges<R: Rng>(&self, k: usize, rng: &mut R) -> Vec<EdgeIndex<Ix>> {
+        sample_indices(self.edge_count(), k, rng).into_iter().map(EdgeIndex::new).collect()
+    }
+
+    /// Return the subgraph induced by `k` nodes chosen uniformly at random:
+    /// those nodes and all the edges between them.
+    ///
+    /// The nodes and edges keep their relative order.
+    ///
+    /// ```
+    /// extern crate rand;
+    /// extern crate petgraph;
+    ///
+    /// use petgraph::generate::complete_graph;
+    /// use petgraph::Undirected;
+    ///
+    /// # fn main() {
+    /// let g = complete_graph::<Undirected>(10);
+    /// let sample = g.random_node_subgraph(4, &mut rand::thread_rng());
+    /// assert_eq!(sample.node_count(), 4);
+    /// assert_eq!(sample.edge_count(), 6);
+    /// # }
+    /// ```
+    pub fn random_node_subgraph<R: Rng>(&self, k: usize, rng: &mut R) -> Self
+        where N: Clone,
+              E: Clone,
+    {
+        let nodes = self.sample_nodes(k, rng);
+        self.induced_subgraph(&nodes)
+    }
+
+    /// Return the subgraph induced by the nodes visited by a random walk,
+    /// until it has visited `k` distinct nodes, or all nodes if there are no
+    /// more than `k`.
+    ///
+    /// The walk starts at a random node and follows outgoing edges (any
+    /// edge, for an undirected graph). When it is stuck, at a node without
+    /// outgoing edges or after `node_count` steps without reaching a new
+    /// node, it jumps to a random node. Compared to
+    /// [`.random_node_subgraph()`](#method.random_node_subgraph), the sample
+    /// keeps more of the local structure of the graph.
+    ///
+    /// The nodes and edges keep their relative order.
+    pub fn random_walk_subgraph<R: Rng>(&self, k: usize, rng: &mut R) -> Self
+        where N: Clone,
+              E: Clone,
+    {
+        let n = self.node_count();
+        let k = if k < n { k } else { n };
+        let mut visited = vec![false; n];
+        let mut nodes = Vec::with_capacity(k);
+        let mut current = None;
+        let mut idle = 0;
+        while nodes.len() < k {
+            let next = match current {
+                Some(a) if idle < n => {
+                    let neighbors = self.neighbors(a).collect::<Vec<_>>();
+                    if neighbors.is_empty() {
+                        None
+                    } else {
+                        Some(neighbors[rng.gen_range(0, neighbors.len())])
+                    }
+                }
+                _ => None,
+            };
+            let a = match next {
+                Some(a) => a,
+                None => {
+                    idle = 0;
+                    NodeIndex::new(rng.gen_range(0, n))
+                }
+            };
+            if visited[a.index()] {
+                idle += 1;
+            } else {
+                visited[a.index()] = true;
+                nodes.push(a);
+                idle = 0;
+            }
+            current = Some(a);
+        }
+        self.induced_subgraph(&nodes)
+    }
+
+    /// Return the subgraph with the nodes `nodes` and the edges between them.
+    fn induced_subgraph(&self, nodes: &[NodeIndex<Ix>]) -> Self
+        where N: Clone,
+              E: Clone,
+    {
+        let mut keep = vec![false; self.node_count()];
+        for &a in nodes {
+            keep[a.index()] = true;
+        }
+        self.filter_map(|a, w| if keep[a.index()] { Some(w.clone()) } else { None },
+                        |_, w| Some(w.clone()))
+    }
+}
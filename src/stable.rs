@@ -3,11 +3,13 @@
 //! ***Unstable: API may change at any time.*** Depends on `feature = "stable_graph"`.
 //!
 
-use std::fmt;
-use std::iter;
-use std::mem::replace;
-use std::ops::{Index, IndexMut};
-use std::slice;
+use core::fmt;
+use core::iter;
+use core::marker::PhantomData;
+use core::mem::replace;
+use core::ops::{Index, IndexMut};
+use core::slice;
+use alloc::vec::Vec;
 
 use {
     EdgeType,
@@ -119,6 +121,16 @@ impl<N, E, Ty, Ix> StableGraph<N, E, Ty, Ix>
         self.g.capacity()
     }
 
+    /// Return an estimate of the heap memory used by the graph, in bytes,
+    /// as [`Graph::memory_usage`](../struct.Graph.html#method.memory_usage).
+    ///
+    /// Vacant places of removed nodes and edges are counted.
+    ///
+    /// Computes in **O(1)** time.
+    pub fn memory_usage(&self) -> usize {
+        self.g.memory_usage()
+    }
+
     /// Remove all nodes and edges
     pub fn clear(&mut self) {
         self.node_count = 0;
@@ -142,6 +154,15 @@ impl<N, E, Ty, Ix> StableGraph<N, E, Ty, Ix>
         self.edge_count
     }
 
+    /// Return an upper bound of the node indices in the graph
+    /// (highest node index in use + 1).
+    ///
+    /// Since nodes may have been removed, the bound can be larger than
+    /// the node count.
+    pub fn node_bound(&self) -> usize {
+        self.node_indices().next_back().map_or(0, |i| i.index() + 1)
+    }
+
     /// Whether the graph has directed edges or not.
     #[inline]
     pub fn is_directed(&self) -> bool {
@@ -220,6 +241,11 @@ impl<N, E, Ty, Ix> StableGraph<N, E, Ty, Ix>
         self.g.nodes.get(a.index()).map_or(false, |no| no.weight.is_some())
     }
 
+    /// Return `true` if the edge `e` is in the graph.
+    pub fn contains_edge(&self, e: EdgeIndex<Ix>) -> bool {
+        self.edge_weight(e).is_some()
+    }
+
     /// Add an edge from `a` to `b` to the graph, with its associated
     /// data `weight`.
     ///
@@ -316,6 +342,22 @@ impl<N, E, Ty, Ix> StableGraph<N, E, Ty, Ix>
         }
     }
 
+    /// Return an iterator over either the nodes without edges to them
+    /// (`Incoming`) or from them (`Outgoing`).
+    ///
+    /// An *internal* node has both incoming and outgoing edges.
+    /// The nodes in `.externals(Incoming)` are the source nodes and
+    /// `.externals(Outgoing)` are the sinks of the graph.
+    ///
+    /// For a graph with undirected edges, both the sinks and the sources are
+    /// just the nodes without edges.
+    ///
+    /// The whole iteration computes in **O(|V|)** time.
+    pub fn externals(&self, dir: EdgeDirection) -> Externals<N, Ty, Ix>
+    {
+        Externals { iter: self.g.nodes.iter().enumerate(), dir: dir, ty: PhantomData }
+    }
+
     /// Return an iterator over the node indices of the graph
     pub fn node_indices(&self) -> NodeIndices<N, Ix> {
         NodeIndices {
@@ -517,6 +559,115 @@ impl<N, E, Ty, Ix> Default for StableGraph<N, E, Ty, Ix>
     fn default() -> Self { Self::with_capacity(0, 0) }
 }
 
+/// Convert a `Graph` into a `StableGraph`
+///
+/// Computes in **O(|V| + |E|)** time.
+///
+/// The resulting graph has the same node and edge indices as
+/// the original graph.
+impl<N, E, Ty, Ix> From<Graph<N, E, Ty, Ix>> for StableGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn from(g: Graph<N, E, Ty, Ix>) -> Self {
+        let nodes = g.nodes.into_iter().map(|e| Node {
+            weight: Some(e.weight),
+            next: e.next,
+        });
+        let edges = g.edges.into_iter().map(|e| Edge {
+            weight: Some(e.weight),
+            node: e.node,
+            next: e.next,
+        });
+        let g = Graph {
+            nodes: nodes.collect::<Vec<_>>(),
+            edges: edges.collect::<Vec<_>>(),
+            ty: g.ty,
+        };
+        StableGraph {
+            node_count: g.node_count(),
+            edge_count: g.edge_count(),
+            g: g,
+            free_node: NodeIndex::end(),
+            free_edge: EdgeIndex::end(),
+        }
+    }
+}
+
+/// Convert a `StableGraph` into a `Graph`
+///
+/// Computes in **O(|V| + |E|)** time.
+///
+/// This translates the stable graph into a graph with node and edge indices in
+/// a compact interval without holes (like `Graph`s always are).
+///
+/// Only if the stable graph had no vacancies after deletions (if node bound was
+/// equal to node count, and the same for edges), would the resulting graph have
+/// the same node and edge indices as the input. Otherwise, the remaining nodes
+/// and edges keep their relative order, but their indices are shifted down to
+/// fill the holes.
+impl<N, E, Ty, Ix> From<StableGraph<N, E, Ty, Ix>> for Graph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn from(graph: StableGraph<N, E, Ty, Ix>) -> Self {
+        let mut result_g = Graph::with_capacity(graph.node_count(), graph.edge_count());
+        // mapping from old node index to new node index, end represents removed.
+        let mut node_index_map = vec![NodeIndex::end(); graph.g.node_count()];
+
+        let (nodes, edges) = graph.g.into_nodes_edges();
+        for (i, node) in nodes.into_iter().enumerate() {
+            if let Some(nw) = node.weight {
+                node_index_map[i] = result_g.add_node(nw);
+            }
+        }
+        for edge in edges {
+            // vacant edges have no endpoints
+            if let Some(ew) = edge.weight {
+                let source = node_index_map[edge.node[0].index()];
+                let target = node_index_map[edge.node[1].index()];
+                debug_assert!(source != NodeIndex::end());
+                debug_assert!(target != NodeIndex::end());
+                result_g.add_edge(source, target, ew);
+            }
+        }
+        result_g
+    }
+}
+
+/// An iterator over either the nodes without edges to them or from them.
+pub struct Externals<'a, N: 'a, Ty, Ix: IndexType = DefIndex> {
+    iter: iter::Enumerate<slice::Iter<'a, Node<Option<N>, Ix>>>,
+    dir: EdgeDirection,
+    ty: PhantomData<Ty>,
+}
+
+impl<'a, N: 'a, Ty, Ix> Iterator for Externals<'a, N, Ty, Ix> where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    type Item = NodeIndex<Ix>;
+    fn next(&mut self) -> Option<NodeIndex<Ix>>
+    {
+        let k = self.dir as usize;
+        loop {
+            match self.iter.next() {
+                None => return None,
+                Some((index, node)) => {
+                    if node.weight.is_some() &&
+                        node.next[k] == EdgeIndex::end() &&
+                        (Ty::is_directed() ||
+                         node.next[1-k] == EdgeIndex::end()) {
+                        return Some(NodeIndex::new(index))
+                    } else {
+                        continue
+                    }
+                },
+            }
+        }
+    }
+}
+
 /// Iterator over the neighbors of a node.
 ///
 /// Iterator element type is `NodeIndex`.
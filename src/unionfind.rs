@@ -1,5 +1,7 @@
 //! `UnionFind<K>` is a disjoint-set data structure.
 
+use alloc::vec::Vec;
+
 use super::graph::IndexType;
 
 /// `UnionFind<K>` is a disjoint-set data structure. It tracks set membership of *n* elements
@@ -24,6 +26,8 @@ pub struct UnionFind<K>
     // Rank is separated out both to save space and to save cache in when searching in the parent
     // vector.
     rank: Vec<u8>,
+    // For a representative, the number of elements in its set.
+    size: Vec<usize>,
 }
 
 #[inline]
@@ -40,9 +44,38 @@ impl<K> UnionFind<K>
     pub fn new(n: usize) -> Self
     {
         let rank = vec![0; n];
+        let size = vec![1; n];
         let parent = (0..n).map(K::new).collect::<Vec<K>>();
 
-        UnionFind{parent: parent, rank: rank}
+        UnionFind{parent: parent, rank: rank, size: size}
+    }
+
+    /// Create a new `UnionFind` of `labels.len()` elements, where each element
+    /// `i` is in the same set as `labels[i]`.
+    ///
+    /// For example, this restores the sets from the vector returned by
+    /// `into_labeling`.
+    ///
+    /// **Panics** if a label is out of bounds.
+    pub fn new_from_labels(labels: &[K]) -> Self
+    {
+        let mut u = Self::new(labels.len());
+        for (i, &label) in labels.iter().enumerate() {
+            u.union(K::new(i), label);
+        }
+        u
+    }
+
+    /// Return the number of elements.
+    pub fn len(&self) -> usize
+    {
+        self.parent.len()
+    }
+
+    /// Return `true` if there are no elements.
+    pub fn is_empty(&self) -> bool
+    {
+        self.parent.is_empty()
     }
 
     /// Return the representative for `x`.
@@ -68,30 +101,65 @@ impl<K> UnionFind<K>
     /// Return the representative for `x`.
     ///
     /// Write back the found representative, flattening the internal
-    /// datastructure in the process and quicken future lookups.
+    /// datastructure in the process and quicken future lookups: every element
+    /// on the path from `x` is made to point directly to the representative.
     ///
     /// **Panics** if `x` is out of bounds.
     pub fn find_mut(&mut self, x: K) -> K
     {
         assert!(x.index() < self.parent.len());
         unsafe {
-            self.find_mut_recursive(x)
+            self.find_mut_unchecked(x)
         }
     }
 
-    unsafe fn find_mut_recursive(&mut self, x: K) -> K
+    unsafe fn find_mut_unchecked(&mut self, x: K) -> K
     {
-        let xparent = *get_unchecked(&self.parent, x.index());
-        if xparent != x {
-            let xrep = self.find_mut_recursive(xparent);
+        let xrep = {
+            let mut xrep = x;
+            loop {
+                let xparent = *get_unchecked(&self.parent, xrep.index());
+                if xparent == xrep {
+                    break xrep
+                }
+                xrep = xparent;
+            }
+        };
+        // second pass: point the whole path to the representative
+        let mut x = x;
+        while x != xrep {
             let xparent = self.parent.get_unchecked_mut(x.index());
+            x = *xparent;
             *xparent = xrep;
-            *xparent
-        } else {
-            xparent
         }
+        xrep
     }
 
+    /// Return `true` if `x` and `y` are in the same set.
+    ///
+    /// **Panics** if `x` or `y` is out of bounds.
+    pub fn equiv(&self, x: K, y: K) -> bool
+    {
+        self.find(x) == self.find(y)
+    }
+
+    /// Return the number of elements in the set containing `x`.
+    ///
+    /// **Panics** if `x` is out of bounds.
+    pub fn size(&self, x: K) -> usize
+    {
+        self.size[self.find(x).index()]
+    }
+
+    /// Return an iterator over the representatives, one for each set, in
+    /// increasing order.
+    pub fn representatives(&self) -> Representatives<K>
+    {
+        Representatives {
+            parent: &self.parent,
+            index: 0,
+        }
+    }
 
     /// Unify the two sets containing `x` and `y`.
     ///
@@ -114,17 +182,21 @@ impl<K> UnionFind<K>
         let yrepu = yrep.index();
         let xrank = self.rank[xrepu];
         let yrank = self.rank[yrepu];
+        let size = self.size[xrepu] + self.size[yrepu];
 
         // The rank corresponds roughly to the depth of the treeset, so put the 
         // smaller set below the larger
         if xrank < yrank {
             self.parent[xrepu] = yrep;
+            self.size[yrepu] = size;
         } else if xrank > yrank {
             self.parent[yrepu] = xrep;
+            self.size[xrepu] = size;
         } else {
             // put y below x when equal.
             self.parent[yrepu] = xrep;
             self.rank[xrepu] += 1;
+            self.size[xrepu] = size;
         }
         true
     }
@@ -136,10 +208,38 @@ impl<K> UnionFind<K>
         unsafe {
             for ix in 0..self.parent.len() {
                 let k = *get_unchecked(&self.parent, ix);
-                let xrep = self.find_mut_recursive(k);
+                let xrep = self.find_mut_unchecked(k);
                 *self.parent.get_unchecked_mut(ix) = xrep;
             }
         }
         self.parent
     }
 }
+
+/// Iterator over the representatives of a `UnionFind`.
+///
+/// Created with [`.representatives()`][1].
+///
+/// [1]: struct.UnionFind.html#method.representatives
+pub struct Representatives<'a, K: 'a> {
+    parent: &'a [K],
+    index: usize,
+}
+
+impl<'a, K> Iterator for Representatives<'a, K>
+    where K: IndexType
+{
+    type Item = K;
+
+    fn next(&mut self) -> Option<K>
+    {
+        while self.index < self.parent.len() {
+            let x = K::new(self.index);
+            self.index += 1;
+            if self.parent[x.index()] == x {
+                return Some(x)
+            }
+        }
+        None
+    }
+}
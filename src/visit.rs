@@ -1,27 +1,40 @@
 //! Graph visitor algorithms.
 //!
 
+use core::cmp::Reverse;
 use fixedbitset::FixedBitSet;
-use std::collections::{
-    HashSet,
+use alloc::collections::{
+    BinaryHeap,
     VecDeque,
 };
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::collections::HashSet;
+#[cfg(feature = "std")]
 use std::hash::Hash;
 
 use super::{
-    graphmap,
     graph,
     EdgeType,
     EdgeDirection,
     Graph,
-    GraphMap,
     Incoming,
     Outgoing,
 };
+#[cfg(feature = "std")]
+use super::{
+    graphmap,
+    GraphMap,
+};
 
 use graph::{
     IndexType,
 };
+use scored::MinScored;
+use graph::inline::{
+    self,
+    InlineGraph,
+};
 #[cfg(feature = "stable_graph")]
 use graph::stable::StableGraph;
 
@@ -30,6 +43,37 @@ pub trait Graphlike {
     type NodeId: Clone;
 }
 
+/// A graph that also defines an edge identifier.
+pub trait GraphEdgeId : Graphlike {
+    type EdgeId: Clone;
+}
+
+impl<N, E, Ty, Ix> GraphEdgeId for Graph<N, E, Ty, Ix> where
+    Ix: IndexType,
+{
+    type EdgeId = graph::EdgeIndex<Ix>;
+}
+
+impl<N, E, Ty, Ix> GraphEdgeId for InlineGraph<N, E, Ty, Ix> where
+    Ix: IndexType,
+{
+    type EdgeId = graph::EdgeIndex<Ix>;
+}
+
+#[cfg(feature = "stable_graph")]
+impl<N, E, Ty, Ix> GraphEdgeId for StableGraph<N, E, Ty, Ix> where
+    Ix: IndexType,
+{
+    type EdgeId = graph::EdgeIndex<Ix>;
+}
+
+/// `GraphMap` edges are identified by their endpoints.
+#[cfg(feature = "std")]
+impl<N: Clone, E> GraphEdgeId for GraphMap<N, E>
+{
+    type EdgeId = (N, N);
+}
+
 /// `NeighborIter` gives access to the neighbors iterator.
 pub trait NeighborIter<'a> : Graphlike {
     type Iter: Iterator<Item=Self::NodeId>;
@@ -49,6 +93,17 @@ impl<'a, N, E: 'a, Ty, Ix> NeighborIter<'a> for Graph<N, E, Ty, Ix> where
     }
 }
 
+impl<'a, N, E, Ty, Ix> NeighborIter<'a> for InlineGraph<N, E, Ty, Ix> where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    type Iter = inline::Neighbors<'a, Ix>;
+    fn neighbors(&'a self, n: graph::NodeIndex<Ix>) -> inline::Neighbors<'a, Ix>
+    {
+        InlineGraph::neighbors(self, n)
+    }
+}
+
 #[cfg(feature = "stable_graph")]
 impl<'a, N, E: 'a, Ty, Ix> NeighborIter<'a> for StableGraph<N, E, Ty, Ix> where
     Ty: EdgeType,
@@ -62,6 +117,7 @@ impl<'a, N, E: 'a, Ty, Ix> NeighborIter<'a> for StableGraph<N, E, Ty, Ix> where
     }
 }
 
+#[cfg(feature = "std")]
 impl<'a, N: 'a, E> NeighborIter<'a> for GraphMap<N, E>
 where N: Copy + Ord + Hash
 {
@@ -101,6 +157,31 @@ impl<'a, 'b, N, E: 'a, Ty, Ix> NeighborIter<'a> for Reversed<&'b Graph<N, E, Ty,
     }
 }
 
+/// `GraphMap` is undirected, so walking it as undirected visits the same
+/// neighbors.
+#[cfg(feature = "std")]
+impl<'a, 'b, N: 'a, E> NeighborIter<'a> for AsUndirected<&'b GraphMap<N, E>>
+    where N: Copy + Ord + Hash
+{
+    type Iter = graphmap::Neighbors<'a, N>;
+    fn neighbors(&'a self, n: N) -> graphmap::Neighbors<'a, N>
+    {
+        GraphMap::neighbors(self.0, n)
+    }
+}
+
+/// `GraphMap` is undirected, so reversing it visits the same neighbors.
+#[cfg(feature = "std")]
+impl<'a, 'b, N: 'a, E> NeighborIter<'a> for Reversed<&'b GraphMap<N, E>>
+    where N: Copy + Ord + Hash
+{
+    type Iter = graphmap::Neighbors<'a, N>;
+    fn neighbors(&'a self, n: N) -> graphmap::Neighbors<'a, N>
+    {
+        GraphMap::neighbors(self.0, n)
+    }
+}
+
 /// `NeighborsDirected` gives access to neighbors of both `Incoming` and
 /// `Outgoing` edges of a node.
 pub trait NeighborsDirected<'a> : Graphlike {
@@ -123,6 +204,18 @@ impl<'a, N, E: 'a, Ty, Ix> NeighborsDirected<'a> for Graph<N, E, Ty, Ix>
     }
 }
 
+impl<'a, N, E, Ty, Ix> NeighborsDirected<'a> for InlineGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    type NeighborsDirected = inline::Neighbors<'a, Ix>;
+    fn neighbors_directed(&'a self, n: graph::NodeIndex<Ix>,
+                          d: EdgeDirection) -> inline::Neighbors<'a, Ix>
+    {
+        InlineGraph::neighbors_directed(self, n, d)
+    }
+}
+
 #[cfg(feature = "stable_graph")]
 impl<'a, N, E: 'a, Ty, Ix> NeighborsDirected<'a> for StableGraph<N, E, Ty, Ix>
     where Ty: EdgeType,
@@ -136,6 +229,20 @@ impl<'a, N, E: 'a, Ty, Ix> NeighborsDirected<'a> for StableGraph<N, E, Ty, Ix>
     }
 }
 
+/// `GraphMap` is undirected, so the neighbors in either direction are
+/// all the neighbors of a node.
+#[cfg(feature = "std")]
+impl<'a, N: 'a, E> NeighborsDirected<'a> for GraphMap<N, E>
+    where N: Copy + Ord + Hash
+{
+    type NeighborsDirected = graphmap::Neighbors<'a, N>;
+    fn neighbors_directed(&'a self, n: N, _: EdgeDirection)
+        -> graphmap::Neighbors<'a, N>
+    {
+        GraphMap::neighbors(self, n)
+    }
+}
+
 impl<'a, 'b,  G> NeighborsDirected<'a> for Reversed<&'b G>
     where G: NeighborsDirected<'a>,
 {
@@ -166,6 +273,29 @@ impl<'a, N: 'a, E, Ty, Ix> Externals<'a> for Graph<N, E, Ty, Ix>
     }
 }
 
+#[cfg(feature = "stable_graph")]
+impl<'a, N: 'a, E, Ty, Ix> Externals<'a> for StableGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    type Externals = graph::stable::Externals<'a, N, Ty, Ix>;
+    fn externals(&'a self, d: EdgeDirection) -> graph::stable::Externals<'a, N, Ty, Ix> {
+        StableGraph::externals(self, d)
+    }
+}
+
+/// `GraphMap` is undirected, so the externals in either direction are
+/// the nodes without edges.
+#[cfg(feature = "std")]
+impl<'a, N: 'a, E> Externals<'a> for GraphMap<N, E>
+    where N: Copy + Ord + Hash
+{
+    type Externals = graphmap::Externals<'a, N>;
+    fn externals(&'a self, _: EdgeDirection) -> graphmap::Externals<'a, N> {
+        GraphMap::externals(self)
+    }
+}
+
 impl<'a, 'b,  G> Externals<'a> for Reversed<&'b G>
     where G: Externals<'a>,
 {
@@ -175,6 +305,209 @@ impl<'a, 'b,  G> Externals<'a> for Reversed<&'b G>
     }
 }
 
+/// Access to the identifiers of all nodes in the graph.
+pub trait NodeIdentifiers<'a> : Graphlike {
+    type NodeIdentifiers: Iterator<Item=Self::NodeId>;
+
+    /// Return an iterator that visits the identifiers of all nodes.
+    fn node_identifiers(&'a self) -> Self::NodeIdentifiers;
+}
+
+impl<'a, N, E, Ty, Ix> NodeIdentifiers<'a> for Graph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    type NodeIdentifiers = graph::NodeIndices<Ix>;
+    fn node_identifiers(&'a self) -> graph::NodeIndices<Ix> {
+        Graph::node_indices(self)
+    }
+}
+
+impl<'a, N, E, Ty, Ix> NodeIdentifiers<'a> for InlineGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    type NodeIdentifiers = graph::NodeIndices<Ix>;
+    fn node_identifiers(&'a self) -> graph::NodeIndices<Ix> {
+        InlineGraph::node_indices(self)
+    }
+}
+
+#[cfg(feature = "stable_graph")]
+impl<'a, N: 'a, E, Ty, Ix> NodeIdentifiers<'a> for StableGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    type NodeIdentifiers = graph::stable::NodeIndices<'a, N, Ix>;
+    fn node_identifiers(&'a self) -> graph::stable::NodeIndices<'a, N, Ix> {
+        StableGraph::node_indices(self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, N: 'a, E> NodeIdentifiers<'a> for GraphMap<N, E>
+    where N: Copy + Ord + Hash
+{
+    type NodeIdentifiers = graphmap::Nodes<'a, N>;
+    fn node_identifiers(&'a self) -> graphmap::Nodes<'a, N> {
+        GraphMap::nodes(self)
+    }
+}
+
+impl<'a, 'b, G> NodeIdentifiers<'a> for Reversed<&'b G>
+    where G: NodeIdentifiers<'a>,
+{
+    type NodeIdentifiers = <G as NodeIdentifiers<'a>>::NodeIdentifiers;
+    fn node_identifiers(&'a self) -> Self::NodeIdentifiers {
+        self.0.node_identifiers()
+    }
+}
+
+impl<'a, 'b, G> NodeIdentifiers<'a> for AsUndirected<&'b G>
+    where G: NodeIdentifiers<'a>,
+{
+    type NodeIdentifiers = <G as NodeIdentifiers<'a>>::NodeIdentifiers;
+    fn node_identifiers(&'a self) -> Self::NodeIdentifiers {
+        self.0.node_identifiers()
+    }
+}
+
+/// The number of nodes in the graph.
+pub trait NodeCount : Graphlike {
+    fn node_count(&self) -> usize;
+}
+
+impl<N, E, Ty, Ix> NodeCount for Graph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn node_count(&self) -> usize {
+        Graph::node_count(self)
+    }
+}
+
+impl<N, E, Ty, Ix> NodeCount for InlineGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn node_count(&self) -> usize {
+        InlineGraph::node_count(self)
+    }
+}
+
+#[cfg(feature = "stable_graph")]
+impl<N, E, Ty, Ix> NodeCount for StableGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn node_count(&self) -> usize {
+        StableGraph::node_count(self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<N, E> NodeCount for GraphMap<N, E>
+    where N: Copy + Ord + Hash
+{
+    fn node_count(&self) -> usize {
+        GraphMap::node_count(self)
+    }
+}
+
+impl<'a, G> NodeCount for Reversed<&'a G>
+    where G: NodeCount
+{
+    fn node_count(&self) -> usize {
+        self.0.node_count()
+    }
+}
+
+impl<'a, G> NodeCount for AsUndirected<&'a G>
+    where G: NodeCount
+{
+    fn node_count(&self) -> usize {
+        self.0.node_count()
+    }
+}
+
+/// The graph's node identifiers can be mapped to integer indices
+/// in the range `0..node_bound()`.
+pub trait NodeIndexable : Graphlike {
+    /// Return an upper bound of the node indices in the graph
+    /// (suitable for the size of a bitmap).
+    fn node_bound(&self) -> usize;
+    /// Convert `a` to an integer index.
+    fn to_index(&self, a: Self::NodeId) -> usize;
+    /// Convert `i` to a node index.
+    fn from_index(&self, i: usize) -> Self::NodeId;
+}
+
+impl<N, E, Ty, Ix> NodeIndexable for Graph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn node_bound(&self) -> usize { self.node_count() }
+    fn to_index(&self, a: graph::NodeIndex<Ix>) -> usize { a.index() }
+    fn from_index(&self, i: usize) -> graph::NodeIndex<Ix> { graph::NodeIndex::new(i) }
+}
+
+impl<N, E, Ty, Ix> NodeIndexable for InlineGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn node_bound(&self) -> usize { self.node_count() }
+    fn to_index(&self, a: graph::NodeIndex<Ix>) -> usize { a.index() }
+    fn from_index(&self, i: usize) -> graph::NodeIndex<Ix> { graph::NodeIndex::new(i) }
+}
+
+#[cfg(feature = "stable_graph")]
+impl<N, E, Ty, Ix> NodeIndexable for StableGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn node_bound(&self) -> usize { StableGraph::node_bound(self) }
+    fn to_index(&self, a: graph::NodeIndex<Ix>) -> usize { a.index() }
+    fn from_index(&self, i: usize) -> graph::NodeIndex<Ix> { graph::NodeIndex::new(i) }
+}
+
+impl<'a, G> NodeIndexable for Reversed<&'a G>
+    where G: NodeIndexable
+{
+    fn node_bound(&self) -> usize { self.0.node_bound() }
+    fn to_index(&self, a: G::NodeId) -> usize { self.0.to_index(a) }
+    fn from_index(&self, i: usize) -> G::NodeId { self.0.from_index(i) }
+}
+
+impl<'a, G> NodeIndexable for AsUndirected<&'a G>
+    where G: NodeIndexable
+{
+    fn node_bound(&self) -> usize { self.0.node_bound() }
+    fn to_index(&self, a: G::NodeId) -> usize { self.0.to_index(a) }
+    fn from_index(&self, i: usize) -> G::NodeId { self.0.from_index(i) }
+}
+
+/// The graph's node identifiers are compact: they map to the indices
+/// `0..node_count()`, so that `node_bound()` equals `node_count()`.
+pub trait NodeCompactIndexable : NodeIndexable + NodeCount { }
+
+impl<N, E, Ty, Ix> NodeCompactIndexable for Graph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{ }
+
+impl<N, E, Ty, Ix> NodeCompactIndexable for InlineGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{ }
+
+impl<'a, G> NodeCompactIndexable for Reversed<&'a G>
+    where G: NodeCompactIndexable
+{ }
+
+impl<'a, G> NodeCompactIndexable for AsUndirected<&'a G>
+    where G: NodeCompactIndexable
+{ }
+
 /// A mapping for storing the visited status for `NodeId` `N`.
 pub trait VisitMap<N> {
     /// Return **true** if the value is not already present.
@@ -208,6 +541,7 @@ impl<Ix> VisitMap<graph::EdgeIndex<Ix>> for FixedBitSet where
     }
 }
 
+#[cfg(feature = "std")]
 impl<N: Eq + Hash> VisitMap<N> for HashSet<N> {
     fn visit(&mut self, x: N) -> bool {
         self.insert(x)
@@ -234,6 +568,12 @@ impl<N, E, Ty, Ix> Graphlike for Graph<N, E, Ty, Ix> where
     type NodeId = graph::NodeIndex<Ix>;
 }
 
+impl<N, E, Ty, Ix> Graphlike for InlineGraph<N, E, Ty, Ix> where
+    Ix: IndexType,
+{
+    type NodeId = graph::NodeIndex<Ix>;
+}
+
 impl<N, E, Ty, Ix> Visitable for Graph<N, E, Ty, Ix> where
     Ty: EdgeType,
     Ix: IndexType,
@@ -252,6 +592,24 @@ impl<N, E, Ty, Ix> Revisitable for Graph<N, E, Ty, Ix>
     }
 }
 
+impl<N, E, Ty, Ix> Visitable for InlineGraph<N, E, Ty, Ix> where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    type Map = FixedBitSet;
+    fn visit_map(&self) -> FixedBitSet { FixedBitSet::with_capacity(self.node_count()) }
+}
+
+impl<N, E, Ty, Ix> Revisitable for InlineGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn reset_map(&self, map: &mut Self::Map) {
+        map.clear();
+        map.grow(self.node_count());
+    }
+}
+
 #[cfg(feature = "stable_graph")]
 impl<N, E, Ty, Ix> Graphlike for StableGraph<N, E, Ty, Ix> where
     Ix: IndexType,
@@ -259,13 +617,15 @@ impl<N, E, Ty, Ix> Graphlike for StableGraph<N, E, Ty, Ix> where
     type NodeId = graph::NodeIndex<Ix>;
 }
 
+/// The visit map is sized by the node bound, since node indices may exceed
+/// the node count after removals.
 #[cfg(feature = "stable_graph")]
 impl<N, E, Ty, Ix> Visitable for StableGraph<N, E, Ty, Ix> where
     Ty: EdgeType,
     Ix: IndexType,
 {
     type Map = FixedBitSet;
-    fn visit_map(&self) -> FixedBitSet { FixedBitSet::with_capacity(self.node_count()) }
+    fn visit_map(&self) -> FixedBitSet { FixedBitSet::with_capacity(self.node_bound()) }
 }
 
 #[cfg(feature = "stable_graph")]
@@ -275,7 +635,7 @@ impl<N, E, Ty, Ix> Revisitable for StableGraph<N, E, Ty, Ix>
 {
     fn reset_map(&self, map: &mut Self::Map) {
         map.clear();
-        map.grow(self.node_count());
+        map.grow(self.node_bound());
     }
 }
 
@@ -287,11 +647,13 @@ impl<'a, G> Revisitable for Reversed<&'a G>
     }
 }
 
+#[cfg(feature = "std")]
 impl<N: Clone, E> Graphlike for GraphMap<N, E>
 {
     type NodeId = N;
 }
 
+#[cfg(feature = "std")]
 impl<N, E> Visitable for GraphMap<N, E>
     where N: Copy + Ord + Hash
 {
@@ -299,6 +661,7 @@ impl<N, E> Visitable for GraphMap<N, E>
     fn visit_map(&self) -> HashSet<N> { HashSet::with_capacity(self.node_count()) }
 }
 
+#[cfg(feature = "std")]
 impl<N, E> Revisitable for GraphMap<N, E>
     where N: Copy + Ord + Hash
 {
@@ -341,6 +704,7 @@ pub trait GetAdjacencyMatrix : Graphlike {
 }
 
 /// The `GraphMap` keeps an adjacency matrix internally.
+#[cfg(feature = "std")]
 impl<N, E> GetAdjacencyMatrix for GraphMap<N, E>
     where N: Copy + Ord + Hash
 {
@@ -683,6 +1047,113 @@ impl<N, VM> Topo<N, VM>
     }
 }
 
+/// A topological order traversal for a graph that visits, among the nodes
+/// whose predecessors are all visited, the one of highest priority first.
+///
+/// The priority of each node is computed once, when it becomes ready to
+/// visit, with the closure given to [`new`](#method.new). Ties are visited
+/// in no particular order. This is list scheduling: with nodes as tasks and
+/// edges as dependencies, the traversal runs the most urgent ready task
+/// first.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::visit::PriorityTopo;
+///
+/// // tasks with their priorities; 1 must wait for 0
+/// let mut g = Graph::<u32, ()>::new();
+/// let a = g.add_node(1);
+/// let b = g.add_node(5);
+/// let c = g.add_node(3);
+/// g.add_edge(a, b, ());
+///
+/// let mut topo = PriorityTopo::new(&g, |n| g[n]);
+/// let mut order = Vec::new();
+/// while let Some(n) = topo.next(&g) {
+///     order.push(n);
+/// }
+/// assert_eq!(order, vec![c, a, b]);
+/// ```
+#[derive(Clone)]
+pub struct PriorityTopo<N, VM, K, F> {
+    ready: BinaryHeap<Reverse<MinScored<K, N>>>,
+    ordered: VM,
+    /// The nodes that have been made ready, so that a node reached through
+    /// parallel edges is pushed only once.
+    queued: VM,
+    priority: F,
+}
+
+impl<N, VM, K, F> PriorityTopo<N, VM, K, F>
+    where N: Clone,
+          VM: VisitMap<N>,
+          K: PartialOrd,
+          F: FnMut(N) -> K,
+{
+    /// Create a new `PriorityTopo`, using the graph's visitor map, with
+    /// `priority` giving the priority of each node, and make all initial
+    /// nodes ready.
+    pub fn new<'a, G>(graph: &'a G, priority: F) -> Self
+        where G: Externals<'a> + Visitable<NodeId=N, Map=VM>,
+    {
+        let mut topo = PriorityTopo {
+            ready: BinaryHeap::new(),
+            ordered: graph.visit_map(),
+            queued: graph.visit_map(),
+            priority: priority,
+        };
+        topo.push_externals(graph);
+        topo
+    }
+
+    /// Clear visited state, and make all initial nodes ready.
+    pub fn reset<'a, G>(&mut self, graph: &'a G)
+        where G: Externals<'a> + Revisitable<NodeId=N, Map=VM>,
+    {
+        graph.reset_map(&mut self.ordered);
+        graph.reset_map(&mut self.queued);
+        self.ready.clear();
+        self.push_externals(graph);
+    }
+
+    fn push_externals<'a, G>(&mut self, graph: &'a G)
+        where G: Externals<'a> + Visitable<NodeId=N, Map=VM>,
+    {
+        for nix in graph.externals(Incoming) {
+            if self.queued.visit(nix.clone()) {
+                let key = (self.priority)(nix.clone());
+                self.ready.push(Reverse(MinScored(key, nix)));
+            }
+        }
+    }
+
+    /// Return the next node in the current topological order traversal, or
+    /// `None` if the traversal is at the end.
+    ///
+    /// *Note:* The graph may not have a complete topological order, and the only
+    /// way to know is to run the whole traversal and make sure it visits every node.
+    pub fn next<'a, G>(&mut self, g: &'a G) -> Option<N>
+        where G: NeighborsDirected<'a> + Visitable<NodeId=N, Map=VM>,
+    {
+        while let Some(Reverse(MinScored(_, nix))) = self.ready.pop() {
+            if self.ordered.is_visited(&nix) {
+                continue;
+            }
+            self.ordered.visit(nix.clone());
+            for neigh in g.neighbors_directed(nix.clone(), Outgoing) {
+                if g.neighbors_directed(neigh.clone(), Incoming).all(|b| self.ordered.is_visited(&b)) &&
+                    self.queued.visit(neigh.clone())
+                {
+                    let key = (self.priority)(neigh.clone());
+                    self.ready.push(Reverse(MinScored(key, neigh)));
+                }
+            }
+            return Some(nix);
+        }
+        None
+    }
+}
+
 /// A topological order traversal for a subgraph.
 ///
 /// `SubTopo` starts at a node, and does a topological order traversal of
@@ -0,0 +1,74 @@
+#![cfg(feature = "dot_parser")]
+
+extern crate petgraph;
+
+use petgraph::{
+    Graph,
+    Undirected,
+};
+use petgraph::dot::Dot;
+use petgraph::io::dot::parse;
+
+fn edge_list(g: &Graph<String, String>) -> Vec<(&str, &str, &str)> {
+    g.raw_edges().iter().map(|e| {
+        (&g[e.source()][..], &g[e.target()][..], &e.weight[..])
+    }).collect()
+}
+
+#[test]
+fn round_trip() {
+    let mut gr = Graph::new();
+    let a = gr.add_node("A");
+    let b = gr.add_node("B \"quoted\"");
+    let c = gr.add_node("C");
+    gr.add_edge(a, b, 1);
+    gr.add_edge(b, c, 2);
+    gr.add_edge(c, a, 3);
+
+    let parsed = parse(&format!("{}", Dot::new(&gr))).unwrap();
+    assert_eq!(parsed.node_count(), 3);
+    assert_eq!(edge_list(&parsed),
+               vec![("A", "B \"quoted\"", "1"),
+                    ("B \"quoted\"", "C", "2"),
+                    ("C", "A", "3")]);
+}
+
+#[test]
+fn statements() {
+    let g = parse(r#"
+        /* a comment */
+        strict graph G {
+            rankdir = LR // a graph attribute
+            node [shape=box, label="default"];
+            edge [label="e"]
+            a; b [color=red label="bee"]
+            a -- b -- "c" + "d" [weight=2]
+            subgraph cluster_0 { x y } -- z
+            -1.5 -- a:port:n
+            html [label=<<b>bold</b>>]
+        }
+    "#).unwrap();
+    let names = g.node_indices().map(|i| &g[i][..]).collect::<Vec<_>>();
+    assert_eq!(names, ["default", "bee", "default", "default", "default",
+                       "default", "default", "<b>bold</b>"]);
+    assert_eq!(g.edge_count(), 5);
+    assert!(g.raw_edges().iter().all(|e| e.weight == "e"));
+
+    let g = g.into_edge_type::<Undirected>();
+    assert_eq!(petgraph::algo::connected_components(&g), 3);
+}
+
+#[test]
+fn errors() {
+    assert!(parse("digraph { a -- b }").is_err());
+    assert!(parse("graph { a -> b }").is_err());
+    assert!(parse("digraph { a -> }").is_err());
+    assert!(parse("digraph { a -> b").is_err());
+    assert!(parse("tree { }").is_err());
+    let err = parse("digraph {\n a -> b\n \"unterminated }").unwrap_err();
+    assert_eq!(err.line(), 3);
+    let deep = format!("digraph {{ {} }}", "{ ".repeat(100000));
+    assert!(parse(&deep).is_err());
+    let nested = format!("digraph {{ {}a{} }}", "{ ".repeat(100), " }".repeat(100));
+    assert_eq!(parse(&nested).unwrap().node_count(), 1);
+}
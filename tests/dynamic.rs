@@ -0,0 +1,191 @@
+extern crate petgraph;
+extern crate rand;
+
+use rand::Rng;
+
+use petgraph::{
+    Graph,
+    Undirected,
+};
+use petgraph::algo::{
+    connected_components,
+    is_cyclic_directed,
+    scc,
+};
+use petgraph::dynamic::{
+    DynamicConnectivity,
+    IncrementalScc,
+    IncrementalTopo,
+};
+use petgraph::graph::node_index as n;
+use petgraph::unionfind::UnionFind;
+
+#[test]
+fn dynamic_connectivity_random() {
+    let mut rng = rand::weak_rng();
+    let nodes = 30;
+    let mut g = Graph::<(), (), Undirected>::with_capacity(nodes, 0);
+    for _ in 0..nodes {
+        g.add_node(());
+    }
+    let mut conn = DynamicConnectivity::new(nodes);
+    for _ in 0..2000 {
+        let a = n(rng.gen_range(0, nodes));
+        let b = n(rng.gen_range(0, nodes));
+        // remove edges a bit more often than they are added, but only when
+        // they exist, so the graph hovers around its connectivity threshold
+        if rng.gen_weighted_bool(2) {
+            match g.find_edge(a, b) {
+                Some(e) => {
+                    g.remove_edge(e);
+                    assert!(conn.remove_edge(a, b));
+                }
+                None => assert!(!conn.remove_edge(a, b)),
+            }
+        } else if g.edge_count() < nodes {
+            g.add_edge(a, b, ());
+            conn.add_edge(a, b);
+        }
+
+        let mut sets = UnionFind::new(nodes);
+        for edge in g.raw_edges() {
+            sets.union(edge.source().index(), edge.target().index());
+        }
+        assert_eq!(conn.component_count(), connected_components(&g));
+        for x in 0..nodes {
+            let y = rng.gen_range(0, nodes);
+            assert_eq!(conn.connected(n(x), n(y)), sets.find(x) == sets.find(y));
+        }
+    }
+}
+
+#[test]
+fn dynamic_connectivity_from_graph() {
+    let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (1, 2), (3, 3)]);
+    let mut conn = DynamicConnectivity::from_graph(&g);
+    assert_eq!(conn.component_count(), 2);
+    assert!(conn.connected(n(0), n(2)));
+
+    // one of the parallel edges remains
+    assert!(conn.remove_edge(n(2), n(1)));
+    assert!(conn.connected(n(0), n(2)));
+    assert!(conn.remove_edge(n(1), n(2)));
+    assert!(!conn.connected(n(0), n(2)));
+    assert!(!conn.remove_edge(n(1), n(2)));
+
+    assert!(conn.remove_edge(n(3), n(3)));
+    let d = conn.add_node();
+    assert_eq!(conn.node_count(), 5);
+    assert_eq!(conn.component_count(), 4);
+    assert!(conn.add_edge(d, n(3)));
+    assert!(!conn.add_edge(n(3), d));
+    assert!(conn.connected(n(3), d));
+}
+
+#[test]
+fn incremental_topo_random() {
+    let mut rng = rand::weak_rng();
+    let nodes = 25;
+    let mut g = Graph::<(), ()>::with_capacity(nodes, 0);
+    for _ in 0..nodes {
+        g.add_node(());
+    }
+    let mut topo = IncrementalTopo::new(nodes);
+    for _ in 0..300 {
+        let a = n(rng.gen_range(0, nodes));
+        let b = n(rng.gen_range(0, nodes));
+        let e = g.add_edge(a, b, ());
+        match topo.add_edge(a, b) {
+            Ok(()) => assert!(!is_cyclic_directed(&g)),
+            Err(err) => {
+                assert_eq!(err.edge(), (a, b));
+                assert!(is_cyclic_directed(&g));
+                g.remove_edge(e);
+            }
+        }
+
+        let order = topo.order();
+        for (i, &a) in order.iter().enumerate() {
+            assert_eq!(topo.position(a), i);
+        }
+        for edge in g.raw_edges() {
+            assert!(topo.position(edge.source()) < topo.position(edge.target()));
+        }
+    }
+}
+
+#[test]
+fn incremental_topo_from_graph() {
+    let mut g = Graph::<(), ()>::from_edges(&[(3, 2), (2, 1), (1, 0)]);
+    let mut topo = IncrementalTopo::from_graph(&g).unwrap();
+    assert_eq!(topo.order(), [n(3), n(2), n(1), n(0)]);
+    assert!(topo.add_edge(n(0), n(3)).is_err());
+    assert!(topo.remove_edge(n(2), n(1)));
+    assert!(!topo.remove_edge(n(2), n(1)));
+    assert!(topo.add_edge(n(0), n(2)).is_ok());
+
+    let d = topo.add_node();
+    assert_eq!(topo.position(d), 4);
+    assert!(topo.add_edge(d, n(3)).is_ok());
+    assert!(topo.position(d) < topo.position(n(3)));
+
+    g.add_edge(n(0), n(3), ());
+    assert!(IncrementalTopo::from_graph(&g).is_err());
+}
+
+#[test]
+fn incremental_scc_random() {
+    let mut rng = rand::weak_rng();
+    let nodes = 30;
+    let mut g = Graph::<(), ()>::with_capacity(nodes, 0);
+    for _ in 0..nodes {
+        g.add_node(());
+    }
+    let mut inc = IncrementalScc::new(nodes);
+    for _ in 0..80 {
+        let a = n(rng.gen_range(0, nodes));
+        let b = n(rng.gen_range(0, nodes));
+        g.add_edge(a, b, ());
+        let count = inc.component_count();
+        let merged = inc.add_edge(a, b);
+        assert_eq!(merged, inc.component_count() < count);
+
+        let mut expected = scc(&g);
+        for c in &mut expected {
+            c.sort();
+        }
+        expected.sort();
+        let components = inc.components();
+        let mut actual = components.clone();
+        for c in &mut actual {
+            c.sort();
+        }
+        actual.sort();
+        assert_eq!(actual, expected);
+        assert_eq!(inc.component_count(), expected.len());
+
+        // the components are in topological order
+        let mut position = vec![0; nodes];
+        for (i, c) in components.iter().enumerate() {
+            for &a in c {
+                position[a.index()] = i;
+                assert_eq!(inc.component(a), inc.component(c[0]));
+            }
+        }
+        for edge in g.raw_edges() {
+            assert!(position[edge.source().index()] <= position[edge.target().index()]);
+        }
+    }
+}
+
+#[test]
+fn incremental_scc_from_graph() {
+    let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (3, 4), (2, 3)]);
+    let mut inc = IncrementalScc::from_graph(&g);
+    assert_eq!(inc.component_count(), 3);
+    assert!(inc.same_component(n(0), n(2)));
+    assert!(!inc.same_component(n(3), n(4)));
+    assert!(inc.add_edge(n(4), n(0)));
+    assert_eq!(inc.component_count(), 1);
+    assert!(!inc.add_edge(n(4), n(0)));
+}
@@ -0,0 +1,59 @@
+#![cfg(feature = "edgelist")]
+
+extern crate petgraph;
+
+use petgraph::Graph;
+use petgraph::io::edgelist::{
+    ReadError,
+    read_edge_list,
+    write_edge_list,
+};
+
+#[test]
+fn round_trip() {
+    let mut gr = Graph::new();
+    let a = gr.add_node("alpha".to_string());
+    let b = gr.add_node("beta gamma".to_string());
+    let c = gr.add_node("delta".to_string());
+    gr.add_edge(a, b, 1.5);
+    gr.add_edge(b, c, -2.);
+    gr.add_edge(c, c, 0.25);
+
+    let mut output = Vec::new();
+    write_edge_list(&mut output, &gr, '\t').unwrap();
+    let parsed = read_edge_list::<String, f64, _>(&output[..], '\t').unwrap();
+    assert_eq!(parsed.node_count(), 3);
+    let edges = parsed.raw_edges().iter().map(|e| {
+        (&parsed[e.source()][..], &parsed[e.target()][..], e.weight)
+    }).collect::<Vec<_>>();
+    assert_eq!(edges, vec![("alpha", "beta gamma", 1.5),
+                           ("beta gamma", "delta", -2.),
+                           ("delta", "delta", 0.25)]);
+}
+
+#[test]
+fn unweighted_and_extra_columns() {
+    let gr = read_edge_list::<u64, String, _>(&b"% comment\n 1 , 2 \n\n2,3,x,y\n"[..], ',')
+                 .unwrap();
+    let edges = gr.raw_edges().iter().map(|e| {
+        (gr[e.source()], gr[e.target()], &e.weight[..])
+    }).collect::<Vec<_>>();
+    assert_eq!(edges, vec![(1, 2, ""), (2, 3, "x,y")]);
+
+    let mut output = Vec::new();
+    write_edge_list(&mut output, &gr, ',').unwrap();
+    assert_eq!(output, b"1,2\n2,3,x,y\n");
+}
+
+#[test]
+fn errors() {
+    fn line_of(input: &str) -> usize {
+        match read_edge_list::<u32, f64, _>(input.as_bytes(), ',') {
+            Err(ReadError::Parse(e)) => e.line(),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+    assert_eq!(line_of("1,2\n3\n"), 2);
+    assert_eq!(line_of("1,2\n\na,2\n"), 3);
+    assert_eq!(line_of("1,2,heavy\n"), 1);
+}
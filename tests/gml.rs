@@ -0,0 +1,80 @@
+#![cfg(feature = "gml")]
+
+extern crate petgraph;
+
+use petgraph::{
+    Graph,
+    Undirected,
+};
+use petgraph::io::gml::{
+    Gml,
+    parse,
+};
+
+fn edge_list(g: &Graph<String, String>) -> Vec<(&str, &str, &str)> {
+    g.raw_edges().iter().map(|e| {
+        (&g[e.source()][..], &g[e.target()][..], &e.weight[..])
+    }).collect()
+}
+
+#[test]
+fn round_trip() {
+    let mut gr = Graph::<_, _, Undirected>::new_undirected();
+    let a = gr.add_node("A");
+    let b = gr.add_node("B \"quoted\" & more");
+    let c = gr.add_node("C");
+    gr.add_edge(a, b, 1);
+    gr.add_edge(b, c, 2);
+    gr.add_edge(c, a, 3);
+
+    let output = Gml::new(&gr).to_string();
+    assert!(output.contains("directed 0"));
+    let parsed = parse(&output).unwrap();
+    assert_eq!(parsed.node_count(), 3);
+    assert_eq!(edge_list(&parsed),
+               vec![("A", "B \"quoted\" & more", "1"),
+                    ("B \"quoted\" & more", "C", "2"),
+                    ("C", "A", "3")]);
+
+    let debug = parse(&format!("{:?}", Gml::new(&gr))).unwrap();
+    assert_eq!(debug[a], "\"A\"");
+}
+
+#[test]
+fn networkx_style() {
+    let g = parse(r##"Creator "networkx"
+# written by networkx
+graph [
+  directed 1
+  multigraph 1
+  graphics [ fill "#ff0000" ]
+  edge [ source -1 target 2.5 label "late" ]
+  node [
+    id -1
+    label "neg"
+    graphics [ x 1.0 y -2e3 ]
+  ]
+  node [ id 2.5 ]
+]
+"##).unwrap();
+
+    assert_eq!(edge_list(&g), vec![("neg", "2.5", "late")]);
+}
+
+#[test]
+fn errors() {
+    assert!(parse("").is_err());
+    assert!(parse("graph 1").is_err());
+    assert!(parse("graph [ node [ label \"x\" ] ]").is_err());
+    assert!(parse("graph [ node [ id 1 ] node [ id 1 ] ]").is_err());
+    let err = parse("graph [\n  node [ id 1 ]\n  edge [ source 1 target 2 ]\n]").unwrap_err();
+    assert_eq!(err.line(), 3);
+    assert!(parse("graph [ node [ id 1 ]").is_err());
+    assert!(parse("graph [ 1 ]").is_err());
+
+    // deep nesting is an error, not a stack overflow
+    let deep = "a [ ".repeat(100000);
+    assert_eq!(parse(&deep).unwrap_err().line(), 1);
+    let nested = format!("graph [ node [ id 1 x {}1{} ] ]", "[ a ".repeat(100), " ]".repeat(100));
+    assert!(parse(&nested).is_ok());
+}
@@ -0,0 +1,107 @@
+#![cfg(feature = "graph6")]
+
+extern crate petgraph;
+
+use petgraph::{
+    Graph,
+    Undirected,
+};
+use petgraph::io::graph6::{
+    from_graph6,
+    from_sparse6,
+    to_graph6,
+    to_sparse6,
+};
+
+fn edge_list(g: &Graph<(), (), Undirected>) -> Vec<(usize, usize)> {
+    let mut edges = g.raw_edges().iter().map(|e| {
+        let (a, b) = (e.source().index(), e.target().index());
+        if a < b { (a, b) } else { (b, a) }
+    }).collect::<Vec<_>>();
+    edges.sort();
+    edges
+}
+
+#[test]
+fn petersen() {
+    // as written by networkx
+    let g = from_graph6(">>graph6<<IheA@GUAo\n").unwrap();
+    let s = from_sparse6(":I`ES@obGkqegW~").unwrap();
+    assert_eq!(g.node_count(), 10);
+    assert_eq!(g.edge_count(), 15);
+    assert_eq!(edge_list(&g), edge_list(&s));
+    assert_eq!(to_graph6(&g), "IheA@GUAo");
+    assert_eq!(to_sparse6(&g), ":I`ES@obGkqegW~");
+}
+
+#[test]
+fn sizes() {
+    for &(g6, s6) in &[("?", ":?"), ("@", ":@"), ("A_", ":An")] {
+        let g = from_graph6(g6).unwrap();
+        assert_eq!(to_graph6(&g), g6);
+        assert_eq!(to_sparse6(&g), s6);
+        assert_eq!(edge_list(&from_sparse6(s6).unwrap()), edge_list(&g));
+    }
+
+    // a path with 300 nodes needs the longer size encoding
+    let mut path = Graph::new_undirected();
+    let mut prev = path.add_node(());
+    for _ in 1..300 {
+        let next = path.add_node(());
+        path.add_edge(prev, next, ());
+        prev = next;
+    }
+    let g6 = to_graph6(&path);
+    assert_eq!(g6.len(), 7479);
+    assert!(g6.starts_with("~?CkhC"));
+    assert_eq!(edge_list(&from_graph6(&g6).unwrap()), edge_list(&path));
+    let s6 = to_sparse6(&path);
+    assert!(s6.starts_with(":~?Ck_A?WA_M@GD_Y@wG_eAgJ_qBWM_}CGP`ICwS"));
+    assert_eq!(edge_list(&from_sparse6(&s6).unwrap()), edge_list(&path));
+}
+
+#[test]
+fn loops_and_parallel_edges() {
+    let mut gr = Graph::new_undirected();
+    let n = (0..4).map(|_| gr.add_node(())).collect::<Vec<_>>();
+    gr.add_edge(n[0], n[1], ());
+    gr.add_edge(n[1], n[0], ());
+    gr.add_edge(n[2], n[2], ());
+    gr.add_edge(n[1], n[3], ());
+    assert_eq!(to_sparse6(&gr), ":C_t");
+    assert_eq!(edge_list(&from_sparse6(":C_t").unwrap()),
+               vec![(0, 1), (0, 1), (1, 3), (2, 2)]);
+    // graph6 can't represent them
+    assert_eq!(edge_list(&from_graph6(&to_graph6(&gr)).unwrap()),
+               vec![(0, 1), (1, 3)]);
+
+    // padding must not be read as an edge to the last node
+    let mut gr = Graph::new_undirected();
+    let n = (0..8).map(|_| gr.add_node(())).collect::<Vec<_>>();
+    gr.add_edge(n[0], n[1], ());
+    assert_eq!(to_sparse6(&gr), ":Gb");
+    assert_eq!(edge_list(&from_sparse6(":Gb").unwrap()), vec![(0, 1)]);
+}
+
+#[test]
+fn errors() {
+    assert!(from_graph6("").is_err());
+    assert!(from_graph6("Bgg").is_err());
+    assert!(from_graph6("B").is_err());
+    assert!(from_graph6("B g").is_err());
+    assert!(from_graph6("~??").is_err());
+    assert!(from_sparse6("Bd").is_err());
+    assert!(from_sparse6(":").is_err());
+}
+
+#[test]
+fn huge_headers() {
+    // 2^36 - 1 nodes in 9 bytes, beyond the u32 node indices
+    assert!(from_sparse6(":~~~~~~~~").is_err());
+    assert!(from_graph6("~~~~~~~~").is_err());
+    // a header that fits the indices, but not the edge data
+    assert!(from_graph6("~~?D????").is_err());
+    // a sparse6 header that fits the indices, but not the edge data
+    assert!(from_sparse6(":~~?D????").is_err());
+    assert_eq!(from_sparse6(":~@??").unwrap().node_count(), 4096);
+}
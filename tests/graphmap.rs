@@ -3,15 +3,20 @@ extern crate petgraph;
 use std::collections::HashSet;
 
 use petgraph::{
+    Graph,
     GraphMap,
     Dfs,
+    Undirected,
 };
 use petgraph::visit::{
     DfsIter,
 };
 
 use petgraph::algo::{
+    connected_components,
     dijkstra,
+    is_cyclic_directed,
+    toposort,
 };
 
 use petgraph::dot::{Dot, Config};
@@ -161,3 +166,145 @@ fn from_edges() {
 
     println!("{:?}", Dot::with_config(&gr, &[Config::EdgeNoLabel]));
 }
+
+#[test]
+fn graph_conversions() {
+    let mut gr = GraphMap::from_edges(&[
+        ("a", "b", 1),
+        ("a", "c", 2),
+        ("c", "d", 3),
+    ]);
+    gr.add_node("e");
+
+    let g: Graph<_, _, Undirected> = gr.clone().into_graph();
+    assert_eq!(g.node_count(), 5);
+    assert_eq!(g.edge_count(), 3);
+    for edge in g.raw_edges() {
+        let (a, b) = (g[edge.source()], g[edge.target()]);
+        assert_eq!(gr.edge_weight(a, b), Some(&edge.weight));
+    }
+
+    // the node map finds each node of the GraphMap in the Graph
+    let (g2, index): (Graph<_, _, Undirected>, _) = gr.clone().into_graph_with_map();
+    assert_eq!(index.len(), 5);
+    for node in gr.nodes() {
+        assert_eq!(g2[index[&node]], node);
+    }
+    assert_eq!(g2[g2.find_edge(index["c"], index["d"]).unwrap()], 3);
+
+    let back = GraphMap::from(g);
+    assert_eq!(back.node_count(), 5);
+    assert_eq!(back.edge_count(), 3);
+    assert!(back.contains_node("e"));
+    assert_eq!(back[("d", "c")], 3);
+
+    // parallel edges collapse, directed edges lose their direction
+    let mut dg = Graph::new();
+    let x = dg.add_node(1);
+    let y = dg.add_node(2);
+    dg.add_edge(x, y, "first");
+    dg.add_edge(y, x, "second");
+    let gm: GraphMap<_, _> = dg.into();
+    assert_eq!(gm.edge_count(), 1);
+    assert_eq!(gm[(1, 2)], "second");
+}
+
+#[test]
+fn graph_algorithms() {
+    let mut gr = GraphMap::<_, ()>::from_edges(&[
+        (0, 1), (1, 2),
+        (3, 4),
+    ]);
+    gr.add_node(5);
+    assert_eq!(connected_components(&gr), 3);
+    assert_eq!(gr.externals().collect::<Vec<_>>(), vec![5]);
+
+    // every undirected edge is a cycle
+    assert!(is_cyclic_directed(&gr));
+    assert_eq!(toposort(&gr), vec![5]);
+}
+
+#[test]
+fn deterministic_order() {
+    let mut gr = GraphMap::new();
+    for &(a, b) in &[(7, 3), (3, 1), (9, 7), (1, 9), (3, 9), (5, 5)] {
+        gr.add_edge(a, b, a * 10 + b);
+    }
+    gr.add_node(4);
+    assert_eq!(gr.nodes().collect::<Vec<_>>(), vec![7, 3, 1, 9, 5, 4]);
+    assert_eq!(gr.neighbors(3).collect::<Vec<_>>(), vec![7, 1, 9]);
+    assert_eq!(gr.all_edges().map(|(_, _, &w)| w).collect::<Vec<_>>(),
+               vec![73, 31, 97, 19, 39, 55]);
+    assert_eq!(gr.externals().collect::<Vec<_>>(), vec![4]);
+
+    // removal moves the last node into the hole
+    gr.remove_node(3);
+    assert_eq!(gr.nodes().collect::<Vec<_>>(), vec![7, 4, 1, 9, 5]);
+    assert_eq!(gr.all_edges().map(|(_, _, &w)| w).collect::<Vec<_>>(), vec![55, 19, 97]);
+
+    let g: Graph<_, _, Undirected> = gr.clone().into_graph();
+    assert_eq!(g.raw_nodes().iter().map(|n| n.weight).collect::<Vec<_>>(), vec![7, 4, 1, 9, 5]);
+    assert_eq!(g.raw_edges().iter().map(|e| e.weight).collect::<Vec<_>>(), vec![55, 19, 97]);
+    assert_eq!(format!("{:?}", gr), "{7: [9], 4: [], 1: [9], 9: [7, 1], 5: [5]}");
+}
+
+#[test]
+fn edge_weights() {
+    let mut gr = GraphMap::from_edges(&[("a", "b", 1), ("b", "c", 2), ("c", "c", 3)]);
+    for (a, b, w) in gr.all_edges_mut() {
+        if a == b {
+            *w = 0;
+        } else {
+            *w *= 10;
+        }
+    }
+    assert_eq!(gr.all_edges().collect::<Vec<_>>(),
+               vec![("a", "b", &10), ("b", "c", &20), ("c", "c", &0)]);
+    assert_eq!(gr.edges("b").collect::<Vec<_>>(), vec![("a", &10), ("c", &20)]);
+    assert_eq!(gr.edges("c").map(|(_, &w)| w).sum::<i32>(), 20);
+    assert_eq!(gr.edges("z").count(), 0);
+}
+
+#[test]
+fn graph_views() {
+    use petgraph::visit::{AsUndirected, Reversed, Topo};
+
+    let mut gr = GraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (3, 4)]);
+    gr.add_node(5);
+
+    // an undirected graph is its own reverse
+    let reversed = Reversed(&gr);
+    let mut dfs = Dfs::new(&reversed, 2);
+    let mut seen = Vec::new();
+    while let Some(n) = dfs.next(&reversed) {
+        seen.push(n);
+    }
+    seen.sort();
+    assert_eq!(seen, vec![0, 1, 2]);
+
+    let undirected = AsUndirected(&gr);
+    let mut dfs = Dfs::new(&undirected, 3);
+    assert_eq!(dfs.next(&undirected), Some(3));
+    assert_eq!(dfs.next(&undirected), Some(4));
+    assert_eq!(dfs.next(&undirected), None);
+
+    assert_eq!(toposort(&reversed), vec![5]);
+    let mut topo = Topo::new(&reversed);
+    assert_eq!(topo.next(&reversed), Some(5));
+    assert_eq!(topo.next(&reversed), None);
+    assert_eq!(connected_components(&reversed), 3);
+}
+
+#[test]
+fn memory_usage() {
+    let mut gr = GraphMap::<u32, u64>::new();
+    let empty = gr.memory_usage();
+    for i in 0..100 {
+        gr.add_edge(i, i + 1, 0);
+    }
+    // at least the node and edge entries and the neighbor lists
+    let used = gr.memory_usage();
+    assert!(used >= empty + 101 * 4 + 100 * (8 + 8) + 200 * 4);
+    gr.clear();
+    assert!(gr.memory_usage() <= used);
+}
@@ -0,0 +1,94 @@
+#![cfg(feature = "graphml")]
+
+extern crate petgraph;
+
+use std::collections::HashMap;
+
+use petgraph::{
+    Graph,
+    Undirected,
+};
+use petgraph::io::graphml::{
+    AttrValue,
+    GraphMl,
+    parse,
+};
+
+fn edge_list(g: &Graph<HashMap<String, String>, HashMap<String, String>>)
+    -> Vec<(usize, usize, Option<&str>)>
+{
+    g.raw_edges().iter().map(|e| {
+        (e.source().index(), e.target().index(), e.weight.get("weight").map(|s| &s[..]))
+    }).collect()
+}
+
+#[test]
+fn round_trip() {
+    let mut gr = Graph::<_, _, Undirected>::new_undirected();
+    let a = gr.add_node("A");
+    let b = gr.add_node("B <\"quoted\" & 'escaped'>");
+    let c = gr.add_node("C");
+    gr.add_edge(a, b, 1);
+    gr.add_edge(b, c, 2);
+    gr.add_edge(c, a, 3);
+
+    let output = GraphMl::new(&gr)
+        .export_node_weights(|n| vec![("name".to_string(), AttrValue::String(n.to_string()))])
+        .export_edge_weights(|&e| vec![("weight".to_string(), AttrValue::Int(e))])
+        .to_string();
+    assert!(output.contains(r#"edgedefault="undirected""#));
+    assert!(output.contains(r#"attr.name="weight" attr.type="int""#));
+
+    let parsed = parse(&output).unwrap();
+    let names = parsed.node_indices().map(|i| &parsed[i]["name"][..]).collect::<Vec<_>>();
+    assert_eq!(names, ["A", "B <\"quoted\" & 'escaped'>", "C"]);
+    assert_eq!(edge_list(&parsed),
+               vec![(0, 1, Some("1")), (1, 2, Some("2")), (2, 0, Some("3"))]);
+}
+
+#[test]
+fn structure_only() {
+    let mut gr = Graph::<(), ()>::new();
+    let a = gr.add_node(());
+    let b = gr.add_node(());
+    gr.add_edge(a, b, ());
+    gr.add_edge(b, b, ());
+
+    let output = GraphMl::new(&gr).to_string();
+    assert!(!output.contains("<key"));
+    let parsed = parse(&output).unwrap();
+    assert_eq!(parsed.node_count(), 2);
+    assert_eq!(edge_list(&parsed), vec![(0, 1, None), (1, 1, None)]);
+}
+
+#[test]
+fn defaults_and_undeclared() {
+    let g = parse(r#"<?xml version="1.0"?>
+<!-- written by hand -->
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+  <key id="w" for="edge" attr.name="weight" attr.type="double"><default>1.0</default></key>
+  <key id="l" for="all" attr.name="label"/>
+  <graph edgedefault="directed">
+    <edge source="x" target="y"><data key="w">2.5</data></edge>
+    <node id="x"><data key="l"><![CDATA[<x>]]></data></node>
+    <edge source="y" target="x"><data key="extra">&#x41;&amp;B</data></edge>
+  </graph>
+</graphml>"#).unwrap();
+
+    assert_eq!(g.node_count(), 2);
+    assert_eq!(edge_list(&g), vec![(0, 1, Some("2.5")), (1, 0, Some("1.0"))]);
+    let x = g.node_indices().next().unwrap();
+    assert_eq!(g[x]["label"], "<x>");
+    assert_eq!(g.raw_edges()[1].weight["extra"], "A&B");
+}
+
+#[test]
+fn errors() {
+    assert!(parse("").is_err());
+    assert!(parse("<graph/>").is_err());
+    let err = parse("<graphml>\n<graph>\n<node/>\n</graph></graphml>").unwrap_err();
+    assert_eq!(err.line(), 3);
+    assert!(parse("<graphml><graph></graphml>").is_err());
+    assert!(parse("<graphml><graph>").is_err());
+    assert!(parse("<graphml><data key='d'>&bogus;</data></graphml>").is_err());
+}
@@ -3,6 +3,9 @@
 #[cfg(feature = "test")]
 extern crate test;
 extern crate petgraph;
+extern crate rand;
+
+use rand::Rng;
 
 use petgraph::{
     Graph,
@@ -631,3 +634,271 @@ fn bench_praust_mst(bb: &mut test::Bencher)
         petgraph::algo::min_spanning_tree(&b))
     });
 }
+
+/// The edges of `g` renumbered in its canonical order, sorted.
+fn canonical_edges<Ty: EdgeType>(g: &Graph<(), (), Ty>) -> Vec<(usize, usize)> {
+    let order = petgraph::algo::canonical_form(g);
+    let mut position = vec![0; order.len()];
+    for (i, a) in order.into_iter().enumerate() {
+        position[a.index()] = i;
+    }
+    let mut edges = g.raw_edges().iter().map(|e| {
+        let (a, b) = (position[e.source().index()], position[e.target().index()]);
+        if g.is_directed() || a <= b { (a, b) } else { (b, a) }
+    }).collect::<Vec<_>>();
+    edges.sort();
+    edges
+}
+
+/// `g` with its nodes and edges shuffled.
+fn shuffled<Ty: EdgeType, R: Rng>(g: &Graph<(), (), Ty>, rng: &mut R) -> Graph<(), (), Ty> {
+    let mut perm = (0..g.node_count()).collect::<Vec<_>>();
+    rng.shuffle(&mut perm);
+    let mut edges = g.raw_edges().iter()
+        .map(|e| (perm[e.source().index()] as u32, perm[e.target().index()] as u32))
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut edges);
+    let mut h = Graph::with_capacity(g.node_count(), g.edge_count());
+    for _ in 0..g.node_count() {
+        h.add_node(());
+    }
+    h.extend_with_edges(edges);
+    h
+}
+
+#[test]
+fn canonical_form_named_graphs()
+{
+    let pairs = [(PETERSEN_A, PETERSEN_B), (FULL_A, FULL_B), (PRAUST_A, PRAUST_B),
+                 (COXETER_A, COXETER_B), (G1U, G2U), (G1U, G4U), (G3_1, G3_2),
+                 (G8_1, G8_2), (S1, S2)];
+    for &(sa, sb) in &pairs {
+        let (a, b) = (str_to_graph(sa), str_to_graph(sb));
+        assert_eq!(canonical_edges(&a) == canonical_edges(&b), is_isomorphic(&a, &b));
+        let (a, b) = (str_to_digraph(sa), str_to_digraph(sb));
+        assert_eq!(canonical_edges(&a) == canonical_edges(&b), is_isomorphic(&a, &b));
+    }
+}
+
+#[test]
+fn canonical_form_random()
+{
+    let mut rng = rand::weak_rng();
+    for _ in 0..200 {
+        let nodes = rng.gen_range(0, 9);
+        let mut g = Graph::<(), ()>::new();
+        for _ in 0..nodes {
+            g.add_node(());
+        }
+        if nodes > 0 {
+            for _ in 0..rng.gen_range(0, 2 * nodes) {
+                let a = node_index(rng.gen_range(0, nodes));
+                let b = node_index(rng.gen_range(0, nodes));
+                g.add_edge(a, b, ());
+            }
+        }
+        let h = shuffled(&g, &mut rng);
+        assert_eq!(canonical_edges(&g), canonical_edges(&h));
+        let (g, h) = (g.into_edge_type::<Undirected>(), h.into_edge_type::<Undirected>());
+        assert_eq!(canonical_edges(&g), canonical_edges(&shuffled(&h, &mut rng)));
+
+        // compare with a graph of the same size
+        let other = shuffled(&g, &mut rng);
+        let mut other = other.into_edge_type::<Undirected>();
+        if nodes > 1 && other.edge_count() > 0 {
+            let e = edge_index(rng.gen_range(0, other.edge_count()));
+            let (a, _) = other.edge_endpoints(e).unwrap();
+            other.remove_edge(e);
+            other.add_edge(a, node_index(rng.gen_range(0, nodes)), ());
+        }
+        assert_eq!(canonical_edges(&g) == canonical_edges(&other), is_isomorphic(&g, &other));
+    }
+}
+
+#[test]
+fn canonical_form_symmetric()
+{
+    // graphs with large automorphism groups are pruned quickly
+    let isolated = Graph::<(), (), Undirected>::from_edges(&[(39, 39)]);
+    assert_eq!(petgraph::algo::canonical_form(&isolated).len(), 40);
+    let mut complete = Graph::<(), (), Undirected>::new_undirected();
+    let mut cycle = Graph::<(), (), Undirected>::new_undirected();
+    for i in 0..20 {
+        for j in i + 1..20 {
+            complete.extend_with_edges(&[(i, j)]);
+        }
+        cycle.extend_with_edges(&[(i, (i + 1) % 30)]);
+    }
+    for i in 20..30 {
+        cycle.extend_with_edges(&[(i, (i + 1) % 30)]);
+    }
+    let mut rng = rand::weak_rng();
+    assert_eq!(canonical_edges(&complete), canonical_edges(&shuffled(&complete, &mut rng)));
+    assert_eq!(canonical_edges(&cycle), canonical_edges(&shuffled(&cycle, &mut rng)));
+    let petersen = str_to_graph(PETERSEN_A);
+    assert_eq!(canonical_edges(&petersen), canonical_edges(&shuffled(&petersen, &mut rng)));
+}
+
+#[test]
+fn canonical_form_by_weight()
+{
+    use petgraph::algo::canonical_form_by;
+
+    fn relabeled(g: &Graph<u8, ()>) -> (Vec<u8>, Vec<(usize, usize)>) {
+        let order = canonical_form_by(g, |&w| w);
+        let mut position = vec![0; order.len()];
+        for (i, &a) in order.iter().enumerate() {
+            position[a.index()] = i;
+        }
+        let mut edges = g.raw_edges().iter()
+            .map(|e| (position[e.source().index()], position[e.target().index()]))
+            .collect::<Vec<_>>();
+        edges.sort();
+        (order.iter().map(|&a| g[a]).collect(), edges)
+    }
+
+    let mut rng = rand::weak_rng();
+    for _ in 0..200 {
+        let nodes = rng.gen_range(1, 8);
+        let mut g = Graph::<u8, ()>::new();
+        let mut h = Graph::<u8, ()>::new();
+        for _ in 0..nodes {
+            g.add_node(rng.gen_range(0, 2));
+            h.add_node(rng.gen_range(0, 2));
+        }
+        for _ in 0..rng.gen_range(0, 2 * nodes) {
+            g.add_edge(node_index(rng.gen_range(0, nodes)), node_index(rng.gen_range(0, nodes)), ());
+            h.add_edge(node_index(rng.gen_range(0, nodes)), node_index(rng.gen_range(0, nodes)), ());
+        }
+        let (keys, _) = relabeled(&g);
+        assert!(keys.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(relabeled(&g) == relabeled(&h),
+                   is_isomorphic_matching(&g, &h, |x, y| x == y, |_, _| true));
+    }
+}
+
+/// The sorted edges of `g` after renumbering each node `a` to `perm[a]`.
+fn permuted_edges<Ty: EdgeType>(g: &Graph<(), (), Ty>, perm: &[usize]) -> Vec<(usize, usize)>
+{
+    let mut edges = g.raw_edges().iter().map(|e| {
+        let (a, b) = (perm[e.source().index()], perm[e.target().index()]);
+        if g.is_directed() || a <= b { (a, b) } else { (b, a) }
+    }).collect::<Vec<_>>();
+    edges.sort();
+    edges
+}
+
+/// All permutations of `0..n`, in lexicographic order.
+fn permutations(n: usize) -> Vec<Vec<usize>>
+{
+    let mut perm = (0..n).collect::<Vec<_>>();
+    let mut all = vec![perm.clone()];
+    loop {
+        let i = match (1..n).rev().find(|&i| perm[i - 1] < perm[i]) {
+            None => return all,
+            Some(i) => i - 1,
+        };
+        let j = (i + 1..n).rev().find(|&j| perm[i] < perm[j]).unwrap();
+        perm.swap(i, j);
+        perm[i + 1..].reverse();
+        all.push(perm.clone());
+    }
+}
+
+#[test]
+fn automorphisms_named_graphs()
+{
+    use petgraph::algo::automorphisms;
+
+    let petersen = str_to_graph(PETERSEN_A);
+    let group = automorphisms(&petersen);
+    assert_eq!(group.group_size, 120.);
+    assert_eq!(group.orbits.len(), 1);
+
+    let mut complete = Graph::<(), (), Undirected>::new_undirected();
+    let mut cycle = Graph::<(), (), Undirected>::new_undirected();
+    let mut directed_cycle = Graph::<(), ()>::new();
+    for i in 0..7 {
+        for j in i + 1..7 {
+            complete.extend_with_edges(&[(i, j)]);
+        }
+    }
+    for i in 0..30 {
+        cycle.extend_with_edges(&[(i, (i + 1) % 30)]);
+        directed_cycle.extend_with_edges(&[(i, (i + 1) % 30)]);
+    }
+    assert_eq!(automorphisms(&complete).group_size, 5040.);
+    assert_eq!(automorphisms(&cycle).group_size, 60.);
+    assert_eq!(automorphisms(&directed_cycle).group_size, 30.);
+
+    // 19! is too large for the float to be exact
+    let isolated = Graph::<(), (), Undirected>::from_edges(&[(19, 19)]);
+    let group = automorphisms(&isolated);
+    let factorial = (1..20).fold(1., |f, i| f * i as f64);
+    assert!((group.group_size - factorial).abs() < factorial * 1e-9);
+    assert_eq!(group.orbits, vec![(0..19).map(node_index).collect::<Vec<_>>(), vec![node_index(19)]]);
+
+    for g in &[petersen, complete, cycle] {
+        let identity = permuted_edges(g, &(0..g.node_count()).collect::<Vec<_>>());
+        for perm in &automorphisms(g).generators {
+            let perm = perm.iter().map(|a| a.index()).collect::<Vec<_>>();
+            assert_eq!(permuted_edges(g, &perm), identity);
+        }
+    }
+
+    let empty = Graph::<(), ()>::new();
+    let group = automorphisms(&empty);
+    assert_eq!(group.group_size, 1.);
+    assert!(group.generators.is_empty() && group.orbits.is_empty());
+}
+
+#[test]
+fn automorphisms_random()
+{
+    use petgraph::algo::automorphisms;
+    use petgraph::unionfind::UnionFind;
+
+    fn check<Ty: EdgeType>(g: &Graph<(), (), Ty>, all_perms: &[Vec<usize>]) {
+        let n = g.node_count();
+        let identity = permuted_edges(g, &all_perms[0]);
+        let group = all_perms.iter().filter(|perm| permuted_edges(g, perm) == identity)
+                             .collect::<Vec<_>>();
+        let found = automorphisms(g);
+        assert_eq!(found.group_size, group.len() as f64);
+        for perm in &found.generators {
+            let perm = perm.iter().map(|a| a.index()).collect::<Vec<_>>();
+            assert_eq!(permuted_edges(g, &perm), identity);
+        }
+        let mut orbits = UnionFind::new(n);
+        for perm in &group {
+            for (a, &b) in perm.iter().enumerate() {
+                orbits.union(a, b);
+            }
+        }
+        for class in &found.orbits {
+            for &a in class {
+                assert!(orbits.equiv(class[0].index(), a.index()));
+            }
+        }
+        assert_eq!(found.orbits.len(), (0..n).filter(|&a| orbits.find(a) == a).count());
+    }
+
+    let mut rng = rand::weak_rng();
+    for nodes in 1..7 {
+        let all_perms = permutations(nodes);
+        for _ in 0..60 {
+            let mut g = Graph::<(), (), Undirected>::new_undirected();
+            let mut h = Graph::<(), ()>::new();
+            for _ in 0..nodes {
+                g.add_node(());
+                h.add_node(());
+            }
+            for _ in 0..rng.gen_range(0, 2 * nodes) {
+                g.add_edge(node_index(rng.gen_range(0, nodes)), node_index(rng.gen_range(0, nodes)), ());
+                h.add_edge(node_index(rng.gen_range(0, nodes)), node_index(rng.gen_range(0, nodes)), ());
+            }
+            check(&g, &all_perms);
+            check(&h, &all_perms);
+        }
+    }
+}
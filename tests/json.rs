@@ -0,0 +1,78 @@
+#![cfg(feature = "json")]
+
+extern crate petgraph;
+
+use petgraph::{
+    Graph,
+    Undirected,
+};
+use petgraph::io::json::{
+    Json,
+    ToJson,
+    from_node_link,
+    to_node_link,
+};
+
+#[derive(Debug, PartialEq)]
+struct City {
+    name: &'static str,
+    size: u32,
+}
+
+impl ToJson for City {
+    fn to_json(&self) -> Json {
+        Json::Object(vec![("name".to_string(), self.name.to_json()),
+                          ("size".to_string(), self.size.to_json())])
+    }
+}
+
+#[test]
+fn round_trip() {
+    let mut gr = Graph::<_, _, Undirected>::new_undirected();
+    let a = gr.add_node(City { name: "A \"quoted\"\n", size: 10 });
+    let b = gr.add_node(City { name: "Bé", size: 20 });
+    gr.add_edge(a, b, Some(1.5));
+    gr.add_edge(b, b, None);
+
+    let output = to_node_link(&gr);
+    assert!(output.starts_with(r#"{"directed":false,"#));
+    let parsed = from_node_link(&output).unwrap();
+    assert_eq!(parsed.node_count(), 2);
+    assert_eq!(parsed[a], Json::Object(vec![
+        ("name".to_string(), Json::String("A \"quoted\"\n".to_string())),
+        ("size".to_string(), Json::Number(10.)),
+    ]));
+    assert_eq!(parsed[b].get("name"), Some(&Json::String("Bé".to_string())));
+    let edges = parsed.raw_edges().iter().map(|e| {
+        (e.source(), e.target(), e.weight.get("weight").cloned())
+    }).collect::<Vec<_>>();
+    assert_eq!(edges, vec![(a, b, Some(Json::Number(1.5))), (b, b, None)]);
+}
+
+#[test]
+fn d3_indices() {
+    // links that refer to nodes by position, as in d3 examples
+    let g = from_node_link(r#"{"nodes": [{"name": "x"}, {"name": "y\u00e9\ud83d\ude00"}],
+                               "links": [{"source": 1, "target": 0}]}"#).unwrap();
+    assert_eq!(g[g.raw_edges()[0].source()].get("name"),
+               Some(&Json::String("yé\u{1f600}".to_string())));
+    assert_eq!(g.raw_edges()[0].target().index(), 0);
+}
+
+#[test]
+fn errors() {
+    assert!(from_node_link("").is_err());
+    assert!(from_node_link("[]").is_err());
+    assert!(from_node_link(r#"{"nodes": [1]}"#).is_err());
+    assert!(from_node_link(r#"{"nodes": [], "links": [{"source": 0, "target": 0}]}"#).is_err());
+    assert!(from_node_link(r#"{"nodes": [{}], "links": [{"source": 0}]}"#).is_err());
+    assert!(from_node_link(r#"{"nodes": []} x"#).is_err());
+    let err = from_node_link("{\n\"nodes\": [\n{\"id\": tru}]}").unwrap_err();
+    assert_eq!(err.line(), 3);
+    // deep nesting is an error, not a stack overflow
+    let deep = "[".repeat(200000);
+    assert_eq!(from_node_link(&deep).unwrap_err().line(), 1);
+    let nested = format!("{{\"nodes\": [{{\"x\": {}{}}}], \"links\": []}}",
+                         "[".repeat(100), "]".repeat(100));
+    assert!(from_node_link(&nested).is_ok());
+}
@@ -0,0 +1,97 @@
+#![cfg(feature = "matrix_market")]
+
+extern crate petgraph;
+
+use petgraph::{
+    Graph,
+    Undirected,
+};
+use petgraph::io::matrix_market::{
+    ReadError,
+    read_matrix_market,
+    write_matrix_market,
+};
+
+fn edge_list(g: &Graph<(), f64>) -> Vec<(usize, usize, f64)> {
+    g.raw_edges().iter().map(|e| {
+        (e.source().index(), e.target().index(), e.weight)
+    }).collect()
+}
+
+#[test]
+fn round_trip() {
+    let mut gr = Graph::new();
+    let a = gr.add_node("a");
+    let b = gr.add_node("b");
+    let c = gr.add_node("c");
+    gr.add_edge(a, b, 1);
+    gr.add_edge(c, a, -2);
+    gr.add_edge(c, c, 3);
+
+    let mut output = Vec::new();
+    write_matrix_market(&mut output, &gr.map(|_, _| (), |_, &w| w as f64 * 1.5)).unwrap();
+    let parsed = read_matrix_market(&output[..]).unwrap();
+    assert_eq!(parsed.node_count(), 3);
+    assert_eq!(edge_list(&parsed), vec![(0, 1, 1.5), (2, 0, -3.), (2, 2, 4.5)]);
+}
+
+#[test]
+fn symmetric() {
+    let mut gr = Graph::<(), u32, Undirected>::new_undirected();
+    let a = gr.add_node(());
+    let b = gr.add_node(());
+    gr.add_edge(a, b, 7);
+    gr.add_edge(b, b, 1);
+
+    let mut output = Vec::new();
+    write_matrix_market(&mut output, &gr).unwrap();
+    assert_eq!(String::from_utf8(output.clone()).unwrap(),
+               "%%MatrixMarket matrix coordinate real symmetric\n2 2 2\n2 1 7\n2 2 1\n");
+    let parsed = read_matrix_market(&output[..]).unwrap();
+    assert_eq!(edge_list(&parsed), vec![(1, 0, 7.), (0, 1, 7.), (1, 1, 1.)]);
+}
+
+#[test]
+fn pattern_and_skew() {
+    let g = read_matrix_market(&b"%%MatrixMarket matrix coordinate pattern general
+%
+2 3 2
+1 3
+2 1
+"[..]).unwrap();
+    assert_eq!(g.node_count(), 3);
+    assert_eq!(edge_list(&g), vec![(0, 2, 1.), (1, 0, 1.)]);
+
+    let g = read_matrix_market(&b"%%MatrixMarket matrix coordinate integer skew-symmetric
+2 2 1
+2 1 4
+"[..]).unwrap();
+    assert_eq!(edge_list(&g), vec![(1, 0, 4.), (0, 1, -4.)]);
+}
+
+#[test]
+fn errors() {
+    fn line_of(input: &str) -> usize {
+        match read_matrix_market(input.as_bytes()) {
+            Err(ReadError::Parse(e)) => e.line(),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+    let header = "%%MatrixMarket matrix coordinate real general\n";
+    assert_eq!(line_of(""), 1);
+    assert_eq!(line_of("%%MatrixMarket matrix array real general\n1 1\n1.0\n"), 1);
+    assert_eq!(line_of("%%MatrixMarket matrix coordinate complex general\n"), 1);
+    assert_eq!(line_of(header), 1);
+    assert_eq!(line_of(&format!("{}2 2\n", header)), 2);
+    assert_eq!(line_of(&format!("{}2 2 1\n3 1 1.0\n", header)), 3);
+    assert_eq!(line_of(&format!("{}2 2 1\n1 1\n", header)), 3);
+    assert_eq!(line_of(&format!("{}2 2 2\n1 1 1.0\n", header)), 3);
+    // sizes that don't fit, rejected before allocating
+    assert_eq!(line_of(&format!("{}99999999999 1 0\n", header)), 2);
+    assert_eq!(line_of(&format!("{}2 2 5\n", header)), 2);
+    assert_eq!(line_of(&format!("{}99999 99999 9999999999\n", header)), 2);
+    // more nodes than the input can describe
+    assert_eq!(line_of(&format!("{}4000000000 4000000000 1\n1 1 1.0\n", header)), 2);
+    let g = read_matrix_market(format!("{}1000 1000 1\n1000 1 1.0\n", header).as_bytes());
+    assert_eq!(g.unwrap().node_count(), 1000);
+}
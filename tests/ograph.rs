@@ -1,4 +1,5 @@
 extern crate petgraph;
+extern crate rand;
 
 use petgraph::{
     Graph,
@@ -18,6 +19,7 @@ use petgraph as pg;
 
 use petgraph::algo::{
     min_spanning_tree,
+    min_spanning_tree_edges,
     is_cyclic_undirected,
 };
 
@@ -36,9 +38,18 @@ use petgraph::visit::{
 };
 use petgraph::algo::{
     dijkstra,
+    dijkstra_bounded,
+    dijkstra_into,
+    dijkstra_path,
+    dijkstra_predecessors,
+    bfs_predecessors,
+    multi_source_dijkstra,
+    shortest_path,
+    widest_path,
 };
 
 use petgraph::dot::{
+    Config,
     Dot,
 };
 
@@ -211,6 +222,41 @@ fn mst() {
 
 }
 
+#[test]
+fn mst_edges() {
+    // weights that are not Clone
+    #[derive(Debug)]
+    struct Road(u32);
+
+    let mut gr = Graph::<(), Road, Undirected>::new_undirected();
+    let a = gr.add_node(());
+    let b = gr.add_node(());
+    let c = gr.add_node(());
+    let d = gr.add_node(());
+    let e = gr.add_node(());
+    gr.add_edge(a, b, Road(4));
+    gr.add_edge(b, c, Road(1));
+    gr.add_edge(c, a, Road(2));
+    gr.add_edge(c, d, Road(4));
+    gr.add_edge(a, d, Road(3));
+    gr.add_edge(e, e, Road(0));
+
+    let tree = min_spanning_tree_edges(&gr, |road| road.0);
+    assert_eq!(tree, vec![EdgeIndex::new(1), EdgeIndex::new(2), EdgeIndex::new(4)]);
+    let total: u32 = tree.iter().map(|&e| gr[e].0).sum();
+    assert_eq!(total, 6);
+
+    // costs by reference, and ties in edge index order
+    let tree = min_spanning_tree_edges(&gr, |_| &());
+    assert_eq!(tree, vec![EdgeIndex::new(0), EdgeIndex::new(1), EdgeIndex::new(3)]);
+
+    // NaN costs are picked last
+    let tree = min_spanning_tree_edges(&gr, |road| if road.0 == 1 { ::std::f64::NAN } else { road.0 as f64 });
+    assert_eq!(tree, vec![EdgeIndex::new(2), EdgeIndex::new(4), EdgeIndex::new(0)]);
+
+    assert_eq!(min_spanning_tree_edges(&Graph::<(), Road>::new(), |road| road.0), vec![]);
+}
+
 #[test]
 fn selfloop() {
     let mut gr = Graph::new();
@@ -335,6 +381,64 @@ fn dijk() {
 
     let scores = dijkstra(&g, a, Some(c), |gr, n| gr.edges(n).map(|(n, &e)| (n, e)));
     assert_eq!(scores[&c], 9);
+
+    // dense scores, indexed by node
+    let map = dijkstra(&g, b, None, |gr, n| gr.edges(n).map(|(n, &e)| (n, e)));
+    let mut dense = Vec::new();
+    dijkstra_into(&g, b, None, |gr, n| gr.edges(n).map(|(n, &e)| (n, e)), &mut dense);
+    for node in g.node_indices() {
+        assert_eq!(dense.get(node.index()).cloned().unwrap_or(None), map.get(&node).cloned());
+    }
+
+    let path = dijkstra_path(&g, a, e, |gr, n| gr.edges(n).map(|(n, &e)| (n, e)));
+    assert_eq!(path, Some((20, vec![a, c, d, e])));
+    let path = dijkstra_path(&g, a, a, |gr, n| gr.edges(n).map(|(n, &e)| (n, e)));
+    assert_eq!(path, Some((0, vec![a])));
+    let (cost, path) = shortest_path(&g, a, e, |&w| w).unwrap();
+    assert_eq!((cost, path.nodes()), (20, &[a, c, d, e][..]));
+    assert_eq!(path.total_weight(|e| g[e]), cost);
+    let (scores, pred) = dijkstra_predecessors(&g, a, None, |gr, n| gr.edges(n).map(|(n, &e)| (n, e)));
+    assert_eq!(pred.len(), scores.len());
+    assert_eq!(pred.predecessor(&a), None);
+    for node in g.node_indices() {
+        let path = pred.path_to(&node).unwrap();
+        assert_eq!((path[0], *path.last().unwrap()), (a, node));
+        let cost: i32 = path.windows(2).map(|w| g[g.find_edge(w[0], w[1]).unwrap()]).sum();
+        assert_eq!(cost, scores[&node]);
+    }
+    let pred = bfs_predecessors(&g, a);
+    assert_eq!(pred.path_to(&e).map(|path| path.len()), Some(3));
+    assert!(pred.iter().all(|(&node, p)| p.map_or(node == a, |&p| g.find_edge(p, node).is_some())));
+    let scores = dijkstra_bounded(&g, a, 11, |gr, n| gr.edges(n).map(|(n, &e)| (n, e)));
+    let mut scores: Vec<_> = scores.into_iter().map(|(n, s)| (g[n], s)).collect();
+    scores.sort();
+    assert_eq!(scores, vec![("A", 0), ("B", 7), ("C", 9), ("D", 11)]);
+
+    let scores = multi_source_dijkstra(&g, vec![(a, 0), (f, 0)],
+                                       |gr, n| gr.edges(n).map(|(n, &e)| (n, e)));
+    let mut scores: Vec<_> = scores.into_iter().map(|(n, (s, src))| (g[n], s, g[src])).collect();
+    scores.sort();
+    assert_eq!(scores, vec![("A", 0, "A"), ("B", 7, "A"), ("C", 9, "A"),
+                            ("D", 11, "A"), ("E", 6, "F"), ("F", 0, "F")]);
+
+    // an initial offset moves the border between the cells
+    let scores = multi_source_dijkstra(&g, vec![(a, 5), (f, 0)],
+                                       |gr, n| gr.edges(n).map(|(n, &e)| (n, e)));
+    assert_eq!(scores[&b], (12, a));
+    assert_eq!(scores[&c], (11, f));
+
+    // the widest path avoids the narrow edges a-b and e-f
+    let path = widest_path(&g, a, f, |gr, n| gr.edges(n).map(|(n, &e)| (n, e)));
+    assert_eq!(path, Some((9, vec![a, c, f])));
+    // the widest closed walk through a goes back and forth along a-d
+    let path = widest_path(&g, a, a, |gr, n| gr.edges(n).map(|(n, &e)| (n, e)));
+    assert_eq!(path, Some((14, vec![a, d, a])));
+
+    let lonely = g.add_node("G");
+    let path = dijkstra_path(&g, a, lonely, |gr, n| gr.edges(n).map(|(n, &e)| (n, e)));
+    assert_eq!(path, None);
+    let path = widest_path(&g, a, lonely, |gr, n| gr.edges(n).map(|(n, &e)| (n, e)));
+    assert_eq!(path, None);
 }
 
 #[cfg(feature = "generate")]
@@ -522,6 +626,76 @@ fn is_cyclic_directed() {
     assert!(petgraph::algo::is_cyclic_directed(&gr));
 }
 
+#[test]
+fn tree_forest_dag() {
+    use petgraph::algo::{is_dag, is_forest, is_tree};
+    let mut gr = Graph::<(), ()>::new();
+    assert!(!is_tree(&gr));
+    assert!(is_forest(&gr));
+    assert!(is_dag(&gr));
+
+    gr.extend_with_edges(&[(0, 1), (0, 2), (2, 3)]);
+    assert!(is_tree(&gr));
+    assert!(is_forest(&gr));
+    assert!(is_dag(&gr));
+
+    // a second component
+    gr.extend_with_edges(&[(5, 4)]);
+    assert!(!is_tree(&gr));
+    assert!(is_forest(&gr));
+
+    // cycle in the underlying undirected graph, but still acyclic
+    gr.extend_with_edges(&[(1, 3)]);
+    assert!(!is_forest(&gr));
+    assert!(is_dag(&gr));
+
+    // right edge count, but a cycle and an isolated node
+    let mut gr = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2), (2, 0)]);
+    gr.add_node(());
+    assert!(!is_tree(&gr));
+    assert!(!is_dag(&gr));
+
+    let gr = Graph::<(), ()>::from_edges(&[(0, 0)]);
+    assert!(!is_forest(&gr));
+    assert!(!is_dag(&gr));
+}
+
+fn assert_is_cycle<Ty: EdgeType>(gr: &Graph<(), (), Ty>, cycle: &[(NodeIndex, EdgeIndex)]) {
+    assert!(!cycle.is_empty());
+    for (i, &(a, e)) in cycle.iter().enumerate() {
+        let b = cycle[(i + 1) % cycle.len()].0;
+        let (s, t) = gr.edge_endpoints(e).unwrap();
+        assert!((s, t) == (a, b) || (!gr.is_directed() && (t, s) == (a, b)),
+                "edge {:?} does not lead from {:?} to {:?}", e, a, b);
+    }
+}
+
+#[test]
+fn find_cycle() {
+    use petgraph::algo::find_cycle;
+    let mut gr = Graph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 3), (2, 3), (3, 4)]);
+    assert_eq!(find_cycle(&gr), None);
+    gr.extend_with_edges(&[(4, 2)]);
+    let cycle = find_cycle(&gr).unwrap();
+    assert_is_cycle(&gr, &cycle);
+    assert_eq!(cycle.len(), 3);
+
+    let gr = Graph::<(), ()>::from_edges(&[(0, 1), (1, 1)]);
+    assert_eq!(find_cycle(&gr), Some(vec![(n(1), EdgeIndex::new(1))]));
+
+    let mut gr = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2), (2, 3)]);
+    assert_eq!(find_cycle(&gr), None);
+    gr.extend_with_edges(&[(3, 1)]);
+    let cycle = find_cycle(&gr).unwrap();
+    assert_is_cycle(&gr, &cycle);
+    assert_eq!(cycle.len(), 3);
+
+    let gr = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 0)]);
+    let cycle = find_cycle(&gr).unwrap();
+    assert_is_cycle(&gr, &cycle);
+    assert_eq!(cycle.len(), 2);
+}
+
 fn assert_sccs_eq(mut res: Vec<Vec<NodeIndex>>, normalized: Vec<Vec<NodeIndex>>) {
     // normalize the result and compare with the answer.
     for scc in res.iter_mut() {
@@ -634,6 +808,34 @@ fn condensation()
     assert!(cond.edge_count() == gr.edge_count());
 }
 
+#[test]
+fn ego() {
+    use petgraph::algo::ego_graph;
+
+    // a directed path 0 -> 1 -> 2 -> 3 -> 4 with a chord 4 -> 2
+    let gr = Graph::<u32, u32>::from_edges(&[(0, 1, 1), (1, 2, 2), (2, 3, 3), (3, 4, 4), (4, 2, 5)]);
+    let (ego, nodes) = ego_graph(&gr, n(2), 1, Outgoing);
+    assert_eq!(nodes, vec![n(2), n(3)]);
+    assert_eq!(ego.edge_count(), 1);
+    assert_eq!(ego.raw_edges()[0].weight, 3);
+
+    let (ego, nodes) = ego_graph(&gr, n(2), 1, Incoming);
+    assert_eq!(nodes, vec![n(1), n(2), n(4)]);
+    assert_eq!(ego.edge_count(), 2);
+
+    let (ego, nodes) = ego_graph(&gr, n(2), 0, Outgoing);
+    assert_eq!(nodes, vec![n(2)]);
+    assert_eq!(ego.edge_count(), 0);
+
+    // all nodes are within reach: the induced subgraph is the whole graph
+    let undirected = gr.clone().into_edge_type::<Undirected>();
+    let (ego, nodes) = ego_graph(&undirected, n(0), 3, Outgoing);
+    assert_eq!(nodes, undirected.node_indices().collect::<Vec<_>>());
+    assert_eq!(ego.edge_count(), 5);
+    let (_, nodes) = ego_graph(&undirected, n(0), 2, Outgoing);
+    assert_eq!(nodes, vec![n(0), n(1), n(2)]);
+}
+
 #[test]
 fn connected_comp()
 {
@@ -667,9 +869,100 @@ fn connected_comp()
 
     gr.add_edge(n(9), n(10), ());
     assert_eq!(petgraph::algo::connected_components(&gr), 2);
+    assert_eq!(petgraph::algo::connected_component_labels(&gr),
+               vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1]);
+    assert_eq!(petgraph::algo::connected_component_groups(&gr),
+               vec![(0..9).map(n).collect::<Vec<_>>(), vec![n(9), n(10)]]);
 
     let gr = gr.into_edge_type::<Undirected>();
     assert_eq!(petgraph::algo::connected_components(&gr), 2);
+    assert_eq!(petgraph::algo::connected_component_groups(&gr).len(), 2);
+}
+
+#[test]
+fn connectivity_predicates()
+{
+    use petgraph::algo::{is_connected, is_strongly_connected, is_weakly_connected};
+    let mut gr = Graph::<(), ()>::new();
+    assert!(is_connected(&gr));
+    assert!(is_strongly_connected(&gr));
+
+    gr.extend_with_edges(&[(0, 1), (1, 2), (3, 2)]);
+    assert!(is_connected(&gr));
+    assert!(is_weakly_connected(&gr));
+    assert!(!is_strongly_connected(&gr));
+
+    gr.extend_with_edges(&[(2, 0), (2, 3)]);
+    assert!(is_strongly_connected(&gr));
+
+    gr.add_node(());
+    assert!(!is_weakly_connected(&gr));
+    assert!(!is_strongly_connected(&gr));
+
+    let gr = gr.into_edge_type::<Undirected>();
+    assert!(!is_connected(&gr));
+
+    let mut gm = GraphMap::new();
+    gm.add_edge(1, 2, ());
+    gm.add_edge(3, 4, ());
+    assert!(!is_connected(&gm));
+    gm.add_edge(2, 3, ());
+    assert!(is_connected(&gm));
+    assert!(is_strongly_connected(&gm));
+}
+
+#[test]
+fn biconnected_components()
+{
+    use petgraph::algo::{biconnected_components, two_edge_connected_components};
+    // a triangle 0 1 2, the bridge 2 - 3, a square 3 4 5 6 with the chord
+    // 4 - 6, a self loop at 5, a double edge 6 = 7 and the isolated node 8
+    let mut gr = Graph::<(), (), Undirected>::from_edges(&[
+        (0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 5), (5, 6), (6, 3),
+        (4, 6), (5, 5), (6, 7), (7, 6)]);
+    gr.add_node(());
+
+    let mut blocks = biconnected_components(&gr).into_iter().map(|mut block| {
+        block.sort();
+        block.into_iter().map(|e| e.index()).collect::<Vec<_>>()
+    }).collect::<Vec<_>>();
+    blocks.sort();
+    assert_eq!(blocks, vec![vec![0, 1, 2], vec![3], vec![4, 5, 6, 7, 8], vec![10, 11]]);
+
+    assert_eq!(two_edge_connected_components(&gr),
+               vec![vec![n(0), n(1), n(2)], vec![n(3), n(4), n(5), n(6), n(7)], vec![n(8)]]);
+
+    // directions are ignored
+    let gr = Graph::<(), ()>::from_edges(&[(0, 1), (2, 1), (2, 0), (3, 2)]);
+    assert_eq!(biconnected_components(&gr).len(), 2);
+    assert_eq!(two_edge_connected_components(&gr),
+               vec![vec![n(0), n(1), n(2)], vec![n(3)]]);
+}
+
+#[test]
+fn block_cut_tree()
+{
+    use petgraph::algo::{block_cut_tree, BlockCutNode};
+    // the path 0 - 1 - 2, a triangle 2 3 4 and the isolated node 5
+    let mut gr = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 4), (4, 2)]);
+    gr.add_node(());
+    let (tree, map) = block_cut_tree(&gr);
+    let weights = tree.node_indices().map(|a| tree[a].clone()).collect::<Vec<_>>();
+    assert_eq!(weights, vec![
+        BlockCutNode::Block(vec![n(0), n(1)]),
+        BlockCutNode::Block(vec![n(1), n(2)]),
+        BlockCutNode::Block(vec![n(2), n(3), n(4)]),
+        BlockCutNode::Block(vec![n(5)]),
+        BlockCutNode::Cut(n(1)),
+        BlockCutNode::Cut(n(2)),
+    ]);
+    assert_eq!(map, vec![n(0), n(4), n(5), n(2), n(2), n(3)]);
+    assert_eq!(tree.edge_count(), 4);
+    assert!(tree.find_edge(n(4), n(0)).is_some());
+    assert!(tree.find_edge(n(4), n(1)).is_some());
+    assert!(tree.find_edge(n(5), n(1)).is_some());
+    assert!(tree.find_edge(n(5), n(2)).is_some());
+    assert!(petgraph::algo::is_forest(&tree));
 }
 
 #[should_panic]
@@ -699,6 +992,26 @@ fn usize_index()
     assert_eq!(gr[e], 1.2);
 }
 
+#[test]
+fn u64_index()
+{
+    let mut gr = Graph::<_, _, Undirected, u64>::with_capacity(0, 0);
+    let a = gr.add_node(0);
+    let b = gr.add_node(1);
+    let c = gr.add_node(2);
+    gr.add_edge(a, b, 1.);
+    gr.add_edge(b, c, 2.);
+    let e = gr.add_edge(c, a, 3.);
+    assert_eq!(NodeIndex::<u64>::end().index(), !0);
+    assert_eq!(gr.find_edge(a, c), Some(e));
+    assert_eq!(gr.neighbors(a).count(), 2);
+
+    gr.remove_node(b);
+    assert_eq!(gr.node_count(), 2);
+    assert_eq!(gr.edge_count(), 1);
+    assert_eq!(gr[gr.find_edge(n(0), n(1)).unwrap()], 3.);
+}
+
 #[test]
 fn u8_index()
 {
@@ -924,6 +1237,110 @@ fn toposort_generic() {
     }
 }
 
+#[test]
+fn toposort_generations() {
+    use petgraph::algo::topological_generations;
+    use rand::Rng;
+
+    let mut rng = rand::weak_rng();
+    for _ in 0..50 {
+        let nodes = rng.gen_range(1, 30);
+        let mut gr = Graph::<(), ()>::new();
+        for _ in 0..nodes {
+            gr.add_node(());
+        }
+        for _ in 0..rng.gen_range(0, 60) {
+            let a = rng.gen_range(0, nodes);
+            let b = rng.gen_range(0, nodes);
+            if a < b {
+                gr.add_edge(n(a), n(b), ());
+            }
+        }
+
+        // the generation of a node is the longest path ending in it
+        let mut longest = vec![0; nodes];
+        for b in 0..nodes {
+            for a in gr.neighbors_directed(n(b), Incoming) {
+                longest[b] = std::cmp::max(longest[b], longest[a.index()] + 1);
+            }
+        }
+        let generations = topological_generations(&gr).unwrap();
+        assert_eq!(generations.iter().map(|g| g.len()).sum::<usize>(), nodes);
+        for (i, generation) in generations.iter().enumerate() {
+            assert!(!generation.is_empty());
+            for w in generation.windows(2) {
+                assert!(w[0] < w[1]);
+            }
+            for &a in generation {
+                assert_eq!(longest[a.index()], i);
+            }
+        }
+
+        gr.add_edge(n(0), n(nodes - 1), ());
+        gr.add_edge(n(nodes - 1), n(0), ());
+        assert_eq!(topological_generations(&gr), None);
+    }
+}
+
+#[test]
+fn toposort_priority() {
+    use petgraph::visit::PriorityTopo;
+    use rand::Rng;
+
+    let mut rng = rand::weak_rng();
+    for _ in 0..50 {
+        let nodes = rng.gen_range(1, 30);
+        let priority = (0..nodes).map(|_| rng.gen_range(0, 10)).collect::<Vec<u32>>();
+        let mut gr = Graph::<(), ()>::new();
+        for _ in 0..nodes {
+            gr.add_node(());
+        }
+        for _ in 0..rng.gen_range(0, 60) {
+            let a = rng.gen_range(0, nodes);
+            let b = rng.gen_range(0, nodes);
+            if a < b {
+                gr.add_edge(n(a), n(b), ());
+            }
+        }
+
+        let mut order = Vec::new();
+        let mut topo = PriorityTopo::new(&gr, |n| priority[n.index()]);
+        while let Some(nx) = topo.next(&gr) {
+            // no ready node has a higher priority
+            for b in gr.node_indices() {
+                if !order.contains(&b) && b != nx &&
+                    gr.neighbors_directed(b, Incoming).all(|a| order.contains(&a))
+                {
+                    assert!(priority[b.index()] <= priority[nx.index()]);
+                }
+            }
+            order.push(nx);
+        }
+        assert_is_topo_order(&gr, &order);
+
+        // a reset traversal stops short of a cycle
+        gr.add_edge(n(0), n(nodes - 1), ());
+        gr.add_edge(n(nodes - 1), n(0), ());
+        topo.reset(&gr);
+        let mut count = 0;
+        while let Some(_) = topo.next(&gr) {
+            count += 1;
+        }
+        assert!(count < nodes);
+    }
+
+    // a node reached through parallel edges gets its priority once
+    let gr = Graph::<(), ()>::from_edges(&[(0, 1), (0, 1), (0, 1), (1, 2), (1, 2)]);
+    let calls = std::cell::Cell::new(0);
+    let mut topo = PriorityTopo::new(&gr, |_| { calls.set(calls.get() + 1); 0 });
+    let mut order = Vec::new();
+    while let Some(nx) = topo.next(&gr) {
+        order.push(nx);
+    }
+    assert_eq!(order, vec![n(0), n(1), n(2)]);
+    assert_eq!(calls.get(), 3);
+}
+
 #[test]
 fn map_filter_map() {
     let mut g = Graph::new_undirected();
@@ -1205,3 +1622,2929 @@ r#"digraph {
 }
 "#);
 }
+
+#[test]
+fn dot_attributes() {
+    let mut gr = Graph::new();
+    let a = gr.add_node("a");
+    let b = gr.add_node("b \"quoted\"");
+    gr.add_edge(a, b, 1);
+    gr.add_edge(b, a, 2);
+
+    let node_attrs = |_: &Graph<&str, i32>, n: NodeIndex| {
+        if n == a {
+            vec![("fill color".to_string(), "say \"hi\"".to_string())]
+        } else {
+            Vec::new()
+        }
+    };
+    let edge_attrs = |g: &Graph<&str, i32>, e: EdgeIndex| {
+        vec![("label".to_string(), format!("w={}", g[e]))]
+    };
+    let dot = Dot::with_attr_getters(&gr, &[], &edge_attrs, &node_attrs)
+                  .directed(false)
+                  .graph_attributes(&[("label", "Graph\nTitle")]);
+    assert_eq!(format!("{}", dot),
+r#"graph {
+    label="Graph\lTitle"
+    0 [label="a", "fill color"="say \"hi\""]
+    1 [label="b \"quoted\""]
+    0 -- 1 [label="w=1"]
+    1 -- 0 [label="w=2"]
+}
+"#);
+
+    // without attributes, the output is unchanged
+    assert_eq!(format!("{}", Dot::with_config(&gr, &[Config::EdgeNoLabel])),
+r#"digraph {
+    0 [label="a"]
+    1 [label="b \"quoted\""]
+    0 -> 1
+    1 -> 0
+}
+"#);
+
+    // backslashes are escaped, so a label can end in one
+    let mut gr = Graph::<_, i32>::new();
+    gr.add_node("C:\\");
+    gr.add_node("\\N");
+    assert_eq!(format!("{}", Dot::new(&gr)),
+r#"digraph {
+    0 [label="C:\\"]
+    1 [label="\\N"]
+}
+"#);
+}
+
+#[test]
+fn adjacency_and_laplacian_matrix() {
+    use petgraph::algo::{adjacency_matrix, laplacian_matrix};
+
+    let mut gr = Graph::new();
+    let a = gr.add_node("a");
+    let b = gr.add_node("b");
+    let c = gr.add_node("c");
+    gr.add_edge(a, b, 1.);
+    gr.add_edge(b, c, 2.);
+    gr.add_edge(c, a, 3.);
+    gr.add_edge(c, c, 4.);
+
+    assert_eq!(adjacency_matrix(&gr), [0., 1., 0.,
+                                       0., 0., 1.,
+                                       1., 0., 1.]);
+    assert_eq!(adjacency_matrix(&Reversed(&gr)), [0., 0., 1.,
+                                                  1., 0., 0.,
+                                                  0., 1., 1.]);
+    assert_eq!(laplacian_matrix(&gr, |_, _| 1.), [ 1., -1.,  0.,
+                                                   0.,  1., -1.,
+                                                  -1.,  0.,  1.]);
+
+    let weight = |x: NodeIndex, y: NodeIndex| {
+        gr[gr.find_edge(x, y).or(gr.find_edge(y, x)).unwrap()]
+    };
+    assert_eq!(laplacian_matrix(&AsUndirected(&gr), weight), [ 4., -1., -3.,
+                                                              -1.,  3., -2.,
+                                                              -3., -2.,  5.]);
+    let empty = Graph::<(), ()>::new();
+    assert!(adjacency_matrix(&empty).is_empty());
+}
+
+#[cfg(feature = "generate")]
+#[test]
+fn test_generate_gnp_gnm() {
+    use petgraph::generate::{gnp_random_graph, gnm_random_graph};
+
+    fn is_simple<Ty: EdgeType>(g: &Graph<(), (), Ty>) -> bool {
+        let mut edges = g.raw_edges().iter().map(|e| {
+            let (a, b) = (e.source(), e.target());
+            if g.is_directed() || a < b { (a, b) } else { (b, a) }
+        }).collect::<Vec<_>>();
+        edges.sort();
+        edges.dedup();
+        edges.len() == g.edge_count() && edges.iter().all(|&(a, b)| a != b)
+    }
+
+    let mut rng = rand::thread_rng();
+    for n in 0..8 {
+        let g = gnp_random_graph::<Directed, _>(n, 1., &mut rng);
+        assert_eq!(g.edge_count(), n * n.saturating_sub(1));
+        let g = gnp_random_graph::<Undirected, _>(n, 0., &mut rng);
+        assert_eq!(g.node_count(), n);
+        assert_eq!(g.edge_count(), 0);
+        let g = gnp_random_graph::<Undirected, _>(n, 0.5, &mut rng);
+        assert!(is_simple(&g));
+
+        let max_edges = n * n.saturating_sub(1) / 2;
+        for m in 0..max_edges + 1 {
+            let g = gnm_random_graph::<Undirected, _>(n, m, &mut rng);
+            assert_eq!(g.edge_count(), m);
+            assert!(is_simple(&g));
+            let g = gnm_random_graph::<Directed, _>(n, 2 * m, &mut rng);
+            assert_eq!(g.edge_count(), 2 * m);
+            assert!(is_simple(&g));
+        }
+    }
+}
+
+#[cfg(feature = "generate")]
+#[should_panic]
+#[test]
+fn test_generate_gnm_too_many_edges() {
+    petgraph::generate::gnm_random_graph::<Undirected, _>(4, 7, &mut rand::thread_rng());
+}
+
+#[cfg(feature = "generate")]
+#[test]
+fn test_generate_barabasi_albert() {
+    use rand::{SeedableRng, StdRng};
+    use petgraph::generate::barabasi_albert;
+
+    let mut rng = rand::thread_rng();
+    for &(n, m) in &[(2, 1), (10, 1), (10, 9), (200, 4)] {
+        let g = barabasi_albert(n, m, &mut rng);
+        assert_eq!(g.node_count(), n);
+        assert_eq!(g.edge_count(), (n - m) * m);
+        assert_eq!(petgraph::algo::connected_components(&g), 1);
+        for (i, node) in g.node_indices().enumerate().skip(m) {
+            // each added node has m distinct neighbors that were added before it
+            let mut older = g.neighbors(node).filter(|&x| x.index() < i).collect::<Vec<_>>();
+            older.sort();
+            older.dedup();
+            assert_eq!(older.len(), m);
+        }
+    }
+
+    let seed: &[_] = &[1, 2, 3, 4];
+    let g1 = barabasi_albert(50, 2, &mut StdRng::from_seed(seed));
+    let g2 = barabasi_albert(50, 2, &mut StdRng::from_seed(seed));
+    let edges = |g: &Graph<(), (), Undirected>| {
+        g.raw_edges().iter().map(|e| (e.source(), e.target())).collect::<Vec<_>>()
+    };
+    assert_eq!(edges(&g1), edges(&g2));
+}
+
+#[cfg(feature = "generate")]
+#[test]
+fn test_generate_watts_strogatz() {
+    use petgraph::generate::watts_strogatz;
+
+    let mut rng = rand::thread_rng();
+    // without rewiring it is the ring lattice
+    let g = watts_strogatz(6, 4, 0., &mut rng);
+    let mut edges = g.raw_edges().iter().map(|e| (e.source().index(), e.target().index()))
+                     .collect::<Vec<_>>();
+    edges.sort();
+    assert_eq!(edges, vec![(0, 1), (0, 2), (1, 2), (1, 3), (2, 3), (2, 4),
+                           (3, 4), (3, 5), (4, 0), (4, 5), (5, 0), (5, 1)]);
+
+    for &(n, k, beta) in &[(10, 2, 0.5), (10, 5, 1.), (30, 6, 0.3), (5, 4, 1.)] {
+        let g = watts_strogatz(n, k, beta, &mut rng);
+        assert_eq!(g.node_count(), n);
+        assert_eq!(g.edge_count(), n * (k / 2));
+        let mut pairs = g.raw_edges().iter().map(|e| {
+            let (a, b) = (e.source(), e.target());
+            assert!(a != b);
+            if a < b { (a, b) } else { (b, a) }
+        }).collect::<Vec<_>>();
+        pairs.sort();
+        pairs.dedup();
+        assert_eq!(pairs.len(), g.edge_count());
+    }
+}
+
+#[cfg(feature = "generate")]
+#[test]
+fn test_generate_classic() {
+    use petgraph::generate::{complete_graph, cycle_graph, path_graph, star_graph, grid_2d};
+
+    fn edges<Ty: EdgeType>(g: &Graph<(), (), Ty>) -> Vec<(usize, usize)> {
+        g.raw_edges().iter().map(|e| (e.source().index(), e.target().index())).collect()
+    }
+
+    assert_eq!(edges(&complete_graph::<Undirected>(4)),
+               vec![(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)]);
+    assert_eq!(edges(&complete_graph::<Directed>(3)),
+               vec![(0, 1), (0, 2), (1, 0), (1, 2), (2, 0), (2, 1)]);
+    assert_eq!(edges(&cycle_graph::<Undirected>(4)), vec![(0, 1), (1, 2), (2, 3), (3, 0)]);
+    assert_eq!(edges(&cycle_graph::<Directed>(1)), vec![(0, 0)]);
+    assert_eq!(edges(&path_graph::<Directed>(4)), vec![(0, 1), (1, 2), (2, 3)]);
+    assert_eq!(edges(&star_graph::<Directed>(4)), vec![(0, 1), (0, 2), (0, 3)]);
+    assert_eq!(edges(&grid_2d::<Undirected>(2, 3)),
+               vec![(0, 1), (0, 3), (1, 2), (1, 4), (2, 5), (3, 4), (4, 5)]);
+
+    for n in 0..3 {
+        assert_eq!(complete_graph::<Directed>(n).node_count(), n);
+        assert_eq!(cycle_graph::<Directed>(n).edge_count(), n);
+        assert_eq!(path_graph::<Undirected>(n).node_count(), n);
+        assert_eq!(star_graph::<Undirected>(n).node_count(), n);
+        assert_eq!(grid_2d::<Undirected>(n, 0).node_count(), 0);
+        assert_eq!(grid_2d::<Undirected>(0, n).edge_count(), 0);
+    }
+    assert_eq!(grid_2d::<Undirected>(1, 5).edge_count(), 4);
+}
+
+#[cfg(feature = "generate")]
+#[test]
+fn test_generate_random_dag_and_tree() {
+    use petgraph::algo::toposort;
+    use petgraph::generate::{random_dag, random_tree};
+
+    let mut rng = rand::thread_rng();
+    for n in 0..20 {
+        let g = random_dag(n, 0.5, &mut rng);
+        assert_eq!(g.node_count(), n);
+        assert!(g.raw_edges().iter().all(|e| e.source() < e.target()));
+        assert_eq!(toposort(&g).len(), n);
+        assert_eq!(random_dag(n, 1., &mut rng).edge_count(), n * n.saturating_sub(1) / 2);
+
+        let t = random_tree(n, &mut rng);
+        assert_eq!(t.node_count(), n);
+        assert_eq!(t.edge_count(), n.saturating_sub(1));
+        assert!(!is_cyclic_undirected(&t));
+        if n > 0 {
+            assert_eq!(pg::algo::connected_components(&t), 1);
+        }
+    }
+
+    // all 16 labeled trees on 4 nodes show up
+    let mut seen = ::std::collections::HashSet::new();
+    for _ in 0..2000 {
+        let t = random_tree(4, &mut rng);
+        let mut edges = t.raw_edges().iter().map(|e| {
+            let (a, b) = (e.source().index(), e.target().index());
+            if a < b { (a, b) } else { (b, a) }
+        }).collect::<Vec<_>>();
+        edges.sort();
+        seen.insert(edges);
+    }
+    assert_eq!(seen.len(), 16);
+}
+
+/// Check that the edge sets are cycles (even degree everywhere) and
+/// independent over GF(2), and return their total cost.
+fn cycle_basis_cost<Ty: EdgeType>(gr: &Graph<(), u32, Ty>, basis: &[Vec<EdgeIndex>]) -> u32 {
+    let mut rows = Vec::<Vec<bool>>::new();
+    let mut cost = 0;
+    for cycle in basis {
+        let mut degree = vec![0; gr.node_count()];
+        let mut row = vec![false; gr.edge_count()];
+        for &e in cycle {
+            let (a, b) = gr.edge_endpoints(e).unwrap();
+            degree[a.index()] += 1;
+            degree[b.index()] += 1;
+            row[e.index()] = true;
+            cost += gr[e];
+        }
+        assert!(degree.iter().all(|&d| d % 2 == 0), "{:?} is not a cycle", cycle);
+        // reduce against the previous rows, keyed by their leading edge
+        for prev in &rows {
+            let lead = prev.iter().position(|&x| x).unwrap();
+            if row[lead] {
+                for (x, &y) in row.iter_mut().zip(prev) {
+                    *x ^= y;
+                }
+            }
+        }
+        assert!(row.iter().any(|&x| x), "cycles are not independent");
+        let lead = row.iter().position(|&x| x).unwrap();
+        for prev in &mut rows {
+            if prev[lead] {
+                for (x, &y) in prev.iter_mut().zip(&row) {
+                    *x ^= y;
+                }
+            }
+        }
+        rows.push(row);
+    }
+    cost
+}
+
+#[test]
+fn minimum_cycle_basis() {
+    use petgraph::algo::minimum_cycle_basis;
+    // K4: three triangles
+    let gr = Graph::<(), u32, Undirected>::from_edges(&[
+        (0, 1, 1), (0, 2, 1), (0, 3, 1), (1, 2, 1), (1, 3, 1), (2, 3, 1)]);
+    let basis = minimum_cycle_basis(&gr, |w| *w);
+    assert_eq!(basis.len(), 3);
+    assert_eq!(cycle_basis_cost(&gr, &basis), 9);
+
+    // two components, a self loop, a double edge, and a heavy chord that
+    // is cheaper to go around
+    let mut gr = Graph::<(), u32>::from_edges(&[
+        (0, 1, 1), (1, 2, 1), (2, 3, 1), (3, 0, 1), (0, 2, 5),
+        (4, 4, 7), (4, 5, 2), (5, 4, 3), (5, 6, 1)]);
+    gr.add_node(());
+    let basis = minimum_cycle_basis(&gr, |w| *w);
+    assert_eq!(basis.len(), 4);
+    assert_eq!(cycle_basis_cost(&gr, &basis), 4 + 7 + 7 + 5);
+    assert!(basis.contains(&vec![EdgeIndex::new(5)]));
+
+    let gr = Graph::<(), u32>::from_edges(&[(0, 1, 1), (1, 2, 1)]);
+    assert!(minimum_cycle_basis(&gr, |w| *w).is_empty());
+}
+
+#[test]
+fn minimum_cycle_basis_random() {
+    use petgraph::algo::minimum_cycle_basis;
+    use rand::Rng;
+    let mut rng = rand::weak_rng();
+    for _ in 0..40 {
+        let nodes = rng.gen_range(1, 7);
+        let mut gr = Graph::<(), u32, Undirected>::with_capacity(nodes, 9);
+        for _ in 0..nodes {
+            gr.add_node(());
+        }
+        for _ in 0..rng.gen_range(0, 10) {
+            let a = n(rng.gen_range(0, nodes));
+            let b = n(rng.gen_range(0, nodes));
+            gr.add_edge(a, b, rng.gen_range(0, 6));
+        }
+        let basis = minimum_cycle_basis(&gr, |w| *w);
+        let cost = cycle_basis_cost(&gr, &basis);
+
+        // greedily pick the cheapest independent elements of the cycle space
+        let m = gr.edge_count();
+        let mut elements = (1..1usize << m).filter_map(|set| {
+            let mut degree = vec![0; nodes];
+            let mut cost = 0;
+            for e in gr.edge_indices().filter(|e| set >> e.index() & 1 == 1) {
+                let (a, b) = gr.edge_endpoints(e).unwrap();
+                degree[a.index()] += 1;
+                degree[b.index()] += 1;
+                cost += gr[e];
+            }
+            if degree.iter().all(|&d| d % 2 == 0) { Some((cost, set)) } else { None }
+        }).collect::<Vec<_>>();
+        elements.sort();
+        let mut chosen = Vec::<usize>::new();
+        let mut best = 0;
+        for (c, set) in elements {
+            let mut reduced = set;
+            for &row in &chosen {
+                reduced = ::std::cmp::min(reduced, reduced ^ row);
+            }
+            if reduced != 0 {
+                chosen.push(reduced);
+                chosen.sort_by(|a, b| b.cmp(a));
+                best += c;
+            }
+        }
+        assert_eq!(basis.len(), chosen.len());
+        assert_eq!(cost, best);
+    }
+}
+
+#[test]
+fn fundamental_cycles() {
+    use petgraph::algo::fundamental_cycles;
+    let e = EdgeIndex::new;
+    // a tree 0 - 1, 1 - 2, 1 - 3, 3 - 4 plus a separate tree 5 - 6
+    let gr = Graph::<(), u32>::from_edges(&[
+        (0, 1, 1), (1, 2, 1), (1, 3, 1), (3, 4, 1), (5, 6, 1),
+        (2, 4, 1), (4, 0, 1), (6, 5, 1), (2, 2, 1), (0, 5, 1)]);
+    let tree = (0..5).map(e).collect::<Vec<_>>();
+    let cycles = fundamental_cycles(&gr, tree);
+    assert_eq!(cycles, vec![
+        vec![e(5), e(3), e(2), e(1)],
+        vec![e(6), e(0), e(2), e(3)],
+        vec![e(7), e(4)],
+        vec![e(8)],
+    ]);
+    let mut basis = cycles.clone();
+    basis.iter_mut().for_each(|cycle| cycle.sort());
+    assert_eq!(cycle_basis_cost(&gr, &basis), 4 + 4 + 2 + 1);
+}
+
+#[should_panic]
+#[test]
+fn fundamental_cycles_not_a_tree() {
+    let gr = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0)]);
+    petgraph::algo::fundamental_cycles(&gr, gr.edge_indices());
+}
+
+/// Minimum cost of assigning each row a distinct column, by trying all
+/// assignments; `None` entries are forbidden.
+fn brute_force_assignment(costs: &[Vec<Option<i32>>], row: usize, used: &mut Vec<bool>)
+    -> Option<i32>
+{
+    if row == costs.len() {
+        return Some(0);
+    }
+    let mut best = None;
+    for j in 0..used.len() {
+        if let (false, Some(c)) = (used[j], costs[row][j]) {
+            used[j] = true;
+            if let Some(rest) = brute_force_assignment(costs, row + 1, used) {
+                if best.map_or(true, |b| c + rest < b) {
+                    best = Some(c + rest);
+                }
+            }
+            used[j] = false;
+        }
+    }
+    best
+}
+
+#[test]
+fn hungarian_random() {
+    use petgraph::algo::{hungarian, hungarian_matching};
+    use rand::Rng;
+    let mut rng = rand::weak_rng();
+    for _ in 0..100 {
+        let rows = rng.gen_range(0, 6);
+        let cols = rng.gen_range(rows, 7);
+        let costs = (0..rows).map(|_| {
+            (0..cols).map(|_| rng.gen_range(-10, 20)).collect::<Vec<i32>>()
+        }).collect::<Vec<_>>();
+        let (total, assignment) = hungarian(&costs);
+        let mut columns = assignment.clone();
+        columns.sort();
+        columns.dedup();
+        assert_eq!(columns.len(), rows);
+        assert_eq!(total, assignment.iter().enumerate().map(|(i, &j)| costs[i][j]).sum());
+        let all = costs.iter().map(|row| row.iter().cloned().map(Some).collect())
+                       .collect::<Vec<_>>();
+        assert_eq!(Some(total), brute_force_assignment(&all, 0, &mut vec![false; cols]));
+
+        // the same as a sparse bipartite graph, rows first
+        let mut gr = Graph::<(), i32, Undirected>::with_capacity(rows + cols, 0);
+        for _ in 0..rows + cols {
+            gr.add_node(());
+        }
+        let mut sparse = vec![vec![None; cols]; rows];
+        for i in 0..rows {
+            for j in 0..cols {
+                if rng.gen_weighted_bool(2) {
+                    gr.add_edge(n(i), n(rows + j), costs[i][j]);
+                    sparse[i][j] = Some(costs[i][j]);
+                }
+            }
+        }
+        let left = (0..rows).map(n).collect::<Vec<_>>();
+        let best = brute_force_assignment(&sparse, 0, &mut vec![false; cols]);
+        match hungarian_matching(&gr, &left, |w| *w) {
+            None => assert_eq!(best, None),
+            Some((total, pairs)) => {
+                assert_eq!(Some(total), best);
+                for (i, &(a, b)) in pairs.iter().enumerate() {
+                    assert_eq!(a, n(i));
+                    assert!(gr.find_edge(a, b).is_some());
+                }
+            }
+        }
+    }
+}
+
+#[should_panic]
+#[test]
+fn hungarian_too_many_rows() {
+    petgraph::algo::hungarian(&[[1.], [2.]]);
+}
+
+#[test]
+fn decompose_flow() {
+    use petgraph::algo::decompose_flow;
+    use rand::Rng;
+    // a flow of 3 from 0 to 3, with a cycle 1 -> 2 -> 4 -> 1, a cycle back
+    // into the source and a self loop
+    let gr = Graph::<(), ()>::from_edges(&[
+        (0, 1), (1, 2), (2, 3), (2, 4), (4, 1), (3, 0), (3, 5), (5, 5)]);
+    let flow = [4, 6, 4, 2, 2, 1, 0, 3];
+    let d = decompose_flow(&gr, &flow, n(0), n(3));
+    assert_eq!(d.paths.iter().map(|p| p.0).sum::<i32>(), 3);
+    let mut total = vec![0; gr.edge_count()];
+    for &(value, ref walk) in d.paths.iter().chain(&d.cycles) {
+        for w in walk.windows(2) {
+            assert_eq!(gr.edge_endpoints(w[0]).unwrap().1, gr.edge_endpoints(w[1]).unwrap().0);
+        }
+        for &e in walk {
+            total[e.index()] += value;
+        }
+    }
+    for &(_, ref path) in &d.paths {
+        assert_eq!(gr.edge_endpoints(path[0]).unwrap().0, n(0));
+        assert_eq!(gr.edge_endpoints(path[path.len() - 1]).unwrap().1, n(3));
+    }
+    for &(_, ref cycle) in &d.cycles {
+        assert_eq!(gr.edge_endpoints(cycle[0]).unwrap().0,
+                   gr.edge_endpoints(cycle[cycle.len() - 1]).unwrap().1);
+    }
+    assert_eq!(total, flow);
+    assert!(d.cycles.contains(&(3, vec![EdgeIndex::new(7)])));
+
+    // random sums of paths and cycles on a complete graph
+    let mut rng = rand::weak_rng();
+    for _ in 0..20 {
+        let mut gr = Graph::<(), ()>::new();
+        for _ in 0..6 {
+            gr.add_node(());
+        }
+        for a in 0..6 {
+            for b in 0..6 {
+                gr.add_edge(n(a), n(b), ());
+            }
+        }
+        let mut flow = vec![0; gr.edge_count()];
+        for _ in 0..5 {
+            let mut walk = vec![0];
+            for _ in 0..rng.gen_range(1, 5) {
+                walk.push(rng.gen_range(0, 6));
+            }
+            walk.push(if rng.gen() { 5 } else { walk[0] });
+            for w in walk.windows(2) {
+                flow[gr.find_edge(n(w[0]), n(w[1])).unwrap().index()] += 1;
+            }
+        }
+        let d = decompose_flow(&gr, &flow, n(0), n(5));
+        let mut total = vec![0; gr.edge_count()];
+        for &(value, ref walk) in d.paths.iter().chain(&d.cycles) {
+            assert!(value > 0);
+            for &e in walk {
+                total[e.index()] += value;
+            }
+        }
+        assert_eq!(total, flow);
+    }
+}
+
+#[test]
+fn decompose_flow_unsigned() {
+    // flow into the source before its out-edges
+    let gr = Graph::<(), ()>::from_edges(&[(1, 0), (0, 1), (1, 2)]);
+    let d = petgraph::algo::decompose_flow(&gr, &[1u32, 2, 1], n(0), n(2));
+    assert_eq!(d.paths, vec![(1, vec![EdgeIndex::new(1), EdgeIndex::new(2)])]);
+    assert_eq!(d.cycles, vec![(1, vec![EdgeIndex::new(1), EdgeIndex::new(0)])]);
+}
+
+#[should_panic]
+#[test]
+fn decompose_flow_not_conserved() {
+    let gr = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2)]);
+    petgraph::algo::decompose_flow(&gr, &[2, 1], n(0), n(2));
+}
+
+#[test]
+fn circulation() {
+    use petgraph::algo::circulation;
+    use rand::Rng;
+
+    fn is_circulation(gr: &Graph<(), ()>, flow: &[i32]) -> bool {
+        let mut balance = vec![0; gr.node_count()];
+        for (edge, &f) in gr.raw_edges().iter().zip(flow) {
+            balance[edge.source().index()] -= f;
+            balance[edge.target().index()] += f;
+        }
+        balance.iter().all(|&b| b == 0)
+    }
+
+    let mut rng = rand::weak_rng();
+    for _ in 0..200 {
+        let nodes = rng.gen_range(1, 5);
+        let mut gr = Graph::<(), ()>::new();
+        for _ in 0..nodes {
+            gr.add_node(());
+        }
+        for _ in 0..rng.gen_range(0, 6) {
+            gr.add_edge(n(rng.gen_range(0, nodes)), n(rng.gen_range(0, nodes)), ());
+        }
+        let lower = gr.edge_indices().map(|_| rng.gen_range(0, 3)).collect::<Vec<i32>>();
+        let upper = lower.iter().map(|&l| l + rng.gen_range(-1, 3)).collect::<Vec<i32>>();
+
+        // try every flow within the bounds
+        let m = gr.edge_count();
+        let mut flow = lower.clone();
+        let mut exists = false;
+        if lower.iter().zip(&upper).all(|(l, u)| l <= u) {
+            'search: loop {
+                if is_circulation(&gr, &flow) {
+                    exists = true;
+                    break;
+                }
+                let mut i = 0;
+                loop {
+                    if i == m {
+                        break 'search;
+                    }
+                    if flow[i] < upper[i] {
+                        flow[i] += 1;
+                        break;
+                    }
+                    flow[i] = lower[i];
+                    i += 1;
+                }
+            }
+        }
+
+        match circulation(&gr, &lower, &upper) {
+            None => assert!(!exists),
+            Some(flow) => {
+                assert!(exists);
+                assert!(is_circulation(&gr, &flow));
+                for i in 0..m {
+                    assert!(lower[i] <= flow[i] && flow[i] <= upper[i]);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn greedy_edge_coloring() {
+    use petgraph::algo::greedy_edge_coloring;
+    use rand::Rng;
+
+    fn check_coloring(gr: &Graph<(), (), Undirected>, colors: &[usize]) -> usize {
+        assert_eq!(colors.len(), gr.edge_count());
+        let mut at = vec![Vec::new(); gr.node_count()];
+        for (i, edge) in gr.raw_edges().iter().enumerate() {
+            at[edge.source().index()].push(colors[i]);
+            if edge.source() != edge.target() {
+                at[edge.target().index()].push(colors[i]);
+            }
+        }
+        for mut node_colors in at {
+            let len = node_colors.len();
+            node_colors.sort();
+            node_colors.dedup();
+            assert_eq!(node_colors.len(), len);
+        }
+        colors.iter().map(|&c| c + 1).max().unwrap_or(0)
+    }
+
+    // K4 is class one, the triangle needs three colors
+    let k4 = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)]);
+    assert!(check_coloring(&k4, &greedy_edge_coloring(&k4)) <= 4);
+    let triangle = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2), (2, 0)]);
+    assert_eq!(check_coloring(&triangle, &greedy_edge_coloring(&triangle)), 3);
+    assert!(greedy_edge_coloring(&Graph::<(), ()>::new()).is_empty());
+
+    let mut rng = rand::weak_rng();
+    for _ in 0..200 {
+        let nodes = rng.gen_range(1, 15);
+        let simple: bool = rng.gen();
+        let mut gr = Graph::new_undirected();
+        for _ in 0..nodes {
+            gr.add_node(());
+        }
+        for _ in 0..rng.gen_range(0, 4 * nodes) {
+            let a = n(rng.gen_range(0, nodes));
+            let b = n(rng.gen_range(0, nodes));
+            if !simple || (a != b && gr.find_edge(a, b).is_none()) {
+                gr.add_edge(a, b, ());
+            }
+        }
+        let mut degree = vec![0; nodes];
+        for edge in gr.raw_edges() {
+            degree[edge.source().index()] += 1;
+            degree[edge.target().index()] += 1;
+        }
+        let max_degree = degree.into_iter().max().unwrap();
+        let used = check_coloring(&gr, &greedy_edge_coloring(&gr));
+        if simple {
+            assert!(used <= max_degree + 1);
+        } else {
+            assert!(used <= 2 * max_degree.max(1) - 1);
+        }
+    }
+}
+
+#[test]
+fn degree_sequences() {
+    use petgraph::algo::{degree_sequence, is_graphical};
+    use petgraph::generate::from_degree_sequence;
+    use rand::Rng;
+
+    assert!(is_graphical(&[]));
+    assert!(is_graphical(&[0, 0]));
+    assert!(!is_graphical(&[1]));
+    assert!(!is_graphical(&[4, 4, 4, 1, 1]));
+    assert_eq!(from_degree_sequence(&[]).map(|g| g.node_count()), Some(0));
+
+    let mut rng = rand::weak_rng();
+    for _ in 0..200 {
+        let nodes = rng.gen_range(0, 12);
+        let seq = (0..nodes).map(|_| rng.gen_range(0, nodes)).collect::<Vec<_>>();
+        match from_degree_sequence(&seq) {
+            None => assert!(!is_graphical(&seq)),
+            Some(gr) => {
+                assert!(is_graphical(&seq));
+                let mut sorted = seq.clone();
+                sorted.sort_by(|a, b| b.cmp(a));
+                assert_eq!(degree_sequence(&gr), sorted);
+                for (i, &d) in seq.iter().enumerate() {
+                    assert_eq!(gr.neighbors(n(i)).count(), d);
+                }
+                let mut pairs = gr.raw_edges().iter()
+                                  .map(|e| (e.source().index().min(e.target().index()),
+                                            e.source().index().max(e.target().index())))
+                                  .collect::<Vec<_>>();
+                assert!(pairs.iter().all(|&(a, b)| a != b));
+                pairs.sort();
+                pairs.dedup();
+                assert_eq!(pairs.len(), gr.edge_count());
+            }
+        }
+
+        // any simple graph's degrees are graphical
+        let gr = pg::generate::gnp_random_graph::<Undirected, _>(nodes, 0.4, &mut rng);
+        assert!(is_graphical(&degree_sequence(&gr)));
+    }
+}
+
+#[test]
+fn rewire_keeps_degrees() {
+    use petgraph::generate::{gnm_random_graph, rewire};
+    use std::collections::HashSet;
+
+    fn degrees<Ty: EdgeType>(gr: &Graph<(), u32, Ty>) -> Vec<(usize, usize)> {
+        let mut degrees = vec![(0, 0); gr.node_count()];
+        for e in gr.raw_edges() {
+            degrees[e.source().index()].0 += 1;
+            if gr.is_directed() {
+                degrees[e.target().index()].1 += 1;
+            } else {
+                degrees[e.target().index()].0 += 1;
+            }
+        }
+        degrees
+    }
+
+    fn no_parallel_edges<Ty: EdgeType>(gr: &Graph<(), u32, Ty>) -> bool {
+        let mut pairs = HashSet::new();
+        gr.raw_edges().iter().all(|e| {
+            let (a, b) = (e.source(), e.target());
+            let pair = if gr.is_directed() || a < b { (a, b) } else { (b, a) };
+            a != b && pairs.insert(pair)
+        })
+    }
+
+    let mut rng = rand::weak_rng();
+    let mut gr = gnm_random_graph::<Undirected, _>(30, 60, &mut rng)
+        .map(|_, _| (), |i, _| i.index() as u32);
+    let before = degrees(&gr);
+    assert!(rewire(&mut gr, 1000, &mut rng) > 0);
+    assert_eq!(degrees(&gr), before);
+    assert!(no_parallel_edges(&gr));
+    let mut weights = gr.edge_indices().map(|e| gr[e]).collect::<Vec<_>>();
+    weights.sort();
+    assert_eq!(weights, (0..60).collect::<Vec<_>>());
+
+    let mut gr = gnm_random_graph::<Directed, _>(30, 90, &mut rng).map(|_, _| (), |_, _| 0);
+    let before = degrees(&gr);
+    assert!(rewire(&mut gr, 1000, &mut rng) > 0);
+    assert_eq!(degrees(&gr), before);
+    assert!(no_parallel_edges(&gr));
+
+    // no swap is possible in a triangle
+    let mut gr = Graph::<(), u32, Undirected>::from_edges(&[(0, 1), (1, 2), (2, 0)]);
+    assert_eq!(rewire(&mut gr, 100, &mut rng), 0);
+}
+
+#[test]
+fn assortativity() {
+    use petgraph::algo::{attribute_assortativity, degree_assortativity};
+
+    fn close(x: Option<f64>, y: f64) -> bool {
+        x.map_or(false, |x| (x - y).abs() < 1e-9)
+    }
+
+    // disjoint cliques: every edge joins equal degrees
+    let cliques = Graph::<(), (), Undirected>::from_edges(&[
+        (0, 1), (2, 3), (3, 4), (4, 2)]);
+    assert!(close(degree_assortativity(&cliques), 1.));
+    let cycle = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2), (2, 0)]);
+    assert_eq!(degree_assortativity(&cycle), None);
+    assert_eq!(degree_assortativity(&Graph::<(), ()>::new()), None);
+
+    // 0 -> 1 -> 2, 0 -> 2: pairs (out, in) are (2, 1), (2, 2), (1, 2)
+    let dag = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (0, 2)]);
+    assert!(close(degree_assortativity(&dag), -0.5));
+
+    // a complete bipartite graph only joins different sides
+    let bipartite = Graph::<(), (), Undirected>::from_edges(&[
+        (0, 2), (0, 3), (1, 2), (1, 3)]);
+    assert!(close(attribute_assortativity(&bipartite, |a| a.index() < 2), -1.));
+    assert!(close(attribute_assortativity(&cliques, |a| a.index() < 2), 1.));
+    assert_eq!(attribute_assortativity(&cycle, |_| 0), None);
+}
+
+#[test]
+fn link_prediction() {
+    use petgraph::algo::link_prediction::{
+        adamic_adar_index,
+        jaccard_coefficient,
+        non_edges,
+        preferential_attachment,
+    };
+    use rand::Rng;
+    use std::collections::HashSet;
+
+    let mut rng = rand::weak_rng();
+    for _ in 0..50 {
+        let nodes = rng.gen_range(1, 12);
+        let mut gr = Graph::<(), ()>::new();
+        for _ in 0..nodes {
+            gr.add_node(());
+        }
+        for _ in 0..rng.gen_range(0, 3 * nodes) {
+            let a = n(rng.gen_range(0, nodes));
+            let b = n(rng.gen_range(0, nodes));
+            gr.add_edge(a, b, ());
+        }
+        let hood = |a: NodeIndex| -> HashSet<NodeIndex> {
+            gr.neighbors_undirected(a).filter(|&b| b != a).collect()
+        };
+
+        let pairs = non_edges(&gr);
+        let mut expected = Vec::new();
+        for a in 0..nodes {
+            for b in a + 1..nodes {
+                if !hood(n(a)).contains(&n(b)) {
+                    expected.push((n(a), n(b)));
+                }
+            }
+        }
+        assert_eq!(pairs, expected);
+
+        let all = (0..nodes).flat_map(|a| (0..nodes).map(move |b| (n(a), n(b))))
+                            .filter(|&(a, b)| a != b)
+                            .collect::<Vec<_>>();
+        let jaccard = jaccard_coefficient(&gr, all.clone());
+        let adamic_adar = adamic_adar_index(&gr, all.clone());
+        let attachment = preferential_attachment(&gr, all.clone());
+        for (i, &(a, b)) in all.iter().enumerate() {
+            let (ha, hb) = (hood(a), hood(b));
+            let common = ha.intersection(&hb).collect::<Vec<_>>();
+            let union = ha.union(&hb).count();
+            let j = if union == 0 { 0. } else { common.len() as f64 / union as f64 };
+            assert_eq!(jaccard[i], (a, b, j));
+            let aa = adamic_adar[i].2;
+            let aa_expected = common.iter().map(|&&c| 1. / (hood(c).len() as f64).ln())
+                                    .fold(0., |x, y| x + y);
+            assert!((aa - aa_expected).abs() < 1e-9);
+            assert_eq!(attachment[i].2, (ha.len() * hb.len()) as f64);
+        }
+    }
+}
+
+#[test]
+fn simrank() {
+    use petgraph::algo::simrank;
+    use rand::Rng;
+
+    let mut rng = rand::weak_rng();
+    for _ in 0..20 {
+        let nodes = rng.gen_range(1, 10);
+        let mut gr = Graph::<(), ()>::new();
+        for _ in 0..nodes {
+            gr.add_node(());
+        }
+        for _ in 0..rng.gen_range(0, 3 * nodes) {
+            let a = n(rng.gen_range(0, nodes));
+            let b = n(rng.gen_range(0, nodes));
+            gr.add_edge(a, b, ());
+        }
+        // the converged scores are a fixed point of the definition
+        let s = simrank(&gr, 0.6, 1000, 1e-12);
+        for a in 0..nodes {
+            assert_eq!(s[a * nodes + a], 1.);
+            for b in 0..nodes {
+                let x = s[a * nodes + b];
+                assert_eq!(x, s[b * nodes + a]);
+                assert!(x >= 0. && x <= 1.);
+                if a == b {
+                    continue;
+                }
+                let ia = gr.neighbors_directed(n(a), Incoming).collect::<Vec<_>>();
+                let ib = gr.neighbors_directed(n(b), Incoming).collect::<Vec<_>>();
+                let mut expected = 0.;
+                for &i in &ia {
+                    for &j in &ib {
+                        expected += s[i.index() * nodes + j.index()];
+                    }
+                }
+                if !ia.is_empty() && !ib.is_empty() {
+                    expected *= 0.6 / (ia.len() * ib.len()) as f64;
+                }
+                assert!((x - expected).abs() < 1e-9);
+            }
+        }
+    }
+
+    let gr = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (0, 2)]);
+    let s = simrank(&gr, 0.8, 0, 0.);
+    assert_eq!(s, vec![1., 0., 0., 0., 1., 0., 0., 0., 1.]);
+    let s = simrank(&gr, 0.8, 100, 1e-9);
+    assert_eq!(s[1 * 3 + 2], 0.8);
+}
+
+#[test]
+fn girvan_newman() {
+    use petgraph::algo::{connected_components, girvan_newman};
+    use rand::Rng;
+
+    // two 4-cliques joined by a path through 8
+    let mut edges = Vec::new();
+    for &base in &[0, 4] {
+        for i in 0..4 {
+            for j in i + 1..4 {
+                edges.push((base + i, base + j));
+            }
+        }
+    }
+    edges.push((3, 8));
+    edges.push((8, 4));
+    let gr = Graph::<(), (), Undirected>::from_edges(&edges);
+    let first = girvan_newman(&gr).next().unwrap();
+    assert_eq!(first.len(), 2);
+    assert!(first.contains(&vec![n(0), n(1), n(2), n(3)]) ||
+            first.contains(&vec![n(4), n(5), n(6), n(7)]));
+
+    let mut rng = rand::weak_rng();
+    for _ in 0..30 {
+        let nodes = rng.gen_range(1, 10);
+        let mut gr = Graph::<(), ()>::new();
+        for _ in 0..nodes {
+            gr.add_node(());
+        }
+        for _ in 0..rng.gen_range(0, 3 * nodes) {
+            let a = n(rng.gen_range(0, nodes));
+            let b = n(rng.gen_range(0, nodes));
+            gr.add_edge(a, b, ());
+        }
+        let mut previous = (0..nodes).map(|_| 0).collect::<Vec<_>>();
+        let mut count = connected_components(&gr);
+        let mut levels = 0;
+        for partition in girvan_newman(&gr) {
+            levels += 1;
+            assert!(partition.len() > count);
+            count = partition.len();
+            // each level refines the previous one
+            let mut label = vec![0; nodes];
+            for (i, component) in partition.iter().enumerate() {
+                for &a in component {
+                    label[a.index()] = i;
+                }
+                for &a in component {
+                    assert_eq!(previous[a.index()], previous[component[0].index()]);
+                }
+            }
+            previous = label;
+        }
+        assert_eq!(count, nodes);
+        assert!(levels <= nodes - connected_components(&gr));
+    }
+}
+
+#[test]
+fn edge_betweenness_centrality() {
+    use petgraph::algo::edge_betweenness_centrality;
+    use rand::Rng;
+
+    /// All shortest paths from `s` to `t`, as edge lists.
+    fn shortest_paths<Ty: EdgeType>(gr: &Graph<(), (), Ty>, s: usize, t: usize)
+        -> Vec<Vec<usize>>
+    {
+        let mut dist = vec![None; gr.node_count()];
+        dist[s] = Some(0);
+        let mut queue = vec![s];
+        let mut i = 0;
+        while i < queue.len() {
+            let a = queue[i];
+            i += 1;
+            for b in gr.neighbors(n(a)) {
+                if dist[b.index()].is_none() {
+                    dist[b.index()] = Some(dist[a].unwrap() + 1);
+                    queue.push(b.index());
+                }
+            }
+        }
+        let mut paths = Vec::new();
+        let mut stack = vec![(s, Vec::new())];
+        while let Some((a, path)) = stack.pop() {
+            if a == t {
+                paths.push(path);
+                continue;
+            }
+            let mut edges = gr.neighbors(n(a)).detach();
+            while let Some((e, b)) = edges.next(gr) {
+                if dist[b.index()] == dist[a].map(|d| d + 1) && dist[b.index()] <= dist[t] {
+                    let mut longer = path.clone();
+                    longer.push(e.index());
+                    stack.push((b.index(), longer));
+                }
+            }
+        }
+        paths
+    }
+
+    fn brute_force<Ty: EdgeType>(gr: &Graph<(), (), Ty>) -> Vec<f64> {
+        let mut scores = vec![0.; gr.edge_count()];
+        for s in 0..gr.node_count() {
+            for t in 0..gr.node_count() {
+                if s == t || (!gr.is_directed() && t < s) {
+                    continue;
+                }
+                let paths = shortest_paths(gr, s, t);
+                for path in &paths {
+                    for &e in path {
+                        scores[e] += 1. / paths.len() as f64;
+                    }
+                }
+            }
+        }
+        scores
+    }
+
+    let mut rng = rand::weak_rng();
+    for _ in 0..30 {
+        let nodes = rng.gen_range(1, 8);
+        let mut directed = Graph::<(), ()>::new();
+        for _ in 0..nodes {
+            directed.add_node(());
+        }
+        for _ in 0..rng.gen_range(0, 2 * nodes) {
+            let a = n(rng.gen_range(0, nodes));
+            let b = n(rng.gen_range(0, nodes));
+            directed.add_edge(a, b, ());
+        }
+        let undirected = directed.clone().into_edge_type::<Undirected>();
+        for (found, expected) in edge_betweenness_centrality(&directed, false).into_iter()
+                                     .zip(brute_force(&directed)) {
+            assert!((found - expected).abs() < 1e-9);
+        }
+        for (found, expected) in edge_betweenness_centrality(&undirected, false).into_iter()
+                                     .zip(brute_force(&undirected)) {
+            assert!((found - expected).abs() < 1e-9);
+        }
+    }
+}
+
+#[test]
+fn graph_summary() {
+    use petgraph::algo::summary;
+
+    let s = summary(&Graph::<(), ()>::new());
+    assert_eq!((s.node_count, s.edge_count, s.density), (0, 0, 0.));
+    assert_eq!((s.min_degree, s.max_degree, s.mean_degree), (0, 0, 0.));
+    assert_eq!(s.connected_components, 0);
+
+    // in a directed graph, opposite edges are not parallel
+    let gr = Graph::<(), ()>::from_edges(&[(0, 1), (1, 0), (0, 1), (2, 2)]);
+    let s = summary(&gr);
+    assert_eq!(s.density, 4. / 6.);
+    assert_eq!((s.min_degree, s.max_degree), (2, 3));
+    assert_eq!((s.self_loops, s.parallel_edges), (1, 1));
+    assert_eq!(s.connected_components, 2);
+
+    let s = summary(&gr.into_edge_type::<Undirected>());
+    assert_eq!(s.density, 4. / 3.);
+    assert_eq!((s.self_loops, s.parallel_edges), (1, 2));
+}
+
+#[test]
+fn self_loops_and_parallel_edges() {
+    use rand::Rng;
+
+    let mut rng = rand::weak_rng();
+    for _ in 0..50 {
+        let nodes = rng.gen_range(1, 6);
+        let mut gr = Graph::<(), u32, Undirected>::new_undirected();
+        for _ in 0..nodes {
+            gr.add_node(());
+        }
+        for i in 0..rng.gen_range(0, 4 * nodes) {
+            let a = n(rng.gen_range(0, nodes));
+            let b = n(rng.gen_range(0, nodes));
+            gr.add_edge(a, b, 1 << i);
+        }
+        let loops = gr.raw_edges().iter().filter(|e| e.source() == e.target()).count();
+        assert_eq!(gr.has_self_loops(), loops > 0);
+
+        // the weights of the merged edges are disjoint bit sets
+        let mut total_weight = vec![0; nodes * nodes];
+        for e in gr.raw_edges() {
+            let (a, b) = (e.source().index(), e.target().index());
+            total_weight[a.min(b) * nodes + a.max(b)] |= e.weight;
+        }
+        let mut simple = gr.clone();
+        assert_eq!(simple.remove_self_loops(), loops);
+        assert!(!simple.has_self_loops());
+        let parallel = simple.parallel_edge_count();
+        assert_eq!(simple.deduplicate_edges(|x, y| *x |= y), parallel);
+        assert_eq!(simple.parallel_edge_count(), 0);
+        for e in simple.raw_edges() {
+            let (a, b) = (e.source().index(), e.target().index());
+            assert_eq!(e.weight, total_weight[a.min(b) * nodes + a.max(b)]);
+            assert!(gr.find_edge(e.source(), e.target()).is_some());
+        }
+        let pairs = total_weight.iter().enumerate()
+                                .filter(|&(i, &w)| w != 0 && i / nodes != i % nodes).count();
+        assert_eq!(simple.edge_count(), pairs);
+    }
+
+    let mut gr = Graph::<(), ()>::from_edges(&[(0, 1), (1, 0), (0, 1)]);
+    assert_eq!(gr.parallel_edge_count(), 1);
+    assert_eq!(gr.deduplicate_edges(|_, _| ()), 1);
+    assert_eq!(gr.edge_count(), 2);
+}
+
+#[test]
+fn sampling() {
+    use petgraph::generate::{cycle_graph, gnm_random_graph};
+    use rand::Rng;
+
+    let mut rng = rand::weak_rng();
+    let gr = gnm_random_graph::<Directed, _>(30, 60, &mut rng)
+        .map(|a, _| a.index(), |e, _| e.index());
+
+    let mut nodes = gr.sample_nodes(10, &mut rng);
+    nodes.sort();
+    nodes.dedup();
+    assert_eq!(nodes.len(), 10);
+    assert_eq!(gr.sample_nodes(100, &mut rng).len(), 30);
+    let mut edges = gr.sample_edges(20, &mut rng);
+    edges.sort();
+    edges.dedup();
+    assert_eq!(edges.len(), 20);
+
+    // a sample is the induced subgraph of the original nodes, in order
+    fn check_induced(gr: &Graph<usize, usize>, sample: &Graph<usize, usize>) {
+        let original = sample.node_indices().map(|a| n(sample[a])).collect::<Vec<_>>();
+        assert!(original.windows(2).all(|w| w[0] < w[1]));
+        let expected = gr.edge_indices().filter(|&e| {
+            let (a, b) = gr.edge_endpoints(e).unwrap();
+            original.contains(&a) && original.contains(&b)
+        }).map(|e| gr[e]).collect::<Vec<_>>();
+        assert_eq!(sample.edge_indices().map(|e| sample[e]).collect::<Vec<_>>(), expected);
+        for e in sample.edge_indices() {
+            let (a, b) = sample.edge_endpoints(e).unwrap();
+            assert_eq!(gr.edge_endpoints(EdgeIndex::new(sample[e])),
+                       Some((original[a.index()], original[b.index()])));
+        }
+    }
+    for k in 0..32 {
+        let sample = gr.random_node_subgraph(k, &mut rng);
+        assert_eq!(sample.node_count(), k.min(30));
+        check_induced(&gr, &sample);
+        let sample = gr.random_walk_subgraph(k, &mut rng);
+        assert_eq!(sample.node_count(), k.min(30));
+        check_induced(&gr, &sample);
+    }
+
+    // a walk around a directed cycle is never stuck
+    let cycle = cycle_graph::<Directed>(50);
+    for _ in 0..10 {
+        let k = rng.gen_range(1, 50);
+        let sample = cycle.random_walk_subgraph(k, &mut rng);
+        assert_eq!(sample.edge_count(), k - 1);
+    }
+}
+
+#[test]
+fn fingerprint() {
+    use petgraph::algo::fingerprint;
+    use rand::Rng;
+
+    /// The graph with node `a` renumbered `perm[a]` and the edges shuffled.
+    fn permuted<R: Rng, Ty: EdgeType>(gr: &Graph<u8, u8, Ty>, rng: &mut R) -> Graph<u8, u8, Ty> {
+        let mut perm = (0..gr.node_count()).collect::<Vec<_>>();
+        rng.shuffle(&mut perm);
+        let mut inverse = vec![0; perm.len()];
+        for (a, &p) in perm.iter().enumerate() {
+            inverse[p] = a;
+        }
+        let mut edges = gr.raw_edges().iter().collect::<Vec<_>>();
+        rng.shuffle(&mut edges);
+        let mut result = Graph::with_capacity(gr.node_count(), gr.edge_count());
+        for &a in &inverse {
+            result.add_node(gr[n(a)]);
+        }
+        for e in edges {
+            let (mut a, mut b) = (perm[e.source().index()], perm[e.target().index()]);
+            if !gr.is_directed() && rng.gen() {
+                std::mem::swap(&mut a, &mut b);
+            }
+            result.add_edge(n(a), n(b), e.weight);
+        }
+        result
+    }
+
+    let mut rng = rand::weak_rng();
+    for _ in 0..50 {
+        let nodes = rng.gen_range(0, 10);
+        let mut gr = Graph::<u8, u8>::new();
+        for _ in 0..nodes {
+            gr.add_node(rng.gen_range(0, 3));
+        }
+        if nodes > 0 {
+            for _ in 0..rng.gen_range(0, 3 * nodes) {
+                let a = n(rng.gen_range(0, nodes));
+                let b = n(rng.gen_range(0, nodes));
+                gr.add_edge(a, b, rng.gen_range(0, 3));
+            }
+        }
+        assert_eq!(fingerprint(&gr), fingerprint(&permuted(&gr, &mut rng)));
+        let undirected = gr.clone().into_edge_type::<Undirected>();
+        assert_eq!(fingerprint(&undirected), fingerprint(&permuted(&undirected, &mut rng)));
+
+        let mut changed = gr.clone();
+        if let Some(w) = changed.edge_weights_mut().next() {
+            *w += 3;
+            assert!(fingerprint(&gr) != fingerprint(&changed));
+        }
+    }
+
+    // the fingerprint is stable
+    let gr = Graph::<u32, u64>::from_edges(&[(0, 1, 1), (1, 2, 2), (2, 0, 3)]);
+    assert_eq!(fingerprint(&gr), 454008528705581416);
+}
+
+#[test]
+fn graph_eq() {
+    use petgraph::algo::graph_eq;
+
+    let mut g = Graph::<&str, i32>::new();
+    let a = g.add_node("a");
+    let b = g.add_node("b");
+    g.add_edge(a, b, 1);
+    g.add_edge(b, b, 2);
+    let mut h = g.clone();
+    assert!(graph_eq(&g, &h));
+
+    // same structure, different weight
+    h[b] = "c";
+    assert!(!graph_eq(&g, &h));
+    h[b] = "b";
+
+    // same edges, in another order
+    let mut k = Graph::<&str, i32>::new();
+    k.add_node("a");
+    k.add_node("b");
+    k.add_edge(b, b, 2);
+    k.add_edge(a, b, 1);
+    assert!(!graph_eq(&g, &k));
+
+    // reversed edges only match when undirected
+    h.reverse();
+    assert!(!graph_eq(&g, &h));
+    let u = g.clone().into_edge_type::<Undirected>();
+    let v = h.into_edge_type::<Undirected>();
+    assert!(graph_eq(&u, &v));
+
+    h = g.clone();
+    h.add_node("d");
+    assert!(!graph_eq(&g, &h));
+    assert!(graph_eq(&Graph::<(), ()>::new(), &Graph::new()));
+}
+
+#[test]
+fn graph_diff() {
+    use petgraph::algo::diff;
+
+    // nodes are matched by key, not by index
+    let old = Graph::<(char, u8), u8>::from_edges(&[(0, 1, 1), (1, 2, 2), (0, 1, 3)]);
+    let mut old = old.map(|i, _| ((b'a' + i.index() as u8) as char, 0), |_, &w| w);
+    old[n(2)].1 = 7;
+    let mut new = Graph::<(char, u8), u8>::new();
+    let c = new.add_node(('c', 7));
+    let d = new.add_node(('d', 0));
+    let b = new.add_node(('b', 1));
+    let a = new.add_node(('a', 0));
+    new.add_edge(a, b, 1);
+    new.add_edge(b, d, 2);
+    new.add_edge(a, b, 4);
+    new.add_edge(b, c, 2);
+
+    let delta = diff(&old, &new, |&(name, _)| name);
+    assert_eq!(delta.added_nodes, vec![d]);
+    assert!(delta.removed_nodes.is_empty());
+    assert_eq!(delta.changed_nodes, vec![(n(1), b)]);
+    assert_eq!(delta.added_edges, vec![EdgeIndex::new(1)]);
+    assert!(delta.removed_edges.is_empty());
+    assert_eq!(delta.changed_edges, vec![(EdgeIndex::new(2), EdgeIndex::new(2))]);
+    assert!(!delta.is_empty());
+
+    assert!(diff(&old, &old, |&(name, _)| name).is_empty());
+    let back = diff(&new, &old, |&(name, _)| name);
+    assert_eq!(back.removed_nodes, vec![d]);
+    assert_eq!(back.removed_edges, vec![EdgeIndex::new(1)]);
+
+    // undirected edges match in either direction
+    let g = Graph::<u8, (), Undirected>::from_edges(&[(0, 1)]).map(|i, _| i.index() as u8, |_, _| ());
+    let h = Graph::<u8, (), Undirected>::from_edges(&[(1, 0)]).map(|i, _| i.index() as u8, |_, _| ());
+    assert!(diff(&g, &h, |&k| k).is_empty());
+}
+
+#[test]
+fn branded_indices() {
+    use petgraph::graph::branded::brand;
+
+    let mut g = Graph::<u32, u32>::from_edges(&[(0, 1, 5), (1, 2, 6)]);
+    assert!(g.contains_node_index(n(2)));
+    assert!(!g.contains_node_index(n(3)));
+    assert!(g.contains_edge_index(EdgeIndex::new(1)));
+    assert!(!g.contains_edge_index(EdgeIndex::new(2)));
+
+    let d = brand(&mut g, |mut g| {
+        assert!(g.node(n(3)).is_none());
+        assert!(g.edge(EdgeIndex::new(2)).is_none());
+        let b = g.node(n(1)).unwrap();
+        let d = g.add_node(9);
+        let e = g.add_edge(b, d, 7);
+        g[e] += 1;
+        g[b] = 4;
+        assert_eq!(g.neighbors(b).map(|x| x.index()).collect::<Vec<_>>(), vec![d.index(), n(2)]);
+        assert_eq!(g.node_indices().count(), 4);
+        assert_eq!(g.graph().edge_count(), 3);
+        d.index()
+    });
+    assert_eq!(g[d], 9);
+    assert_eq!(g[n(1)], 4);
+    assert_eq!(g.edge_weight(EdgeIndex::new(2)), Some(&8));
+}
+
+#[test]
+fn node_index_by_weight() {
+    use petgraph::graph::indexed::IndexedGraph;
+
+    let mut g = Graph::<&str, u32>::new();
+    let a = g.add_node("a");
+    let b = g.add_node("b");
+    let c = g.add_node("c");
+    g.add_edge(a, b, 1);
+    g.add_edge(b, c, 2);
+    assert_eq!(g.node_index_by_weight(&"c"), Some(c));
+    assert_eq!(g.node_index_by_weight(&"z"), None);
+
+    let mut ig = IndexedGraph::from_graph(g);
+    assert_eq!(ig.node_index_by_weight("b"), Some(b));
+    let d = ig.add_node("d");
+    let e = ig.add_edge(d, a, 3);
+    ig[e] += 1;
+    assert_eq!(ig.edge_weight(e), Some(&4));
+
+    // the last node takes the index of the removed one
+    assert_eq!(ig.remove_node(b), Some("b"));
+    assert_eq!(ig.node_index_by_weight("b"), None);
+    assert_eq!(ig.node_index_by_weight("d"), Some(b));
+    assert_eq!(ig[b], "d");
+    for w in ig.node_indices().map(|x| ig[x]).collect::<Vec<_>>() {
+        assert_eq!(ig[ig.node_index_by_weight(w).unwrap()], w);
+    }
+    assert_eq!(ig.remove_node(n(7)), None);
+    assert_eq!(ig.edge_count(), 1);
+
+    let g = ig.into_graph();
+    assert_eq!(g.node_count(), 3);
+}
+
+#[test]
+#[should_panic]
+fn indexed_graph_duplicate_weights() {
+    use petgraph::graph::indexed::IndexedGraph;
+
+    let g = Graph::<u8, ()>::from_edges(&[(0, 1)]).map(|_, _| 0, |_, _| ());
+    IndexedGraph::from_graph(g);
+}
+
+#[test]
+fn graph_builder() {
+    use petgraph::graph::builder::GraphBuilder;
+
+    // without merging, every record is an edge
+    let mut builder = GraphBuilder::<&str, u32>::new();
+    builder.extend(vec![("a", "b", 1), ("b", "a", 2), ("a", "b", 3)]);
+    assert_eq!(builder.add_node("c"), n(2));
+    assert_eq!(builder.add_node("a"), n(0));
+    let (g, index) = builder.build();
+    assert_eq!(g.node_count(), 3);
+    assert_eq!(g.edge_count(), 3);
+    assert_eq!(index.len(), 3);
+    for (&key, &a) in &index {
+        assert_eq!(g[a], key);
+    }
+
+    // undirected records in either direction merge
+    let mut builder = GraphBuilder::new_undirected()
+        .merge_edges(|w: &mut Vec<u32>, more: Vec<u32>| w.extend(more));
+    builder.reserve(3, 4);
+    let ab = builder.add_edge("a", "b", vec![1]);
+    assert_eq!(builder.add_edge("b", "a", vec![2]), ab);
+    builder.add_edge("b", "c", vec![3]);
+    assert_eq!(builder.add_edge("a", "b", vec![4]), ab);
+    let (g, index) = builder.build();
+    assert_eq!(g.edge_count(), 2);
+    assert_eq!(g[ab], vec![1, 2, 4]);
+    assert_eq!(g.neighbors(index["c"]).collect::<Vec<_>>(), vec![index["b"]]);
+
+    // directed records in opposite directions don't merge
+    let mut builder = GraphBuilder::new().merge_edges(|w: &mut u32, more| *w += more);
+    builder.extend(vec![(1, 2, 1), (2, 1, 1), (1, 2, 1)]);
+    let (g, _) = builder.build();
+    assert_eq!(g.raw_edges().iter().map(|e| e.weight).collect::<Vec<_>>(), vec![2, 1]);
+}
+
+#[test]
+fn checked_graph() {
+    use petgraph::graph::checked::{CheckedGraph, GraphError, Policy};
+
+    let mut g = CheckedGraph::<u8, u8, Undirected>::new_undirected(Policy::simple());
+    let a = g.try_add_node(0).unwrap();
+    let b = g.try_add_node(1).unwrap();
+    let ab = g.try_add_edge(a, b, 5).unwrap();
+    // undirected edges are parallel in either direction
+    assert_eq!(g.try_add_edge(b, a, 6), Err(GraphError::ParallelEdge(b, a)));
+    assert_eq!(g.try_add_edge(b, b, 6), Err(GraphError::SelfLoop(b)));
+    assert_eq!(g.try_add_edge(n(2), b, 6), Err(GraphError::NodeNotFound(n(2))));
+    g[ab] += 1;
+    g[a] = 9;
+    assert_eq!(g.try_remove_edge(ab), Ok(6));
+    assert_eq!(g.try_remove_edge(ab), Err(GraphError::EdgeNotFound(ab)));
+    assert!(g.try_add_edge(b, a, 7).is_ok());
+    assert_eq!(g.try_remove_node(a), Ok(9));
+    assert_eq!(g.try_remove_node(n(1)), Err(GraphError::NodeNotFound(n(1))));
+    assert_eq!(g.edge_count(), 0);
+    assert_eq!(format!("{}", GraphError::ParallelEdge::<u32>(n(1), n(2))),
+               "parallel edge between nodes 1 and 2");
+
+    // a directed graph accepts opposite edges
+    let policy = Policy { self_loops: true, parallel_edges: false };
+    let mut g = CheckedGraph::from_graph(Graph::<(), (), Directed, u8>::with_capacity(0, 0), policy).unwrap();
+    let a = g.try_add_node(()).unwrap();
+    let b = g.try_add_node(()).unwrap();
+    assert!(g.try_add_edge(a, b, ()).is_ok());
+    assert!(g.try_add_edge(b, a, ()).is_ok());
+    assert!(g.try_add_edge(a, a, ()).is_ok());
+    assert!(g.try_add_edge(a, b, ()).is_err());
+    for _ in 2..255 {
+        g.try_add_node(()).unwrap();
+    }
+    assert_eq!(g.try_add_node(()), Err(GraphError::TooManyNodes));
+
+    // existing graphs are checked
+    let looped = Graph::<(), ()>::from_edges(&[(0, 1), (1, 1)]);
+    assert_eq!(CheckedGraph::from_graph(looped.clone(), Policy::simple()).err(),
+               Some(GraphError::SelfLoop(n(1))));
+    assert!(CheckedGraph::from_graph(looped, Policy::any()).is_ok());
+    let doubled = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (0, 1)]);
+    assert_eq!(CheckedGraph::from_graph(doubled, Policy::simple()).err(),
+               Some(GraphError::ParallelEdge(n(0), n(1))));
+}
+
+#[test]
+fn flat_graph() {
+    use petgraph::graph::FlatGraph;
+
+    let mut g = Graph::<_, _, Undirected, u16>::with_capacity(0, 0);
+    let a = g.add_node("a");
+    let b = g.add_node("b");
+    let c = g.add_node("c");
+    g.add_node("isolated");
+    g.add_edge(a, b, 1);
+    g.add_edge(c, b, 2);
+    g.add_edge(c, c, 3);
+    let flat = g.as_flat();
+    assert_eq!(flat.node_count, 4);
+    assert_eq!(flat.sources, vec![0u16, 2, 2]);
+    assert_eq!(flat.targets, vec![1u16, 1, 2]);
+
+    // the structure and indices are the same, weights are defaults
+    let h = Graph::<&str, i32, Undirected, u16>::from_flat(&flat);
+    assert_eq!(h.node_count(), 4);
+    assert_eq!(h.edge_count(), 3);
+    assert_eq!(h.find_edge(n(1), n(2)), Some(EdgeIndex::new(1)));
+    assert!(h.raw_nodes().iter().all(|node| node.weight == ""));
+    assert_eq!(h.as_flat(), flat);
+
+    let empty = Graph::<(), ()>::new();
+    assert_eq!(empty.as_flat(), FlatGraph { node_count: 0, sources: vec![], targets: vec![] });
+    assert_eq!(Graph::<(), ()>::from_flat(&empty.as_flat()).node_count(), 0);
+}
+
+#[test]
+#[should_panic]
+fn flat_graph_out_of_bounds() {
+    use petgraph::graph::FlatGraph;
+
+    let flat = FlatGraph { node_count: 2, sources: vec![0u32], targets: vec![2] };
+    Graph::<(), ()>::from_flat(&flat);
+}
+
+fn assert_batch_matches_add_edge<Ty: EdgeType>(mut g: Graph<(), i32, Ty>) {
+    let edges = [(0, 1, 1), (1, 2, 2), (2, 2, 3), (2, 0, 4), (0, 1, 5), (3, 1, 6)];
+    let mut h = g.clone();
+    for &(a, b, w) in &edges {
+        g.add_edge(n(a), n(b), w);
+    }
+    let new = h.add_edges_batch(edges.iter().map(|&(a, b, w)| (n(a), n(b), w)));
+    assert_eq!(new.collect::<Vec<_>>(), (2..8).map(EdgeIndex::new).collect::<Vec<_>>());
+
+    // the adjacency lists are the same as with add_edge
+    for a in g.node_indices() {
+        for &dir in &[Outgoing, Incoming] {
+            assert_eq!(g.edges_directed(a, dir).collect::<Vec<_>>(),
+                       h.edges_directed(a, dir).collect::<Vec<_>>());
+        }
+    }
+    assert_eq!(g.as_flat(), h.as_flat());
+}
+
+#[test]
+fn add_edges_batch() {
+    use std::panic;
+
+    let mut g = Graph::<(), i32>::from_edges(&[(0, 1, 0)]);
+    g.extend_with_edges(&[(3, 2, 0)]);
+    assert_batch_matches_add_edge(g.clone());
+    assert_batch_matches_add_edge(g.into_edge_type::<Undirected>());
+
+    let mut g = Graph::<(), ()>::from_edges(&[(0, 1)]);
+    assert_eq!(g.add_edges_batch(Vec::<(u32, u32)>::new()).count(), 0);
+
+    // a missing node adds no edge
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        g.add_edges_batch(vec![(1, 0), (0, 2)]);
+    }));
+    assert!(result.is_err());
+    assert_eq!(g.edge_count(), 1);
+    assert_eq!(g.neighbors_undirected(n(0)).count(), 1);
+
+    // so does a full index type
+    let mut g = Graph::<(), (), Directed, u8>::with_capacity(0, 0);
+    g.add_node(());
+    g.add_edges_batch((0..254).map(|_| (0u8, 0u8)));
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        g.add_edges_batch(vec![(0u8, 0u8), (0, 0)]);
+    }));
+    assert!(result.is_err());
+    assert_eq!(g.edge_count(), 254);
+    g.add_edges_batch(vec![(0u8, 0u8)]);
+    assert_eq!(g.edge_count(), 255);
+}
+
+fn assert_csr_matches_graph<Ty: EdgeType>(g: &Graph<(), (), Ty>) {
+    use petgraph::csr::Csr;
+
+    let csr = Csr::from_graph(g);
+    assert_eq!(csr.node_count(), g.node_count());
+    assert_eq!(csr.edge_count(), g.edge_count());
+    assert_eq!(csr.is_directed(), g.is_directed());
+    let adjacent = |a, b| g.find_edge(a, b).is_some();
+    for a in g.node_indices() {
+        let mut expected = g.edges(a).map(|(b, _)| b).collect::<Vec<_>>();
+        expected.sort();
+        assert_eq!(csr.neighbors(a), &expected[..]);
+        assert_eq!(csr.degree(a), expected.len());
+        for (&b, &e) in csr.neighbors(a).iter().zip(csr.neighbor_edges(a)) {
+            let (x, y) = g.edge_endpoints(e).unwrap();
+            assert!((x, y) == (a, b) || !g.is_directed() && (x, y) == (b, a));
+        }
+        for b in g.node_indices() {
+            assert_eq!(csr.contains_edge(a, b), adjacent(a, b));
+            // the lowest of the parallel edges
+            let lowest = g.edge_indices().find(|&e| {
+                let (x, y) = g.edge_endpoints(e).unwrap();
+                (x, y) == (a, b) || !g.is_directed() && (x, y) == (b, a)
+            });
+            assert_eq!(csr.find_edge(a, b), lowest);
+
+            let common = g.node_indices().filter(|&c| adjacent(a, c) && adjacent(b, c))
+                          .collect::<Vec<_>>();
+            assert_eq!(csr.common_neighbors(a, b).collect::<Vec<_>>(), common);
+        }
+    }
+
+    let mut triangles = 0;
+    for a in g.node_indices() {
+        for b in g.node_indices() {
+            for c in g.node_indices() {
+                if a != b && b != c && a != c && adjacent(a, b) && adjacent(a, c) && adjacent(b, c) {
+                    triangles += 1;
+                }
+            }
+        }
+    }
+    if !g.is_directed() {
+        triangles /= 6;
+    }
+    assert_eq!(csr.triangle_count(), triangles);
+}
+
+#[test]
+fn csr() {
+    use rand::Rng;
+
+    let mut rng = rand::thread_rng();
+    for _ in 0..50 {
+        let nodes = rng.gen_range(1, 12);
+        let m = rng.gen_range(0, 3 * nodes);
+        let mut g = Graph::<(), ()>::with_capacity(nodes, m);
+        for _ in 0..nodes {
+            g.add_node(());
+        }
+        for _ in 0..m {
+            let (a, b) = (rng.gen_range(0, nodes), rng.gen_range(0, nodes));
+            g.add_edge(n(a), n(b), ());
+        }
+        assert_csr_matches_graph(&g);
+        assert_csr_matches_graph(&g.into_edge_type::<Undirected>());
+    }
+
+    // a directed three-cycle is not a triangle, a transitive triple is
+    let cycle = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0)]);
+    assert_eq!(petgraph::csr::Csr::from_graph(&cycle).triangle_count(), 0);
+    let transitive = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (0, 2), (0, 2)]);
+    assert_eq!(petgraph::csr::Csr::from_graph(&transitive).triangle_count(), 1);
+}
+
+fn assert_inline_matches_graph<Ty: EdgeType>(g: Graph<(), usize, Ty>) {
+    use petgraph::graph::inline::InlineGraph;
+
+    let inline = InlineGraph::from(g.clone());
+    assert_eq!(inline.node_count(), g.node_count());
+    assert_eq!(inline.edge_count(), g.edge_count());
+    for a in g.node_indices() {
+        for &dir in &[Outgoing, Incoming] {
+            let mut expected = g.neighbors_directed(a, dir).collect::<Vec<_>>();
+            let mut neighbors = inline.neighbors_directed(a, dir).collect::<Vec<_>>();
+            expected.sort();
+            neighbors.sort();
+            assert_eq!(neighbors, expected);
+        }
+        let mut expected = g.edges(a).map(|(b, &w)| (b, w)).collect::<Vec<_>>();
+        let mut edges = inline.edges(a).map(|(b, &w)| (b, w)).collect::<Vec<_>>();
+        expected.sort();
+        edges.sort();
+        assert_eq!(edges, expected);
+        for b in g.node_indices() {
+            assert_eq!(inline.find_edge(a, b).is_some(), g.find_edge(a, b).is_some());
+        }
+    }
+    for e in g.edge_indices() {
+        assert_eq!(inline.edge_endpoints(e), g.edge_endpoints(e));
+        assert_eq!(inline[e], g[e]);
+    }
+    assert_eq!(inline.into_graph().as_flat(), g.as_flat());
+}
+
+#[test]
+fn inline_graph() {
+    use rand::Rng;
+    use petgraph::graph::inline::InlineGraph;
+
+    let mut rng = rand::thread_rng();
+    for _ in 0..50 {
+        // some nodes have more edges than fit inline
+        let nodes = rng.gen_range(1, 10);
+        let m = rng.gen_range(0, 6 * nodes);
+        let mut g = Graph::<(), usize>::with_capacity(nodes, m);
+        for _ in 0..nodes {
+            g.add_node(());
+        }
+        for i in 0..m {
+            let (a, b) = (rng.gen_range(0, nodes), rng.gen_range(0, nodes));
+            g.add_edge(n(a), n(b), i);
+        }
+        assert_inline_matches_graph(g.clone());
+        assert_inline_matches_graph(g.into_edge_type::<Undirected>());
+    }
+
+    // neighbors are listed in the order their edges were added
+    let mut g = InlineGraph::<_, _, Undirected>::new_undirected();
+    let a = g.add_node("a");
+    let others = (0..6).map(|_| g.add_node("b")).collect::<Vec<_>>();
+    for &b in &others {
+        g.add_edge(a, b, 1.);
+    }
+    g.add_edge(a, a, 2.);
+    assert_eq!(g.neighbors(a).collect::<Vec<_>>(),
+               others.iter().cloned().chain(Some(a)).collect::<Vec<_>>());
+    assert_eq!(g.neighbors(others[3]).collect::<Vec<_>>(), vec![a]);
+    g[a] = "c";
+    g[EdgeIndex::new(6)] = 3.;
+    assert_eq!(g.node_weight(a), Some(&"c"));
+    assert_eq!(g.edge_weight(EdgeIndex::new(6)), Some(&3.));
+    assert_eq!(g.neighbors(n(7)).count(), 0);
+
+    let mut dfs = Dfs::new(&g, others[0]);
+    let mut count = 0;
+    while let Some(_) = dfs.next(&g) {
+        count += 1;
+    }
+    assert_eq!(count, 7);
+}
+
+#[test]
+fn memory_usage() {
+    use std::mem::size_of;
+    use petgraph::graph::{Edge, Node};
+
+    let mut g = Graph::<[u8; 16], u64>::with_capacity(10, 20);
+    let (nodes, edges) = g.capacity();
+    assert_eq!(g.memory_usage(),
+               nodes * size_of::<Node<[u8; 16]>>() + edges * size_of::<Edge<u64>>());
+    assert!(g.memory_usage() >= 10 * 16 + 20 * 8);
+    let before = g.memory_usage();
+    for _ in 0..100 {
+        g.add_node([0; 16]);
+    }
+    assert!(g.memory_usage() >= before + 90 * size_of::<Node<[u8; 16]>>());
+    g.clear();
+    g.shrink_to_fit();
+    assert_eq!(g.memory_usage(), 0);
+}
+
+#[test]
+fn path_type() {
+    use petgraph::path::Path;
+
+    let mut g = Graph::<&str, u32, Undirected>::new_undirected();
+    let a = g.add_node("a");
+    let b = g.add_node("b");
+    let c = g.add_node("c");
+    let d = g.add_node("d");
+    let ab = g.add_edge(a, b, 1);
+    let bc = g.add_edge(b, c, 2);
+    let ca = g.add_edge(c, a, 4);
+    g.add_edge(c, d, 8);
+
+    let mut p = Path::<Graph<&str, u32, Undirected>>::new(a);
+    assert!(p.is_empty());
+    assert!(p.is_simple());
+    assert!(!p.is_simple_cycle());
+    assert_eq!(p.total_weight(|e| g[e]), 0);
+    p.push(ab, b);
+    p.push(bc, c);
+    assert_eq!(p.nodes(), &[a, b, c]);
+    assert_eq!(p.edges(), &[ab, bc]);
+    assert_eq!(p.total_weight(|e| g[e]), 3);
+    assert!(p.is_simple());
+
+    let back = Path::from_parts(vec![c, a], vec![ca]);
+    let cycle = p.clone().concat(back);
+    assert_eq!(cycle.len(), 3);
+    assert_eq!((cycle.start(), cycle.end()), (&a, &a));
+    assert!(!cycle.is_simple());
+    assert!(cycle.is_simple_cycle());
+    assert_eq!(cycle.total_weight(|e| g[e] as f64), 7.);
+
+    let (sub, nodes) = p.subgraph(&g);
+    assert_eq!(nodes, vec![a, b, c]);
+    assert_eq!(sub.node_count(), 3);
+    assert_eq!(sub.edge_count(), 2);
+    assert!(sub.find_edge(n(0), n(1)).is_some());
+    assert!(sub.find_edge(n(0), n(2)).is_none());
+
+    let (sub, _) = cycle.concat(Path::new(a)).subgraph(&g);
+    assert_eq!(sub.edge_count(), 3);
+}
+
+#[should_panic]
+#[test]
+fn path_concat_disjoint() {
+    use petgraph::path::Path;
+
+    let p = Path::<Graph<(), ()>>::from_parts(vec![n(0), n(1)], vec![EdgeIndex::new(0)]);
+    p.concat(Path::new(n(2)));
+}
+
+#[test]
+fn shortest_path_edges() {
+    // the path takes the cheapest of the parallel edges
+    let mut g = Graph::<(), u32>::new();
+    let a = g.add_node(());
+    let b = g.add_node(());
+    let c = g.add_node(());
+    g.add_edge(a, b, 5);
+    let ab = g.add_edge(a, b, 1);
+    g.add_edge(a, b, 3);
+    let bc = g.add_edge(b, c, 2);
+    let (cost, path) = shortest_path(&g, a, c, |&w| w).unwrap();
+    assert_eq!(cost, 3);
+    assert_eq!(path.edges(), &[ab, bc]);
+    assert!(path.is_simple());
+
+    let (cost, path) = shortest_path(&g, a, a, |&w| w).unwrap();
+    assert_eq!((cost, path.len()), (0, 0));
+    assert!(shortest_path(&g, c, a, |&w| w).is_none());
+}
+
+#[test]
+fn floyd_warshall_matrix() {
+    use petgraph::algo::{floyd_warshall, floyd_warshall_paths};
+
+    let g = Graph::<(), u32>::from_edges(&[
+        (0, 1, 7), (0, 2, 9), (0, 5, 14), (1, 2, 10), (1, 3, 15), (2, 3, 11),
+        (2, 5, 2), (3, 4, 6), (5, 4, 9), (4, 4, 1), (6, 0, 1),
+    ]);
+    let dist = floyd_warshall_paths(&g, |w| *w).unwrap();
+    assert!(dist.has_paths());
+    assert_eq!(dist.node_count(), 7);
+    assert_eq!(dist.rows().count(), 7);
+    for (i, row) in dist.rows().enumerate() {
+        assert_eq!(row, dist.row(n(i)));
+        let scores = dijkstra(&g, n(i), None, |gr, a| gr.edges(a).map(|(b, &w)| (b, w)));
+        for (j, &cost) in row.iter().enumerate() {
+            assert_eq!(cost, scores.get(&n(j)).cloned());
+            match dist.path(n(i), n(j)) {
+                None => assert!(cost.is_none()),
+                Some(path) => {
+                    assert_eq!((path[0], *path.last().unwrap()), (n(i), n(j)));
+                    let total: u32 = path.windows(2).map(|w| g[g.find_edge(w[0], w[1]).unwrap()]).sum();
+                    assert_eq!(Some(total), cost);
+                }
+            }
+        }
+    }
+    assert!(!floyd_warshall(&g, |w| *w).unwrap().has_paths());
+
+    // negative costs, and a negative cycle
+    let mut g = Graph::<(), i32>::from_edges(&[(0, 1, 2), (1, 2, -3), (2, 3, 1)]);
+    let dist = floyd_warshall(&g, |w| *w).unwrap();
+    assert_eq!(dist.get(n(0), n(3)), Some(0));
+    g.add_edge(n(2), n(0), 0);
+    assert!(floyd_warshall(&g, |w| *w).is_none());
+    let u = Graph::<(), i32, Undirected>::from_edges(&[(0, 1, -1)]);
+    assert!(floyd_warshall(&u, |w| *w).is_none());
+    // a dense negative cycle is found before the distances overflow
+    let mut dense = Graph::<(), i32>::new();
+    for _ in 0..64 {
+        dense.add_node(());
+    }
+    for a in 0..64 {
+        for b in 0..64 {
+            if a != b {
+                dense.add_edge(n(a), n(b), -1);
+            }
+        }
+    }
+    assert!(floyd_warshall(&dense, |w| *w).is_none());
+    assert!(floyd_warshall_paths(&dense, |w| *w).is_none());
+
+    let u = Graph::<(), f64, Undirected>::from_edges(&[(0, 1, 1.5), (1, 2, 1.), (0, 2, 3.)]);
+    let dist = floyd_warshall_paths(&u, |w| *w).unwrap();
+    assert_eq!(dist.get(n(2), n(0)), Some(2.5));
+    assert_eq!(dist.path(n(2), n(0)), Some(vec![n(2), n(1), n(0)]));
+
+    let empty = floyd_warshall(&Graph::<(), u32>::new(), |w| *w).unwrap();
+    assert_eq!(empty.rows().count(), 0);
+}
+
+#[test]
+fn bfs_tree_depths() {
+    use petgraph::algo::bfs_tree;
+
+    let mut g = Graph::<(), (), Undirected>::from_edges(&[
+        (0, 1), (1, 2), (2, 3), (3, 0), (2, 4), (4, 5), (5, 5), (1, 4),
+    ]);
+    g.add_node(());
+    let tree = bfs_tree(&g, n(0));
+    let scores = dijkstra(&g, n(0), None, |gr, a| gr.neighbors(a).map(|b| (b, 1)));
+    for a in g.node_indices() {
+        assert_eq!(tree.depth[a.index()], scores.get(&a).cloned());
+        if let Some(e) = tree.parent_edge[a.index()] {
+            let p = tree.parent[a.index()].unwrap();
+            let (x, y) = g.edge_endpoints(e).unwrap();
+            assert!((x, y) == (p, a) || (y, x) == (p, a));
+            assert_eq!(tree.depth[p.index()].map(|d| d + 1), tree.depth[a.index()]);
+        }
+    }
+    assert_eq!(tree.order.len(), 6);
+    assert_eq!(tree.order[0], n(0));
+    assert!(tree.order.windows(2).all(|w| tree.depth[w[0].index()] <= tree.depth[w[1].index()]));
+
+    let t = tree.to_graph();
+    assert_eq!(t.node_count(), g.node_count());
+    assert_eq!(t.edge_count(), 5);
+    assert!(!is_cyclic_undirected(&t));
+    assert_eq!(t[n(6)], None);
+
+    // edges are followed in their direction
+    let d = Graph::<(), ()>::from_edges(&[(1, 0), (1, 2), (2, 0)]);
+    let tree = bfs_tree(&d, n(1));
+    assert_eq!(tree.depth, vec![Some(1), Some(0), Some(1)]);
+    assert_eq!(bfs_tree(&d, n(0)).order, vec![n(0)]);
+}
+
+#[cfg(feature = "generate")]
+#[test]
+fn test_generate_random_spanning_tree() {
+    use std::collections::HashMap;
+    use rand::{SeedableRng, StdRng};
+    use petgraph::generate::{complete_graph, random_spanning_tree};
+
+    // K4 has 16 spanning trees, which should come up about equally often
+    let g = complete_graph::<Undirected>(4);
+    let seed: &[_] = &[5, 6, 7, 8];
+    let mut rng = StdRng::from_seed(seed);
+    let mut counts = HashMap::new();
+    for _ in 0..16000 {
+        let tree = random_spanning_tree(&g, &mut rng);
+        assert_eq!(tree.len(), 3);
+        let t = g.filter_map(|_, &w| Some(w), |e, &w| if tree.contains(&e) { Some(w) } else { None });
+        assert!(!is_cyclic_undirected(&t));
+        *counts.entry(tree).or_insert(0) += 1;
+    }
+    assert_eq!(counts.len(), 16);
+    assert!(counts.values().all(|&c| c > 800 && c < 1200), "{:?}", counts);
+
+    // a forest for each component, ignoring self loops and directions
+    let mut g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (3, 4), (4, 4), (1, 0)]);
+    g.add_node(());
+    for _ in 0..20 {
+        let tree = random_spanning_tree(&g, &mut rng);
+        assert_eq!(tree.len(), 3);
+        assert!(tree.contains(&EdgeIndex::new(3)));
+        assert!(!tree.contains(&EdgeIndex::new(4)));
+    }
+    assert!(random_spanning_tree(&Graph::<(), ()>::new(), &mut rng).is_empty());
+}
+
+#[test]
+fn spanning_tree_enumeration() {
+    use std::collections::HashSet;
+    use petgraph::algo::{spanning_tree_count, spanning_tree_count_f64, spanning_trees};
+
+    fn check<Ty: petgraph::EdgeType>(g: &Graph<(), (), Ty>, expected: usize) {
+        let mut seen = HashSet::new();
+        for tree in spanning_trees(g) {
+            assert_eq!(tree.len(), g.node_count() - 1);
+            let t = g.filter_map(|_, &w| Some(w), |e, &w| if tree.contains(&e) { Some(w) } else { None });
+            assert_eq!(pg::algo::connected_components(&t), 1);
+            assert!(seen.insert(tree));
+        }
+        assert_eq!(seen.len(), expected);
+        assert_eq!(spanning_trees(g).count(), expected);
+        assert_eq!(spanning_tree_count(g), Some(expected as u64));
+        assert!((spanning_tree_count_f64(g) - expected as f64).abs() < 1e-6);
+        // counting after the first tree enumerates the rest
+        let mut rest = spanning_trees(g);
+        if rest.next().is_some() {
+            assert_eq!(rest.count() + 1, expected);
+        }
+    }
+
+    let mut k5 = Graph::<(), (), Undirected>::new_undirected();
+    for _ in 0..5 {
+        k5.add_node(());
+    }
+    for i in 0..5 {
+        for j in i + 1..5 {
+            k5.add_edge(n(i), n(j), ());
+        }
+    }
+    check(&k5, 125);
+    // a 3 × 3 grid
+    let grid = Graph::<(), (), Undirected>::from_edges(&[
+        (0, 1), (1, 2), (3, 4), (4, 5), (6, 7), (7, 8),
+        (0, 3), (3, 6), (1, 4), (4, 7), (2, 5), (5, 8),
+    ]);
+    check(&grid, 192);
+    // parallel edges give distinct trees, self loops none, directions are ignored
+    check(&Graph::<(), ()>::from_edges(&[(0, 1), (1, 0), (0, 1), (1, 1), (2, 1)]), 3);
+    check(&Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 4), (4, 0)]), 5);
+
+    let mut single = Graph::<(), ()>::new();
+    single.add_node(());
+    check(&single, 1);
+    single.add_node(());
+    check(&single, 0);
+    assert_eq!(spanning_trees(&Graph::<(), ()>::new()).count(), 0);
+}
+
+#[test]
+fn spanning_tree_count_overflow() {
+    use petgraph::algo::{spanning_tree_count, spanning_tree_count_f64};
+
+    let complete = |nodes: usize| {
+        let mut g = Graph::<(), (), Undirected>::new_undirected();
+        for _ in 0..nodes {
+            g.add_node(());
+        }
+        for i in 0..nodes {
+            for j in i + 1..nodes {
+                g.add_edge(n(i), n(j), ());
+            }
+        }
+        g
+    };
+    // Cayley's formula, n^(n - 2)
+    assert_eq!(spanning_tree_count(&complete(15)), Some(15u64.pow(13)));
+    assert_eq!(spanning_tree_count(&complete(30)), None);
+    let count = spanning_tree_count_f64(&complete(30));
+    let expected = 30f64.powi(28);
+    assert!((count - expected).abs() < expected * 1e-9);
+    assert_eq!(spanning_tree_count(&Graph::<(), ()>::new()), Some(0));
+    assert_eq!(spanning_tree_count_f64(&Graph::<(), ()>::new()), 0.);
+}
+
+#[test]
+fn greedy_matching() {
+    use petgraph::algo::{
+        greedy_maximal_matching,
+        is_matching,
+        is_maximal_matching,
+        is_perfect_matching,
+    };
+
+    // a 6-cycle with a chord, a self loop and a parallel edge
+    let mut g = Graph::<(), (), Undirected>::from_edges(&[
+        (0, 0), (0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 0), (1, 4), (1, 0),
+    ]);
+    let matching = greedy_maximal_matching(&g);
+    assert_eq!(matching, vec![EdgeIndex::new(1), EdgeIndex::new(3), EdgeIndex::new(5)]);
+    assert!(is_matching(&g, &matching));
+    assert!(is_maximal_matching(&g, &matching));
+    assert!(is_perfect_matching(&g, &matching));
+
+    // the self loop, a repeated edge, and a missing edge
+    assert!(!is_matching(&g, &[EdgeIndex::new(0)]));
+    assert!(!is_matching(&g, &[EdgeIndex::new(2), EdgeIndex::new(2)]));
+    assert!(!is_matching(&g, &[EdgeIndex::new(9)]));
+    // the parallel edges share both endpoints
+    assert!(!is_matching(&g, &[EdgeIndex::new(1), EdgeIndex::new(8)]));
+    // the chord alone leaves 2-3 and 5-0 free
+    let chord = [EdgeIndex::new(7)];
+    assert!(is_matching(&g, &chord));
+    assert!(!is_maximal_matching(&g, &chord));
+    assert!(!is_perfect_matching(&g, &chord));
+    assert!(is_matching(&g, &[]));
+
+    // a star has maximal matchings of one edge only
+    g = Graph::from_edges(&[(0, 1), (0, 2), (0, 3)]);
+    let matching = greedy_maximal_matching(&g);
+    assert_eq!(matching.len(), 1);
+    assert!(is_maximal_matching(&g, &matching));
+    assert!(!is_perfect_matching(&g, &matching));
+    assert!(greedy_maximal_matching(&Graph::<(), ()>::new()).is_empty());
+}
+
+#[test]
+fn partition_balanced() {
+    use petgraph::algo::partition::{bisect, cut_size, k_way};
+    use rand::Rng;
+
+    let sizes = |parts: &[usize], k: usize| {
+        let mut sizes = vec![0; k];
+        for &p in parts {
+            sizes[p] += 1;
+        }
+        sizes
+    };
+
+    let square_grid = |side: usize| {
+        let mut grid = Graph::<(), (), Undirected>::new_undirected();
+        for _ in 0..side * side {
+            grid.add_node(());
+        }
+        for i in 0..side {
+            for j in 0..side {
+                if i + 1 < side {
+                    grid.add_edge(n(i * side + j), n((i + 1) * side + j), ());
+                }
+                if j + 1 < side {
+                    grid.add_edge(n(i * side + j), n(i * side + j + 1), ());
+                }
+            }
+        }
+        grid
+    };
+
+    // a 20 × 20 grid is best cut along the middle
+    let grid = square_grid(20);
+    let parts = bisect(&grid);
+    assert_eq!(sizes(&parts, 2), vec![200, 200]);
+    assert!(cut_size(&grid, &parts) <= 22);
+    let parts = k_way(&grid, 4);
+    assert_eq!(sizes(&parts, 4), vec![100; 4]);
+    assert!(cut_size(&grid, &parts) <= 44);
+
+    // a slack of a fifth of the total weight lets refinement drift too far
+    // from the balance, and cuts this grid into eight parts with 216 edges
+    let grid = square_grid(50);
+    let parts = k_way(&grid, 8);
+    assert!(sizes(&parts, 8).iter().all(|&s| s == 312 || s == 313));
+    assert!(cut_size(&grid, &parts) <= 210);
+
+    // disconnected cliques are not cut
+    let mut cliques = Graph::<(), ()>::new();
+    for _ in 0..60 {
+        cliques.add_node(());
+    }
+    for i in 0..60 {
+        for j in i + 1..60 {
+            if i / 20 == j / 20 {
+                cliques.add_edge(n(i), n(j), ());
+            }
+        }
+    }
+    let parts = k_way(&cliques, 3);
+    assert_eq!(cut_size(&cliques, &parts), 0);
+    assert_eq!(sizes(&parts, 3), vec![20; 3]);
+
+    // random graphs stay balanced, even with more parts than nodes
+    let mut rng = rand::weak_rng();
+    for _ in 0..30 {
+        let nodes = rng.gen_range(1, 100);
+        let mut gr = Graph::<(), ()>::new();
+        for _ in 0..nodes {
+            gr.add_node(());
+        }
+        for _ in 0..rng.gen_range(0, 3 * nodes + 1) {
+            let a = n(rng.gen_range(0, nodes));
+            let b = n(rng.gen_range(0, nodes));
+            gr.add_edge(a, b, ());
+        }
+        for k in 1..6 {
+            let parts = k_way(&gr, k);
+            let sizes = sizes(&parts, k);
+            let (min, max) = (sizes.iter().min().unwrap(), sizes.iter().max().unwrap());
+            assert!(max - min <= 1, "{:?}", sizes);
+        }
+    }
+    assert!(bisect(&Graph::<(), ()>::new()).is_empty());
+}
+
+#[test]
+fn coarsen_hierarchy() {
+    use petgraph::algo::coarsen;
+
+    // a 4 × 4 grid with a self loop and a doubled edge
+    let mut g = Graph::<(), ()>::new();
+    for _ in 0..16 {
+        g.add_node(());
+    }
+    for i in 0..4 {
+        for j in 0..4 {
+            if i + 1 < 4 {
+                g.add_edge(n(i * 4 + j), n((i + 1) * 4 + j), ());
+            }
+            if j + 1 < 4 {
+                g.add_edge(n(i * 4 + j), n(i * 4 + j + 1), ());
+            }
+        }
+    }
+    g.add_edge(n(5), n(5), ());
+    g.add_edge(n(6), n(5), ());
+
+    let levels = coarsen(&g, 10);
+    assert_eq!(levels.last().unwrap().graph.node_count(), 1);
+    let mut fine_count = g.node_count();
+    let mut fine_edges = g.raw_edges().iter()
+                          .filter(|e| e.source() != e.target())
+                          .map(|e| (e.source(), e.target(), 1))
+                          .collect::<Vec<_>>();
+    let mut fine_weights = vec![1; fine_count];
+    for level in &levels {
+        let coarse = &level.graph;
+        assert_eq!(level.projection.len(), fine_count);
+        assert!(coarse.node_count() < fine_count);
+        // each coarse node contracts one or two finer nodes, and weighs them
+        let mut weight = vec![0; coarse.node_count()];
+        let mut members = vec![0; coarse.node_count()];
+        for (a, &c) in level.projection.iter().enumerate() {
+            weight[c.index()] += fine_weights[a];
+            members[c.index()] += 1;
+        }
+        assert!(members.iter().all(|&m| m == 1 || m == 2));
+        assert_eq!(weight, coarse.node_indices().map(|a| coarse[a]).collect::<Vec<_>>());
+        // the coarse edges add up the finer edges between their ends
+        for e in coarse.raw_edges() {
+            let (a, b) = (e.source(), e.target());
+            let expected = fine_edges.iter()
+                .filter(|&&(x, y, _)| {
+                    let (cx, cy) = (level.projection[x.index()], level.projection[y.index()]);
+                    (cx, cy) == (a, b) || (cx, cy) == (b, a)
+                })
+                .map(|&(_, _, w)| w)
+                .sum::<usize>();
+            assert_eq!(e.weight, expected);
+        }
+        assert_eq!(coarse.raw_edges().iter().map(|e| e.weight).sum::<usize>(),
+                   fine_edges.iter()
+                             .filter(|&&(x, y, _)| level.projection[x.index()] != level.projection[y.index()])
+                             .map(|&(_, _, w)| w)
+                             .sum::<usize>());
+        fine_count = coarse.node_count();
+        fine_edges = coarse.raw_edges().iter().map(|e| (e.source(), e.target(), e.weight)).collect();
+        fine_weights = weight;
+    }
+
+    assert_eq!(coarsen(&g, 1).len(), 1);
+    assert!(coarsen(&g, 0).is_empty());
+    // nothing to contract
+    let mut isolated = Graph::<(), ()>::new();
+    isolated.add_node(());
+    isolated.add_node(());
+    assert!(coarsen(&isolated, 3).is_empty());
+}
+
+#[test]
+fn force_layout() {
+    use petgraph::layout::{ForceLayout, ForceModel};
+
+    // two 6-cliques joined by a single edge
+    let mut g = Graph::<(), (), Undirected>::new_undirected();
+    for _ in 0..12 {
+        g.add_node(());
+    }
+    for i in 0..12 {
+        for j in i + 1..12 {
+            if i / 6 == j / 6 {
+                g.add_edge(n(i), n(j), ());
+            }
+        }
+    }
+    g.add_edge(n(0), n(6), ());
+
+    let dist = |p: (f32, f32), q: (f32, f32)| ((p.0 - q.0).powi(2) + (p.1 - q.1).powi(2)).sqrt();
+    for &model in &[ForceModel::FruchtermanReingold, ForceModel::ForceAtlas2] {
+        let settings = ForceLayout::new().model(model).size(10., 5.).iterations(300).seed(42);
+        let pos = settings.layout(&g);
+        assert_eq!(pos, settings.layout(&g));
+        assert!(pos != settings.clone().seed(43).layout(&g));
+        assert!(pos.iter().all(|&(x, y)| 0. <= x && x <= 10. && 0. <= y && y <= 5.));
+        // the cliques are drawn apart
+        let (mut inner, mut outer) = (0., 0.);
+        for i in 0..12 {
+            for j in i + 1..12 {
+                if i / 6 == j / 6 {
+                    inner += dist(pos[i], pos[j]) / 30.;
+                } else {
+                    outer += dist(pos[i], pos[j]) / 36.;
+                }
+            }
+        }
+        assert!(inner * 3. < outer, "{:?}: {} {}", model, inner, outer);
+    }
+
+    // without iterations, the start positions
+    let start = ForceLayout::new().iterations(0).layout(&g);
+    assert!(start.iter().all(|&(x, y)| 0. <= x && x < 1. && 0. <= y && y < 1.));
+    assert!(ForceLayout::default().layout(&Graph::<(), ()>::new()).is_empty());
+}
+
+#[test]
+fn layered_layout_dag() {
+    use petgraph::layout::layered_layout;
+    use rand::Rng;
+
+    // 0 → 3 and 1 → 2 cross unless layer 1 is reordered
+    let g = Graph::<(), ()>::from_edges(&[(0, 3), (1, 2)]);
+    let layout = layered_layout(&g).unwrap();
+    assert_eq!(layout.layers, vec![vec![n(0), n(1)], vec![n(3), n(2)]]);
+    assert!(layout.position[3].0 < layout.position[2].0);
+
+    let mut cyclic = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0)]);
+    assert_eq!(layered_layout(&cyclic), None);
+    cyclic.remove_edge(EdgeIndex::new(2));
+    // self loops are ignored
+    cyclic.add_edge(n(1), n(1), ());
+    let layout = layered_layout(&cyclic).unwrap();
+    assert_eq!(layout.layer, vec![0, 1, 2]);
+    assert!(layout.bends[2].is_empty());
+
+    let mut rng = rand::weak_rng();
+    for _ in 0..30 {
+        let nodes = rng.gen_range(1, 30);
+        let mut dag = Graph::<(), ()>::new();
+        for _ in 0..nodes {
+            dag.add_node(());
+        }
+        for _ in 0..rng.gen_range(0, 2 * nodes) {
+            let a = rng.gen_range(0, nodes);
+            let b = rng.gen_range(0, nodes);
+            if a < b {
+                dag.add_edge(n(a), n(b), ());
+            }
+        }
+        let layout = layered_layout(&dag).unwrap();
+        for (i, edge) in dag.raw_edges().iter().enumerate() {
+            let (a, b) = (edge.source().index(), edge.target().index());
+            assert!(layout.layer[a] < layout.layer[b]);
+            assert_eq!(layout.bends[i].len(), layout.layer[b] - layout.layer[a] - 1);
+            for (j, &(_, y)) in layout.bends[i].iter().enumerate() {
+                assert_eq!(y, (layout.layer[a] + j + 1) as f32);
+            }
+        }
+        // the longest path layering puts each node right below a predecessor
+        for b in dag.node_indices() {
+            let l = layout.layer[b.index()];
+            let preds = dag.neighbors_directed(b, petgraph::Incoming).map(|a| layout.layer[a.index()]);
+            assert_eq!(preds.max().map_or(0, |p| p + 1), l);
+        }
+        let mut seen = 0;
+        for (l, layer) in layout.layers.iter().enumerate() {
+            seen += layer.len();
+            for w in layer.windows(2) {
+                assert!(layout.position[w[0].index()].0 + 1. <= layout.position[w[1].index()].0);
+            }
+            for &a in layer {
+                assert_eq!(layout.layer[a.index()], l);
+                assert_eq!(layout.position[a.index()].1, l as f32);
+            }
+        }
+        assert_eq!(seen, nodes);
+        assert!(layout.position.iter().all(|&(x, _)| x >= 0.));
+    }
+}
+
+#[test]
+fn planar_embedding_faces() {
+    use petgraph::algo::{is_planar, planar_embedding};
+    use rand::Rng;
+
+    // Euler's formula holds for each component with edges, and the
+    // rotations list each edge at both ends
+    fn check_embedding(g: &Graph<(), (), Undirected>) {
+        let embedding = planar_embedding(g).expect("planar");
+        let mut degree = vec![0; g.node_count()];
+        for a in g.node_indices() {
+            for (e, b) in embedding.rotation(a) {
+                let (s, t) = g.edge_endpoints(e).unwrap();
+                assert!((s, t) == (a, b) || (s, t) == (b, a));
+                degree[a.index()] += 1;
+            }
+            assert_eq!(degree[a.index()], g.edges(a).count() +
+                       g.edges(a).filter(|&(b, _)| b == a).count());
+        }
+        let mut sets = pg::unionfind::UnionFind::new(g.node_count());
+        for e in g.raw_edges() {
+            sets.union(e.source().index(), e.target().index());
+        }
+        let components = g.raw_edges().iter()
+                          .map(|e| sets.find(e.source().index()))
+                          .collect::<std::collections::HashSet<_>>();
+        let nodes = g.node_indices().filter(|&a| degree[a.index()] > 0).count();
+        let faces = embedding.faces().collect::<Vec<_>>();
+        assert_eq!(faces.len() + nodes, g.edge_count() + 2 * components.len());
+        assert_eq!(faces.iter().map(|f| f.len()).sum::<usize>(), 2 * g.edge_count());
+        for face in &faces {
+            // consecutive steps meet
+            for (i, &(a, e)) in face.iter().enumerate() {
+                let next = face[(i + 1) % face.len()].0;
+                let (s, t) = g.edge_endpoints(e).unwrap();
+                assert!((s, t) == (a, next) || (s, t) == (next, a));
+            }
+        }
+    }
+
+    let complete = |nodes: usize| {
+        let mut g = Graph::<(), (), Undirected>::new_undirected();
+        for _ in 0..nodes {
+            g.add_node(());
+        }
+        for i in 0..nodes {
+            for j in i + 1..nodes {
+                g.add_edge(n(i), n(j), ());
+            }
+        }
+        g
+    };
+    check_embedding(&complete(4));
+    let mut k5 = complete(5);
+    assert!(!is_planar(&k5));
+    k5.remove_edge(EdgeIndex::new(0));
+    check_embedding(&k5);
+
+    let k33 = Graph::<(), (), Undirected>::from_edges(&[
+        (0, 3), (0, 4), (0, 5), (1, 3), (1, 4), (1, 5), (2, 3), (2, 4), (2, 5),
+    ]);
+    assert!(!is_planar(&k33));
+    let petersen = Graph::<(), (), Undirected>::from_edges(&[
+        (0, 1), (1, 2), (2, 3), (3, 4), (4, 0),
+        (0, 5), (1, 6), (2, 7), (3, 8), (4, 9),
+        (5, 7), (7, 9), (9, 6), (6, 8), (8, 5),
+    ]);
+    assert!(!is_planar(&petersen));
+
+    // subgraphs of a triangulated grid, shuffled, with parallel edges and
+    // self loops, are planar
+    let mut rng = rand::weak_rng();
+    let side = 7;
+    for _ in 0..40 {
+        let mut label = (0..side * side).collect::<Vec<usize>>();
+        rng.shuffle(&mut label);
+        let mut edges = Vec::new();
+        for i in 0..side {
+            for j in 0..side {
+                let a = label[i * side + j];
+                if i + 1 < side {
+                    edges.push((a, label[(i + 1) * side + j]));
+                }
+                if j + 1 < side {
+                    edges.push((a, label[i * side + j + 1]));
+                }
+                if i + 1 < side && j + 1 < side {
+                    edges.push((a, label[(i + 1) * side + j + 1]));
+                }
+            }
+        }
+        rng.shuffle(&mut edges);
+        let keep = rng.gen_range(0, edges.len() + 1);
+        let mut g = Graph::<(), (), Undirected>::new_undirected();
+        for _ in 0..side * side {
+            g.add_node(());
+        }
+        for &(a, b) in &edges[..keep] {
+            g.add_edge(n(a), n(b), ());
+            if rng.gen_weighted_bool(10) {
+                g.add_edge(n(b), n(a), ());
+            }
+            if rng.gen_weighted_bool(20) {
+                g.add_edge(n(a), n(a), ());
+            }
+        }
+        check_embedding(&g);
+
+        // with a subdivided K3,3 attached, it is not
+        let base = g.node_count();
+        for _ in 0..6 {
+            g.add_node(());
+        }
+        for &(a, b) in k33.raw_edges().iter().map(|e| (e.source().index(), e.target().index())).collect::<Vec<_>>().iter() {
+            let middle = g.add_node(());
+            g.add_edge(n(base + a), middle, ());
+            g.add_edge(middle, n(base + b), ());
+        }
+        g.add_edge(n(0), n(base), ());
+        assert!(!is_planar(&g));
+    }
+
+    // planarity is kept by removing edges and lost for good by adding them
+    for _ in 0..200 {
+        let nodes = rng.gen_range(1, 9);
+        let mut g = Graph::<(), (), Undirected>::new_undirected();
+        for _ in 0..nodes {
+            g.add_node(());
+        }
+        for _ in 0..rng.gen_range(0, 20) {
+            let a = rng.gen_range(0, nodes);
+            let b = rng.gen_range(0, nodes);
+            g.add_edge(n(a), n(b), ());
+        }
+        if is_planar(&g) {
+            check_embedding(&g);
+            for e in 0..g.edge_count() {
+                let mut h = g.clone();
+                h.remove_edge(EdgeIndex::new(e));
+                assert!(is_planar(&h));
+            }
+        } else {
+            for a in 0..nodes {
+                for b in 0..nodes {
+                    let mut h = g.clone();
+                    h.add_edge(n(a), n(b), ());
+                    assert!(!is_planar(&h));
+                }
+            }
+        }
+    }
+    assert_eq!(planar_embedding(&Graph::<(), ()>::new()).unwrap().faces().count(), 0);
+
+    // a long cycle does not overflow the stack
+    let edges = (0..100_000).map(|i| (i, (i + 1) % 100_000)).collect::<Vec<_>>();
+    let cycle = Graph::<(), (), Undirected, u32>::from_edges(&edges);
+    assert_eq!(planar_embedding(&cycle).unwrap().faces().count(), 2);
+}
+
+#[test]
+fn dual_graph_faces() {
+    use petgraph::algo::{dual_graph, is_planar, planar_embedding};
+    use rand::Rng;
+
+    let mut rng = rand::weak_rng();
+    let side = 6;
+    for _ in 0..40 {
+        // a connected subgraph of a triangulated grid, with parallel edges
+        let mut edges = Vec::new();
+        for i in 0..side {
+            for j in 0..side {
+                let a = i * side + j;
+                if i + 1 < side {
+                    edges.push((a, a + side));
+                }
+                if j + 1 < side {
+                    edges.push((a, a + 1));
+                }
+                if i + 1 < side && j + 1 < side && rng.gen() {
+                    edges.push((a, a + side + 1));
+                }
+            }
+        }
+        rng.shuffle(&mut edges);
+        let mut g = Graph::<(), (), Undirected>::new_undirected();
+        for _ in 0..side * side {
+            g.add_node(());
+        }
+        let mut sets = pg::unionfind::UnionFind::new(side * side);
+        for &(a, b) in &edges {
+            if sets.union(a, b) || rng.gen_weighted_bool(3) {
+                g.add_edge(n(a), n(b), ());
+            }
+            if rng.gen_weighted_bool(15) {
+                g.add_edge(n(b), n(a), ());
+            }
+        }
+        // a pendant path of bridges
+        let tail = g.add_node(());
+        g.add_edge(n(0), tail, ());
+
+        let embedding = planar_embedding(&g).unwrap();
+        let faces = embedding.faces().collect::<Vec<_>>();
+        let dual = dual_graph(&embedding);
+        assert_eq!(dual.node_count(), faces.len());
+        assert_eq!(dual.edge_count(), g.edge_count());
+        for (i, face) in dual.node_indices().zip(&faces) {
+            assert_eq!(&dual[i], face);
+        }
+        for (e, edge) in dual.raw_edges().iter().enumerate() {
+            let e = EdgeIndex::new(e);
+            assert_eq!(edge.weight, e);
+            // the dual edge leaves the face walking the edge forward and
+            // enters the one walking it backward
+            let (s, t) = g.edge_endpoints(e).unwrap();
+            assert!(dual[edge.source()].contains(&(s, e)));
+            assert!(dual[edge.target()].contains(&(t, e)));
+            // exactly the bridges become self loops
+            let mut h = g.clone();
+            h.remove_edge(e);
+            let bridge = pg::algo::connected_components(&h) > 1;
+            assert_eq!(edge.source() == edge.target(), bridge);
+        }
+        // the dual of a connected planar graph is connected and planar
+        assert_eq!(pg::algo::connected_components(&dual), 1);
+        assert!(is_planar(&dual));
+    }
+}
+
+#[test]
+fn dataflow_fixpoint() {
+    use petgraph::algo::fixpoint;
+    use rand::Rng;
+
+    // the nodes that reach `start` along `dir`, as a bit set
+    fn reach(gr: &Graph<(), ()>, start: NodeIndex, dir: pg::EdgeDirection) -> u64 {
+        let mut seen = 1 << start.index();
+        let mut stack = vec![start];
+        while let Some(a) = stack.pop() {
+            for b in gr.neighbors_directed(a, dir) {
+                if seen & 1 << b.index() == 0 {
+                    seen |= 1 << b.index();
+                    stack.push(b);
+                }
+            }
+        }
+        seen
+    }
+
+    let mut rng = rand::weak_rng();
+    for _ in 0..100 {
+        let nodes = rng.gen_range(1, 64);
+        let mut gr = Graph::<(), ()>::new();
+        for _ in 0..nodes {
+            gr.add_node(());
+        }
+        let acyclic: bool = rng.gen();
+        for _ in 0..rng.gen_range(0, 2 * nodes) {
+            let a = rng.gen_range(0, nodes);
+            let b = rng.gen_range(0, nodes);
+            if !acyclic || a < b {
+                gr.add_edge(n(a), n(b), ());
+            }
+        }
+
+        let mut transfers = 0;
+        let forward = fixpoint(&gr, Outgoing, 0u64, |a, b| a | b, |n, input| {
+            transfers += 1;
+            input | 1 << n.index()
+        });
+        if acyclic {
+            assert_eq!(transfers, nodes);
+        }
+        let backward = fixpoint(&gr, Incoming, 0u64, |a, b| a | b, |n, input| input | 1 << n.index());
+        for a in gr.node_indices() {
+            assert_eq!(forward.output[a.index()], reach(&gr, a, Incoming));
+            assert_eq!(backward.output[a.index()], reach(&gr, a, Outgoing));
+            let input = gr.neighbors_directed(a, Incoming)
+                          .fold(0, |s, b| s | forward.output[b.index()]);
+            assert_eq!(forward.input[a.index()], input);
+        }
+    }
+}
+
+#[test]
+fn reachability_index() {
+    use petgraph::algo::Reachability;
+    use rand::Rng;
+
+    let mut rng = rand::weak_rng();
+    for _ in 0..100 {
+        let nodes = rng.gen_range(1, 40);
+        let mut gr = Graph::<(), ()>::new();
+        for _ in 0..nodes {
+            gr.add_node(());
+        }
+        let acyclic: bool = rng.gen();
+        for _ in 0..rng.gen_range(0, 2 * nodes) {
+            let a = rng.gen_range(0, nodes);
+            let b = rng.gen_range(0, nodes);
+            if !acyclic || a < b {
+                gr.add_edge(n(a), n(b), ());
+            }
+        }
+
+        let reach = Reachability::new(&gr);
+        assert_eq!(reach.node_count(), nodes);
+        for a in gr.node_indices() {
+            let mut seen = vec![false; nodes];
+            let mut dfs = Dfs::new(&gr, a);
+            while let Some(b) = dfs.next(&gr) {
+                seen[b.index()] = true;
+            }
+            for b in gr.node_indices() {
+                assert_eq!(reach.can_reach(a, b), seen[b.index()]);
+            }
+        }
+    }
+
+    // a path needs a single chain
+    let path = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (0, 2)]);
+    assert_eq!(Reachability::new(&path).chain_count(), 1);
+}
+
+#[test]
+fn ancestors_descendants() {
+    use petgraph::algo::{ancestors, descendants, descendants_of_set};
+    use rand::Rng;
+
+    let mut rng = rand::weak_rng();
+    for _ in 0..50 {
+        let nodes = rng.gen_range(1, 30);
+        let mut gr = Graph::<(), ()>::new();
+        for _ in 0..nodes {
+            gr.add_node(());
+        }
+        for _ in 0..rng.gen_range(0, 2 * nodes) {
+            let a = rng.gen_range(0, nodes);
+            let b = rng.gen_range(0, nodes);
+            gr.add_edge(n(a), n(b), ());
+        }
+
+        // b descends from a if a successor of a reaches it
+        let reach = |a: NodeIndex, b: NodeIndex| {
+            gr.neighbors(a).any(|s| {
+                let mut dfs = Dfs::new(&gr, s);
+                while let Some(c) = dfs.next(&gr) {
+                    if c == b {
+                        return true
+                    }
+                }
+                false
+            })
+        };
+        for a in gr.node_indices() {
+            let below = descendants(&gr, a);
+            let above = ancestors(&gr, a);
+            assert_eq!(above, descendants(&Reversed(&gr), a));
+            for b in gr.node_indices() {
+                assert_eq!(below.contains(b.index()), reach(a, b));
+                assert_eq!(above.contains(b.index()), reach(b, a));
+            }
+        }
+
+        let set = [n(0), n(nodes / 2)];
+        let mut union = descendants(&gr, set[0]);
+        for i in descendants(&gr, set[1]).ones() {
+            union.insert(i);
+        }
+        assert_eq!(descendants_of_set(&gr, set.iter().cloned()), union);
+    }
+
+    // a GraphMap gives a HashSet
+    let gm = GraphMap::<_, ()>::from_edges(&[("a", "b"), ("b", "c")]);
+    let below = descendants(&gm, "a");
+    assert_eq!(below.len(), 3);
+    assert!(below.contains("a") && below.contains("c"));
+}
+
+#[test]
+fn path_connecting() {
+    use petgraph::algo::{has_path_connecting, DfsSpace, Reachability};
+    use rand::Rng;
+
+    let mut rng = rand::weak_rng();
+    let mut gr = Graph::<(), ()>::new();
+    // the workspace grows with the graph
+    let mut space = DfsSpace::new(&gr);
+    for _ in 0..30 {
+        for _ in 0..rng.gen_range(1, 4) {
+            gr.add_node(());
+        }
+        let nodes = gr.node_count();
+        for _ in 0..rng.gen_range(0, 4) {
+            let a = rng.gen_range(0, nodes);
+            let b = rng.gen_range(0, nodes);
+            gr.add_edge(n(a), n(b), ());
+        }
+
+        let reach = Reachability::new(&gr);
+        for a in gr.node_indices() {
+            for b in gr.node_indices() {
+                let connected = has_path_connecting(&gr, a, b, Some(&mut space));
+                assert_eq!(connected, reach.can_reach(a, b));
+                assert_eq!(connected, has_path_connecting(&gr, a, b, None));
+                assert_eq!(connected, has_path_connecting(&Reversed(&gr), b, a, None));
+            }
+        }
+    }
+}
+
+#[test]
+fn disjoint_paths() {
+    use petgraph::EdgeType;
+    use petgraph::algo::{edge_disjoint_paths, has_path_connecting, vertex_disjoint_paths};
+    use rand::Rng;
+
+    // check that `paths` are simple paths from a to b, and return how often
+    // each node and edge is used
+    fn check_paths<Ty: EdgeType>(g: &Graph<(), (), Ty>, a: NodeIndex, b: NodeIndex,
+                                 paths: &[Vec<EdgeIndex>]) -> (Vec<usize>, Vec<usize>) {
+        let mut node_use = vec![0; g.node_count()];
+        let mut edge_use = vec![0; g.edge_count()];
+        for path in paths {
+            let mut seen = vec![false; g.node_count()];
+            let mut x = a;
+            seen[a.index()] = true;
+            for &e in path {
+                edge_use[e.index()] += 1;
+                let (s, t) = g.edge_endpoints(e).unwrap();
+                x = if s == x { t } else {
+                    assert!(!g.is_directed() && t == x);
+                    s
+                };
+                assert!(!seen[x.index()]);
+                seen[x.index()] = true;
+                node_use[x.index()] += 1;
+            }
+            assert_eq!(x, b);
+        }
+        (node_use, edge_use)
+    }
+
+    fn connected_without<Ty: EdgeType>(g: &Graph<(), (), Ty>, a: NodeIndex, b: NodeIndex,
+                                       nodes: usize, edges: usize) -> bool {
+        let h = g.filter_map(|x, _| if nodes & 1 << x.index() == 0 { Some(()) } else { None },
+                             |e, _| if edges & 1 << e.index() == 0 { Some(()) } else { None });
+        // only nodes other than a and b are removed, so their indices stay
+        let index = |x: NodeIndex| n((0..x.index()).filter(|i| nodes & 1 << i == 0).count());
+        has_path_connecting(&h, index(a), index(b), None)
+    }
+
+    fn check<Ty: EdgeType>(g: &Graph<(), (), Ty>, a: NodeIndex, b: NodeIndex) {
+        let paths = edge_disjoint_paths(g, a, b);
+        let (_, edge_use) = check_paths(g, a, b, &paths);
+        assert!(edge_use.iter().all(|&u| u <= 1));
+        // the smallest edge cut, by brute force
+        let min_cut = (0..1usize << g.edge_count())
+            .filter(|&cut| !connected_without(g, a, b, 0, cut))
+            .map(|cut| cut.count_ones() as usize)
+            .min().unwrap();
+        assert_eq!(paths.len(), min_cut);
+
+        let paths = vertex_disjoint_paths(g, a, b);
+        let (node_use, _) = check_paths(g, a, b, &paths);
+        for x in g.node_indices() {
+            if x != b {
+                assert!(node_use[x.index()] <= 1);
+            }
+        }
+        // direct edges are paths of their own, the rest is cut at nodes
+        let direct = g.raw_edges().iter().filter(|e| {
+            (e.source(), e.target()) == (a, b) || !g.is_directed() && (e.source(), e.target()) == (b, a)
+        }).count();
+        let direct_edges = g.raw_edges().iter().enumerate().filter(|&(_, e)| {
+            e.source() == a && e.target() == b || e.source() == b && e.target() == a
+        }).fold(0, |set, (i, _)| set | 1 << i);
+        let min_cut = (0..1usize << g.node_count())
+            .filter(|&cut| cut & (1 << a.index() | 1 << b.index()) == 0)
+            .filter(|&cut| !connected_without(g, a, b, cut, direct_edges))
+            .map(|cut| cut.count_ones() as usize)
+            .min().unwrap();
+        assert_eq!(paths.len(), min_cut + direct);
+    }
+
+    let mut rng = rand::weak_rng();
+    for _ in 0..100 {
+        let nodes = rng.gen_range(2, 7);
+        let mut edges = Vec::new();
+        for _ in 0..rng.gen_range(0, 11) {
+            edges.push((rng.gen_range(0, nodes), rng.gen_range(0, nodes)));
+        }
+        let mut directed = Graph::<(), ()>::new();
+        let mut undirected = Graph::<(), (), Undirected>::new_undirected();
+        for _ in 0..nodes {
+            directed.add_node(());
+            undirected.add_node(());
+        }
+        for &(x, y) in &edges {
+            directed.add_edge(n(x), n(y), ());
+            undirected.add_edge(n(x), n(y), ());
+        }
+        let a = rng.gen_range(0, nodes);
+        let b = (a + rng.gen_range(1, nodes)) % nodes;
+        check(&directed, n(a), n(b));
+        check(&undirected, n(a), n(b));
+    }
+
+    // no paths from a node to itself
+    let triangle = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2), (2, 0)]);
+    assert!(edge_disjoint_paths(&triangle, n(0), n(0)).is_empty());
+    assert!(vertex_disjoint_paths(&triangle, n(0), n(0)).is_empty());
+}
+
+#[test]
+fn connectivity() {
+    use petgraph::EdgeType;
+    use petgraph::algo::{edge_connectivity, is_strongly_connected, local_edge_connectivity,
+                         local_node_connectivity, node_connectivity};
+    use rand::Rng;
+
+    fn check<Ty: EdgeType>(g: &Graph<(), (), Ty>) {
+        // the smallest cuts, by brute force
+        let edge_cut = (0..1usize << g.edge_count())
+            .filter(|&cut| {
+                let h = g.filter_map(|_, _| Some(()),
+                                     |e, _| if cut & 1 << e.index() == 0 { Some(()) } else { None });
+                !is_strongly_connected(&h)
+            })
+            .map(|cut| cut.count_ones() as usize)
+            .min();
+        let expected = if g.node_count() < 2 { 0 } else { edge_cut.unwrap() };
+        assert_eq!(edge_connectivity(g), expected);
+
+        let node_cut = (0..1usize << g.node_count())
+            .filter(|&cut| {
+                let h = g.filter_map(|x, _| if cut & 1 << x.index() == 0 { Some(()) } else { None },
+                                     |_, _| Some(()));
+                h.node_count() <= 1 || !is_strongly_connected(&h)
+            })
+            .map(|cut| cut.count_ones() as usize)
+            .min();
+        let expected = if g.node_count() == 0 { 0 } else { node_cut.unwrap() };
+        assert_eq!(node_connectivity(g), expected);
+
+        // the global values are the least local ones
+        for a in g.node_indices() {
+            for b in g.node_indices() {
+                if a != b {
+                    assert!(local_edge_connectivity(g, a, b) >= edge_connectivity(g));
+                    if g.find_edge(a, b).is_none() {
+                        assert!(local_node_connectivity(g, a, b) >= node_connectivity(g));
+                    }
+                }
+            }
+        }
+    }
+
+    let mut rng = rand::weak_rng();
+    for _ in 0..100 {
+        let nodes = rng.gen_range(0, 7);
+        let mut directed = Graph::<(), ()>::new();
+        let mut undirected = Graph::<(), (), Undirected>::new_undirected();
+        for _ in 0..nodes {
+            directed.add_node(());
+            undirected.add_node(());
+        }
+        if nodes > 0 {
+            for _ in 0..rng.gen_range(0, 13) {
+                let (x, y) = (rng.gen_range(0, nodes), rng.gen_range(0, nodes));
+                directed.add_edge(n(x), n(y), ());
+                undirected.add_edge(n(x), n(y), ());
+            }
+        }
+        check(&directed);
+        check(&undirected);
+    }
+
+    // complete graphs
+    for nodes in 1..7 {
+        let mut g = Graph::<(), (), Undirected>::new_undirected();
+        for _ in 0..nodes {
+            g.add_node(());
+        }
+        for a in 0..nodes {
+            for b in a + 1..nodes {
+                g.add_edge(n(a), n(b), ());
+            }
+        }
+        assert_eq!(node_connectivity(&g), nodes - 1);
+        assert_eq!(edge_connectivity(&g), nodes - 1);
+        for a in g.node_indices() {
+            assert_eq!(local_node_connectivity(&g, a, a), 0);
+            assert_eq!(local_edge_connectivity(&g, a, a), 0);
+        }
+    }
+}
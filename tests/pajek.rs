@@ -0,0 +1,69 @@
+#![cfg(feature = "pajek")]
+
+extern crate petgraph;
+
+use petgraph::{
+    Graph,
+    Undirected,
+};
+use petgraph::io::pajek::{
+    Pajek,
+    parse,
+};
+
+fn edge_list(g: &Graph<String, f64>) -> Vec<(&str, &str, f64)> {
+    g.raw_edges().iter().map(|e| {
+        (&g[e.source()][..], &g[e.target()][..], e.weight)
+    }).collect()
+}
+
+#[test]
+fn round_trip() {
+    let mut gr = Graph::<_, _, Undirected>::new_undirected();
+    let a = gr.add_node("Ann Lee");
+    let b = gr.add_node("Bob");
+    let c = gr.add_node("Cy");
+    gr.add_edge(a, b, 1.5);
+    gr.add_edge(b, c, -2.);
+    gr.add_edge(c, c, 3.);
+
+    let output = Pajek::new(&gr).to_string();
+    assert!(output.contains("*Edges\n"));
+    let parsed = parse(&output).unwrap();
+    assert_eq!(parsed.node_count(), 3);
+    assert_eq!(edge_list(&parsed),
+               vec![("Ann Lee", "Bob", 1.5), ("Bob", "Cy", -2.), ("Cy", "Cy", 3.)]);
+}
+
+#[test]
+fn lists_and_attributes() {
+    let g = parse("% a comment
+*vertices 4
+1 Ann 0.1 0.2 0.5 ic Red
+3 \"C \"
+*arcslist
+1 2 3
+*Matrix
+0 1 0 0
+*Edges
+4 2 2.5 c Blue
+").unwrap();
+    assert_eq!(edge_list(&g),
+               vec![("Ann", "2", 1.), ("Ann", "C ", 1.), ("4", "2", 2.5)]);
+}
+
+#[test]
+fn errors() {
+    let err = parse("*Vertices 2\n1 \"a\"\n*Arcs\n1 3\n").unwrap_err();
+    assert_eq!(err.line(), 4);
+    assert!(parse("1 2\n").is_err());
+    assert!(parse("*Vertices x\n").is_err());
+    assert!(parse("*Vertices 2\n1 \"a\n").is_err());
+    assert!(parse("*Vertices 2\n*Arcs\n1\n").is_err());
+    assert!(parse("*Vertices 2\n*Arcs\n1 2 heavy\n").is_err());
+    // more vertices than u32 indices, rejected before adding any
+    assert_eq!(parse("*Vertices 99999999999\n").unwrap_err().line(), 1);
+    // more vertices than the input can list
+    assert_eq!(parse("*Network x\n*Vertices 4000000000\n").unwrap_err().line(), 2);
+    assert_eq!(parse("*Vertices 1000\n*Edges\n1 1000\n").unwrap().node_count(), 1000);
+}
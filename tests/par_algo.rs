@@ -0,0 +1,106 @@
+extern crate petgraph;
+extern crate rand;
+
+use rand::Rng;
+
+use petgraph::{
+    Graph,
+    Undirected,
+};
+use petgraph::algo::dijkstra;
+#[cfg(feature = "rayon")]
+use petgraph::algo::connected_components;
+use petgraph::graph::NodeIndex;
+use petgraph::par_algo::delta_stepping;
+#[cfg(feature = "rayon")]
+use petgraph::par_algo::{
+    par_all_pairs_shortest_paths,
+    par_betweenness_centrality,
+    par_connected_components,
+};
+
+fn random_graph<R: Rng>(rng: &mut R, n: usize, m: usize) -> Graph<(), u32> {
+    let mut g = Graph::with_capacity(n, m);
+    for _ in 0..n {
+        g.add_node(());
+    }
+    for _ in 0..m {
+        let a = NodeIndex::new(rng.gen_range(0, n));
+        let b = NodeIndex::new(rng.gen_range(0, n));
+        g.add_edge(a, b, rng.gen_range(0, 10));
+    }
+    g
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn betweenness_star() {
+    // the center of a star lies on the path between every pair of leaves
+    let g = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (0, 2), (0, 3), (0, 4)]);
+    assert_eq!(par_betweenness_centrality(&g, false), [6., 0., 0., 0., 0.]);
+    assert_eq!(par_betweenness_centrality(&g, true), [1., 0., 0., 0., 0.]);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn betweenness_directed() {
+    // two shortest paths from 0 to 3, through 1 and through 2
+    let g = Graph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 3), (2, 3), (3, 4)]);
+    assert_eq!(par_betweenness_centrality(&g, false), [0., 1., 1., 3., 0.]);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn all_pairs_matches_dijkstra() {
+    let mut rng = rand::weak_rng();
+    let g = random_graph(&mut rng, 40, 120);
+    let paths = par_all_pairs_shortest_paths(&g, |w| *w);
+    assert_eq!(paths.len(), g.node_count());
+    for a in g.node_indices() {
+        let scores = dijkstra(&g, a, None, |g, n| g.edges(n).map(|(b, w)| (b, *w)));
+        assert_eq!(paths[a.index()], scores);
+    }
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn components_match_serial() {
+    let mut rng = rand::weak_rng();
+    for &m in &[0, 10, 30, 60, 200] {
+        let g = random_graph(&mut rng, 100, m);
+        assert_eq!(par_connected_components(&g), connected_components(&g));
+    }
+}
+
+#[test]
+fn delta_stepping_matches_dijkstra() {
+    let mut rng = rand::weak_rng();
+    for &delta in &[0.5, 3., 100.] {
+        let g = random_graph(&mut rng, 60, 200);
+        let a = NodeIndex::new(0);
+        let costs = delta_stepping(&g, a, delta, |w| *w as f64);
+        let scores = dijkstra(&g, a, None, |g, n| g.edges(n).map(|(b, w)| (b, *w as f64)));
+        for b in g.node_indices() {
+            match scores.get(&b) {
+                Some(&score) => assert_eq!(costs[b.index()], score),
+                None => assert!(costs[b.index()].is_infinite()),
+            }
+        }
+    }
+}
+
+#[test]
+fn delta_stepping_undirected() {
+    let g = Graph::<(), f64, Undirected>::from_edges(&[(0, 1, 1.), (1, 2, 5.), (2, 3, 1.), (0, 3, 9.)]);
+    assert_eq!(delta_stepping(&g, NodeIndex::new(3), 2., |w| *w), [7., 6., 1., 0.]);
+}
+
+#[test]
+fn delta_stepping_huge_costs() {
+    // bucket indices far apart, past the range of usize, and infinite
+    let g = Graph::<(), f64>::from_edges(&[
+        (0, 1, 1e11), (1, 2, 1.), (0, 3, 1e300), (3, 4, 1e300), (0, 5, f64::INFINITY),
+    ]);
+    let costs = delta_stepping(&g, NodeIndex::new(0), 1., |w| *w);
+    assert_eq!(costs, [0., 1e11, 1e11 + 1., 1e300, 2e300, f64::INFINITY]);
+}
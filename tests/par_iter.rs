@@ -0,0 +1,48 @@
+#![cfg(feature = "rayon")]
+
+extern crate petgraph;
+extern crate rayon;
+
+use rayon::prelude::*;
+
+use petgraph::{
+    Graph,
+    GraphMap,
+};
+use petgraph::graph::NodeIndex;
+
+#[test]
+fn graph_par_iters() {
+    let mut g = Graph::<u32, u32>::new();
+    for i in 0..100 {
+        g.add_node(i);
+    }
+    for i in 0..99 {
+        g.add_edge(NodeIndex::new(i), NodeIndex::new(i + 1), i as u32);
+    }
+
+    let indices: Vec<_> = g.par_node_indices().collect();
+    assert_eq!(indices, g.node_indices().collect::<Vec<_>>());
+    assert_eq!(g.par_node_indices().len(), 100);
+
+    let weights: Vec<_> = g.par_node_weights().map(|&w| w * 2).collect();
+    assert_eq!(weights, (0..100).map(|w| w * 2).collect::<Vec<_>>());
+
+    let edges: Vec<_> = g.par_edge_references()
+                         .map(|(i, e)| (i, e.source(), e.target(), e.weight))
+                         .collect();
+    assert_eq!(edges.len(), 99);
+    for (i, a, b, w) in edges {
+        assert_eq!(g.edge_endpoints(i), Some((a, b)));
+        assert_eq!(g[i], w);
+    }
+}
+
+#[test]
+fn graphmap_par_nodes() {
+    let g = GraphMap::<u32, ()>::from_edges(&[(1, 2), (2, 3), (7, 8)]);
+    let mut nodes: Vec<_> = g.par_nodes().collect();
+    nodes.sort();
+    assert_eq!(nodes, [1, 2, 3, 7, 8]);
+    assert_eq!(g.par_nodes().map(|n| g.neighbors(n).count()).sum::<usize>(), 6);
+}
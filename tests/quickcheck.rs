@@ -657,3 +657,58 @@ fn sub_topo() {
     }
     quickcheck::quickcheck(prop as fn(_) -> bool);
 }
+
+#[test]
+fn shrink_removes_nodes_and_edges() {
+    use quickcheck::Arbitrary;
+
+    fn prop(g: Graph<(), u32>) -> bool {
+        let size = g.node_count() + g.edge_count();
+        let shrunk = g.shrink().collect::<Vec<_>>();
+        // one candidate for each node and each edge, and no larger graphs
+        shrunk.len() >= size &&
+            shrunk.iter().all(|h| h.node_count() + h.edge_count() < size)
+    }
+    quickcheck::quickcheck(prop as fn(_) -> bool);
+
+    #[cfg(feature = "stable_graph")]
+    fn prop_stable(g: StableGraph<(), u32>) -> bool {
+        let size = g.node_count() + g.edge_count();
+        let shrunk = g.shrink().collect::<Vec<_>>();
+        shrunk.len() == size &&
+            shrunk.iter().all(|h| h.node_count() + h.edge_count() < size)
+    }
+    #[cfg(feature = "stable_graph")]
+    quickcheck::quickcheck(prop_stable as fn(_) -> bool);
+
+    fn prop_map(g: GraphMap<i8, ()>) -> bool {
+        let size = g.node_count() + g.edge_count();
+        let shrunk = g.shrink().collect::<Vec<_>>();
+        shrunk.len() == size &&
+            shrunk.iter().all(|h| h.node_count() + h.edge_count() < size)
+    }
+    quickcheck::quickcheck(prop_map as fn(_) -> bool);
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn shrink_stable_graph_parallel_edges() {
+    use quickcheck::Arbitrary;
+
+    let mut g = StableGraph::<(), u32>::new();
+    let a = g.add_node(());
+    let b = g.add_node(());
+    for w in 0..4 {
+        g.add_edge(a, b, w);
+    }
+    g.remove_edge(edge_index(1));
+    // each edge, parallel or not, is dropped by exactly one candidate
+    let mut dropped = g.shrink()
+                       .filter(|h| h.node_count() == 2)
+                       .map(|h| (0..4).filter(|&i| g.edge_weight(edge_index(i)).is_some() &&
+                                               h.edge_weight(edge_index(i)).is_none())
+                                      .collect::<Vec<_>>())
+                       .collect::<Vec<_>>();
+    dropped.sort();
+    assert_eq!(dropped, vec![vec![0], vec![2], vec![3]]);
+}
@@ -0,0 +1,192 @@
+extern crate petgraph;
+extern crate rand;
+
+use rand::Rng;
+
+use petgraph::{
+    Graph,
+    Undirected,
+};
+use petgraph::algo::{
+    is_connected,
+    is_tree,
+    spqr_tree,
+    SkeletonEdge,
+    SpqrKind,
+    SpqrNode,
+};
+use petgraph::graph::{
+    node_index as n,
+    NodeIndex,
+};
+
+type Tree = Graph<SpqrNode<u32>, (NodeIndex, NodeIndex), Undirected>;
+
+/// Return `true` if removing any two nodes leaves the skeleton connected.
+fn is_triconnected(skeleton: &[(NodeIndex, NodeIndex, SkeletonEdge<u32>)]) -> bool {
+    let mut nodes = skeleton.iter().flat_map(|&(a, b, _)| vec![a, b]).collect::<Vec<_>>();
+    nodes.sort();
+    nodes.dedup();
+    let local = |a: NodeIndex| NodeIndex::new(nodes.binary_search(&a).unwrap());
+    for x in 0..nodes.len() {
+        for y in x + 1..nodes.len() {
+            let mut h = Graph::<(), (), Undirected>::new_undirected();
+            for _ in &nodes {
+                h.add_node(());
+            }
+            for &(a, b, _) in skeleton {
+                let (a, b) = (local(a), local(b));
+                if a.index() != x && a.index() != y && b.index() != x && b.index() != y {
+                    h.add_edge(a, b, ());
+                }
+            }
+            h.remove_node(n(y));
+            h.remove_node(n(x));
+            if !is_connected(&h) {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+fn assert_valid_tree(g: &Graph<(), (), Undirected>, tree: &Tree) {
+    assert!(is_tree(tree));
+    let mut real = vec![0; g.edge_count()];
+    let mut virtual_owners = vec![Vec::new(); tree.edge_count()];
+    for t in tree.node_indices() {
+        let node = &tree[t];
+        for &(a, b, edge) in &node.skeleton {
+            match edge {
+                SkeletonEdge::Real(e) => {
+                    real[e.index()] += 1;
+                    let (s, u) = g.edge_endpoints(e).unwrap();
+                    assert!((s, u) == (a, b) || (u, s) == (a, b));
+                }
+                SkeletonEdge::Virtual(e) => {
+                    virtual_owners[e.index()].push(t);
+                    let (s, u) = tree[e];
+                    assert!((s, u) == (a, b) || (u, s) == (a, b));
+                }
+            }
+        }
+        match node.kind {
+            SpqrKind::Series => {
+                assert!(node.skeleton.len() >= 3);
+                let len = node.skeleton.len();
+                for (i, &(_, b, _)) in node.skeleton.iter().enumerate() {
+                    assert_eq!(b, node.skeleton[(i + 1) % len].0);
+                }
+            }
+            SpqrKind::Parallel => {
+                let (a, b, _) = node.skeleton[0];
+                assert!(node.skeleton.iter().all(|&(x, y, _)| (x, y) == (a, b) || (y, x) == (a, b)));
+            }
+            SpqrKind::Rigid => {
+                assert!(node.skeleton.len() >= 6);
+                assert!(is_triconnected(&node.skeleton));
+            }
+        }
+    }
+    assert!(real.iter().all(|&count| count == 1));
+    for (i, owners) in virtual_owners.iter().enumerate() {
+        let (s, t) = tree.edge_endpoints(petgraph::graph::edge_index(i)).unwrap();
+        assert!(owners == &[s, t] || owners == &[t, s]);
+        let (ks, kt) = (tree[s].kind, tree[t].kind);
+        assert!(ks == SpqrKind::Rigid || ks != kt, "adjacent {:?} nodes", ks);
+    }
+}
+
+fn kinds(tree: &Tree) -> Vec<SpqrKind> {
+    let mut kinds = tree.node_indices().map(|t| tree[t].kind).collect::<Vec<_>>();
+    kinds.sort_by_key(|&k| k as u8);
+    kinds
+}
+
+#[test]
+fn spqr_not_biconnected() {
+    let g = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2), (2, 0), (2, 3)]);
+    assert!(spqr_tree(&g).is_none());
+    let mut g = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2), (2, 0)]);
+    g.add_node(());
+    assert!(spqr_tree(&g).is_none());
+    let g = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2), (2, 0), (1, 1)]);
+    assert!(spqr_tree(&g).is_none());
+    assert!(spqr_tree(&Graph::<(), ()>::new()).is_none());
+}
+
+#[test]
+fn spqr_cycle() {
+    let g = Graph::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 4), (4, 0)]);
+    let tree = spqr_tree(&g).unwrap();
+    assert_valid_tree(&g, &tree);
+    assert_eq!(kinds(&tree), [SpqrKind::Series]);
+    assert_eq!(tree[n(0)].skeleton.len(), 5);
+}
+
+#[test]
+fn spqr_rigid() {
+    let g = Graph::from_edges(&[(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)]);
+    let tree = spqr_tree(&g).unwrap();
+    assert_valid_tree(&g, &tree);
+    assert_eq!(kinds(&tree), [SpqrKind::Rigid]);
+}
+
+#[test]
+fn spqr_theta() {
+    // three paths of length two between 0 and 1, and a double edge 0 = 1
+    let g = Graph::from_edges(&[(0, 2), (2, 1), (0, 3), (3, 1), (0, 4), (4, 1), (0, 1), (1, 0)]);
+    let tree = spqr_tree(&g).unwrap();
+    assert_valid_tree(&g, &tree);
+    assert_eq!(kinds(&tree), [SpqrKind::Series, SpqrKind::Series, SpqrKind::Series,
+                              SpqrKind::Parallel]);
+    let p = tree.node_indices().find(|&t| tree[t].kind == SpqrKind::Parallel).unwrap();
+    assert_eq!(tree[p].skeleton.len(), 5);
+    assert_eq!(tree.neighbors(p).count(), 3);
+}
+
+#[test]
+fn spqr_two_rigid() {
+    // two K4 sharing the edge 2 - 3, with a path from 0 to 5 that ties
+    // them together into one rigid component
+    let g = Graph::from_edges(&[(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3),
+                                (2, 4), (2, 5), (3, 4), (3, 5), (4, 5),
+                                (0, 6), (6, 7), (7, 5)]);
+    let tree = spqr_tree(&g).unwrap();
+    assert_valid_tree(&g, &tree);
+    assert_eq!(kinds(&tree), [SpqrKind::Series, SpqrKind::Rigid]);
+    let r = tree.node_indices().find(|&t| tree[t].kind == SpqrKind::Rigid).unwrap();
+    assert_eq!(tree[r].skeleton.len(), 12);
+
+    // without the way round, the K4 are split at 2 and 3
+    let g = Graph::from_edges(&[(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3),
+                                (2, 4), (2, 5), (3, 4), (3, 5), (4, 5)]);
+    let tree = spqr_tree(&g).unwrap();
+    assert_valid_tree(&g, &tree);
+    assert_eq!(kinds(&tree), [SpqrKind::Parallel, SpqrKind::Rigid, SpqrKind::Rigid]);
+}
+
+#[test]
+fn spqr_random() {
+    let mut rng = rand::weak_rng();
+    let mut checked = 0;
+    while checked < 60 {
+        let nodes = rng.gen_range(3, 12);
+        let edges = rng.gen_range(nodes, 3 * nodes);
+        let mut g = Graph::<(), (), Undirected>::with_capacity(nodes, edges);
+        for _ in 0..nodes {
+            g.add_node(());
+        }
+        for _ in 0..edges {
+            let a = rng.gen_range(0, nodes);
+            let b = rng.gen_range(0, nodes);
+            if a != b {
+                g.add_edge(n(a), n(b), ());
+            }
+        }
+        if let Some(tree) = spqr_tree(&g) {
+            assert_valid_tree(&g, &tree);
+            checked += 1;
+        }
+    }
+}
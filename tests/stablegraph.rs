@@ -2,7 +2,20 @@
 
 extern crate petgraph;
 
+use petgraph::{
+    Bfs,
+    Dfs,
+    Graph,
+    Incoming,
+    Outgoing,
+};
+use petgraph::algo::{
+    connected_components,
+    is_cyclic_directed,
+    toposort,
+};
 use petgraph::graph::stable::StableGraph;
+use petgraph::visit::NodeIndexable;
 
 #[test]
 fn node_indices() {
@@ -16,3 +29,157 @@ fn node_indices() {
     assert_eq!(iter.next(), Some(c));
     assert_eq!(iter.next(), None);
 }
+
+#[test]
+fn graph_conversions() {
+    let mut g = Graph::new();
+    let a = g.add_node("a");
+    let b = g.add_node("b");
+    let c = g.add_node("c");
+    let ab = g.add_edge(a, b, 1);
+    let bc = g.add_edge(b, c, 2);
+
+    let mut sg = StableGraph::from(g);
+    assert_eq!(sg.node_count(), 3);
+    assert_eq!(sg.edge_count(), 2);
+    assert_eq!(sg[a], "a");
+    assert_eq!(sg[bc], 2);
+    assert_eq!(sg.edge_endpoints(ab), Some((a, b)));
+
+    sg.remove_node(a);
+    let d = sg.add_node("d");
+    sg.add_edge(c, d, 3);
+    sg.remove_node(b);
+
+    // indices are compacted, relative order is kept
+    let g: Graph<_, _> = sg.into();
+    assert_eq!(g.node_count(), 2);
+    assert_eq!(g.edge_count(), 1);
+    let names = g.node_indices().map(|i| g[i]).collect::<Vec<_>>();
+    assert_eq!(names, vec!["d", "c"]);
+    let edge = &g.raw_edges()[0];
+    assert_eq!((g[edge.source()], g[edge.target()], edge.weight), ("c", "d", 3));
+}
+
+#[test]
+fn graph_algorithms() {
+    let mut g = StableGraph::<_, _>::new();
+    let a = g.add_node("a");
+    let b = g.add_node("b");
+    let c = g.add_node("c");
+    let d = g.add_node("d");
+    g.add_edge(b, c, ());
+    g.add_edge(a, b, ());
+    g.add_edge(a, c, ());
+
+    assert_eq!(g.externals(Incoming).collect::<Vec<_>>(), vec![a, d]);
+    assert_eq!(g.externals(Outgoing).collect::<Vec<_>>(), vec![c, d]);
+    assert_eq!(connected_components(&g), 2);
+    assert!(!is_cyclic_directed(&g));
+    let order = toposort(&g);
+    assert_eq!(order.len(), 4);
+    let pos = |n| order.iter().position(|&x| x == n).unwrap();
+    assert!(pos(a) < pos(b));
+    assert!(pos(b) < pos(c));
+
+    let ca = g.add_edge(c, a, ());
+    assert!(is_cyclic_directed(&g));
+    g.remove_edge(ca);
+    assert!(!is_cyclic_directed(&g));
+}
+
+#[test]
+fn dfs_with_holes() {
+    let mut g = StableGraph::<_, ()>::new();
+    let a = g.add_node(0);
+    let b = g.add_node(1);
+    let c = g.add_node(2);
+    let d = g.add_node(3);
+    g.add_edge(a, d, ());
+    g.add_edge(d, c, ());
+    g.remove_node(b);
+    assert_eq!(g.node_count(), 3);
+    assert_eq!(g.node_bound(), 4);
+    assert_eq!(NodeIndexable::node_bound(&g), 4);
+
+    let mut dfs = Dfs::new(&g, a);
+    let mut visited = Vec::new();
+    while let Some(nx) = dfs.next(&g) {
+        visited.push(nx);
+    }
+    assert_eq!(visited, vec![a, d, c]);
+
+    g.remove_node(d);
+    assert_eq!(g.node_bound(), 3);
+    assert_eq!(Bfs::new(&g, c).next(&g), Some(c));
+}
+
+#[test]
+fn property_maps() {
+    use petgraph::property::{EdgeMap, NodeMap};
+
+    let mut gr = StableGraph::<_, _>::new();
+    let a = gr.add_node("a");
+    let b = gr.add_node("b");
+    let c = gr.add_node("c");
+    let ab = gr.add_edge(a, b, 1);
+    let bc = gr.add_edge(b, c, 2);
+    let d = gr.add_node("d");
+    gr.remove_node(b);
+
+    for mut map in vec![NodeMap::sparse(), NodeMap::dense(0), NodeMap::default()] {
+        assert!(map.is_empty());
+        assert_eq!(map.insert(c, 3), None);
+        assert_eq!(map.insert(a, 1), None);
+        assert_eq!(map.insert(c, 4), Some(3));
+        *map.entry(d).or_insert(0) += 10;
+        *map.entry(d).or_insert(0) += 10;
+        map.entry(a).and_modify(|x| *x *= 7).or_default();
+        assert_eq!(map.len(), 3);
+        assert_eq!(map[a], 7);
+        assert_eq!(map.get(b), None);
+        assert!(map.contains_key(d));
+        map[d] += 1;
+        let mut items = map.iter().map(|(i, &x)| (i, x)).collect::<Vec<_>>();
+        items.sort();
+        assert_eq!(items, vec![(a, 7), (c, 4), (d, 21)]);
+
+        assert_eq!(map.remove(c), Some(4));
+        assert_eq!(map.remove(c), None);
+        assert_eq!(map.len(), 2);
+        map.clear();
+        assert!(map.is_empty() && map.get(a).is_none());
+    }
+
+    let mut lengths = EdgeMap::dense(2);
+    lengths.insert(bc, 2.5);
+    assert_eq!(lengths.iter().collect::<Vec<_>>(), vec![(bc, &2.5)]);
+    assert_eq!(lengths.get(ab), None);
+
+    // an index type from outside the crate can be iterated too
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    struct Key(usize);
+    impl petgraph::graph::GraphIndex for Key {
+        fn index(&self) -> usize { self.0 }
+        fn is_node_index() -> bool { true }
+    }
+    let mut map = petgraph::property::PropertyMap::sparse();
+    map.insert(Key(3), "x");
+    assert_eq!(map.iter().collect::<Vec<_>>(), vec![(Key(3), &"x")]);
+}
+
+#[test]
+fn u64_index() {
+    let mut g = StableGraph::<_, _, petgraph::Directed, u64>::with_capacity(0, 0);
+    let a = g.add_node(0);
+    let b = g.add_node(1);
+    g.add_edge(a, b, ());
+    g.remove_node(a);
+    // the free list reuses the vacant index
+    let c = g.add_node(2);
+    assert_eq!(c, a);
+    assert_eq!(g.node_count(), 2);
+    assert_eq!(g.edge_count(), 0);
+    let e = g.add_edge(b, c, ());
+    assert_eq!(g.edge_endpoints(e), Some((b, c)));
+}
@@ -0,0 +1,198 @@
+extern crate petgraph;
+extern crate rand;
+
+use rand::Rng;
+
+use petgraph::{
+    Graph,
+    Undirected,
+};
+use petgraph::algo::tree::{
+    center,
+    centroid_decomposition,
+    diameter,
+    euler_tour,
+    heavy_light_decomposition,
+    parents,
+    subtree_sizes,
+};
+use petgraph::graph::{
+    node_index as n,
+    NodeIndex,
+};
+
+/// A random tree on `nodes` nodes, plus an isolated node.
+fn random_tree<R: Rng>(rng: &mut R, nodes: usize) -> Graph<(), (), Undirected> {
+    let mut g = Graph::new_undirected();
+    g.add_node(());
+    for i in 1..nodes {
+        let a = g.add_node(());
+        let b = n(rng.gen_range(0, i));
+        g.add_edge(b, a, ());
+    }
+    g.add_node(());
+    g
+}
+
+/// Distances in edges from `start`, `None` if unreachable.
+fn distances(g: &Graph<(), (), Undirected>, start: NodeIndex) -> Vec<Option<usize>> {
+    let mut dist = vec![None; g.node_count()];
+    dist[start.index()] = Some(0);
+    let mut queue = vec![start];
+    let mut i = 0;
+    while i < queue.len() {
+        let a = queue[i];
+        i += 1;
+        for b in g.neighbors(a) {
+            if dist[b.index()].is_none() {
+                dist[b.index()] = Some(dist[a.index()].unwrap() + 1);
+                queue.push(b);
+            }
+        }
+    }
+    dist
+}
+
+fn eccentricity(g: &Graph<(), (), Undirected>, a: NodeIndex) -> usize {
+    distances(g, a).into_iter().filter_map(|d| d).max().unwrap()
+}
+
+#[test]
+fn tree_small() {
+    let g = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2), (1, 3), (3, 4)]);
+    assert_eq!(parents(&g, n(1)), vec![Some(n(1)), None, Some(n(1)), Some(n(1)), Some(n(3))]);
+    assert_eq!(subtree_sizes(&g, n(1)), vec![1, 5, 1, 2, 1]);
+    assert_eq!(subtree_sizes(&g, n(4)), vec![1, 3, 1, 4, 5]);
+    let mut centers = center(&g, n(0));
+    centers.sort();
+    assert_eq!(centers, vec![n(1), n(3)]);
+    assert_eq!(diameter(&g, n(0)).len(), 4);
+
+    let single = Graph::<(), (), Undirected>::from_edges(&[(0, 0)]);
+    assert_eq!(euler_tour(&single, n(0)), vec![n(0)]);
+    assert_eq!(diameter(&single, n(0)), vec![n(0)]);
+    assert_eq!(center(&single, n(0)), vec![n(0)]);
+    assert_eq!(centroid_decomposition(&single, n(0)), vec![None]);
+}
+
+#[test]
+fn tree_directed_with_cycle() {
+    // directions are ignored, and the cycle is broken by the search
+    let g = Graph::<(), ()>::from_edges(&[(1, 0), (2, 1), (0, 2), (3, 2)]);
+    let sizes = subtree_sizes(&g, n(0));
+    assert_eq!(sizes[0], 4);
+    assert_eq!(euler_tour(&g, n(0)).len(), 7);
+}
+
+#[test]
+fn tree_random() {
+    let mut rng = rand::weak_rng();
+    for _ in 0..50 {
+        let nodes = rng.gen_range(1, 40);
+        let g = random_tree(&mut rng, nodes);
+        let outside: NodeIndex = n(nodes);
+        let root = n(rng.gen_range(0, nodes));
+        let parent = parents(&g, root);
+        assert_eq!(parent[root.index()], None);
+        assert_eq!(parent[outside.index()], None);
+
+        // each edge is walked down and up once
+        let tour = euler_tour(&g, root);
+        assert_eq!(tour.len(), 2 * nodes - 1);
+        assert_eq!((tour[0], tour[tour.len() - 1]), (root, root));
+        for w in tour.windows(2) {
+            assert!(parent[w[0].index()] == Some(w[1]) || parent[w[1].index()] == Some(w[0]));
+        }
+
+        let sizes = subtree_sizes(&g, root);
+        assert_eq!(sizes[root.index()], nodes);
+        assert_eq!(sizes[outside.index()], 0);
+        for a in (0..nodes).map(n) {
+            let children = (0..nodes).filter(|&b| parent[b] == Some(a)).map(|b| sizes[b]);
+            assert_eq!(sizes[a.index()], 1 + children.sum::<usize>());
+        }
+
+        let path = diameter(&g, root);
+        let longest = (0..nodes).map(|a| eccentricity(&g, n(a))).max().unwrap();
+        assert_eq!(path.len(), longest + 1);
+        for w in path.windows(2) {
+            assert!(g.find_edge(w[0], w[1]).is_some());
+        }
+
+        let radius = (0..nodes).map(|a| eccentricity(&g, n(a))).min().unwrap();
+        let centers = (0..nodes).map(n).filter(|&a| eccentricity(&g, a) == radius)
+                                .collect::<Vec<_>>();
+        let mut found = center(&g, root);
+        found.sort();
+        assert_eq!(found, centers);
+
+        // every centroid splits its part in halves; parts shrink at least
+        // by half going down the centroid tree
+        let cparent = centroid_decomposition(&g, root);
+        assert_eq!(cparent.iter().filter(|p| p.is_none()).count(), 2);
+        for a in 0..nodes {
+            let mut depth = 0;
+            let mut x = a;
+            while let Some(p) = cparent[x] {
+                x = p.index();
+                depth += 1;
+            }
+            assert!(1 << depth <= nodes);
+        }
+    }
+}
+
+#[test]
+fn heavy_light_random() {
+    let mut rng = rand::weak_rng();
+    for _ in 0..50 {
+        let nodes = rng.gen_range(1, 60);
+        let g = random_tree(&mut rng, nodes);
+        let root = n(rng.gen_range(0, nodes));
+        let parent = parents(&g, root);
+        let hld = heavy_light_decomposition(&g, root);
+        assert_eq!(hld.position(n(nodes)), None);
+        assert_eq!(hld.chain(n(nodes)), None);
+
+        // positions are a permutation, chains are contiguous from the top
+        let mut positions = (0..nodes).map(|a| hld.position(n(a)).unwrap()).collect::<Vec<_>>();
+        positions.sort();
+        assert_eq!(positions, (0..nodes).collect::<Vec<_>>());
+        for a in (0..nodes).map(n) {
+            assert_eq!(hld.parent(a), parent[a.index()]);
+            let h = hld.head(a);
+            assert_eq!(hld.chain(a), hld.chain(h));
+            assert_eq!(hld.position(a).unwrap() - hld.position(h).unwrap(),
+                       hld.depth(a) - hld.depth(h));
+        }
+        assert_eq!(hld.chain_count(),
+                   (0..nodes).filter(|&a| hld.head(n(a)) == n(a)).count());
+
+        for _ in 0..10 {
+            let a = n(rng.gen_range(0, nodes));
+            let b = n(rng.gen_range(0, nodes));
+            let mut ancestors = vec![a];
+            while let Some(p) = parent[ancestors[ancestors.len() - 1].index()] {
+                ancestors.push(p);
+            }
+            let mut path_b = vec![b];
+            while !ancestors.contains(&path_b[path_b.len() - 1]) {
+                let p = parent[path_b[path_b.len() - 1].index()].unwrap();
+                path_b.push(p);
+            }
+            let lca = path_b[path_b.len() - 1];
+            assert_eq!(hld.lca(a, b), lca);
+
+            let mut on_path = ancestors.iter().cloned().take_while(|&x| x != lca)
+                                       .chain(path_b)
+                                       .map(|x| hld.position(x).unwrap())
+                                       .collect::<Vec<_>>();
+            on_path.sort();
+            let segments = hld.path_segments(a, b);
+            assert!(1 << (segments.len() / 2) <= 2 * nodes);
+            let mut covered = segments.into_iter().flat_map(|r| r).collect::<Vec<_>>();
+            covered.sort();
+            assert_eq!(covered, on_path);
+        }
+    }
+}
@@ -76,3 +76,51 @@ fn labeling()
     let v = u.into_labeling();
     assert!(v.iter().all(|x| *x == v[0]));
 }
+
+#[test]
+fn uf_sizes_and_representatives()
+{
+    let mut u = UnionFind::<u32>::new(10);
+    u.union(0, 1);
+    u.union(2, 3);
+    u.union(1, 3);
+    u.union(7, 8);
+    assert_eq!(u.size(2), 4);
+    assert_eq!(u.size(8), 2);
+    assert_eq!(u.size(5), 1);
+    assert!(u.equiv(0, 2));
+    assert!(!u.equiv(0, 7));
+
+    let reps = u.representatives().collect::<Vec<_>>();
+    assert_eq!(reps.len(), 6);
+    for &r in &reps {
+        assert_eq!(u.find(r), r);
+    }
+    assert_eq!(reps.iter().map(|&r| u.size(r)).sum::<usize>(), u.len());
+
+    let v = UnionFind::new_from_labels(&u.clone().into_labeling());
+    for i in 0..10 {
+        for j in 0..10 {
+            assert_eq!(u.equiv(i, j), v.equiv(i, j));
+        }
+        assert_eq!(u.size(i), v.size(i));
+    }
+}
+
+#[test]
+fn uf_find_mut_deep_tree()
+{
+    // build the deepest tree union by rank allows, by merging sets of equal rank
+    let n = 1 << 16;
+    let mut u = UnionFind::<u32>::new(n);
+    let mut width = 1;
+    while width < n {
+        for i in (0..n).filter(|i| i % (2 * width) == 0) {
+            u.union(i as u32, (i + width) as u32);
+        }
+        width *= 2;
+    }
+    let rep = u.find_mut(n as u32 - 1);
+    assert_eq!(u.size(rep), n);
+    assert_eq!(u.representatives().count(), 1);
+}